@@ -16,7 +16,10 @@ extern crate indexer_base;
 extern crate log;
 
 use dlt_core::filtering::DltFilterConfig;
-use parsers::{Attachment, dlt::DltParser};
+use parsers::{
+    Attachment,
+    dlt::{DltParser, v2},
+};
 use processor::producer::{MessageProducer, ProduceSummary};
 use sources::binary::raw::BinaryByteSource;
 use std::{
@@ -30,9 +33,12 @@ use crate::attachments_collector::AttachmentsCollector;
 
 mod attachments_collector;
 
+/// Scans the DLT file for file transfers. The file can contain messages of protocol
+/// version 1 and 2; `v2_filter` is applied to version 2 messages only.
 pub async fn scan_dlt_ft(
     input: PathBuf,
     filter: Option<DltFilterConfig>,
+    v2_filter: Option<v2::FilterConfig>,
     with_storage_header: bool,
     cancel: CancellationToken,
 ) -> Result<Vec<Attachment>, String> {
@@ -40,13 +46,16 @@ pub async fn scan_dlt_ft(
         Ok(input) => {
             let reader = BufReader::new(&input);
             let source = BinaryByteSource::new(reader);
-            let parser = DltParser::new(
+            let mut parser = DltParser::new(
                 filter.map(|f| f.into()),
                 None,
                 None,
                 None,
                 with_storage_header,
             );
+            if let Some(v2_filter) = v2_filter {
+                parser = parser.with_v2_filter(v2_filter);
+            }
 
             let mut producer = MessageProducer::new(parser, source);
 
@@ -137,7 +146,7 @@ mod tests {
         let input: PathBuf = Path::new(DLT_FT_SAMPLE).into();
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input, None, true, cancel).await {
+        match scan_dlt_ft(input, None, None, true, cancel).await {
             Ok(files) => {
                 assert_eq!(files.len(), 3);
                 assert_eq!("test1.txt", files.first().unwrap().name);
@@ -157,7 +166,7 @@ mod tests {
         let cancel = CancellationToken::new();
         cancel.cancel();
 
-        match scan_dlt_ft(input, None, true, cancel).await {
+        match scan_dlt_ft(input, None, None, true, cancel).await {
             Ok(files) => {
                 assert_eq!(files.len(), 0);
             }
@@ -181,7 +190,7 @@ mod tests {
         };
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input, Some(filter), true, cancel).await {
+        match scan_dlt_ft(input, Some(filter), None, true, cancel).await {
             Ok(files) => {
                 assert_eq!(files.len(), 1);
                 assert_eq!("test2.txt", files.first().unwrap().name);
@@ -198,7 +207,7 @@ mod tests {
         let output = TempDir::new();
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input.clone(), None, true, cancel.clone()).await {
+        match scan_dlt_ft(input.clone(), None, None, true, cancel.clone()).await {
             Ok(files) => {
                 match extract_dlt_ft(
                     &output.dir,
@@ -229,7 +238,7 @@ mod tests {
         let output = TempDir::new();
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input.clone(), None, true, cancel).await {
+        match scan_dlt_ft(input.clone(), None, None, true, cancel).await {
             Ok(files) => {
                 let cancel = CancellationToken::new();
                 cancel.cancel();
@@ -263,7 +272,7 @@ mod tests {
         };
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input.clone(), Some(filter), true, cancel).await {
+        match scan_dlt_ft(input.clone(), Some(filter), None, true, cancel).await {
             Ok(files) => {
                 let cancel = CancellationToken::new();
                 match extract_dlt_ft(&output.dir, FileExtractor::files_with_names(files), cancel) {
@@ -289,7 +298,7 @@ mod tests {
         let output = TempDir::new();
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input.clone(), None, true, cancel).await {
+        match scan_dlt_ft(input.clone(), None, None, true, cancel).await {
             Ok(files) => {
                 let cancel = CancellationToken::new();
                 match extract_dlt_ft(
@@ -328,7 +337,7 @@ mod tests {
         };
 
         let cancel = CancellationToken::new();
-        match scan_dlt_ft(input.clone(), Some(filter), true, cancel).await {
+        match scan_dlt_ft(input.clone(), Some(filter), None, true, cancel).await {
             Ok(files) => {
                 let cancel = CancellationToken::new();
                 match extract_dlt_ft(&output.dir, FileExtractor::files_with_names(files), cancel) {
@@ -0,0 +1,240 @@
+//! # Vector ASC traces
+//!
+//! Reads the CAN and CAN FD frames of ASCII logging files. The `date` and
+//! `base` header lines define the start of the measurement, the number base
+//! and whether the timestamps are relative to the start or to the previous
+//! event. Other events (error frames, statistics, comments) are skipped.
+use crate::{
+    Error, ParseOutput, SingleParser,
+    can::{CanFrame, CanMessage, Dbc, Direction, dlc_to_len, parse_data, parse_id},
+    text::pattern::next_line,
+};
+use chrono::NaiveDateTime;
+use std::sync::Arc;
+
+/// Formats of the `date` header line.
+const DATE_FORMATS: &[&str] = &[
+    "%a %b %d %I:%M:%S%.f %p %Y",
+    "%a %b %d %H:%M:%S%.f %Y",
+    "%a %b %d %I:%M:%S %p %Y",
+    "%a %b %d %H:%M:%S %Y",
+];
+
+pub struct AscParser {
+    dbc: Option<Arc<Dbc>>,
+    radix: u32,
+    /// Timestamps are relative to the previous event.
+    relative: bool,
+    /// Start of the measurement in milliseconds since the epoch.
+    start: Option<i64>,
+    /// Seconds since the start of the measurement of the last event.
+    last: f64,
+}
+
+impl AscParser {
+    pub fn new(dbc: Option<Arc<Dbc>>) -> Self {
+        Self {
+            dbc,
+            radix: 16,
+            relative: false,
+            start: None,
+            last: 0.0,
+        }
+    }
+
+    fn header(&mut self, line: &str) {
+        if let Some(date) = line.strip_prefix("date ") {
+            let date = date.split_whitespace().collect::<Vec<&str>>().join(" ");
+            self.start = DATE_FORMATS.iter().find_map(|format| {
+                NaiveDateTime::parse_from_str(&date, format)
+                    .ok()
+                    .map(|datetime| datetime.and_utc().timestamp_millis())
+            });
+        } else if line.starts_with("base ") {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            self.radix = if tokens.get(1) == Some(&"dec") {
+                10
+            } else {
+                16
+            };
+            self.relative = tokens.get(3) == Some(&"relative");
+        }
+    }
+
+    fn message(&mut self, raw: &str) -> Option<CanMessage> {
+        let line = raw.trim();
+        let mut tokens = line.split_whitespace();
+        let time_str = tokens.next()?;
+        let Ok(time) = time_str.parse::<f64>() else {
+            self.header(line);
+            return None;
+        };
+        let offset = if self.relative {
+            self.last + time
+        } else {
+            time
+        };
+        self.last = offset;
+        let frame = match tokens.next()? {
+            "CANFD" => self.fd_frame(tokens)?,
+            channel if channel.chars().all(|c| c.is_ascii_digit()) => {
+                self.frame(channel.to_owned(), tokens)?
+            }
+            _ => return None,
+        };
+        let timestamp = self
+            .start
+            .map(|start| start + (offset * 1e3).round() as i64);
+        Some(CanMessage::new(
+            time_str.to_owned(),
+            timestamp,
+            frame,
+            self.dbc.as_ref(),
+            raw.as_bytes().to_vec(),
+        ))
+    }
+
+    /// Parses `<id> <dir> d <dlc> <data>` or `<id> <dir> r`.
+    fn frame<'a>(
+        &self,
+        channel: String,
+        mut tokens: impl Iterator<Item = &'a str>,
+    ) -> Option<CanFrame> {
+        let (id, extended) = parse_id(tokens.next()?, self.radix)?;
+        let direction = direction(tokens.next()?)?;
+        let (rtr, data) = match tokens.next()? {
+            "r" => (true, Vec::new()),
+            "d" => {
+                let dlc = u8::from_str_radix(tokens.next()?, 16).ok()?;
+                let data = parse_data(tokens.take(dlc_to_len(dlc, false)), self.radix)?;
+                (false, data)
+            }
+            _ => return None,
+        };
+        Some(CanFrame {
+            channel,
+            id,
+            extended,
+            fd: false,
+            brs: false,
+            rtr,
+            direction: Some(direction),
+            data,
+        })
+    }
+
+    /// Parses `<channel> <dir> <id> [<name>] <brs> <esi> <dlc> <len> <data>`.
+    fn fd_frame<'a>(&self, tokens: impl Iterator<Item = &'a str>) -> Option<CanFrame> {
+        let tokens: Vec<&str> = tokens.collect();
+        let [channel, dir, id, rest @ ..] = &tokens[..] else {
+            return None;
+        };
+        let (id, extended) = parse_id(id, self.radix)?;
+        // The symbolic name of the message is optional.
+        let rest = match rest.first() {
+            Some(&"0") | Some(&"1") => rest,
+            _ => rest.get(1..)?,
+        };
+        let [brs, _esi, _dlc, len, data @ ..] = rest else {
+            return None;
+        };
+        let len: usize = len.parse().ok()?;
+        Some(CanFrame {
+            channel: (*channel).to_owned(),
+            id,
+            extended,
+            fd: true,
+            brs: *brs == "1",
+            rtr: false,
+            direction: Some(direction(dir)?),
+            data: parse_data(data.iter().take(len).copied(), self.radix)?,
+        })
+    }
+}
+
+fn direction(value: &str) -> Option<Direction> {
+    match value {
+        "Rx" => Some(Direction::Rx),
+        "Tx" | "TxRq" => Some(Direction::Tx),
+        _ => None,
+    }
+}
+
+impl SingleParser for AscParser {
+    type Output = CanMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<CanMessage>, Error> {
+        let Some((line, consumed)) = next_line(input, false) else {
+            return Ok(ParseOutput::new(input.len(), None));
+        };
+        Ok(ParseOutput::new(
+            consumed,
+            self.message(&line).map(|msg| msg.into()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogMessage, ParseYield, Parser};
+
+    #[test]
+    fn classic_and_fd_frames() {
+        let mut parser = AscParser::new(None);
+        let input = b"date Sun Mar 17 04:13:38.811 pm 2024\n\
+            base hex  timestamps absolute\n\
+            internal events logged\n\
+            Begin Triggerblock Sun Mar 17 04:13:38.811 pm 2024\n   \
+               0.000000 Start of measurement\n   \
+               1.500000 1  123             Rx   d 2 88 13  Length = 0 BitCount = 0 ID = 291\n   \
+               1.600000 2  18FEF100x       Tx   r\n   \
+               1.700000 1  ErrorFrame\n   \
+               2.000000 CANFD   1 Rx        1A3  Engine                          1 0 9 12 00 01 02 03 04 05 06 07 08 09 0a 0b  0 0 0 0 0 0 0 0\n\
+            End TriggerBlock\n";
+        let messages: Vec<CanMessage> = parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_020_311));
+        assert_eq!(
+            messages[0].to_string(),
+            "1.500000\u{4}1\u{4}123\u{4}Rx\u{4}CAN\u{4}2\u{4}88 13\u{4}\u{4}"
+        );
+        assert!(messages[1].frame.extended && messages[1].frame.rtr);
+        assert_eq!(messages[1].frame.direction, Some(Direction::Tx));
+        assert!(messages[2].frame.fd && messages[2].frame.brs);
+        assert_eq!(messages[2].frame.id, 0x1A3);
+        assert_eq!(messages[2].frame.data.len(), 12);
+        assert_eq!(messages[2].frame.data[11], 0x0B);
+    }
+
+    #[test]
+    fn relative_decimal_timestamps() {
+        let mut parser = AscParser::new(None);
+        let input = b"base dec  timestamps relative\n\
+            0.500000 1  291 Rx d 1 255\n\
+            0.250000 1  291 Rx d 1 16\n";
+        let messages: Vec<CanMessage> = parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages[1].frame.id, 0x123);
+        assert_eq!(messages[1].frame.data, vec![16]);
+        assert_eq!(messages[0].timestamp(), None);
+        assert_eq!(parser.last, 0.75);
+    }
+}
@@ -0,0 +1,474 @@
+//! # Vector BLF traces
+//!
+//! Reads the CAN and CAN FD frames of binary logging files. The objects of a
+//! BLF file are usually stored in zlib compressed log containers, an object may
+//! be split between two containers. Objects other than CAN frames are skipped.
+//!
+//! The raw form of a message is its uncompressed object. BLF files may contain
+//! objects outside of log containers, so messages written after the header of
+//! the original file (see [`read_file_header`]) make a valid BLF file.
+use crate::{
+    Error, ParseOutput, Parser,
+    can::{CanFrame, CanMessage, Dbc, Direction, dlc_to_len},
+};
+use chrono::NaiveDate;
+use flate2::read::ZlibDecoder;
+use log::warn;
+use std::{
+    io::{self, Read},
+    sync::Arc,
+};
+
+const FILE_SIGNATURE: &[u8] = b"LOGG";
+const OBJ_SIGNATURE: &[u8] = b"LOBJ";
+/// Length of the object header common to all versions.
+const OBJ_BASE_HEADER_LEN: usize = 16;
+/// Offset of the start time (`SYSTEMTIME`) in the file header.
+const START_TIME_OFFSET: usize = 40;
+
+const CAN_MESSAGE: u32 = 1;
+const LOG_CONTAINER: u32 = 10;
+const CAN_MESSAGE2: u32 = 86;
+const CAN_FD_MESSAGE: u32 = 100;
+const CAN_FD_MESSAGE_64: u32 = 101;
+
+const COMPRESSION_NONE: u16 = 0;
+const COMPRESSION_ZLIB: u16 = 2;
+
+/// Timestamps of objects with this flag are in 10 µs, otherwise in ns.
+const TIME_TEN_MICS: u32 = 1;
+const CAN_EXTENDED_ID: u32 = 0x8000_0000;
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        bytes.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+/// Reads the file header, which has to be written before exported messages.
+pub fn read_file_header<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut header = vec![0; 8];
+    reader.read_exact(&mut header)?;
+    if !header.starts_with(FILE_SIGNATURE) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a BLF file"));
+    }
+    let len = u32_at(&header, 4).unwrap_or_default() as usize;
+    header.resize(len.max(header.len()), 0);
+    reader.read_exact(&mut header[8..])?;
+    Ok(header)
+}
+
+/// Returns the number of padding bytes before the next object and its size.
+fn next_object(input: &[u8]) -> Result<(usize, usize), Error> {
+    if input.len() < OBJ_BASE_HEADER_LEN {
+        return Err(Error::Incomplete);
+    }
+    // Objects are padded, the padding isn't always part of the object size.
+    let skip = (0..4)
+        .find(|idx| input[*idx..].starts_with(OBJ_SIGNATURE))
+        .ok_or_else(|| Error::Parse(String::from("Missing BLF object signature")))?;
+    let size = u32_at(input, skip + 8).ok_or(Error::Incomplete)? as usize;
+    if size < OBJ_BASE_HEADER_LEN {
+        return Err(Error::Parse(format!("Invalid BLF object size {size}")));
+    }
+    if input.len() < skip + size {
+        return Err(Error::Incomplete);
+    }
+    Ok((skip, size))
+}
+
+fn obj_type(obj: &[u8]) -> u32 {
+    u32_at(obj, 12).unwrap_or_default()
+}
+
+pub struct BlfParser {
+    dbc: Option<Arc<Dbc>>,
+    header_read: bool,
+    /// Start of the measurement in milliseconds since the epoch.
+    start: Option<i64>,
+    /// Unpacked bytes of log containers which weren't processed yet.
+    pending: Vec<u8>,
+}
+
+impl BlfParser {
+    pub fn new(dbc: Option<Arc<Dbc>>) -> Self {
+        Self {
+            dbc,
+            header_read: false,
+            start: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Reads the file header and returns its length.
+    fn file_header(&mut self, input: &[u8]) -> Result<usize, Error> {
+        if input.len() < 8 {
+            return Err(Error::Incomplete);
+        }
+        if !input.starts_with(FILE_SIGNATURE) {
+            return Err(Error::Unrecoverable(String::from("Not a BLF file")));
+        }
+        let len = u32_at(input, 4).unwrap_or_default() as usize;
+        if input.len() < len {
+            return Err(Error::Incomplete);
+        }
+        let time: Option<Vec<u16>> = (0..8)
+            .map(|idx| u16_at(&input[..len], START_TIME_OFFSET + idx * 2))
+            .collect();
+        self.start = time.and_then(|time| {
+            // SYSTEMTIME: year, month, day of week, day, hour, minute, second, ms
+            NaiveDate::from_ymd_opt(time[0] as i32, time[1] as u32, time[3] as u32)?
+                .and_hms_milli_opt(
+                    time[4] as u32,
+                    time[5] as u32,
+                    time[6] as u32,
+                    time[7] as u32,
+                )
+                .map(|datetime| datetime.and_utc().timestamp_millis())
+        });
+        self.header_read = true;
+        Ok(len)
+    }
+
+    /// Appends the unpacked content of the given log container to the pending bytes.
+    fn unpack(&mut self, obj: &[u8]) -> Result<(), Error> {
+        let method = u16_at(obj, OBJ_BASE_HEADER_LEN).unwrap_or_default();
+        let data = obj.get(OBJ_BASE_HEADER_LEN + 16..).unwrap_or_default();
+        match method {
+            COMPRESSION_NONE => self.pending.extend_from_slice(data),
+            COMPRESSION_ZLIB => {
+                ZlibDecoder::new(data)
+                    .read_to_end(&mut self.pending)
+                    .map_err(|e| Error::Parse(format!("Fail to unpack BLF container: {e}")))?;
+            }
+            method => {
+                return Err(Error::Parse(format!(
+                    "Unsupported BLF compression method {method}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes the messages of all complete objects from the pending bytes.
+    fn pending_messages(&mut self) -> Vec<CanMessage> {
+        let mut messages = Vec::new();
+        let mut offset = 0;
+        loop {
+            match next_object(&self.pending[offset..]) {
+                Ok((skip, size)) => {
+                    let obj = &self.pending[offset + skip..offset + skip + size];
+                    messages.extend(self.message(obj));
+                    offset += skip + size;
+                }
+                Err(Error::Incomplete) => break,
+                Err(err) => {
+                    warn!("Dropping unpacked BLF data: {err}");
+                    offset = self.pending.len();
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..offset);
+        messages
+    }
+
+    fn message(&self, obj: &[u8]) -> Option<CanMessage> {
+        let header_len = u16_at(obj, 4)? as usize;
+        let flags = u32_at(obj, OBJ_BASE_HEADER_LEN)?;
+        let time = u64_at(obj, OBJ_BASE_HEADER_LEN + 8)?;
+        let nanos = if flags & TIME_TEN_MICS != 0 {
+            time.saturating_mul(10_000)
+        } else {
+            time
+        };
+        let body = obj.get(header_len..)?;
+        let frame = match obj_type(obj) {
+            CAN_MESSAGE | CAN_MESSAGE2 => can_frame(body)?,
+            CAN_FD_MESSAGE => can_fd_frame(body)?,
+            CAN_FD_MESSAGE_64 => can_fd_64_frame(body)?,
+            _ => return None,
+        };
+        // Objects are followed by `size % 4` bytes of padding.
+        let mut raw = obj.to_vec();
+        raw.resize(obj.len() + obj.len() % 4, 0);
+        Some(CanMessage::new(
+            format!("{:.6}", nanos as f64 / 1e9),
+            self.start.map(|start| start + (nanos / 1_000_000) as i64),
+            frame,
+            self.dbc.as_ref(),
+            raw,
+        ))
+    }
+}
+
+/// `channel: u16, flags: u8, dlc: u8, id: u32, data: [u8; 8]`
+fn can_frame(body: &[u8]) -> Option<CanFrame> {
+    let flags = *body.get(2)?;
+    let dlc = *body.get(3)?;
+    let id = u32_at(body, 4)?;
+    let rtr = flags & 0x80 != 0;
+    let len = if rtr { 0 } else { dlc_to_len(dlc, false) };
+    Some(CanFrame {
+        channel: u16_at(body, 0)?.to_string(),
+        id: id & !CAN_EXTENDED_ID,
+        extended: id & CAN_EXTENDED_ID != 0,
+        fd: false,
+        brs: false,
+        rtr,
+        direction: Some(if flags & 0x01 != 0 {
+            Direction::Tx
+        } else {
+            Direction::Rx
+        }),
+        data: body.get(8..8 + len)?.to_vec(),
+    })
+}
+
+/// `channel: u16, flags: u8, dlc: u8, id: u32, frame_length: u32, bit_count: u8,
+/// fd_flags: u8, valid_bytes: u8, reserved: [u8; 5], data: [u8; 64]`
+fn can_fd_frame(body: &[u8]) -> Option<CanFrame> {
+    let flags = *body.get(2)?;
+    let dlc = *body.get(3)?;
+    let id = u32_at(body, 4)?;
+    let fd_flags = *body.get(13)?;
+    let valid_bytes = *body.get(14)? as usize;
+    let fd = fd_flags & 0x01 != 0;
+    let rtr = flags & 0x80 != 0;
+    let len = if rtr {
+        0
+    } else {
+        dlc_to_len(dlc, fd).min(valid_bytes)
+    };
+    Some(CanFrame {
+        channel: u16_at(body, 0)?.to_string(),
+        id: id & !CAN_EXTENDED_ID,
+        extended: id & CAN_EXTENDED_ID != 0,
+        fd,
+        brs: fd_flags & 0x02 != 0,
+        rtr,
+        direction: Some(if flags & 0x01 != 0 {
+            Direction::Tx
+        } else {
+            Direction::Rx
+        }),
+        data: body.get(20..20 + len)?.to_vec(),
+    })
+}
+
+/// `channel: u8, dlc: u8, valid_bytes: u8, tx_count: u8, id: u32, frame_length: u32,
+/// flags: u32, ..., dir: u8 (at 34), ext_data_offset: u8, crc: u32, data`
+fn can_fd_64_frame(body: &[u8]) -> Option<CanFrame> {
+    let dlc = *body.get(1)?;
+    let valid_bytes = *body.get(2)? as usize;
+    let id = u32_at(body, 4)?;
+    let flags = u32_at(body, 12)?;
+    let fd = flags & 0x1000 != 0;
+    let rtr = flags & 0x0010 != 0;
+    let len = if rtr {
+        0
+    } else {
+        dlc_to_len(dlc, fd).min(valid_bytes)
+    };
+    Some(CanFrame {
+        channel: body.first()?.to_string(),
+        id: id & !CAN_EXTENDED_ID,
+        extended: id & CAN_EXTENDED_ID != 0,
+        fd,
+        brs: flags & 0x2000 != 0,
+        rtr,
+        direction: Some(if *body.get(34)? == 1 {
+            Direction::Tx
+        } else {
+            Direction::Rx
+        }),
+        data: body.get(40..40 + len)?.to_vec(),
+    })
+}
+
+impl Parser for BlfParser {
+    type Output = CanMessage;
+
+    fn parse(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<impl Iterator<Item = ParseOutput<CanMessage>>, Error> {
+        let mut outputs = Vec::new();
+        let mut offset = 0;
+        if !self.header_read {
+            offset = self.file_header(input)?;
+            outputs.push(ParseOutput::new(offset, None));
+        }
+        loop {
+            let (skip, size) = match next_object(&input[offset..]) {
+                Ok(next) => next,
+                Err(err) if outputs.is_empty() => return Err(err),
+                Err(_) => break,
+            };
+            let obj = &input[offset + skip..offset + skip + size];
+            let consumed = skip + size;
+            if obj_type(obj) == LOG_CONTAINER {
+                if let Err(err) = self.unpack(obj) {
+                    if outputs.is_empty() {
+                        return Err(err);
+                    }
+                    break;
+                }
+                let mut messages = self.pending_messages().into_iter();
+                // The bytes of the container are assigned to its first message.
+                outputs.push(ParseOutput::new(
+                    consumed,
+                    messages.next().map(|msg| msg.into()),
+                ));
+                outputs.extend(messages.map(|msg| ParseOutput::new(0, Some(msg.into()))));
+            } else {
+                outputs.push(ParseOutput::new(
+                    consumed,
+                    self.message(obj).map(|msg| msg.into()),
+                ));
+            }
+            offset += consumed;
+        }
+        Ok(outputs.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogMessage, ParseYield};
+    use flate2::{Compression, write::ZlibEncoder};
+    use std::io::Write;
+
+    fn file_header() -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend_from_slice(FILE_SIGNATURE);
+        header.extend_from_slice(&144u32.to_le_bytes());
+        header.resize(START_TIME_OFFSET, 0);
+        for value in [2024u16, 3, 0, 17, 16, 13, 38, 811] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        header.resize(144, 0);
+        header
+    }
+
+    fn object(obj_type: u32, nanos: u64, body: &[u8]) -> Vec<u8> {
+        let size = 32 + body.len() as u32;
+        let mut obj = Vec::new();
+        obj.extend_from_slice(OBJ_SIGNATURE);
+        obj.extend_from_slice(&32u16.to_le_bytes());
+        obj.extend_from_slice(&1u16.to_le_bytes());
+        obj.extend_from_slice(&size.to_le_bytes());
+        obj.extend_from_slice(&obj_type.to_le_bytes());
+        obj.extend_from_slice(&2u32.to_le_bytes());
+        obj.extend_from_slice(&[0; 4]);
+        obj.extend_from_slice(&nanos.to_le_bytes());
+        obj.extend_from_slice(body);
+        obj
+    }
+
+    fn container(content: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content).unwrap();
+        let data = encoder.finish().unwrap();
+        let size = 32 + data.len() as u32;
+        let mut obj = Vec::new();
+        obj.extend_from_slice(OBJ_SIGNATURE);
+        obj.extend_from_slice(&16u16.to_le_bytes());
+        obj.extend_from_slice(&1u16.to_le_bytes());
+        obj.extend_from_slice(&size.to_le_bytes());
+        obj.extend_from_slice(&LOG_CONTAINER.to_le_bytes());
+        obj.extend_from_slice(&COMPRESSION_ZLIB.to_le_bytes());
+        obj.extend_from_slice(&[0; 6]);
+        obj.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        obj.extend_from_slice(&[0; 4]);
+        obj.extend_from_slice(&data);
+        obj.resize(obj.len() + size as usize % 4, 0);
+        obj
+    }
+
+    #[test]
+    fn plain_and_compressed_objects() {
+        let mut can = vec![1, 0, 0, 2];
+        can.extend_from_slice(&0x123u32.to_le_bytes());
+        can.extend_from_slice(&[0x88, 0x13, 0, 0, 0, 0, 0, 0]);
+        let mut fd = vec![2, 9, 12, 0];
+        fd.extend_from_slice(&0x98FE_F100u32.to_le_bytes());
+        fd.extend_from_slice(&[0; 4]);
+        fd.extend_from_slice(&0x3000u32.to_le_bytes());
+        fd.resize(34, 0);
+        fd.extend_from_slice(&[1, 0, 0, 0, 0, 0]);
+        fd.extend_from_slice(&[0xAB; 12]);
+        let fd_obj = object(CAN_FD_MESSAGE_64, 2_000_000_000, &fd);
+        // The second container holds the rest of the split CAN FD object.
+        let (first, second) = fd_obj.split_at(30);
+        let mut content = object(CAN_MESSAGE, 1_500_000_000, &can);
+        content.extend_from_slice(first);
+
+        let mut input = file_header();
+        input.extend(object(CAN_MESSAGE, 1_000_000_000, &can));
+        input.extend(container(&content));
+        input.extend(container(second));
+
+        let mut parser = BlfParser::new(None);
+        let outputs: Vec<ParseOutput<CanMessage>> = parser.parse(&input, None).unwrap().collect();
+        // Only the padding of the last container remains.
+        assert!(input.len() - outputs.iter().map(|out| out.consumed).sum::<usize>() < 4);
+        let messages: Vec<CanMessage> = outputs
+            .into_iter()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0].to_string(),
+            "1.000000\u{4}1\u{4}123\u{4}Rx\u{4}CAN\u{4}2\u{4}88 13\u{4}\u{4}"
+        );
+        assert_eq!(messages[1].timestamp(), Some(1_710_692_020_311));
+        let fd = &messages[2].frame;
+        assert_eq!(
+            (fd.id, fd.extended, fd.fd, fd.brs),
+            (0x18FE_F100, true, true, true)
+        );
+        assert_eq!(fd.direction, Some(Direction::Tx));
+        assert_eq!(fd.data, vec![0xAB; 12]);
+        assert!(parser.pending.is_empty());
+
+        // The exported messages along with the file header are a valid BLF file.
+        let mut exported = read_file_header(&mut input.as_slice()).unwrap();
+        assert_eq!(exported, file_header());
+        for msg in messages.iter() {
+            msg.to_writer(&mut exported).unwrap();
+        }
+        let mut parser = BlfParser::new(None);
+        let reparsed: Vec<CanMessage> = parser
+            .parse(&exported, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reparsed, messages);
+
+        assert!(matches!(
+            BlfParser::new(None).parse(b"not a blf file", None),
+            Err(Error::Unrecoverable(_))
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+//! # Linux `candump` traces
+//!
+//! Supports the log format (`candump -l`), e.g. `(1436509052.249713) can0 123#DEADBEEF`
+//! and the default output format, e.g. `(1436509052.249713)  can0  123   [4]  DE AD BE EF`.
+use crate::{
+    Error, ParseOutput, SingleParser,
+    can::{CanFrame, CanMessage, Dbc, Direction, parse_data, parse_id},
+    text::pattern::next_line,
+};
+use chrono::NaiveDateTime;
+use std::sync::Arc;
+
+/// Epoch timestamps before this value (2001-09-09) are treated as relative.
+const MIN_EPOCH_SEC: f64 = 1e9;
+
+pub struct CandumpParser {
+    dbc: Option<Arc<Dbc>>,
+}
+
+impl CandumpParser {
+    pub fn new(dbc: Option<Arc<Dbc>>) -> Self {
+        Self { dbc }
+    }
+
+    fn message(&self, raw: &str) -> Option<CanMessage> {
+        let line = raw.trim();
+        let (time, timestamp, rest) = match line.strip_prefix('(') {
+            Some(rest) => {
+                let (time, rest) = rest.split_once(')')?;
+                (time.to_owned(), parse_time(time), rest)
+            }
+            None => (String::new(), None, line),
+        };
+        let mut tokens = rest.split_whitespace();
+        let channel = tokens.next()?.to_owned();
+        let frame = tokens.next()?;
+        let frame = if frame.contains('#') {
+            parse_log_frame(channel, frame, tokens.next())?
+        } else {
+            parse_output_frame(channel, frame, tokens)?
+        };
+        Some(CanMessage::new(
+            time,
+            timestamp,
+            frame,
+            self.dbc.as_ref(),
+            raw.as_bytes().to_vec(),
+        ))
+    }
+}
+
+fn parse_time(time: &str) -> Option<i64> {
+    if let Ok(sec) = time.parse::<f64>() {
+        return if sec >= MIN_EPOCH_SEC {
+            Some((sec * 1e3).round() as i64)
+        } else {
+            None
+        };
+    }
+    NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+}
+
+/// Parses `<id>#<data>`, `<id>#R[<len>]` or `<id>##<flags><data>` with an
+/// optional direction (`T` or `R`).
+fn parse_log_frame(channel: String, frame: &str, direction: Option<&str>) -> Option<CanFrame> {
+    let (id_str, payload) = frame.split_once('#')?;
+    let (id, _) = parse_id(id_str, 16)?;
+    // Extended identifiers are always written with 8 digits.
+    let extended = id_str.len() > 3;
+    let (fd, brs, rtr, payload) = if let Some(payload) = payload.strip_prefix('#') {
+        let flags = u8::from_str_radix(payload.get(..1)?, 16).ok()?;
+        (true, flags & 0x1 != 0, false, &payload[1..])
+    } else if payload.starts_with(['R', 'r']) {
+        (false, false, true, "")
+    } else {
+        (false, false, false, payload)
+    };
+    let payload = payload.replace('.', "");
+    if payload.len() % 2 != 0 {
+        return None;
+    }
+    let data = parse_data(
+        (0..payload.len())
+            .step_by(2)
+            .map(|idx| &payload[idx..idx + 2]),
+        16,
+    )?;
+    Some(CanFrame {
+        channel,
+        id,
+        extended,
+        fd,
+        brs,
+        rtr,
+        direction: match direction {
+            Some("T") => Some(Direction::Tx),
+            Some("R") => Some(Direction::Rx),
+            _ => None,
+        },
+        data,
+    })
+}
+
+/// Parses `<id> [<len>] <data>` or `<id> [<len>] remote request`.
+fn parse_output_frame<'a>(
+    channel: String,
+    id_str: &str,
+    mut tokens: impl Iterator<Item = &'a str>,
+) -> Option<CanFrame> {
+    let (id, _) = parse_id(id_str, 16)?;
+    let extended = id_str.len() > 3;
+    let len = tokens.next()?;
+    let len: usize = len.strip_prefix('[')?.strip_suffix(']')?.parse().ok()?;
+    let rest: Vec<&str> = tokens.collect();
+    let rtr = rest.first() == Some(&"remote");
+    let data = if rtr {
+        Vec::new()
+    } else {
+        parse_data(rest.iter().take(len).copied(), 16)?
+    };
+    Some(CanFrame {
+        channel,
+        id,
+        extended,
+        fd: len > 8,
+        brs: false,
+        rtr,
+        direction: None,
+        data,
+    })
+}
+
+impl SingleParser for CandumpParser {
+    type Output = CanMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<CanMessage>, Error> {
+        let Some((line, consumed)) = next_line(input, false) else {
+            return Ok(ParseOutput::new(input.len(), None));
+        };
+        Ok(ParseOutput::new(
+            consumed,
+            self.message(&line).map(|msg| msg.into()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogMessage, ParseYield, Parser};
+
+    #[test]
+    fn log_and_output_format() {
+        let mut dbc = Dbc::default();
+        dbc.add("BO_ 291 Engine: 8 ECU\n SG_ Speed : 0|16@1+ (0.25,0) [0|0] \"rpm\" X\n");
+        let mut parser = CandumpParser::new(Some(Arc::new(dbc)));
+        let input = b"(1710692018.811000) can0 123#8813\n\
+            (1710692018.812000) can1 18FEF100#R T\n\
+            (1710692018.813000) can0 1A3##1000102030405060708090A0B\n\
+            garbage\n\
+            (000.000100)  vcan0  7FF   [3]  01 02 03\n";
+        let messages: Vec<CanMessage> = parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_018_811));
+        assert_eq!(
+            messages[0].to_string(),
+            "1710692018.811000\u{4}can0\u{4}123\u{4}\u{4}CAN\u{4}2\u{4}88 13\u{4}Engine\u{4}Speed=1250 rpm"
+        );
+        assert!(messages[1].frame.rtr && messages[1].frame.extended);
+        assert_eq!(messages[1].frame.id, 0x18FE_F100);
+        assert_eq!(messages[1].frame.direction, Some(Direction::Tx));
+        assert!(messages[2].frame.fd && messages[2].frame.brs);
+        assert_eq!(messages[2].frame.data.len(), 12);
+        assert_eq!(messages[3].timestamp(), None);
+        assert_eq!(messages[3].frame.data, vec![1, 2, 3]);
+        let mut raw = Vec::new();
+        messages[0].to_writer(&mut raw).unwrap();
+        assert_eq!(raw, b"(1710692018.811000) can0 123#8813");
+    }
+}
@@ -0,0 +1,389 @@
+//! # DBC database
+//!
+//! Reads the messages, signals and value tables of DBC files and decodes CAN
+//! frames with them. Multiplexed signals are only decoded if the value of
+//! their multiplexor matches.
+use crate::{
+    Error,
+    can::{CanFrame, DecodedMessage, DecodedSignal},
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Flag of extended identifiers in DBC files.
+const DBC_EXTENDED_ID: u32 = 0x8000_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Intel, the start bit is the least significant bit.
+    LittleEndian,
+    /// Motorola, the start bit is the most significant bit.
+    BigEndian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Unsigned,
+    Signed,
+    Float,
+    Double,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexing {
+    None,
+    Multiplexor,
+    Multiplexed(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signal {
+    pub name: String,
+    pub start_bit: u16,
+    pub size: u16,
+    pub byte_order: ByteOrder,
+    pub value_type: ValueType,
+    pub factor: f64,
+    pub offset: f64,
+    pub unit: Option<String>,
+    pub multiplexing: Multiplexing,
+    /// Descriptions of raw values.
+    pub values: HashMap<i64, String>,
+}
+
+impl Signal {
+    /// Returns the raw value of the signal, `None` if the payload is too short.
+    fn raw(&self, data: &[u8]) -> Option<u64> {
+        if self.size == 0 || self.size > 64 {
+            return None;
+        }
+        let bit = |pos: usize| -> Option<u64> {
+            data.get(pos / 8)
+                .map(|byte| ((byte >> (pos % 8)) & 1) as u64)
+        };
+        let mut raw = 0u64;
+        match self.byte_order {
+            ByteOrder::LittleEndian => {
+                for i in 0..self.size as usize {
+                    raw |= bit(self.start_bit as usize + i)? << i;
+                }
+            }
+            ByteOrder::BigEndian => {
+                let mut pos = self.start_bit as usize;
+                for _ in 0..self.size {
+                    raw = (raw << 1) | bit(pos)?;
+                    pos = if pos % 8 == 0 { pos + 15 } else { pos - 1 };
+                }
+            }
+        }
+        Some(raw)
+    }
+
+    /// Returns the raw value as integer and the physical value.
+    fn decode(&self, data: &[u8]) -> Option<(i64, f64)> {
+        let raw = self.raw(data)?;
+        let (int, value) = match self.value_type {
+            ValueType::Unsigned => (raw as i64, raw as f64),
+            ValueType::Signed => {
+                let shift = 64 - self.size as u32;
+                let signed = ((raw << shift) as i64) >> shift;
+                (signed, signed as f64)
+            }
+            ValueType::Float => (raw as i64, f32::from_bits(raw as u32) as f64),
+            ValueType::Double => (raw as i64, f64::from_bits(raw)),
+        };
+        Some((int, value * self.factor + self.offset))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub name: String,
+    pub signals: Vec<Signal>,
+}
+
+/// Messages of one or more DBC files.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Dbc {
+    /// Messages by identifier and extended flag.
+    messages: HashMap<(u32, bool), Message>,
+}
+
+impl Dbc {
+    /// Reads and merges the given DBC files.
+    pub fn from_files(paths: &[PathBuf]) -> Result<Self, Error> {
+        let mut dbc = Self::default();
+        for path in paths {
+            dbc.add_file(path)?;
+        }
+        Ok(dbc)
+    }
+
+    fn add_file(&mut self, path: &Path) -> Result<(), Error> {
+        let bytes = fs::read(path).map_err(|e| {
+            Error::Unrecoverable(format!("Fail to read DBC file {}: {e}", path.display()))
+        })?;
+        // DBC files are often encoded as Windows-1252, descriptions aren't important here.
+        self.add(&String::from_utf8_lossy(&bytes));
+        Ok(())
+    }
+
+    /// Adds the definitions of the given DBC content.
+    pub fn add(&mut self, content: &str) {
+        let mut current: Option<(u32, bool)> = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("BO_ ") {
+                current = parse_message(rest).map(|(key, msg)| {
+                    self.messages.insert(key, msg);
+                    key
+                });
+            } else if let Some(rest) = line.strip_prefix("SG_ ") {
+                if let Some(msg) = current.and_then(|key| self.messages.get_mut(&key))
+                    && let Some(signal) = parse_signal(rest)
+                {
+                    msg.signals.push(signal);
+                }
+            } else {
+                if line.is_empty() {
+                    current = None;
+                }
+                if let Some(rest) = line.strip_prefix("VAL_ ") {
+                    self.add_values(rest);
+                } else if let Some(rest) = line.strip_prefix("SIG_VALTYPE_ ") {
+                    self.add_value_type(rest);
+                }
+            }
+        }
+    }
+
+    fn signal_mut(&mut self, id: &str, name: &str) -> Option<&mut Signal> {
+        let key = dbc_id(id.parse().ok()?);
+        self.messages
+            .get_mut(&key)?
+            .signals
+            .iter_mut()
+            .find(|signal| signal.name == name)
+    }
+
+    /// Adds a value table: `<id> <signal> <value> "<description>" ... ;`
+    fn add_values(&mut self, rest: &str) {
+        let mut parts = rest.splitn(3, ' ');
+        let (Some(id), Some(name), Some(mut rest)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return;
+        };
+        let Some(signal) = self.signal_mut(id, name) else {
+            return;
+        };
+        while let Some((value, tail)) = rest.trim_start().split_once(" \"") {
+            let Some((description, tail)) = tail.split_once('"') else {
+                break;
+            };
+            if let Ok(value) = value.trim().parse::<i64>() {
+                signal.values.insert(value, description.to_owned());
+            }
+            rest = tail;
+        }
+    }
+
+    /// Sets the type of a float signal: `<id> <signal> : <1|2>;`
+    fn add_value_type(&mut self, rest: &str) {
+        let parts: Vec<&str> = rest
+            .trim_end_matches(';')
+            .split([' ', ':'])
+            .filter(|part| !part.is_empty())
+            .collect();
+        if let [id, name, value_type] = parts[..]
+            && let Some(signal) = self.signal_mut(id, name)
+        {
+            signal.value_type = match value_type {
+                "1" => ValueType::Float,
+                "2" => ValueType::Double,
+                _ => signal.value_type,
+            };
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Decodes the given frame, `None` if the message isn't defined.
+    pub fn decode(&self, frame: &CanFrame) -> Option<DecodedMessage> {
+        if frame.rtr {
+            return None;
+        }
+        let msg = self.messages.get(&(frame.id, frame.extended))?;
+        let mux = msg
+            .signals
+            .iter()
+            .find(|signal| signal.multiplexing == Multiplexing::Multiplexor)
+            .and_then(|signal| signal.raw(&frame.data));
+        let signals = msg
+            .signals
+            .iter()
+            .filter(|signal| match signal.multiplexing {
+                Multiplexing::Multiplexed(value) => mux == Some(value),
+                _ => true,
+            })
+            .filter_map(|signal| {
+                signal
+                    .decode(&frame.data)
+                    .map(|(raw, value)| DecodedSignal {
+                        name: signal.name.clone(),
+                        value,
+                        unit: signal.unit.clone(),
+                        label: signal.values.get(&raw).cloned(),
+                    })
+            })
+            .collect();
+        Some(DecodedMessage {
+            name: msg.name.clone(),
+            signals,
+        })
+    }
+}
+
+fn dbc_id(id: u32) -> (u32, bool) {
+    if id & DBC_EXTENDED_ID != 0 {
+        (id & !DBC_EXTENDED_ID, true)
+    } else {
+        (id, false)
+    }
+}
+
+/// Parses a message definition: `<id> <name>: <dlc> <sender>`
+fn parse_message(rest: &str) -> Option<((u32, bool), Message)> {
+    let (id, rest) = rest.split_once(' ')?;
+    let (name, _) = rest.split_once(':')?;
+    Some((
+        dbc_id(id.parse().ok()?),
+        Message {
+            name: name.trim().to_owned(),
+            signals: Vec::new(),
+        },
+    ))
+}
+
+/// Parses a signal definition:
+/// `<name> [M|m<value>] : <start>|<size>@<order><sign> (<factor>,<offset>) [<min>|<max>] "<unit>" <receivers>`
+fn parse_signal(rest: &str) -> Option<Signal> {
+    let (head, tail) = rest.split_once(':')?;
+    let mut head = head.split_whitespace();
+    let name = head.next()?.to_owned();
+    let multiplexing = match head.next() {
+        None => Multiplexing::None,
+        Some("M") => Multiplexing::Multiplexor,
+        Some(mux) => Multiplexing::Multiplexed(mux.strip_prefix('m')?.parse().ok()?),
+    };
+    let tail = tail.trim_start();
+    let (layout, tail) = tail.split_once(' ')?;
+    let (start_bit, layout) = layout.split_once('|')?;
+    let (size, layout) = layout.split_once('@')?;
+    let mut layout = layout.chars();
+    let byte_order = match layout.next()? {
+        '0' => ByteOrder::BigEndian,
+        '1' => ByteOrder::LittleEndian,
+        _ => return None,
+    };
+    let value_type = match layout.next()? {
+        '+' => ValueType::Unsigned,
+        '-' => ValueType::Signed,
+        _ => return None,
+    };
+    let (scaling, tail) = tail.trim_start().strip_prefix('(')?.split_once(')')?;
+    let (factor, offset) = scaling.split_once(',')?;
+    let unit = tail
+        .split_once('"')
+        .and_then(|(_, unit)| unit.split_once('"'))
+        .map(|(unit, _)| unit.to_owned())
+        .filter(|unit| !unit.is_empty());
+    Some(Signal {
+        name,
+        start_bit: start_bit.trim().parse().ok()?,
+        size: size.trim().parse().ok()?,
+        byte_order,
+        value_type,
+        factor: factor.trim().parse().ok()?,
+        offset: offset.trim().parse().ok()?,
+        unit,
+        multiplexing,
+        values: HashMap::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DBC: &str = r#"VERSION ""
+
+BO_ 291 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dashboard
+ SG_ Temperature : 16|8@1- (1,-40) [-40|215] "degC" Dashboard
+ SG_ Counter : 31|4@0+ (1,0) [0|15] "" Dashboard
+
+BO_ 2566844672 Status: 8 Gateway
+ SG_ Mode M : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Gear m1 : 8|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Voltage m2 : 8|16@1+ (0.001,0) [0|65.535] "V" Vector__XXX
+
+VAL_ 2566844672 Gear 0 "Park" 1 "Drive" 2 "Reverse" ;
+"#;
+
+    fn frame(id: u32, extended: bool, data: &[u8]) -> CanFrame {
+        CanFrame {
+            channel: String::from("1"),
+            id,
+            extended,
+            fd: false,
+            brs: false,
+            rtr: false,
+            direction: None,
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn decode_signals() {
+        let mut dbc = Dbc::default();
+        dbc.add(DBC);
+        let decoded = dbc
+            .decode(&frame(0x123, false, &[0x88, 0x13, 0xEC, 0x50, 0, 0, 0, 0]))
+            .unwrap();
+        assert_eq!(decoded.name, "Engine");
+        let values: Vec<(&str, f64)> = decoded
+            .signals
+            .iter()
+            .map(|s| (s.name.as_str(), s.value))
+            .collect();
+        // Temperature is -20 raw, the Motorola counter uses the upper nibble of byte 3.
+        assert_eq!(
+            values,
+            vec![("Speed", 1250.0), ("Temperature", -60.0), ("Counter", 5.0)]
+        );
+        assert_eq!(decoded.signals[0].to_string(), "Speed=1250 rpm");
+
+        let decoded = dbc
+            .decode(&frame(0x18FE_F100, true, &[1, 1, 0, 0, 0, 0, 0, 0]))
+            .unwrap();
+        assert_eq!(
+            decoded
+                .signals
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>(),
+            vec!["Mode=1", "Gear=1 (Drive)"]
+        );
+        let decoded = dbc
+            .decode(&frame(0x18FE_F100, true, &[2, 0xE0, 0x2E, 0, 0, 0, 0, 0]))
+            .unwrap();
+        assert_eq!(decoded.signals[1].to_string(), "Voltage=12 V");
+
+        assert!(dbc.decode(&frame(0x123, true, &[0; 8])).is_none());
+    }
+}
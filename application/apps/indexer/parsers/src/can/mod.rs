@@ -0,0 +1,207 @@
+//! # CAN trace parsers
+//!
+//! Parsers for CAN and CAN FD traces recorded in the Vector ASC and BLF formats
+//! and in the log format of Linux `candump`. Frames are decoded into messages
+//! and signals with physical values if a DBC database is available.
+//!
+//! Decoded signals are shown as `Name=value unit` pairs, so their values can be
+//! extracted by the values/chart search with terms like `EngineSpeed=(-?[\d.]+)`.
+pub mod asc;
+pub mod blf;
+pub mod candump;
+pub mod dbc;
+
+use crate::{LogMessage, separators::COLUMN_SEP};
+use serde::Serialize;
+use std::{fmt, io::Write, sync::Arc};
+
+pub use self::{asc::AscParser, blf::BlfParser, candump::CandumpParser, dbc::Dbc};
+
+/// Maximal payload length of a CAN FD frame.
+pub const CAN_FD_MAX_LEN: usize = 64;
+
+/// Payload lengths of CAN FD frames for the DLC values 9 to 15.
+const CAN_FD_DLC_LEN: [usize; 7] = [12, 16, 20, 24, 32, 48, 64];
+
+/// Returns the payload length for the given DLC.
+pub fn dlc_to_len(dlc: u8, fd: bool) -> usize {
+    match dlc {
+        0..=8 => dlc as usize,
+        9..=15 if fd => CAN_FD_DLC_LEN[dlc as usize - 9],
+        _ => 8,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Direction {
+    Rx,
+    Tx,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rx => write!(f, "Rx"),
+            Self::Tx => write!(f, "Tx"),
+        }
+    }
+}
+
+/// A single CAN or CAN FD frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CanFrame {
+    pub channel: String,
+    /// The identifier without the extended frame flag.
+    pub id: u32,
+    pub extended: bool,
+    pub fd: bool,
+    /// Bit rate switch of CAN FD frames.
+    pub brs: bool,
+    /// Remote transmission request.
+    pub rtr: bool,
+    pub direction: Option<Direction>,
+    pub data: Vec<u8>,
+}
+
+impl CanFrame {
+    fn fmt_id(&self) -> String {
+        if self.extended {
+            format!("{:08X}x", self.id)
+        } else {
+            format!("{:03X}", self.id)
+        }
+    }
+}
+
+/// A signal decoded with a DBC database.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedSignal {
+    pub name: String,
+    /// The physical value (`raw * factor + offset`).
+    pub value: f64,
+    pub unit: Option<String>,
+    /// The description of the raw value from the value table.
+    pub label: Option<String>,
+}
+
+impl fmt::Display for DecodedSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(unit) = &self.unit {
+            write!(f, " {unit}")?;
+        }
+        if let Some(label) = &self.label {
+            write!(f, " ({label})")?;
+        }
+        Ok(())
+    }
+}
+
+/// A message decoded with a DBC database.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedMessage {
+    pub name: String,
+    pub signals: Vec<DecodedSignal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CanMessage {
+    /// The time as written in the trace, relative or absolute.
+    pub time: String,
+    /// The timestamp in milliseconds since the epoch, if the start of the
+    /// measurement is known.
+    timestamp: Option<i64>,
+    pub frame: CanFrame,
+    pub decoded: Option<DecodedMessage>,
+    /// The original bytes of the frame in the trace.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl CanMessage {
+    fn new(
+        time: String,
+        timestamp: Option<i64>,
+        frame: CanFrame,
+        dbc: Option<&Arc<Dbc>>,
+        raw: Vec<u8>,
+    ) -> Self {
+        let decoded = dbc.and_then(|dbc| dbc.decode(&frame));
+        Self {
+            time,
+            timestamp,
+            frame,
+            decoded,
+            raw,
+        }
+    }
+}
+
+impl fmt::Display for CanMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frame = &self.frame;
+        let kind = match (frame.fd, frame.brs, frame.rtr) {
+            (true, true, _) => "FD BRS",
+            (true, false, _) => "FD",
+            (false, _, true) => "RTR",
+            (false, _, false) => "CAN",
+        };
+        let data = frame
+            .data
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let (name, signals) = match &self.decoded {
+            Some(decoded) => (
+                decoded.name.as_str(),
+                decoded
+                    .signals
+                    .iter()
+                    .map(|signal| signal.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            None => ("", String::new()),
+        };
+        write!(
+            f,
+            "{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}",
+            self.time,
+            frame.channel,
+            frame.fmt_id(),
+            frame.direction.map(|d| d.to_string()).unwrap_or_default(),
+            kind,
+            frame.data.len(),
+            data,
+            name,
+            signals
+        )
+    }
+}
+
+impl LogMessage for CanMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+/// Parses an identifier, a trailing `x` marks extended identifiers.
+fn parse_id(value: &str, radix: u32) -> Option<(u32, bool)> {
+    match value.strip_suffix(['x', 'X']) {
+        Some(id) => u32::from_str_radix(id, radix).ok().map(|id| (id, true)),
+        None => u32::from_str_radix(value, radix)
+            .ok()
+            .map(|id| (id, id > 0x7FF)),
+    }
+}
+
+/// Parses space separated bytes.
+fn parse_data<'a>(bytes: impl Iterator<Item = &'a str>, radix: u32) -> Option<Vec<u8>> {
+    bytes.map(|b| u8::from_str_radix(b, radix).ok()).collect()
+}
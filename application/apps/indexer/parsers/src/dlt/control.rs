@@ -0,0 +1,452 @@
+//! # Decoding of DLT control messages
+//!
+//! Decodes the responses of the control services `get_log_info`,
+//! `get_default_log_level` and `get_software_version` into readable text and
+//! encodes control requests which can be sent to an ECU.
+use crate::dlt::fmt::DLT_ARGUMENT_SENTINAL;
+use dlt_core::dlt::Endianness;
+use std::fmt;
+
+/// Service id of `set_log_level`.
+pub const SERVICE_SET_LOG_LEVEL: u8 = 0x01;
+/// Service id of `get_log_info`.
+pub const SERVICE_GET_LOG_INFO: u8 = 0x03;
+/// Service id of `get_default_log_level`.
+pub const SERVICE_GET_DEFAULT_LOG_LEVEL: u8 = 0x04;
+/// Service id of `store_config`.
+pub const SERVICE_STORE_CONFIG: u8 = 0x05;
+/// Service id of `reset_to_factory_default`.
+pub const SERVICE_RESET_TO_FACTORY_DEFAULT: u8 = 0x06;
+/// Service id of `set_default_trace_status`.
+pub const SERVICE_SET_DEFAULT_TRACE_STATUS: u8 = 0x12;
+/// Service id of `get_software_version`.
+pub const SERVICE_GET_SOFTWARE_VERSION: u8 = 0x13;
+
+/// Application and context id used for sent control requests.
+const REQUEST_APP_ID: &[u8; 4] = b"DA1\0";
+const REQUEST_CONTEXT_ID: &[u8; 4] = b"DC1\0";
+/// Communication interface of sent control requests.
+const REQUEST_COM_INTERFACE: &[u8; 4] = b"remo";
+/// Get all infos (log level, trace status and descriptions) with `get_log_info`.
+const LOG_INFO_ALL: u8 = 7;
+
+/// Number of service id bytes which are still part of the control message data,
+/// as the parser only consumes the first byte of the 32bit service id.
+const SERVICE_ID_REST_LEN: usize = 3;
+
+/// The context information of a `get_log_info` response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogInfoContext {
+    pub context_id: String,
+    pub log_level: Option<i8>,
+    pub trace_status: Option<i8>,
+    pub description: Option<String>,
+}
+
+/// The application information of a `get_log_info` response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogInfoApp {
+    pub app_id: String,
+    pub description: Option<String>,
+    pub contexts: Vec<LogInfoContext>,
+}
+
+/// A decoded control response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlResponse {
+    /// The registered applications and contexts.
+    LogInfo(Vec<LogInfoApp>),
+    /// The default log level of the ECU.
+    DefaultLogLevel(i8),
+    /// The software version of the ECU.
+    SoftwareVersion(String),
+    /// A response without further content, or an unsuccessful response.
+    Status(u8),
+}
+
+/// A control request to be sent to an ECU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlRequest {
+    SetLogLevel {
+        app_id: String,
+        context_id: String,
+        log_level: i8,
+    },
+    SetDefaultTraceStatus(i8),
+    /// Empty ids request all applications or contexts.
+    GetLogInfo {
+        app_id: String,
+        context_id: String,
+    },
+    StoreConfig,
+    ResetToFactoryDefault,
+}
+
+impl ControlRequest {
+    pub fn service_id(&self) -> u8 {
+        match self {
+            ControlRequest::SetLogLevel { .. } => SERVICE_SET_LOG_LEVEL,
+            ControlRequest::SetDefaultTraceStatus(_) => SERVICE_SET_DEFAULT_TRACE_STATUS,
+            ControlRequest::GetLogInfo { .. } => SERVICE_GET_LOG_INFO,
+            ControlRequest::StoreConfig => SERVICE_STORE_CONFIG,
+            ControlRequest::ResetToFactoryDefault => SERVICE_RESET_TO_FACTORY_DEFAULT,
+        }
+    }
+
+    /// Encodes the request as DLT control message (without storage header)
+    /// addressed to the given ECU.
+    pub fn to_message(&self, ecu_id: &str) -> Vec<u8> {
+        let mut payload = u32::from(self.service_id()).to_le_bytes().to_vec();
+        match self {
+            ControlRequest::SetLogLevel {
+                app_id,
+                context_id,
+                log_level,
+            } => {
+                payload.extend_from_slice(&id_bytes(app_id));
+                payload.extend_from_slice(&id_bytes(context_id));
+                payload.push(*log_level as u8);
+                payload.extend_from_slice(REQUEST_COM_INTERFACE);
+            }
+            ControlRequest::SetDefaultTraceStatus(status) => {
+                payload.push(*status as u8);
+                payload.extend_from_slice(REQUEST_COM_INTERFACE);
+            }
+            ControlRequest::GetLogInfo { app_id, context_id } => {
+                payload.push(LOG_INFO_ALL);
+                payload.extend_from_slice(&id_bytes(app_id));
+                payload.extend_from_slice(&id_bytes(context_id));
+                payload.extend_from_slice(REQUEST_COM_INTERFACE);
+            }
+            ControlRequest::StoreConfig | ControlRequest::ResetToFactoryDefault => {}
+        }
+        // Standard header (with ECU id) and extended header.
+        let len = 4 + 4 + 10 + payload.len();
+        let mut message = Vec::with_capacity(len);
+        // Version 1, extended header and ECU id, little endian payload.
+        message.push(0x25);
+        message.push(0);
+        message.extend_from_slice(&(len as u16).to_be_bytes());
+        message.extend_from_slice(&id_bytes(ecu_id));
+        // Non verbose control request without arguments.
+        message.push(0x16);
+        message.push(0);
+        message.extend_from_slice(REQUEST_APP_ID);
+        message.extend_from_slice(REQUEST_CONTEXT_ID);
+        message.extend_from_slice(&payload);
+        message
+    }
+}
+
+/// Returns the 4 byte representation of a DLT id, padded with zeros.
+fn id_bytes(id: &str) -> [u8; 4] {
+    let mut bytes = [0u8; 4];
+    id.bytes()
+        .take(4)
+        .zip(bytes.iter_mut())
+        .for_each(|(src, dst)| *dst = src);
+    bytes
+}
+
+impl ControlResponse {
+    /// Decodes the response for the given service from the control message data.
+    pub fn parse(service_id: u8, data: &[u8], endianness: Endianness) -> Option<Self> {
+        let mut reader = Reader {
+            data: data.get(SERVICE_ID_REST_LEN..)?,
+            endianness,
+        };
+        let status = reader.u8()?;
+        match (service_id, status) {
+            (SERVICE_GET_LOG_INFO, 3..=7) => parse_log_info(&mut reader, status),
+            (SERVICE_GET_DEFAULT_LOG_LEVEL, 0) => {
+                Some(ControlResponse::DefaultLogLevel(reader.u8()? as i8))
+            }
+            (SERVICE_GET_SOFTWARE_VERSION, 0) => {
+                let len = reader.u32()? as usize;
+                Some(ControlResponse::SoftwareVersion(reader.string(len)?))
+            }
+            _ => Some(ControlResponse::Status(status)),
+        }
+    }
+}
+
+fn parse_log_info(reader: &mut Reader, status: u8) -> Option<ControlResponse> {
+    let with_log_level = matches!(status, 4 | 6 | 7);
+    let with_trace_status = matches!(status, 5..=7);
+    let with_description = status == 7;
+    let apps_count = reader.u16()?;
+    let mut apps = Vec::with_capacity(apps_count as usize);
+    for _ in 0..apps_count {
+        let app_id = reader.id()?;
+        let contexts_count = reader.u16()?;
+        let mut contexts = Vec::with_capacity(contexts_count as usize);
+        for _ in 0..contexts_count {
+            let context_id = reader.id()?;
+            let log_level = if with_log_level {
+                Some(reader.u8()? as i8)
+            } else {
+                None
+            };
+            let trace_status = if with_trace_status {
+                Some(reader.u8()? as i8)
+            } else {
+                None
+            };
+            let description = if with_description {
+                let len = reader.u16()? as usize;
+                Some(reader.string(len)?)
+            } else {
+                None
+            };
+            contexts.push(LogInfoContext {
+                context_id,
+                log_level,
+                trace_status,
+                description,
+            });
+        }
+        let description = if with_description {
+            let len = reader.u16()? as usize;
+            Some(reader.string(len)?)
+        } else {
+            None
+        };
+        apps.push(LogInfoApp {
+            app_id,
+            description,
+            contexts,
+        });
+    }
+    Some(ControlResponse::LogInfo(apps))
+}
+
+/// Returns the name of the given DLT log level.
+pub fn log_level_name(level: i8) -> &'static str {
+    match level {
+        -1 => "default",
+        0 => "off",
+        1 => "fatal",
+        2 => "error",
+        3 => "warn",
+        4 => "info",
+        5 => "debug",
+        6 => "verbose",
+        _ => "invalid",
+    }
+}
+
+/// Returns the name of the given DLT trace status.
+pub fn trace_status_name(status: i8) -> &'static str {
+    match status {
+        -1 => "default",
+        0 => "off",
+        1 => "on",
+        _ => "invalid",
+    }
+}
+
+fn status_name(status: u8) -> &'static str {
+    match status {
+        0 => "ok",
+        1 => "not supported",
+        2 => "error",
+        8 => "no matching context",
+        9 => "overflow",
+        _ => "unknown status",
+    }
+}
+
+impl fmt::Display for ControlResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ControlResponse::LogInfo(apps) => {
+                write!(f, "{} apps", apps.len())?;
+                for app in apps {
+                    write!(f, "{DLT_ARGUMENT_SENTINAL}{}", app.app_id)?;
+                    if let Some(description) = &app.description {
+                        write!(f, " \"{description}\"")?;
+                    }
+                    for context in &app.contexts {
+                        write!(
+                            f,
+                            "{DLT_ARGUMENT_SENTINAL}{}:{}",
+                            app.app_id, context.context_id
+                        )?;
+                        if let Some(level) = context.log_level {
+                            write!(f, " level:{}", log_level_name(level))?;
+                        }
+                        if let Some(status) = context.trace_status {
+                            write!(f, " trace:{}", trace_status_name(status))?;
+                        }
+                        if let Some(description) = &context.description {
+                            write!(f, " \"{description}\"")?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            ControlResponse::DefaultLogLevel(level) => write!(f, "{}", log_level_name(*level)),
+            ControlResponse::SoftwareVersion(version) => write!(f, "{version}"),
+            ControlResponse::Status(status) => write!(f, "{}", status_name(*status)),
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    endianness: Endianness,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let data = self.data;
+        let (bytes, rest) = data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let bytes: [u8; 2] = self.take(2)?.try_into().ok()?;
+        Some(match self.endianness {
+            Endianness::Big => u16::from_be_bytes(bytes),
+            Endianness::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().ok()?;
+        Some(match self.endianness {
+            Endianness::Big => u32::from_be_bytes(bytes),
+            Endianness::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    /// Reads a 4 byte DLT id (ECU, application or context id).
+    fn id(&mut self) -> Option<String> {
+        self.string(4)
+    }
+
+    fn string(&mut self, len: usize) -> Option<String> {
+        let bytes = self.take(len)?;
+        Some(
+            String::from_utf8_lossy(bytes)
+                .trim_end_matches('\0')
+                .to_owned(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_log_info() {
+        let data: &[u8] = &[
+            0x00, 0x00, 0x00, // rest of service id
+            0x07, // status
+            0x01, 0x00, // apps count
+            b'A', b'P', b'P', b'1', // app id
+            0x02, 0x00, // contexts count
+            b'C', b'T', b'X', b'1', // context id
+            0x04, 0x01, // log level, trace status
+            0x03, 0x00, b'o', b'n', b'e', // description
+            b'C', b'T', b'X', b'2', // context id
+            0xFF, 0x00, // log level, trace status
+            0x00, 0x00, // description
+            0x03, 0x00, b'a', b'p', b'p', // description
+            b'r', b'e', b'm', b'o', // com interface
+        ];
+        let response =
+            ControlResponse::parse(SERVICE_GET_LOG_INFO, data, Endianness::Little).unwrap();
+        assert_eq!(
+            response,
+            ControlResponse::LogInfo(vec![LogInfoApp {
+                app_id: String::from("APP1"),
+                description: Some(String::from("app")),
+                contexts: vec![
+                    LogInfoContext {
+                        context_id: String::from("CTX1"),
+                        log_level: Some(4),
+                        trace_status: Some(1),
+                        description: Some(String::from("one")),
+                    },
+                    LogInfoContext {
+                        context_id: String::from("CTX2"),
+                        log_level: Some(-1),
+                        trace_status: Some(0),
+                        description: Some(String::new()),
+                    },
+                ],
+            }])
+        );
+        assert_eq!(
+            "1 apps\u{5}APP1 \"app\"\u{5}APP1:CTX1 level:info trace:on \"one\"\u{5}APP1:CTX2 level:default trace:off \"\"",
+            response.to_string()
+        );
+    }
+
+    #[test]
+    fn encode_requests() {
+        let request = ControlRequest::SetLogLevel {
+            app_id: String::from("APP1"),
+            context_id: String::from("CTX"),
+            log_level: 5,
+        };
+        assert_eq!(
+            request.to_message("ECU"),
+            vec![
+                0x25, 0x00, 0x00, 0x23, // header type, counter, length
+                b'E', b'C', b'U', 0x00, // ecu id
+                0x16, 0x00, // message info (control request), argument count
+                b'D', b'A', b'1', 0x00, // app id
+                b'D', b'C', b'1', 0x00, // context id
+                0x01, 0x00, 0x00, 0x00, // service id
+                b'A', b'P', b'P', b'1', // app id
+                b'C', b'T', b'X', 0x00, // context id
+                0x05, // log level
+                b'r', b'e', b'm', b'o', // com interface
+            ]
+        );
+        let message = ControlRequest::StoreConfig.to_message("ECU1");
+        assert_eq!(message.len(), 22);
+        assert_eq!(&message[18..], &[SERVICE_STORE_CONFIG, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn parse_default_log_level_and_version() {
+        let data: &[u8] = &[0x00, 0x00, 0x00, 0x00, 0x05];
+        assert_eq!(
+            "debug",
+            ControlResponse::parse(SERVICE_GET_DEFAULT_LOG_LEVEL, data, Endianness::Little)
+                .unwrap()
+                .to_string()
+        );
+
+        let data: &[u8] = &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, b'1', b'.', b'0',
+        ];
+        assert_eq!(
+            ControlResponse::SoftwareVersion(String::from("1.0")),
+            ControlResponse::parse(SERVICE_GET_SOFTWARE_VERSION, data, Endianness::Big).unwrap()
+        );
+
+        let data: &[u8] = &[0x00, 0x00, 0x00, 0x01];
+        assert_eq!(
+            "not supported",
+            ControlResponse::parse(SERVICE_GET_SOFTWARE_VERSION, data, Endianness::Big)
+                .unwrap()
+                .to_string()
+        );
+        assert!(
+            ControlResponse::parse(SERVICE_GET_SOFTWARE_VERSION, &[], Endianness::Big).is_none()
+        );
+    }
+}
@@ -11,7 +11,10 @@
 // from E.S.R.Labs.
 
 //! # Formatting dlt messages as text
-use crate::someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser};
+use crate::{
+    dlt::{control::ControlResponse, nw_trace::write_network_trace},
+    someip::FibexMetadata as FibexSomeipMetadata,
+};
 use chrono::{
     TimeZone,
     prelude::{DateTime, Utc},
@@ -28,7 +31,6 @@ use dlt_core::{
     service_id::service_id_lookup,
 };
 use log::trace;
-use someip_tools::parse_prefix;
 
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
@@ -39,6 +41,9 @@ use std::{
 
 /// Separator to used between the columns in DLT [`FormattableMessage`].
 pub const DLT_COLUMN_SENTINAL: char = '\u{0004}';
+/// Empty columns of tags, privacy level, segmentation and source location, which
+/// are defined for DLT v2 messages only and precede the payload.
+pub(crate) const NO_V2_COLUMNS: &str = "\u{0004}\u{0004}\u{0004}\u{0004}";
 /// Separator to used between the arguments in the payload of DLT [`FormattableMessage`].
 pub const DLT_ARGUMENT_SENTINAL: char = '\u{0005}';
 
@@ -54,7 +59,7 @@ fn try_new_from_fibex_message_info(message_info: &str) -> Option<MessageType> {
     }))
 }
 
-struct DltMessageType<'a>(&'a MessageType);
+pub(crate) struct DltMessageType<'a>(pub(crate) &'a MessageType);
 
 impl fmt::Display for DltMessageType<'_> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
@@ -151,7 +156,7 @@ impl fmt::Display for DltDltTimeStamp<'_> {
 }
 
 /// Formats the provided date time according to `RFC3339`
-fn format_time_rfc3339<Tz: TimeZone>(dt: &DateTime<Tz>) -> String {
+pub(crate) fn format_time_rfc3339<Tz: TimeZone>(dt: &DateTime<Tz>) -> String {
     dt.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)
 }
 
@@ -428,19 +433,20 @@ impl FormattableMessage<'_> {
             Some(ext) => {
                 write!(
                     f,
-                    "{}{}{}{}{}{}",
+                    "{}{}{}{}{}{}{}",
                     ext.application_id,
                     DLT_COLUMN_SENTINAL,
                     ext.context_id,
                     DLT_COLUMN_SENTINAL,
                     DltMessageType(&ext.message_type),
                     DLT_COLUMN_SENTINAL,
+                    NO_V2_COLUMNS,
                 )?;
             }
             None => {
                 write!(
                     f,
-                    "-{DLT_COLUMN_SENTINAL}-{DLT_COLUMN_SENTINAL}-{DLT_COLUMN_SENTINAL}",
+                    "-{DLT_COLUMN_SENTINAL}-{DLT_COLUMN_SENTINAL}-{DLT_COLUMN_SENTINAL}{NO_V2_COLUMNS}",
                 )?;
             }
         };
@@ -468,7 +474,7 @@ impl FormattableMessage<'_> {
             } else {
                 write!(f, "-")?;
             }
-            write!(f, "{DLT_COLUMN_SENTINAL}")?;
+            write!(f, "{DLT_COLUMN_SENTINAL}{NO_V2_COLUMNS}")?;
             fibex_info_added = !non_verbose_info.arguments.is_empty();
             for arg in non_verbose_info.arguments {
                 write!(f, "{}{} ", DLT_ARGUMENT_SENTINAL, DltArgument(&arg))?;
@@ -584,42 +590,38 @@ impl fmt::Display for FormattableMessage<'_> {
                     .try_for_each(|arg| write!(f, "{}{}", DLT_ARGUMENT_SENTINAL, DltArgument(arg)))
             }
             PayloadContent::NonVerbose(id, data) => self.format_nonverbose_data(*id, data, f),
-            PayloadContent::ControlMsg(ctrl_id, _data) => {
+            PayloadContent::ControlMsg(ctrl_id, data) => {
                 self.write_app_id_context_id_and_message_type(f)?;
                 match service_id_lookup(ctrl_id.value()) {
-                    Some((name, _desc)) => write!(f, "[{name}]"),
-                    None => write!(f, "[Unknown CtrlCommand]"),
+                    Some((name, _desc)) => write!(f, "[{name}]")?,
+                    None => write!(f, "[Unknown CtrlCommand]")?,
+                }
+                if let Some(MessageType::Control(ControlType::Response)) = self
+                    .message
+                    .extended_header
+                    .as_ref()
+                    .map(|ext_header| &ext_header.message_type)
+                    && let Some(response) = ControlResponse::parse(
+                        ctrl_id.value(),
+                        data,
+                        self.message.header.endianness,
+                    )
+                {
+                    write!(f, " {response}")?;
                 }
+                Ok(())
             }
             PayloadContent::NetworkTrace(slices) => {
                 self.write_app_id_context_id_and_message_type(f)?;
 
-                if self
+                if let Some(MessageType::NetworkTrace(trace_type)) = self
                     .message
                     .extended_header
                     .as_ref()
-                    .is_some_and(|ext_header| {
-                        matches!(
-                            ext_header.message_type,
-                            MessageType::NetworkTrace(NetworkTraceType::Ipc)
-                                | MessageType::NetworkTrace(NetworkTraceType::Someip)
-                        )
-                    })
-                    && let Some(slice) = slices.get(1)
+                    .map(|ext_header| &ext_header.message_type)
+                    && write_network_trace(f, trace_type, slices, self.fibex_someip_metadata)?
                 {
-                    match SomeipParser::parse_message(self.fibex_someip_metadata, slice, None) {
-                        Ok((_, message)) => {
-                            let prefix = slices.first().map_or_else(String::default, |s| {
-                                parse_prefix(s)
-                                    .ok()
-                                    .map_or_else(String::default, |p| format!("{} ", p.1))
-                            });
-                            return write!(f, "SOME/IP {prefix}{message:?}");
-                        }
-                        Err(error) => {
-                            return write!(f, "SOME/IP '{error}' {slice:02X?}");
-                        }
-                    }
+                    return Ok(());
                 }
 
                 slices
@@ -1,5 +1,9 @@
 pub mod attachment;
+pub mod control;
 pub mod fmt;
+mod nw_trace;
+pub mod registry;
+pub mod v2;
 
 use crate::{
     Error, LogMessage, ParseOutput, ParseYield, SingleParser, dlt::fmt::FormattableMessage,
@@ -18,11 +22,20 @@ pub use dlt_core::{
 use serde::Serialize;
 use std::{io::Write, ops::Range};
 
-use self::{attachment::FtScanner, fmt::FormatOptions};
+use self::{
+    attachment::FtScanner,
+    control::ControlResponse,
+    fmt::FormatOptions,
+    registry::{RegistryWriter, SharedDltRegistry},
+};
 
 /// The most likely minimal bytes count needed to parse a DLT message.
 const MIN_MSG_LEN: usize = 20;
 
+/// Callback for received control responses, called with the service id and
+/// the decoded response.
+pub type ControlListener = Box<dyn FnMut(u8, ControlResponse) + Send>;
+
 impl LogMessage for FormattableMessage<'_> {
     fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
         let bytes = self.message.as_bytes();
@@ -30,6 +43,63 @@ impl LogMessage for FormattableMessage<'_> {
         writer.write_all(&bytes)?;
         Ok(len)
     }
+
+    fn timestamp(&self) -> Option<i64> {
+        self.message.storage_header.as_ref().map(|header| {
+            i64::from(header.timestamp.seconds) * 1000
+                + i64::from(header.timestamp.microseconds / 1000)
+        })
+    }
+}
+
+/// A DLT message of protocol version 1 or 2.
+pub enum DltMessage<'m> {
+    V1(FormattableMessage<'m>),
+    V2(v2::FormattableMessage<'m>),
+}
+
+impl std::fmt::Display for DltMessage<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DltMessage::V1(msg) => std::fmt::Display::fmt(msg, f),
+            DltMessage::V2(msg) => std::fmt::Display::fmt(msg, f),
+        }
+    }
+}
+
+impl Serialize for DltMessage<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            DltMessage::V1(msg) => msg.serialize(serializer),
+            DltMessage::V2(msg) => msg.serialize(serializer),
+        }
+    }
+}
+
+impl LogMessage for DltMessage<'_> {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        match self {
+            DltMessage::V1(msg) => msg.to_writer(writer),
+            DltMessage::V2(msg) => {
+                let bytes = msg.message.as_bytes();
+                writer.write_all(&bytes)?;
+                Ok(bytes.len())
+            }
+        }
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        match self {
+            DltMessage::V1(msg) => msg.timestamp(),
+            DltMessage::V2(msg) => msg.message.storage_header.as_ref().map(|header| {
+                header.timestamp.seconds as i64 * 1000
+                    + i64::from(header.timestamp.nanoseconds / 1_000_000)
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -78,6 +148,9 @@ pub struct DltParser<'m> {
     pub with_storage_header: bool,
     ft_scanner: FtScanner,
     fibex_someip_metadata: Option<&'m FibexSomeipMetadata>,
+    registry: Option<RegistryWriter>,
+    control_listener: Option<ControlListener>,
+    v2_filter_config: Option<v2::FilterConfig>,
     offset: usize,
 }
 
@@ -119,9 +192,91 @@ impl<'m> DltParser<'m> {
             fmt_options,
             ft_scanner: FtScanner::new(),
             fibex_someip_metadata,
+            registry: None,
+            control_listener: None,
+            v2_filter_config: None,
             offset: 0,
         }
     }
+
+    /// Filters version 2 messages by the fields, which version 1 messages don't have.
+    #[must_use]
+    pub fn with_v2_filter(mut self, filter_config: v2::FilterConfig) -> Self {
+        self.v2_filter_config = Some(filter_config);
+        self
+    }
+
+    /// Registers the applications and contexts of all parsed messages in the given registry.
+    /// The registry is locked only for new contexts and log infos.
+    #[must_use]
+    pub fn with_registry(mut self, registry: SharedDltRegistry) -> Self {
+        self.registry = Some(RegistryWriter::new(registry));
+        self
+    }
+
+    /// Passes all received control responses to the given listener.
+    #[must_use]
+    pub fn with_control_listener(mut self, listener: ControlListener) -> Self {
+        self.control_listener = Some(listener);
+        self
+    }
+
+    fn notify_control_response(&mut self, msg: &dlt::Message) {
+        let Some(listener) = self.control_listener.as_mut() else {
+            return;
+        };
+        if let Some(dlt::MessageType::Control(dlt::ControlType::Response)) = msg
+            .extended_header
+            .as_ref()
+            .map(|ext_header| &ext_header.message_type)
+            && let dlt::PayloadContent::ControlMsg(ctrl_id, data) = &msg.payload
+            && let Some(response) =
+                ControlResponse::parse(ctrl_id.value(), data, msg.header.endianness)
+        {
+            listener(ctrl_id.value(), response);
+        }
+    }
+
+    /// Parses a message of protocol version 2. File transfers aren't scanned,
+    /// as they are defined for version 1 messages.
+    fn parse_v2_item(
+        &mut self,
+        input: &[u8],
+        timestamp: Option<u64>,
+    ) -> Result<ParseOutput<DltMessage<'m>>, Error> {
+        let (consumed, mut message) = v2::parse_message(input)?;
+        self.offset += consumed;
+        if self
+            .filter_config
+            .as_ref()
+            .is_some_and(|filter| !message.matches(filter))
+            || self
+                .v2_filter_config
+                .as_ref()
+                .is_some_and(|filter| !message.matches_v2(filter))
+        {
+            return Ok(ParseOutput::new(consumed, None));
+        }
+        if let Some(registry) = self.registry.as_mut() {
+            registry.process_v2(&message);
+        }
+        if let Some(listener) = self.control_listener.as_mut()
+            && let Some((service_id, response)) = message.control_response()
+        {
+            listener(service_id, response);
+        }
+        if message.storage_header.is_none() {
+            message.add_storage_header(timestamp);
+        }
+        let msg = v2::FormattableMessage {
+            message,
+            options: self.fmt_options,
+        };
+        Ok(ParseOutput::new(
+            consumed,
+            Some(ParseYield::Message(DltMessage::V2(msg))),
+        ))
+    }
 }
 
 impl From<DltParseError> for Error {
@@ -136,7 +291,7 @@ impl From<DltParseError> for Error {
 }
 
 impl<'m> SingleParser for DltParser<'m> {
-    type Output = FormattableMessage<'m>;
+    type Output = DltMessage<'m>;
 
     const MIN_MSG_LEN: usize = MIN_MSG_LEN;
 
@@ -144,7 +299,10 @@ impl<'m> SingleParser for DltParser<'m> {
         &mut self,
         input: &[u8],
         timestamp: Option<u64>,
-    ) -> Result<ParseOutput<FormattableMessage<'m>>, Error> {
+    ) -> Result<ParseOutput<DltMessage<'m>>, Error> {
+        if v2::is_v2(input) {
+            return self.parse_v2_item(input, timestamp);
+        }
         match dlt_message(input, self.filter_config.as_ref(), self.with_storage_header)? {
             (rest, dlt_core::parse::ParsedMessage::FilteredOut(_n)) => {
                 let consumed = input.len() - rest.len();
@@ -156,18 +314,22 @@ impl<'m> SingleParser for DltParser<'m> {
             }
             (rest, dlt_core::parse::ParsedMessage::Item(i)) => {
                 let attachment = self.ft_scanner.process(&i);
+                if let Some(registry) = self.registry.as_mut() {
+                    registry.process(&i);
+                }
+                self.notify_control_response(&i);
                 let msg_with_storage_header = if i.storage_header.is_some() {
                     i
                 } else {
                     i.add_storage_header(timestamp.map(dlt::DltTimeStamp::from_ms))
                 };
 
-                let msg = FormattableMessage {
+                let msg = DltMessage::V1(FormattableMessage {
                     message: msg_with_storage_header,
                     fibex_dlt_metadata: self.fibex_dlt_metadata,
                     options: self.fmt_options,
                     fibex_someip_metadata: self.fibex_someip_metadata,
-                };
+                });
                 let consumed = input.len() - rest.len();
                 self.offset += consumed;
                 let item = ParseOutput::new(
@@ -185,6 +347,16 @@ impl<'m> SingleParser for DltParser<'m> {
     }
 }
 
+/// Consumes the message of protocol version 1 or 2 at the start of `input`.
+fn consume_msg(input: &[u8]) -> Result<(&[u8], Option<usize>), Error> {
+    if v2::is_v2(input) {
+        let len = v2::message_len(input)?;
+        return Ok((&input[len..], Some(len)));
+    }
+    let (rest, consumed) = dlt_consume_msg(input)?;
+    Ok((rest, consumed.map(|c| c as usize)))
+}
+
 impl SingleParser for DltRangeParser {
     type Output = RangeMessage;
 
@@ -195,13 +367,13 @@ impl SingleParser for DltRangeParser {
         input: &[u8],
         _timestamp: Option<u64>,
     ) -> Result<ParseOutput<RangeMessage>, Error> {
-        let (rest, consumed) = dlt_consume_msg(input)?;
+        let (rest, consumed) = consume_msg(input)?;
         let msg = consumed.map(|c| {
-            self.offset += c as usize;
+            self.offset += c;
             RangeMessage {
                 range: Range {
                     start: self.offset,
-                    end: self.offset + c as usize,
+                    end: self.offset + c,
                 },
             }
         });
@@ -222,9 +394,9 @@ impl SingleParser for DltRawParser {
         input: &[u8],
         _timestamp: Option<u64>,
     ) -> Result<ParseOutput<RawMessage>, Error> {
-        let (rest, consumed) = dlt_consume_msg(input)?;
+        let (rest, consumed) = consume_msg(input)?;
         let msg = consumed.map(|c| RawMessage {
-            content: Vec::from(&input[0..c as usize]),
+            content: Vec::from(&input[0..c]),
         });
         let total_consumed = input.len() - rest.len();
         let item = ParseOutput::new(total_consumed, msg.map(|m| m.into()));
@@ -0,0 +1,251 @@
+//! # Decoding of DLT network trace payloads
+//!
+//! Network trace messages carry the traced bus traffic as raw slices. Known
+//! protocols are decoded here, so that a single DLT file shows the traffic with
+//! the columns of a dedicated SOME/IP session as arguments of the payload.
+use crate::{
+    Error,
+    dlt::fmt::DLT_ARGUMENT_SENTINAL,
+    someip::{
+        FibexMetadata as FibexSomeipMetadata, SomeipLogMessage, SomeipParser, labeled_columns,
+    },
+};
+use dlt_core::dlt::NetworkTraceType;
+use etherparse::{NetSlice, SlicedPacket, TransportSlice};
+use someip_tools::parse_prefix;
+use std::{
+    fmt::{self, Formatter},
+    net::IpAddr,
+};
+
+/// Writes the decoded payload of a network trace message.
+///
+/// Returns `false` if the trace type or its content is not supported, in which
+/// case nothing has been written.
+pub(crate) fn write_network_trace<S: AsRef<[u8]>>(
+    f: &mut Formatter,
+    trace_type: &NetworkTraceType,
+    slices: &[S],
+    fibex_metadata: Option<&FibexSomeipMetadata>,
+) -> Result<bool, fmt::Error> {
+    match trace_type {
+        NetworkTraceType::Ipc | NetworkTraceType::Someip => {
+            // The first slice contains the prefix with the endpoint infos,
+            // the second slice the SOME/IP message(s).
+            let (prefix, data) = match slices {
+                [prefix, data, ..] => (
+                    parse_prefix(prefix.as_ref()).ok().map(|p| p.1),
+                    data.as_ref(),
+                ),
+                _ => return Ok(false),
+            };
+            write_someip(f, prefix.as_deref(), someip_messages(data, fibex_metadata))?;
+            Ok(true)
+        }
+        NetworkTraceType::Ethernet => {
+            // Any slice of 14 bytes is a valid ethernet frame, so only frames with
+            // an IP packet are accepted.
+            let Some(packet) = slices.iter().find_map(|slice| {
+                SlicedPacket::from_ethernet(slice.as_ref())
+                    .ok()
+                    .filter(|packet| packet.net.is_some())
+            }) else {
+                return Ok(false);
+            };
+            write_ethernet(f, &packet, fibex_metadata)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Writes an ethernet frame, the transport payload is decoded as SOME/IP if possible.
+fn write_ethernet(
+    f: &mut Formatter,
+    packet: &SlicedPacket,
+    fibex_metadata: Option<&FibexSomeipMetadata>,
+) -> Result<(), fmt::Error> {
+    let (source, destination): (Option<IpAddr>, Option<IpAddr>) = match &packet.net {
+        Some(NetSlice::Ipv4(slice)) => (
+            Some(slice.header().source_addr().into()),
+            Some(slice.header().destination_addr().into()),
+        ),
+        Some(NetSlice::Ipv6(slice)) => (
+            Some(slice.header().source_addr().into()),
+            Some(slice.header().destination_addr().into()),
+        ),
+        _ => (None, None),
+    };
+    let (proto, ports, payload) = match &packet.transport {
+        Some(TransportSlice::Udp(slice)) => (
+            "UDP",
+            Some((slice.source_port(), slice.destination_port())),
+            slice.payload(),
+        ),
+        Some(TransportSlice::Tcp(slice)) => (
+            "TCP",
+            Some((slice.source_port(), slice.destination_port())),
+            slice.payload(),
+        ),
+        _ => return write!(f, "ETH {}", endpoints(source, destination, None)),
+    };
+    let endpoints = format!("{} {proto}", endpoints(source, destination, ports));
+    let mut messages = someip_messages(payload, fibex_metadata).peekable();
+    if payload.is_empty() || matches!(messages.peek(), Some(Err(_))) {
+        return write!(f, "ETH {endpoints} {payload:02X?}");
+    }
+    write_someip(f, Some(&endpoints), messages)
+}
+
+fn endpoints(
+    source: Option<IpAddr>,
+    destination: Option<IpAddr>,
+    ports: Option<(u16, u16)>,
+) -> String {
+    let endpoint = |addr: Option<IpAddr>, port: Option<u16>| match (addr, port) {
+        (Some(addr), Some(port)) => format!("{addr}:{port}"),
+        (Some(addr), None) => addr.to_string(),
+        (None, Some(port)) => format!(":{port}"),
+        (None, None) => String::from("?"),
+    };
+    format!(
+        "{} >> {}",
+        endpoint(source, ports.map(|p| p.0)),
+        endpoint(destination, ports.map(|p| p.1))
+    )
+}
+
+/// Parses the SOME/IP messages contained in the given data. Parsing stops after
+/// the first error, which is yielded along with the remaining data.
+fn someip_messages<'a>(
+    data: &'a [u8],
+    fibex_metadata: Option<&'a FibexSomeipMetadata>,
+) -> impl Iterator<Item = Result<SomeipLogMessage, (Error, &'a [u8])>> {
+    let mut rest = data;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        match SomeipParser::parse_message(fibex_metadata, None, rest, None) {
+            Ok((consumed, message)) => {
+                // Nothing more can be parsed, if nothing has been consumed.
+                rest = if consumed == 0 {
+                    &[]
+                } else {
+                    &rest[consumed.min(rest.len())..]
+                };
+                Some(Ok(message))
+            }
+            Err(error) => {
+                let failed = rest;
+                rest = &[];
+                Some(Err((error, failed)))
+            }
+        }
+    })
+}
+
+/// Writes SOME/IP messages with the columns of the SOME/IP parser as arguments. The
+/// header columns are labeled (e.g. `SERV:123`), as in the merged SOME/IP output.
+fn write_someip<'a>(
+    f: &mut Formatter,
+    prefix: Option<&str>,
+    messages: impl Iterator<Item = Result<SomeipLogMessage, (Error, &'a [u8])>>,
+) -> Result<(), fmt::Error> {
+    write!(f, "SOME/IP")?;
+    if let Some(prefix) = prefix {
+        write!(f, " {prefix}")?;
+    }
+    for message in messages {
+        match message {
+            Ok(message) => {
+                for column in labeled_columns(&message.to_string()) {
+                    write!(f, "{DLT_ARGUMENT_SENTINAL}{column}")?;
+                }
+            }
+            Err((error, rest)) => write!(f, "{DLT_ARGUMENT_SENTINAL}'{error}' {rest:02X?}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Trace<'a>(NetworkTraceType, Vec<&'a [u8]>);
+
+    impl fmt::Display for Trace<'_> {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            if !write_network_trace(f, &self.0, &self.1, None)? {
+                write!(f, "-")?;
+            }
+            Ok(())
+        }
+    }
+
+    const SOMEIP: &[u8] = &[
+        0x01, 0x03, 0x80, 0x05, // serviceId(u16), methodId(u16)
+        0x00, 0x00, 0x00, 0x0A, // length(u32)
+        0x00, 0x01, 0x00, 0x02, // clientId(u16), sessionId(u16)
+        0x01, 0x01, 0x02, 0x00, // proto(u8), version(u8), messageType,(u8) returnCode(u8)
+        0x01, 0x02, // payload([u8;2])
+    ];
+
+    const SOMEIP_COLUMNS: &str = "RPC\u{5}SERV:259\u{5}METH:32773\u{5}LENG:10\u{5}CLID:1\u{5}SEID:2\u{5}IVER:1\u{5}MSTP:2\u{5}RETC:0\u{5}[01, 02]";
+
+    #[test]
+    fn someip_trace() {
+        let prefix: &[u8] = &[0x7F, 0x00, 0x00, 0x01, 0x75, 0x30, 0x02, 0x01, 0x01];
+        let messages = [SOMEIP, SOMEIP].concat();
+
+        assert_eq!(
+            format!(
+                "SOME/IP 127.0.0.1:30000 >> INST:1 UDP\u{5}{SOMEIP_COLUMNS}\u{5}{SOMEIP_COLUMNS}"
+            ),
+            Trace(NetworkTraceType::Someip, vec![prefix, &messages[..]]).to_string()
+        );
+        assert_eq!(
+            "SOME/IP 127.0.0.1:30000 >> INST:1 UDP\u{5}'Incomplete, not enough data for a message' [01, 03]",
+            Trace(NetworkTraceType::Ipc, vec![prefix, &SOMEIP[..2]]).to_string()
+        );
+        assert_eq!("-", Trace(NetworkTraceType::Can, vec![SOMEIP]).to_string());
+    }
+
+    #[test]
+    fn ethernet_trace() {
+        let mut frame: Vec<u8> = vec![
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x02, // destination mac
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x01, // source mac
+            0x08, 0x00, // ether type (IPv4)
+            0x45, 0x00, 0x00, 0x2E, // version, ihl, dscp, total length (46)
+            0x00, 0x00, 0x40, 0x00, // identification, flags (don't fragment)
+            0x40, 0x11, 0x00, 0x00, // ttl, protocol (UDP), checksum
+            0xC0, 0xA8, 0x00, 0x01, // source address
+            0xC0, 0xA8, 0x00, 0x02, // destination address
+            0x75, 0x30, 0x75, 0x31, // source port, destination port
+            0x00, 0x1A, 0x00, 0x00, // length (26), checksum
+        ];
+        frame.extend_from_slice(SOMEIP);
+
+        assert_eq!(
+            format!("SOME/IP 192.168.0.1:30000 >> 192.168.0.2:30001 UDP\u{5}{SOMEIP_COLUMNS}"),
+            Trace(NetworkTraceType::Ethernet, vec![&frame[..]]).to_string()
+        );
+
+        let frame_len = frame.len();
+        frame.truncate(frame_len - SOMEIP.len() + 4);
+        frame[17] = 0x20; // total length (32)
+        frame[39] = 0x0C; // udp length (12)
+        assert_eq!(
+            "ETH 192.168.0.1:30000 >> 192.168.0.2:30001 UDP [01, 03, 80, 05]",
+            Trace(NetworkTraceType::Ethernet, vec![&frame[..]]).to_string()
+        );
+
+        // Frames without an IP packet aren't taken as ethernet frames.
+        assert_eq!(
+            "-",
+            Trace(NetworkTraceType::Ethernet, vec![&[0u8; 20][..]]).to_string()
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! # Registry of ECUs, applications and contexts
+//!
+//! Collects the applications and contexts seen in a DLT stream per ECU. The
+//! entries are enriched with the descriptions and log levels reported by
+//! `get_log_info` control responses.
+//!
+//! Parsers register messages with a [`RegistryWriter`], which counts messages
+//! of known contexts without locking the shared registry.
+use crate::dlt::{
+    control::{ControlResponse, LogInfoApp, SERVICE_GET_LOG_INFO},
+    v2,
+};
+use dlt_core::dlt::{ControlType, Message, MessageType, PayloadContent};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// A registry shared between the parser and its consumers.
+pub type SharedDltRegistry = Arc<Mutex<DltRegistry>>;
+
+#[derive(Debug, Default)]
+struct ContextEntry {
+    description: Option<String>,
+    log_level: Option<i8>,
+    trace_status: Option<i8>,
+    /// Shared with the writers, which count messages of the context.
+    messages: Arc<AtomicU64>,
+}
+
+#[derive(Debug, Default)]
+struct AppEntry {
+    description: Option<String>,
+    contexts: BTreeMap<String, ContextEntry>,
+}
+
+/// A single context of the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DltContextEntry {
+    pub ecu_id: String,
+    pub app_id: String,
+    pub app_description: Option<String>,
+    pub context_id: String,
+    pub context_description: Option<String>,
+    pub log_level: Option<i8>,
+    pub trace_status: Option<i8>,
+    /// The number of messages seen for this context.
+    pub messages: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct DltRegistry {
+    ecus: BTreeMap<String, BTreeMap<String, AppEntry>>,
+}
+
+impl DltRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> SharedDltRegistry {
+        Arc::new(Mutex::new(Self::new()))
+    }
+
+    /// Registers the application and context of the given message.
+    pub fn process(&mut self, msg: &Message) {
+        if let Some((ecu_id, app_id, context_id)) = context_of(msg) {
+            self.counter(ecu_id, app_id, context_id)
+                .fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if let (Some(ext_header), PayloadContent::ControlMsg(ctrl_id, data)) =
+            (&msg.extended_header, &msg.payload)
+            && matches!(
+                ext_header.message_type,
+                MessageType::Control(ControlType::Response)
+            )
+            && ctrl_id.value() == SERVICE_GET_LOG_INFO
+            && let Some(ControlResponse::LogInfo(apps)) =
+                ControlResponse::parse(ctrl_id.value(), data, msg.header.endianness)
+        {
+            self.add_log_info(ecu_of(msg), apps);
+        }
+    }
+
+    /// Registers the application and context of the given message of protocol version 2.
+    pub fn process_v2(&mut self, msg: &v2::Message) {
+        if let Some((ecu_id, app_id, context_id)) = context_of_v2(msg) {
+            self.counter(ecu_id, app_id, context_id)
+                .fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if matches!(
+            msg.header.message_type,
+            MessageType::Control(ControlType::Response)
+        ) && let Some((SERVICE_GET_LOG_INFO, ControlResponse::LogInfo(apps))) =
+            msg.control_response()
+        {
+            self.add_log_info(msg.ecu_id().unwrap_or_default(), apps);
+        }
+    }
+
+    /// Returns the message counter of the context, the context is added if it's new.
+    fn counter(&mut self, ecu_id: &str, app_id: &str, context_id: &str) -> Arc<AtomicU64> {
+        self.ecus
+            .entry(ecu_id.to_owned())
+            .or_default()
+            .entry(app_id.to_owned())
+            .or_default()
+            .contexts
+            .entry(context_id.to_owned())
+            .or_default()
+            .messages
+            .clone()
+    }
+
+    fn add_log_info(&mut self, ecu_id: &str, apps: Vec<LogInfoApp>) {
+        let ecu = self.ecus.entry(ecu_id.to_owned()).or_default();
+        for app in apps {
+            let entry = ecu.entry(app.app_id).or_default();
+            if app.description.is_some() {
+                entry.description = app.description;
+            }
+            for context in app.contexts {
+                let ctx_entry = entry.contexts.entry(context.context_id).or_default();
+                if context.description.is_some() {
+                    ctx_entry.description = context.description;
+                }
+                if context.log_level.is_some() {
+                    ctx_entry.log_level = context.log_level;
+                }
+                if context.trace_status.is_some() {
+                    ctx_entry.trace_status = context.trace_status;
+                }
+            }
+        }
+    }
+
+    /// Returns all contexts ordered by ECU, application and context id.
+    pub fn entries(&self) -> Vec<DltContextEntry> {
+        let mut entries = Vec::new();
+        for (ecu_id, apps) in &self.ecus {
+            for (app_id, app) in apps {
+                for (context_id, context) in &app.contexts {
+                    entries.push(DltContextEntry {
+                        ecu_id: ecu_id.clone(),
+                        app_id: app_id.clone(),
+                        app_description: app.description.clone(),
+                        context_id: context_id.clone(),
+                        context_description: context.description.clone(),
+                        log_level: context.log_level,
+                        trace_status: context.trace_status,
+                        messages: context.messages.load(Ordering::Relaxed),
+                    });
+                }
+            }
+        }
+        entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ecus.is_empty()
+    }
+}
+
+/// Returns the ECU id of the message.
+fn ecu_of(msg: &Message) -> &str {
+    msg.header
+        .ecu_id
+        .as_deref()
+        .or_else(|| msg.storage_header.as_ref().map(|h| h.ecu_id.as_str()))
+        .unwrap_or_default()
+}
+
+/// Returns the ECU, application and context id of messages, which are counted.
+/// Control responses aren't counted.
+fn context_of(msg: &Message) -> Option<(&str, &str, &str)> {
+    let ext_header = msg.extended_header.as_ref()?;
+    if matches!(
+        ext_header.message_type,
+        MessageType::Control(ControlType::Response)
+    ) && matches!(msg.payload, PayloadContent::ControlMsg(..))
+    {
+        return None;
+    }
+    Some((
+        ecu_of(msg),
+        &ext_header.application_id,
+        &ext_header.context_id,
+    ))
+}
+
+/// Returns the ECU, application and context id of messages of protocol version 2,
+/// which are counted. Control responses aren't counted.
+fn context_of_v2(msg: &v2::Message) -> Option<(&str, &str, &str)> {
+    let (Some(app_id), Some(context_id)) = (&msg.header.app_id, &msg.header.context_id) else {
+        return None;
+    };
+    if matches!(
+        msg.header.message_type,
+        MessageType::Control(ControlType::Response)
+    ) {
+        return None;
+    }
+    Some((msg.ecu_id().unwrap_or_default(), app_id, context_id))
+}
+
+/// Registers messages of a parser in the shared registry. Messages of known
+/// contexts are counted with the cached counters of the contexts; the shared
+/// registry is locked only for new contexts and log infos.
+#[derive(Debug)]
+pub struct RegistryWriter {
+    shared: SharedDltRegistry,
+    /// Counters of known contexts by ECU, application and context id.
+    counters: HashMap<String, HashMap<String, HashMap<String, Arc<AtomicU64>>>>,
+}
+
+impl RegistryWriter {
+    pub fn new(shared: SharedDltRegistry) -> Self {
+        Self {
+            shared,
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Registers the application and context of the given message.
+    pub fn process(&mut self, msg: &Message) {
+        self.register(context_of(msg), |registry| registry.process(msg));
+    }
+
+    /// Registers the application and context of the given message of protocol version 2.
+    pub fn process_v2(&mut self, msg: &v2::Message) {
+        self.register(context_of_v2(msg), |registry| registry.process_v2(msg));
+    }
+
+    /// Counts the message of a known context, otherwise registers the message with
+    /// `process` in the shared registry and caches the counter of its context.
+    fn register(
+        &mut self,
+        context: Option<(&str, &str, &str)>,
+        process: impl FnOnce(&mut DltRegistry),
+    ) {
+        if let Some((ecu_id, app_id, context_id)) = context
+            && let Some(counter) = self
+                .counters
+                .get(ecu_id)
+                .and_then(|apps| apps.get(app_id))
+                .and_then(|contexts| contexts.get(context_id))
+        {
+            counter.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        let counter = {
+            let Ok(mut shared) = self.shared.lock() else {
+                return;
+            };
+            process(&mut shared);
+            context.map(|(ecu_id, app_id, context_id)| shared.counter(ecu_id, app_id, context_id))
+        };
+        if let (Some((ecu_id, app_id, context_id)), Some(counter)) = (context, counter) {
+            self.counters
+                .entry(ecu_id.to_owned())
+                .or_default()
+                .entry(app_id.to_owned())
+                .or_default()
+                .insert(context_id.to_owned(), counter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dlt_core::parse::{ParsedMessage, dlt_message};
+
+    #[test]
+    fn register_messages_and_log_info() {
+        #[rustfmt::skip]
+        let log_msg: &[u8] = &[
+            0x3D, 0x01, 0x00, 0x1F, // header type, counter, length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x00, 0x01, // session id
+            0x00, 0x00, 0x00, 0x00, // timestamp
+            0x41, 0x01, // message info (verbose log info), argument count
+            b'A', b'P', b'P', b'1', // app id
+            b'C', b'T', b'X', b'1', // context id
+            0x11, 0x00, 0x00, 0x00, // type info (bool)
+            0x01, // value
+        ];
+        #[rustfmt::skip]
+        let control_msg: &[u8] = &[
+            0x3D, 0x02, 0x00, 0x37, // header type, counter, length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x00, 0x01, // session id
+            0x00, 0x00, 0x00, 0x00, // timestamp
+            0x26, 0x00, // message info (control response), argument count
+            b'D', b'A', b'0', b'1', // app id
+            b'D', b'C', b'0', b'1', // context id
+            0x03, 0x00, 0x00, 0x00, // service id
+            0x07, // status
+            0x01, 0x00, // apps count
+            b'A', b'P', b'P', b'1', // app id
+            0x01, 0x00, // contexts count
+            b'C', b'T', b'X', b'1', // context id
+            0x04, 0x01, // log level, trace status
+            0x03, 0x00, b'o', b'n', b'e', // description
+            0x03, 0x00, b'a', b'p', b'p', // description
+        ];
+        let shared = DltRegistry::shared();
+        let mut writer = RegistryWriter::new(shared.clone());
+        for input in [log_msg, log_msg, control_msg] {
+            let Ok((_, ParsedMessage::Item(msg))) = dlt_message(input, None, false) else {
+                panic!("invalid message");
+            };
+            writer.process(&msg);
+        }
+        // The counter of the known context is cached by the writer.
+        assert_eq!(writer.counters["ECU1"]["APP1"].len(), 1);
+        assert_eq!(
+            shared.lock().unwrap().entries(),
+            vec![DltContextEntry {
+                ecu_id: String::from("ECU1"),
+                app_id: String::from("APP1"),
+                app_description: Some(String::from("app")),
+                context_id: String::from("CTX1"),
+                context_description: Some(String::from("one")),
+                log_level: Some(4),
+                trace_status: Some(1),
+                messages: 2,
+            }]
+        );
+    }
+}
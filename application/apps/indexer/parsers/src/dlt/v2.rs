@@ -0,0 +1,975 @@
+//! # DLT protocol version 2
+//!
+//! Parsing of messages of the DLT protocol version 2, introduced with AUTOSAR
+//! R22-11. `dlt-core` handles version 1 only, so version 2 messages are parsed
+//! here. Both versions can be mixed within the same stream, as the version is
+//! encoded at the same position of the header.
+//!
+//! All fields of version 2 headers and payloads are big endian. A message can
+//! be preceded by the storage header of version 1 or by the one of version 2,
+//! which carries a nanosecond timestamp and an ECU id of variable length.
+//!
+//! File transfers and FIBEX descriptions of non verbose messages are supported
+//! for version 1 messages only.
+use crate::{
+    Error,
+    dlt::{
+        control::ControlResponse,
+        fmt::{
+            DLT_ARGUMENT_SENTINAL, DLT_COLUMN_SENTINAL, DltMessageType, FormatOptions,
+            format_time_rfc3339,
+        },
+    },
+};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use chrono::{DateTime, Utc};
+use dlt_core::{
+    dlt::{ControlType, Endianness, LogLevel, MessageType, NetworkTraceType},
+    filtering::ProcessedDltFilterConfig,
+    service_id::service_id_lookup,
+};
+use serde::{
+    Serialize,
+    ser::{SerializeStruct, Serializer},
+};
+use std::{
+    collections::HashSet,
+    fmt::{self, Formatter},
+};
+
+/// The protocol version of the header type.
+pub const DLT_V2_VERSION: u8 = 2;
+
+const STORAGE_HEADER_V1_PATTERN: &[u8] = b"DLT\x01";
+const STORAGE_HEADER_V2_PATTERN: &[u8] = b"DLT\x02";
+const STORAGE_HEADER_V1_LEN: usize = 16;
+/// Length of the storage header of version 2 without the ECU id.
+const STORAGE_HEADER_V2_MIN_LEN: usize = 14;
+/// Length of the header type, message counter and length fields.
+const BASE_HEADER_LEN: usize = 7;
+
+// Flags of the first byte of the header type.
+const HTYP_CNTI_MASK: u8 = 0x03;
+const HTYP_WEID: u8 = 0x04;
+const HTYP_WACID: u8 = 0x08;
+const HTYP_WSID: u8 = 0x10;
+// Flags of the second byte of the header type.
+const HTYP_WSFLN: u8 = 0x01;
+const HTYP_WTGS: u8 = 0x02;
+const HTYP_WPVL: u8 = 0x04;
+const HTYP_WSGM: u8 = 0x08;
+
+// Flags of the type info of verbose arguments.
+const TYPE_LENGTH_MASK: u32 = 0x0F;
+const TYPE_BOOL: u32 = 0x10;
+const TYPE_SINT: u32 = 0x20;
+const TYPE_UINT: u32 = 0x40;
+const TYPE_FLOA: u32 = 0x80;
+const TYPE_STRG: u32 = 0x200;
+const TYPE_RAWD: u32 = 0x400;
+const TYPE_VARI: u32 = 0x800;
+/// Arrays, fixed point values, trace info and structures aren't decoded.
+const TYPE_UNSUPPORTED: u32 = 0x100 | 0x1000 | 0x2000 | 0x4000;
+
+/// The kind of content, given by the content information of the header type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ContentInfo {
+    Verbose,
+    NonVerbose,
+    Control,
+}
+
+/// A timestamp with nanosecond resolution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Timestamp {
+    pub seconds: u64,
+    pub nanoseconds: u32,
+}
+
+impl Timestamp {
+    pub fn from_ms(ms: u64) -> Self {
+        Self {
+            seconds: ms / 1000,
+            nanoseconds: (ms % 1000) as u32 * 1_000_000,
+        }
+    }
+
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(i64::try_from(self.seconds).ok()?, self.nanoseconds)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{:09}", self.seconds, self.nanoseconds)
+    }
+}
+
+/// Segmentation of a large payload into several messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Segmentation {
+    /// The first frame, with the total length of the payload.
+    First { total_len: u64 },
+    /// A consecutive frame, with its sequence counter.
+    Consecutive { sequence: u32 },
+    /// The last frame.
+    Last,
+    /// The transfer was aborted for the given reason.
+    Abort { reason: u8 },
+}
+
+impl fmt::Display for Segmentation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Segmentation::First { total_len } => write!(f, "FF/{total_len}"),
+            Segmentation::Consecutive { sequence } => write!(f, "CF/{sequence}"),
+            Segmentation::Last => write!(f, "LF"),
+            Segmentation::Abort { reason } => write!(f, "ABORT/{reason}"),
+        }
+    }
+}
+
+/// The location in the source code the message was sent from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageHeader {
+    pub timestamp: Timestamp,
+    pub ecu_id: String,
+}
+
+/// The header of a version 2 message, including the extension header.
+#[derive(Debug, Clone, Serialize)]
+pub struct Header {
+    pub content: ContentInfo,
+    pub message_counter: u8,
+    /// The message type, given by the message info.
+    pub message_type: MessageType,
+    /// The number of arguments of verbose and control messages.
+    pub argument_count: Option<u8>,
+    /// The timestamp of verbose and non verbose messages.
+    pub timestamp: Option<Timestamp>,
+    /// The message id of non verbose messages.
+    pub message_id: Option<u32>,
+    pub ecu_id: Option<String>,
+    pub app_id: Option<String>,
+    pub context_id: Option<String>,
+    pub session_id: Option<u32>,
+    pub source: Option<SourceLocation>,
+    pub tags: Vec<String>,
+    pub privacy_level: Option<u8>,
+    pub segmentation: Option<Segmentation>,
+}
+
+/// A message of the DLT protocol version 2.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub storage_header: Option<StorageHeader>,
+    pub header: Header,
+    pub payload: Vec<u8>,
+    /// The bytes of the storage header the message was read with.
+    storage_bytes: Vec<u8>,
+    /// The bytes of the message without the storage header.
+    bytes: Vec<u8>,
+}
+
+/// Filters of the fields, which are available in version 2 messages only.
+/// Version 1 messages aren't affected by these filters.
+#[derive(Debug, Clone, Default)]
+pub struct FilterConfig {
+    /// Only messages with at least one of the tags are selected.
+    pub tags: Option<HashSet<String>>,
+    /// Only messages without privacy level, or with the privacy level up to
+    /// the given one, are selected.
+    pub max_privacy_level: Option<u8>,
+}
+
+impl FilterConfig {
+    pub fn new(tags: Option<Vec<String>>, max_privacy_level: Option<u8>) -> Self {
+        Self {
+            tags: tags.map(|tags| tags.into_iter().collect()),
+            max_privacy_level,
+        }
+    }
+}
+
+/// Reads the big endian fields of a message whose length is already known,
+/// so missing bytes mean that the message is malformed.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(Error::Parse(String::from(
+                "DLT v2 message is shorter than its fields",
+            )));
+        }
+        let (head, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        Ok(BigEndian::read_u16(self.take(2)?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(BigEndian::read_u32(self.take(4)?))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(BigEndian::read_u64(self.take(8)?))
+    }
+
+    /// Reads 32 bits of nanoseconds followed by 40 bits of seconds.
+    fn timestamp(&mut self) -> Result<Timestamp, Error> {
+        let nanoseconds = self.u32()?;
+        let seconds = BigEndian::read_uint(self.take(5)?, 5);
+        Ok(Timestamp {
+            seconds,
+            nanoseconds,
+        })
+    }
+
+    /// Reads a string prefixed with its 8 bit length.
+    fn string(&mut self) -> Result<String, Error> {
+        let len = self.u8()? as usize;
+        Ok(text(self.take(len)?))
+    }
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .to_owned()
+}
+
+/// Checks whether the message at the start of `input` is of version 2. The
+/// message can be preceded by a storage header of either version.
+pub fn is_v2(input: &[u8]) -> bool {
+    if input.starts_with(STORAGE_HEADER_V2_PATTERN) {
+        return true;
+    }
+    let offset = if input.starts_with(STORAGE_HEADER_V1_PATTERN) {
+        STORAGE_HEADER_V1_LEN
+    } else {
+        0
+    };
+    input
+        .get(offset)
+        .is_some_and(|htyp| htyp >> 5 == DLT_V2_VERSION)
+}
+
+/// Parses the storage header at the start of `input`, if there is one, and
+/// returns its length.
+fn storage_header(input: &[u8]) -> Result<Option<(usize, StorageHeader)>, Error> {
+    if input.starts_with(STORAGE_HEADER_V1_PATTERN) {
+        let header = input
+            .get(..STORAGE_HEADER_V1_LEN)
+            .ok_or(Error::Incomplete)?;
+        let seconds = LittleEndian::read_u32(&header[4..8]);
+        let microseconds = LittleEndian::read_u32(&header[8..12]);
+        let timestamp = Timestamp {
+            seconds: u64::from(seconds),
+            nanoseconds: microseconds.saturating_mul(1000),
+        };
+        return Ok(Some((
+            STORAGE_HEADER_V1_LEN,
+            StorageHeader {
+                timestamp,
+                ecu_id: text(&header[12..16]),
+            },
+        )));
+    }
+    if input.starts_with(STORAGE_HEADER_V2_PATTERN) {
+        let header = input
+            .get(..STORAGE_HEADER_V2_MIN_LEN)
+            .ok_or(Error::Incomplete)?;
+        let ecu_len = header[STORAGE_HEADER_V2_MIN_LEN - 1] as usize;
+        let len = STORAGE_HEADER_V2_MIN_LEN + ecu_len;
+        let ecu_id = input
+            .get(STORAGE_HEADER_V2_MIN_LEN..len)
+            .ok_or(Error::Incomplete)?;
+        let timestamp = Timestamp {
+            nanoseconds: BigEndian::read_u32(&header[4..8]),
+            seconds: BigEndian::read_uint(&header[8..13], 5),
+        };
+        return Ok(Some((
+            len,
+            StorageHeader {
+                timestamp,
+                ecu_id: text(ecu_id),
+            },
+        )));
+    }
+    Ok(None)
+}
+
+/// Returns the length of the version 2 message at the start of `input`,
+/// including its storage header.
+pub fn message_len(input: &[u8]) -> Result<usize, Error> {
+    let storage_len = storage_header(input)?.map_or(0, |(len, _)| len);
+    let base = input
+        .get(storage_len..storage_len + BASE_HEADER_LEN)
+        .ok_or(Error::Incomplete)?;
+    let len = BigEndian::read_u16(&base[5..7]) as usize;
+    if len < BASE_HEADER_LEN {
+        return Err(Error::Parse(format!("invalid DLT v2 message length {len}")));
+    }
+    if input.len() < storage_len + len {
+        return Err(Error::Incomplete);
+    }
+    Ok(storage_len + len)
+}
+
+/// Parses the version 2 message at the start of `input` and returns the
+/// number of consumed bytes.
+pub fn parse_message(input: &[u8]) -> Result<(usize, Message), Error> {
+    let consumed = message_len(input)?;
+    let (storage_len, storage_header) = match storage_header(input)? {
+        Some((len, header)) => (len, Some(header)),
+        None => (0, None),
+    };
+    let bytes = &input[storage_len..consumed];
+    let version = bytes[0] >> 5;
+    if version != DLT_V2_VERSION {
+        return Err(Error::Parse(format!(
+            "unexpected DLT protocol version {version}"
+        )));
+    }
+    let mut reader = Reader {
+        data: &bytes[BASE_HEADER_LEN..],
+    };
+    let header = parse_header(bytes, &mut reader)?;
+    Ok((
+        consumed,
+        Message {
+            storage_header,
+            header,
+            payload: reader.data.to_vec(),
+            storage_bytes: input[..storage_len].to_vec(),
+            bytes: bytes.to_vec(),
+        },
+    ))
+}
+
+fn parse_header(bytes: &[u8], reader: &mut Reader) -> Result<Header, Error> {
+    let htyp = [bytes[0], bytes[1]];
+    let content = match htyp[0] & HTYP_CNTI_MASK {
+        0 => ContentInfo::Verbose,
+        1 => ContentInfo::NonVerbose,
+        2 => ContentInfo::Control,
+        n => {
+            return Err(Error::Parse(format!(
+                "reserved DLT v2 content information {n}"
+            )));
+        }
+    };
+    let message_info = reader.u8()?;
+    let argument_count = if content != ContentInfo::NonVerbose {
+        Some(reader.u8()?)
+    } else {
+        None
+    };
+    let timestamp = if content != ContentInfo::Control {
+        Some(reader.timestamp()?)
+    } else {
+        None
+    };
+    let message_id = if content == ContentInfo::NonVerbose {
+        Some(reader.u32()?)
+    } else {
+        None
+    };
+    let ecu_id = if htyp[0] & HTYP_WEID != 0 {
+        Some(reader.string()?)
+    } else {
+        None
+    };
+    let (app_id, context_id) = if htyp[0] & HTYP_WACID != 0 {
+        (Some(reader.string()?), Some(reader.string()?))
+    } else {
+        (None, None)
+    };
+    let session_id = if htyp[0] & HTYP_WSID != 0 {
+        Some(reader.u32()?)
+    } else {
+        None
+    };
+    let source = if htyp[1] & HTYP_WSFLN != 0 {
+        Some(SourceLocation {
+            file: reader.string()?,
+            line: reader.u32()?,
+        })
+    } else {
+        None
+    };
+    let mut tags = Vec::new();
+    if htyp[1] & HTYP_WTGS != 0 {
+        for _ in 0..reader.u8()? {
+            tags.push(reader.string()?);
+        }
+    }
+    let privacy_level = if htyp[1] & HTYP_WPVL != 0 {
+        Some(reader.u8()?)
+    } else {
+        None
+    };
+    let segmentation = if htyp[1] & HTYP_WSGM != 0 {
+        Some(match reader.u8()? {
+            0 => Segmentation::First {
+                total_len: reader.u64()?,
+            },
+            1 => Segmentation::Consecutive {
+                sequence: reader.u32()?,
+            },
+            2 => Segmentation::Last,
+            3 => Segmentation::Abort {
+                reason: reader.u8()?,
+            },
+            n => {
+                return Err(Error::Parse(format!(
+                    "invalid DLT v2 segmentation frame type {n}"
+                )));
+            }
+        })
+    } else {
+        None
+    };
+    Ok(Header {
+        content,
+        message_counter: bytes[4],
+        message_type: message_type(message_info),
+        argument_count,
+        timestamp,
+        message_id,
+        ecu_id,
+        app_id,
+        context_id,
+        session_id,
+        source,
+        tags,
+        privacy_level,
+        segmentation,
+    })
+}
+
+/// Maps the message type and the message type info of the message info.
+fn message_type(message_info: u8) -> MessageType {
+    let mstp = (message_info >> 1) & 0b111;
+    let mtin = message_info >> 4;
+    match (mstp, mtin) {
+        (0, 1) => MessageType::Log(LogLevel::Fatal),
+        (0, 2) => MessageType::Log(LogLevel::Error),
+        (0, 3) => MessageType::Log(LogLevel::Warn),
+        (0, 4) => MessageType::Log(LogLevel::Info),
+        (0, 5) => MessageType::Log(LogLevel::Debug),
+        (0, 6) => MessageType::Log(LogLevel::Verbose),
+        (2, 1) => MessageType::NetworkTrace(NetworkTraceType::Ipc),
+        (2, 2) => MessageType::NetworkTrace(NetworkTraceType::Can),
+        (2, 3) => MessageType::NetworkTrace(NetworkTraceType::Flexray),
+        (2, 4) => MessageType::NetworkTrace(NetworkTraceType::Most),
+        (2, 5) => MessageType::NetworkTrace(NetworkTraceType::Ethernet),
+        (2, 6) => MessageType::NetworkTrace(NetworkTraceType::Someip),
+        (3, 1) => MessageType::Control(ControlType::Request),
+        (3, 2) => MessageType::Control(ControlType::Response),
+        _ => MessageType::Unknown((mstp, mtin)),
+    }
+}
+
+/// Decodes the verbose arguments and returns the remaining bytes, which
+/// couldn't be decoded.
+fn verbose_arguments(payload: &[u8], count: u8) -> (Vec<String>, &[u8]) {
+    let mut reader = Reader { data: payload };
+    let mut arguments = Vec::new();
+    for _ in 0..count {
+        let before = reader.data;
+        match argument(&mut reader) {
+            Ok(argument) => arguments.push(argument),
+            Err(_) => {
+                reader.data = before;
+                break;
+            }
+        }
+    }
+    (arguments, reader.data)
+}
+
+/// Decodes a verbose argument, formatted like the ones of version 1.
+fn argument(reader: &mut Reader) -> Result<String, Error> {
+    let type_info = reader.u32()?;
+    if type_info & TYPE_UNSUPPORTED != 0 {
+        return Err(Error::Parse(format!(
+            "unsupported argument type {type_info:#010X}"
+        )));
+    }
+    let with_name = type_info & TYPE_VARI != 0;
+    let mut argument = String::new();
+    if type_info & (TYPE_STRG | TYPE_RAWD) != 0 {
+        let len = reader.u16()? as usize;
+        if with_name {
+            let name_len = reader.u16()? as usize;
+            argument.push_str(&text(reader.take(name_len)?));
+        }
+        let value = reader.take(len)?;
+        if type_info & TYPE_STRG != 0 {
+            text(value).lines().for_each(|line| argument.push_str(line));
+        } else {
+            argument.push_str(&format!("{value:02X?}"));
+        }
+        return Ok(argument);
+    }
+    let len = match type_info & TYPE_LENGTH_MASK {
+        1 => 1,
+        2 => 2,
+        3 => 4,
+        4 => 8,
+        5 => 16,
+        n => return Err(Error::Parse(format!("invalid argument type length {n}"))),
+    };
+    if with_name {
+        let name_len = reader.u16()? as usize;
+        let unit_len = if type_info & TYPE_BOOL == 0 {
+            reader.u16()? as usize
+        } else {
+            0
+        };
+        argument.push_str(&text(reader.take(name_len)?));
+        argument.push_str(&text(reader.take(unit_len)?));
+    }
+    let number = BigEndian::read_uint128(reader.take(len)?, len);
+    let value = if type_info & TYPE_BOOL != 0 {
+        (number != 0).to_string()
+    } else if type_info & TYPE_SINT != 0 {
+        let shift = 128 - len * 8;
+        (((number << shift) as i128) >> shift).to_string()
+    } else if type_info & TYPE_UINT != 0 {
+        number.to_string()
+    } else if type_info & TYPE_FLOA != 0 && len == 4 {
+        f32::from_bits(number as u32).to_string()
+    } else if type_info & TYPE_FLOA != 0 && len == 8 {
+        f64::from_bits(number as u64).to_string()
+    } else {
+        return Err(Error::Parse(format!(
+            "unsupported argument type {type_info:#010X}"
+        )));
+    };
+    argument.push_str(&value);
+    Ok(argument)
+}
+
+/// Checks whether the optional list of allowed ids contains the given id.
+fn allowed<'a>(ids: Option<impl IntoIterator<Item = &'a String>>, id: Option<&str>) -> bool {
+    match ids {
+        Some(ids) => id.is_some_and(|id| ids.into_iter().any(|allowed| allowed == id)),
+        None => true,
+    }
+}
+
+impl Message {
+    /// The ECU id of the header, or of the storage header as fallback.
+    pub fn ecu_id(&self) -> Option<&str> {
+        self.header
+            .ecu_id
+            .as_deref()
+            .or_else(|| self.storage_header.as_ref().map(|h| h.ecu_id.as_str()))
+    }
+
+    /// Checks the message against the ECU, application and context ids and
+    /// the minimal log level of the filter.
+    pub fn matches(&self, filter: &ProcessedDltFilterConfig) -> bool {
+        if let (Some(min_level), MessageType::Log(level)) =
+            (filter.min_log_level.as_ref(), &self.header.message_type)
+            && level > min_level
+        {
+            return false;
+        }
+        allowed(filter.ecu_ids.as_ref(), self.ecu_id())
+            && allowed(filter.app_ids.as_ref(), self.header.app_id.as_deref())
+            && allowed(
+                filter.context_ids.as_ref(),
+                self.header.context_id.as_deref(),
+            )
+    }
+
+    /// Checks the message against the tags and the privacy level of the filter.
+    pub fn matches_v2(&self, filter: &FilterConfig) -> bool {
+        if let (Some(max_level), Some(level)) =
+            (filter.max_privacy_level, self.header.privacy_level)
+            && level > max_level
+        {
+            return false;
+        }
+        filter
+            .tags
+            .as_ref()
+            .is_none_or(|tags| self.header.tags.iter().any(|tag| tags.contains(tag)))
+    }
+
+    /// Adds a storage header with the given timestamp in milliseconds, or the
+    /// timestamp of the message if there is none.
+    pub fn add_storage_header(&mut self, timestamp: Option<u64>) {
+        let timestamp = timestamp
+            .map(Timestamp::from_ms)
+            .or(self.header.timestamp)
+            .unwrap_or_default();
+        self.storage_header = Some(StorageHeader {
+            timestamp,
+            ecu_id: self.header.ecu_id.clone().unwrap_or_default(),
+        });
+    }
+
+    /// The service id of control messages.
+    pub fn service_id(&self) -> Option<u8> {
+        if self.header.content != ContentInfo::Control {
+            return None;
+        }
+        u8::try_from(BigEndian::read_u32(self.payload.get(..4)?)).ok()
+    }
+
+    /// Decodes the payload of control responses.
+    pub fn control_response(&self) -> Option<(u8, ControlResponse)> {
+        if !matches!(
+            self.header.message_type,
+            MessageType::Control(ControlType::Response)
+        ) {
+            return None;
+        }
+        let service_id = self.service_id()?;
+        // The response data starts with the last 3 bytes of the service id.
+        ControlResponse::parse(service_id, self.payload.get(1..)?, Endianness::Big)
+            .map(|response| (service_id, response))
+    }
+
+    /// Returns the decoded arguments of verbose messages.
+    fn arguments(&self) -> Option<Vec<String>> {
+        if self.header.content != ContentInfo::Verbose {
+            return None;
+        }
+        let (mut arguments, rest) =
+            verbose_arguments(&self.payload, self.header.argument_count.unwrap_or(0));
+        if !rest.is_empty() {
+            arguments.push(format!("{rest:02X?}"));
+        }
+        Some(arguments)
+    }
+
+    /// Formats the payload of non verbose and control messages.
+    fn payload_summary(&self) -> String {
+        match self.header.content {
+            ContentInfo::Control => {
+                let mut summary = match self.service_id().and_then(service_id_lookup) {
+                    Some((name, _desc)) => format!("[{name}]"),
+                    None => String::from("[Unknown CtrlCommand]"),
+                };
+                if let Some((_, response)) = self.control_response() {
+                    summary.push_str(&format!(" {response}"));
+                }
+                summary
+            }
+            _ => format!(
+                "{DLT_ARGUMENT_SENTINAL}[{}]{DLT_ARGUMENT_SENTINAL} {:02X?}",
+                self.header.message_id.unwrap_or_default(),
+                self.payload
+            ),
+        }
+    }
+
+    /// Returns the message as bytes, preceded by the storage header it was read
+    /// with. An added storage header is written as the one of version 2.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.storage_bytes.len() + self.bytes.len());
+        if !self.storage_bytes.is_empty() {
+            bytes.extend_from_slice(&self.storage_bytes);
+        } else if let Some(header) = &self.storage_header {
+            let ecu_id = &header.ecu_id.as_bytes()[..header.ecu_id.len().min(u8::MAX as usize)];
+            bytes.extend_from_slice(STORAGE_HEADER_V2_PATTERN);
+            bytes.extend_from_slice(&header.timestamp.nanoseconds.to_be_bytes());
+            bytes.extend_from_slice(&header.timestamp.seconds.to_be_bytes()[3..]);
+            bytes.push(ecu_id.len() as u8);
+            bytes.extend_from_slice(ecu_id);
+        }
+        bytes.extend_from_slice(&self.bytes);
+        bytes
+    }
+}
+
+/// A version 2 message that can be formatted.
+pub struct FormattableMessage<'a> {
+    pub message: Message,
+    pub options: Option<&'a FormatOptions>,
+}
+
+impl fmt::Display for FormattableMessage<'_> {
+    /// Formats the message with the columns of version 1 messages. The tags,
+    /// privacy level, segmentation and source location precede the payload.
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let header = &self.message.header;
+        if let Some(storage_header) = &self.message.storage_header {
+            let timestamp = &storage_header.timestamp;
+            match (timestamp.date_time(), self.options.and_then(|o| o.tz)) {
+                (Some(dt), Some(tz)) => write!(f, "{}", dt.with_timezone(&tz))?,
+                (Some(dt), None) => write!(f, "{}", format_time_rfc3339(&dt))?,
+                (None, _) => write!(f, "no valid timestamp for {timestamp}")?,
+            }
+            write!(f, "{DLT_COLUMN_SENTINAL}{}", storage_header.ecu_id)?;
+        }
+        write!(
+            f,
+            "{DLT_COLUMN_SENTINAL}{DLT_V2_VERSION}{DLT_COLUMN_SENTINAL}"
+        )?;
+        if let Some(id) = header.session_id {
+            write!(f, "{id}")?;
+        }
+        write!(
+            f,
+            "{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}",
+            header.message_counter
+        )?;
+        if let Some(timestamp) = &header.timestamp {
+            write!(f, "{timestamp}")?;
+        }
+        write!(
+            f,
+            "{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}",
+            header.ecu_id.as_deref().unwrap_or_default(),
+            header.app_id.as_deref().unwrap_or("-"),
+            header.context_id.as_deref().unwrap_or("-"),
+            DltMessageType(&header.message_type),
+        )?;
+        write!(f, "{}{DLT_COLUMN_SENTINAL}", header.tags.join(", "))?;
+        if let Some(level) = header.privacy_level {
+            write!(f, "{level}")?;
+        }
+        write!(f, "{DLT_COLUMN_SENTINAL}")?;
+        if let Some(segmentation) = &header.segmentation {
+            write!(f, "{segmentation}")?;
+        }
+        write!(f, "{DLT_COLUMN_SENTINAL}")?;
+        if let Some(source) = &header.source {
+            write!(f, "{source}")?;
+        }
+        write!(f, "{DLT_COLUMN_SENTINAL}")?;
+        match self.message.arguments() {
+            Some(arguments) => {
+                for argument in arguments {
+                    write!(f, "{DLT_ARGUMENT_SENTINAL}{argument}")?;
+                }
+            }
+            None => write!(f, "{}", self.message.payload_summary())?,
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for FormattableMessage<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Message", 15)?;
+        let header = &self.message.header;
+        state.serialize_field(
+            "date-time",
+            &self
+                .message
+                .storage_header
+                .as_ref()
+                .and_then(|sh| sh.timestamp.date_time())
+                .map(|dt| format_time_rfc3339(&dt)),
+        )?;
+        state.serialize_field("version", &DLT_V2_VERSION)?;
+        state.serialize_field("message-counter", &header.message_counter)?;
+        state.serialize_field("ecu-id", &header.ecu_id)?;
+        state.serialize_field("session-id", &header.session_id)?;
+        state.serialize_field("timestamp", &header.timestamp)?;
+        state.serialize_field("app-id", &header.app_id)?;
+        state.serialize_field("context-id", &header.context_id)?;
+        state.serialize_field("message-type", &header.message_type)?;
+        match self.message.arguments() {
+            Some(arguments) => state.serialize_field("payload", &arguments.join("|"))?,
+            None => state.serialize_field("payload", &self.message.payload_summary())?,
+        }
+        state.serialize_field("tags", &header.tags)?;
+        state.serialize_field("privacy-level", &header.privacy_level)?;
+        state.serialize_field("segmentation", &header.segmentation)?;
+        state.serialize_field("source", &header.source)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dlt_core::filtering::DltFilterConfig;
+
+    /// Verbose info message with ECU, application and context ids, tags,
+    /// privacy level and segmentation, carrying a string and a number.
+    fn verbose_message() -> Vec<u8> {
+        let mut bytes = vec![0x4C, 0x0E, 0x00, 0x00, 0x07, 0x00, 0x00];
+        // Message info, number of arguments and timestamp.
+        bytes.extend_from_slice(&[0x41, 0x02]);
+        bytes.extend_from_slice(&[0x1D, 0xCD, 0x65, 0x00, 0x00, 0x65, 0x53, 0xF1, 0x00]);
+        bytes.extend_from_slice(b"\x04ECU1\x04APP1\x04CTX1");
+        bytes.extend_from_slice(b"\x02\x03foo\x03bar");
+        // Privacy level and last frame.
+        bytes.extend_from_slice(&[0x01, 0x02]);
+        bytes.extend_from_slice(&[0x00, 0x00, 0x82, 0x00, 0x00, 0x06]);
+        bytes.extend_from_slice(b"hello\0");
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x43, 0x00, 0x00, 0x00, 0x2A]);
+        let len = bytes.len() as u16;
+        bytes[5..7].copy_from_slice(&len.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn parse_verbose_message() {
+        let bytes = verbose_message();
+        assert!(is_v2(&bytes));
+        let (consumed, message) = parse_message(&bytes).unwrap();
+        assert_eq!(consumed, bytes.len());
+        let header = &message.header;
+        assert_eq!(header.content, ContentInfo::Verbose);
+        assert_eq!(header.message_counter, 7);
+        assert!(matches!(
+            header.message_type,
+            MessageType::Log(LogLevel::Info)
+        ));
+        assert_eq!(
+            header.timestamp,
+            Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanoseconds: 500_000_000,
+            })
+        );
+        assert_eq!(header.ecu_id.as_deref(), Some("ECU1"));
+        assert_eq!(header.app_id.as_deref(), Some("APP1"));
+        assert_eq!(header.context_id.as_deref(), Some("CTX1"));
+        assert_eq!(header.tags, vec![String::from("foo"), String::from("bar")]);
+        assert_eq!(header.privacy_level, Some(1));
+        assert_eq!(header.segmentation, Some(Segmentation::Last));
+        assert_eq!(
+            message.arguments(),
+            Some(vec![String::from("hello"), String::from("42")])
+        );
+
+        let mut message = message;
+        message.add_storage_header(None);
+        let text = FormattableMessage {
+            message,
+            options: None,
+        }
+        .to_string();
+        let columns: Vec<&str> = text.split(DLT_COLUMN_SENTINAL).collect();
+        assert_eq!(columns.len(), 15);
+        assert_eq!(columns[0], "2023-11-14T22:13:20.500000000Z");
+        assert_eq!(columns[2], "2");
+        assert_eq!(columns[5], "1700000000.500000000");
+        assert_eq!(columns[7], "APP1");
+        assert_eq!(&columns[10..14], ["foo, bar", "1", "LF", ""]);
+        assert_eq!(columns[14], "\u{5}hello\u{5}42");
+    }
+
+    #[test]
+    fn storage_headers_and_mixed_versions() {
+        let message = verbose_message();
+        let mut stored = b"DLT\x01\x00\xF1\x53\x65\x20\xA1\x07\x00ECU1".to_vec();
+        stored.extend_from_slice(&message);
+        assert!(is_v2(&stored));
+        assert_eq!(message_len(&stored).unwrap(), stored.len());
+        let (_, parsed) = parse_message(&stored).unwrap();
+        assert_eq!(
+            parsed.storage_header,
+            Some(StorageHeader {
+                timestamp: Timestamp {
+                    seconds: 1_700_000_000,
+                    nanoseconds: 500_000_000,
+                },
+                ecu_id: String::from("ECU1"),
+            })
+        );
+        assert_eq!(parsed.as_bytes(), stored);
+
+        let mut stored = b"DLT\x02\x1D\xCD\x65\x00\x00\x65\x53\xF1\x00\x05ECU10".to_vec();
+        stored.extend_from_slice(&message);
+        let (consumed, mut parsed) = parse_message(&stored).unwrap();
+        assert_eq!(consumed, stored.len());
+        assert_eq!(parsed.as_bytes(), stored);
+        assert_eq!(parsed.storage_header.as_ref().unwrap().ecu_id, "ECU10");
+
+        // Added storage header is of version 2.
+        parsed.storage_header = None;
+        parsed.storage_bytes.clear();
+        parsed.header.ecu_id = Some(String::from("ECU10"));
+        parsed.add_storage_header(None);
+        assert_eq!(parsed.as_bytes(), stored);
+
+        // Version 1 header with extended header, with and without storage header.
+        assert!(!is_v2(&[0x35, 0x00, 0x00, 0x20]));
+        assert!(!is_v2(b"DLT\x01\0\0\0\0\0\0\0\0ECU1\x35\x00"));
+        assert!(matches!(
+            parse_message(&message[..message.len() - 1]),
+            Err(Error::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn filter_messages() {
+        let (_, message) = parse_message(&verbose_message()).unwrap();
+        let filter = |config: DltFilterConfig| message.matches(&config.into());
+        let config = DltFilterConfig {
+            min_log_level: None,
+            app_ids: Some(vec![String::from("APP1")]),
+            ecu_ids: Some(vec![String::from("ECU1")]),
+            context_ids: None,
+            app_id_count: 0,
+            context_id_count: 0,
+        };
+        assert!(filter(config.clone()));
+        assert!(!filter(DltFilterConfig {
+            app_ids: Some(vec![String::from("APP2")]),
+            ..config.clone()
+        }));
+        assert!(!filter(DltFilterConfig {
+            min_log_level: Some(3),
+            ..config.clone()
+        }));
+        assert!(filter(DltFilterConfig {
+            min_log_level: Some(5),
+            ..config
+        }));
+    }
+
+    #[test]
+    fn filter_v2_fields() {
+        let (_, message) = parse_message(&verbose_message()).unwrap();
+        let filter = |tags: Option<&[&str]>, max_privacy_level: Option<u8>| {
+            message.matches_v2(&FilterConfig::new(
+                tags.map(|tags| tags.iter().map(|tag| tag.to_string()).collect()),
+                max_privacy_level,
+            ))
+        };
+        assert!(filter(None, None));
+        assert!(filter(Some(&["bar", "baz"]), None));
+        assert!(!filter(Some(&["baz"]), None));
+        assert!(filter(None, Some(1)));
+        assert!(!filter(None, Some(0)));
+        assert!(!filter(Some(&["foo"]), Some(0)));
+    }
+}
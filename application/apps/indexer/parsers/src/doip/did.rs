@@ -0,0 +1,203 @@
+//! # Data identifiers
+//!
+//! Names of the data identifiers (DIDs) read and written with UDS. The names
+//! of the identifiers standardized by ISO 14229-1 are always known, others can
+//! be described with CSV files (`<did>;<name>`, the DID in hex) or taken from
+//! the `ReadDataByIdentifier` and `WriteDataByIdentifier` requests of ODX files.
+use crate::Error;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Service ids of requests with a DID parameter in ODX files.
+const ODX_DID_SERVICES: [u64; 2] = [0x22, 0x2E];
+
+lazy_static! {
+    static ref ODX_REQUEST: Regex =
+        Regex::new(r"(?s)<REQUEST\b[^>]*>.*?<SHORT-NAME>([^<]+)</SHORT-NAME>(.*?)</REQUEST>")
+            .unwrap();
+    static ref ODX_PARAM: Regex = Regex::new(r"(?s)<PARAM\b([^>]*)>(.*?)</PARAM>").unwrap();
+    static ref ODX_SEMANTIC: Regex = Regex::new(r#"\bSEMANTIC="([^"]+)""#).unwrap();
+    static ref ODX_CODED_VALUE: Regex =
+        Regex::new(r"<CODED-VALUE>\s*(\w+)\s*</CODED-VALUE>").unwrap();
+}
+
+/// Returns the name of a DID standardized by ISO 14229-1.
+pub fn standard_did_name(did: u16) -> Option<&'static str> {
+    Some(match did {
+        0xF180 => "BootSoftwareIdentification",
+        0xF181 => "ApplicationSoftwareIdentification",
+        0xF182 => "ApplicationDataIdentification",
+        0xF183 => "BootSoftwareFingerprint",
+        0xF184 => "ApplicationSoftwareFingerprint",
+        0xF185 => "ApplicationDataFingerprint",
+        0xF186 => "ActiveDiagnosticSession",
+        0xF187 => "VehicleManufacturerSparePartNumber",
+        0xF188 => "VehicleManufacturerECUSoftwareNumber",
+        0xF189 => "VehicleManufacturerECUSoftwareVersionNumber",
+        0xF18A => "SystemSupplierIdentifier",
+        0xF18B => "ECUManufacturingDate",
+        0xF18C => "ECUSerialNumber",
+        0xF18D => "SupportedFunctionalUnits",
+        0xF18E => "VehicleManufacturerKitAssemblyPartNumber",
+        0xF190 => "VIN",
+        0xF191 => "VehicleManufacturerECUHardwareNumber",
+        0xF192 => "SystemSupplierECUHardwareNumber",
+        0xF193 => "SystemSupplierECUHardwareVersionNumber",
+        0xF194 => "SystemSupplierECUSoftwareNumber",
+        0xF195 => "SystemSupplierECUSoftwareVersionNumber",
+        0xF196 => "ExhaustRegulationOrTypeApprovalNumber",
+        0xF197 => "SystemNameOrEngineType",
+        0xF198 => "RepairShopCodeOrTesterSerialNumber",
+        0xF199 => "ProgrammingDate",
+        0xF19D => "ECUInstallationDate",
+        0xF19E => "ODXFile",
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct DidNames {
+    names: HashMap<u16, String>,
+}
+
+impl DidNames {
+    /// Reads the DIDs of the given CSV or ODX files.
+    pub fn from_files(paths: &[PathBuf]) -> Result<Self, Error> {
+        let mut dids = Self::default();
+        for path in paths {
+            dids.add_file(path)?;
+        }
+        Ok(dids)
+    }
+
+    fn add_file(&mut self, path: &Path) -> Result<(), Error> {
+        let bytes = fs::read(path).map_err(|e| {
+            Error::Unrecoverable(format!("Fail to read DID file {}: {e}", path.display()))
+        })?;
+        let content = String::from_utf8_lossy(&bytes);
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        if is_csv {
+            self.add_csv(&content);
+        } else {
+            self.add_odx(&content);
+        }
+        Ok(())
+    }
+
+    /// Adds the DIDs of CSV content, lines which don't start with a DID (e.g.
+    /// headers) are skipped.
+    pub fn add_csv(&mut self, content: &str) {
+        for line in content.lines() {
+            let mut columns = line.split([';', ',', '\t']).map(|column| column.trim());
+            let (Some(did), Some(name)) = (columns.next(), columns.next()) else {
+                continue;
+            };
+            let did = did.trim_start_matches("0x").trim_start_matches("0X");
+            if let Ok(did) = u16::from_str_radix(did, 16)
+                && !name.is_empty()
+            {
+                self.names.insert(did, name.trim_matches('"').to_owned());
+            }
+        }
+    }
+
+    /// Adds the DIDs of the data identifier requests of ODX content.
+    pub fn add_odx(&mut self, content: &str) {
+        for request in ODX_REQUEST.captures_iter(content) {
+            let mut service = None;
+            let mut did = None;
+            for param in ODX_PARAM.captures_iter(&request[2]) {
+                let (Some(semantic), Some(value)) = (
+                    ODX_SEMANTIC.captures(&param[1]),
+                    ODX_CODED_VALUE.captures(&param[2]),
+                ) else {
+                    continue;
+                };
+                let value = parse_coded_value(&value[1]);
+                match &semantic[1] {
+                    "SERVICE-ID" => service = value,
+                    "ID" | "DATA-ID" => did = value,
+                    _ => {}
+                }
+            }
+            if let (Some(service), Some(did)) = (service, did)
+                && ODX_DID_SERVICES.contains(&service)
+                && let Ok(did) = u16::try_from(did)
+            {
+                let name = request[1].trim();
+                let name = ["RQ_", "Req_", "Read_", "Write_"]
+                    .iter()
+                    .fold(name, |name, prefix| {
+                        name.strip_prefix(prefix).unwrap_or(name)
+                    });
+                self.names.entry(did).or_insert_with(|| name.to_owned());
+            }
+        }
+    }
+
+    /// Returns the name of the DID, described ones take precedence.
+    pub fn name(&self, did: u16) -> Option<&str> {
+        self.names
+            .get(&did)
+            .map(|name| name.as_str())
+            .or_else(|| standard_did_name(did))
+    }
+}
+
+fn parse_coded_value(value: &str) -> Option<u64> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_and_odx() {
+        let mut dids = DidNames::default();
+        dids.add_csv("DID;Name\n0x0100;OdometerValue\nF190;VehicleIdentNumber\n");
+        dids.add_odx(
+            r#"<REQUEST ID="RQ.Read_BatteryVoltage">
+                <SHORT-NAME>RQ_Read_BatteryVoltage</SHORT-NAME>
+                <PARAMS>
+                  <PARAM SEMANTIC="SERVICE-ID" xsi:type="CODED-CONST">
+                    <SHORT-NAME>SID</SHORT-NAME><CODED-VALUE>34</CODED-VALUE>
+                  </PARAM>
+                  <PARAM SEMANTIC="ID" xsi:type="CODED-CONST">
+                    <SHORT-NAME>DID</SHORT-NAME><CODED-VALUE>4660</CODED-VALUE>
+                  </PARAM>
+                </PARAMS>
+              </REQUEST>
+              <REQUEST ID="RQ.Reset">
+                <SHORT-NAME>RQ_HardReset</SHORT-NAME>
+                <PARAMS>
+                  <PARAM SEMANTIC="SERVICE-ID" xsi:type="CODED-CONST">
+                    <SHORT-NAME>SID</SHORT-NAME><CODED-VALUE>17</CODED-VALUE>
+                  </PARAM>
+                  <PARAM SEMANTIC="SUBFUNCTION" xsi:type="CODED-CONST">
+                    <SHORT-NAME>Type</SHORT-NAME><CODED-VALUE>1</CODED-VALUE>
+                  </PARAM>
+                </PARAMS>
+              </REQUEST>"#,
+        );
+        assert_eq!(dids.name(0x0100), Some("OdometerValue"));
+        assert_eq!(dids.name(0xF190), Some("VehicleIdentNumber"));
+        assert_eq!(dids.name(0x1234), Some("BatteryVoltage"));
+        assert_eq!(dids.name(0xF18C), Some("ECUSerialNumber"));
+        assert_eq!(dids.name(0x0001), None);
+        assert_eq!(dids.names.len(), 3);
+    }
+}
@@ -0,0 +1,365 @@
+//! # DoIP parser
+//!
+//! Parses Diagnostics over IP (ISO 13400-2) messages of TCP and UDP payloads,
+//! e.g. provided by the PCAP sources. Besides the DoIP header and the vehicle
+//! discovery and routing activation messages, the UDS services carried by
+//! diagnostic messages are decoded.
+pub mod did;
+pub mod uds;
+
+use crate::{Error, LogMessage, ParseOutput, SingleParser, separators::COLUMN_SEP};
+use serde::Serialize;
+use std::{fmt, io::Write};
+
+pub use did::DidNames;
+
+/// Length of the DoIP generic header.
+const HEADER_LEN: usize = 8;
+/// Maximal accepted payload length, larger values are treated as invalid header.
+const MAX_PAYLOAD_LEN: usize = 0x10_0000;
+/// Protocol versions of ISO 13400-2:2010, 2012 and 2019, and the default
+/// version of vehicle identification requests.
+const PROTOCOL_VERSIONS: [u8; 5] = [0x01, 0x02, 0x03, 0x04, 0xFF];
+
+/// Formats data as space separated hex bytes.
+pub(crate) fn hex(data: &[u8]) -> String {
+    data.iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Returns the name of a DoIP payload type.
+pub fn payload_type_name(payload_type: u16) -> Option<&'static str> {
+    Some(match payload_type {
+        0x0000 => "GenericNack",
+        0x0001 => "VehicleIdentificationRequest",
+        0x0002 => "VehicleIdentificationRequestEid",
+        0x0003 => "VehicleIdentificationRequestVin",
+        0x0004 => "VehicleAnnouncement",
+        0x0005 => "RoutingActivationRequest",
+        0x0006 => "RoutingActivationResponse",
+        0x0007 => "AliveCheckRequest",
+        0x0008 => "AliveCheckResponse",
+        0x4001 => "EntityStatusRequest",
+        0x4002 => "EntityStatusResponse",
+        0x4003 => "PowerModeRequest",
+        0x4004 => "PowerModeResponse",
+        0x8001 => "DiagnosticMessage",
+        0x8002 => "DiagnosticMessageAck",
+        0x8003 => "DiagnosticMessageNack",
+        _ => return None,
+    })
+}
+
+fn generic_nack_name(code: u8) -> &'static str {
+    match code {
+        0x00 => "incorrect pattern format",
+        0x01 => "unknown payload type",
+        0x02 => "message too large",
+        0x03 => "out of memory",
+        0x04 => "invalid payload length",
+        _ => "reserved",
+    }
+}
+
+fn activation_type_name(activation_type: u8) -> &'static str {
+    match activation_type {
+        0x00 => "default",
+        0x01 => "WWH-OBD",
+        0xE0 => "central security",
+        _ => "OEM specific",
+    }
+}
+
+fn activation_response_name(code: u8) -> &'static str {
+    match code {
+        0x00 => "unknown source address",
+        0x01 => "all sockets registered",
+        0x02 => "different source address",
+        0x03 => "source address already active",
+        0x04 => "missing authentication",
+        0x05 => "rejected confirmation",
+        0x06 => "unsupported activation type",
+        0x07 => "TLS required",
+        0x10 => "successfully activated",
+        0x11 => "confirmation required",
+        _ => "reserved",
+    }
+}
+
+fn diagnostic_nack_name(code: u8) -> &'static str {
+    match code {
+        0x02 => "invalid source address",
+        0x03 => "unknown target address",
+        0x04 => "diagnostic message too large",
+        0x05 => "out of memory",
+        0x06 => "target unreachable",
+        0x07 => "unknown network",
+        0x08 => "transport protocol error",
+        _ => "reserved",
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct DoipMessage {
+    pub protocol_version: u8,
+    pub payload_type: u16,
+    /// Logical address of the sender, if part of the payload.
+    pub source: Option<u16>,
+    /// Logical address of the receiver, if part of the payload.
+    pub target: Option<u16>,
+    /// The UDS service or the kind of the DoIP message.
+    pub service: String,
+    /// The decoded parameters of the message.
+    pub details: String,
+    /// The original bytes of the message.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for DoipMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let address = |address: Option<u16>| {
+            address
+                .map(|address| format!("0x{address:04X}"))
+                .unwrap_or_default()
+        };
+        write!(
+            f,
+            "{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}",
+            payload_type_name(self.payload_type)
+                .map(|name| name.to_owned())
+                .unwrap_or_else(|| format!("0x{:04X}", self.payload_type)),
+            address(self.source),
+            address(self.target),
+            self.service,
+            self.details
+        )
+    }
+}
+
+impl LogMessage for DoipMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+}
+
+pub struct DoipParser {
+    dids: Option<DidNames>,
+}
+
+impl DoipParser {
+    /// Creates a parser, the DIDs of UDS services are named with `dids` or
+    /// the names of the standardized DIDs.
+    pub fn new(dids: Option<DidNames>) -> Self {
+        Self { dids }
+    }
+
+    /// Decodes the addresses, service and details of the payload.
+    fn decode(&self, payload_type: u16, payload: &[u8]) -> Option<DecodedPayload> {
+        let u16_at = |offset: usize| -> Option<u16> {
+            Some(u16::from_be_bytes([
+                *payload.get(offset)?,
+                *payload.get(offset + 1)?,
+            ]))
+        };
+        let mut decoded = DecodedPayload::default();
+        match payload_type {
+            0x0000 => {
+                decoded.details = generic_nack_name(*payload.first()?).to_owned();
+            }
+            0x0002 => decoded.details = format!("EID [{}]", hex(payload.get(..6)?)),
+            0x0003 => {
+                decoded.details = format!("VIN {}", String::from_utf8_lossy(payload.get(..17)?));
+            }
+            0x0004 => {
+                decoded.source = u16_at(17);
+                decoded.details = format!(
+                    "VIN {} EID [{}] GID [{}] further action 0x{:02X}",
+                    String::from_utf8_lossy(payload.get(..17)?),
+                    hex(payload.get(19..25)?),
+                    hex(payload.get(25..31)?),
+                    payload.get(31)?
+                );
+            }
+            0x0005 => {
+                decoded.source = u16_at(0);
+                decoded.details =
+                    format!("activation type {}", activation_type_name(*payload.get(2)?));
+            }
+            0x0006 => {
+                decoded.target = u16_at(0);
+                decoded.source = u16_at(2);
+                let code = *payload.get(4)?;
+                decoded.details = format!("0x{code:02X} ({})", activation_response_name(code));
+            }
+            0x0008 => decoded.source = u16_at(0),
+            0x4002 => {
+                let node_type = match *payload.first()? {
+                    0x00 => "gateway",
+                    0x01 => "node",
+                    _ => "reserved",
+                };
+                decoded.details = format!(
+                    "{node_type} sockets {}/{}",
+                    payload.get(2)?,
+                    payload.get(1)?
+                );
+            }
+            0x4004 => {
+                decoded.details = match *payload.first()? {
+                    0x00 => "not ready",
+                    0x01 => "ready",
+                    0x02 => "not supported",
+                    _ => "reserved",
+                }
+                .to_owned();
+            }
+            0x8001 => {
+                decoded.source = u16_at(0);
+                decoded.target = u16_at(2);
+                let data = payload.get(4..)?;
+                match uds::describe(data, self.dids.as_ref()) {
+                    Some(uds) => {
+                        decoded.service = uds.service;
+                        decoded.details = uds.details;
+                    }
+                    None => decoded.details = format!("[{}]", hex(data)),
+                }
+            }
+            0x8002 | 0x8003 => {
+                decoded.source = u16_at(0);
+                decoded.target = u16_at(2);
+                let code = *payload.get(4)?;
+                decoded.details = if payload_type == 0x8002 {
+                    format!("ack 0x{code:02X}")
+                } else {
+                    format!("nack 0x{code:02X} ({})", diagnostic_nack_name(code))
+                };
+            }
+            _ => {}
+        }
+        Some(decoded)
+    }
+}
+
+#[derive(Default)]
+struct DecodedPayload {
+    source: Option<u16>,
+    target: Option<u16>,
+    service: String,
+    details: String,
+}
+
+impl SingleParser for DoipParser {
+    type Output = DoipMessage;
+    const MIN_MSG_LEN: usize = HEADER_LEN;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<DoipMessage>, Error> {
+        if input.len() < HEADER_LEN {
+            return Err(Error::Incomplete);
+        }
+        let protocol_version = input[0];
+        if !PROTOCOL_VERSIONS.contains(&protocol_version) || input[1] != !protocol_version {
+            return Err(Error::Parse("Invalid DoIP protocol version".to_owned()));
+        }
+        let payload_type = u16::from_be_bytes([input[2], input[3]]);
+        if payload_type_name(payload_type).is_none() {
+            return Err(Error::Parse(format!(
+                "Unknown DoIP payload type 0x{payload_type:04X}"
+            )));
+        }
+        let len = u32::from_be_bytes([input[4], input[5], input[6], input[7]]) as usize;
+        if len > MAX_PAYLOAD_LEN {
+            return Err(Error::Parse(format!("Invalid DoIP payload length {len}")));
+        }
+        if input.len() < HEADER_LEN + len {
+            return Err(Error::Incomplete);
+        }
+        let payload = &input[HEADER_LEN..HEADER_LEN + len];
+        let decoded = self
+            .decode(payload_type, payload)
+            .unwrap_or_else(|| DecodedPayload {
+                details: format!("invalid payload [{}]", hex(payload)),
+                ..Default::default()
+            });
+        let msg = DoipMessage {
+            protocol_version,
+            payload_type,
+            source: decoded.source,
+            target: decoded.target,
+            service: decoded.service,
+            details: decoded.details,
+            raw: input[..HEADER_LEN + len].to_vec(),
+        };
+        Ok(ParseOutput::new(HEADER_LEN + len, Some(msg.into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseYield, Parser};
+
+    fn message(payload_type: u16, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x02, 0xFD];
+        bytes.extend_from_slice(&payload_type.to_be_bytes());
+        bytes.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[test]
+    fn routing_activation_and_diagnostics() {
+        let mut input = message(0x0005, &[0x0E, 0x80, 0x00, 0, 0, 0, 0]);
+        input.extend(message(0x0006, &[0x0E, 0x80, 0x10, 0x01, 0x10, 0, 0, 0, 0]));
+        input.extend(message(0x8001, &[0x0E, 0x80, 0x10, 0x01, 0x22, 0xF1, 0x90]));
+        input.extend(message(0x8002, &[0x10, 0x01, 0x0E, 0x80, 0x00]));
+        input.extend(message(0x8001, &[0x10, 0x01, 0x0E, 0x80, 0x7F, 0x22, 0x31]));
+        // The next message is incomplete.
+        input.extend(&message(0x8001, &[0x10, 0x01, 0x0E, 0x80, 0x62])[..10]);
+        let mut parser = DoipParser::new(None);
+        let outputs: Vec<ParseOutput<DoipMessage>> = parser.parse(&input, None).unwrap().collect();
+        let consumed: usize = outputs.iter().map(|out| out.consumed).sum();
+        assert_eq!(consumed, input.len() - 10);
+        let messages: Vec<DoipMessage> = outputs
+            .into_iter()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 5);
+        assert_eq!(
+            messages[0].to_string(),
+            "RoutingActivationRequest\u{4}0x0E80\u{4}\u{4}\u{4}activation type default"
+        );
+        assert_eq!(
+            messages[1].to_string(),
+            "RoutingActivationResponse\u{4}0x1001\u{4}0x0E80\u{4}\u{4}0x10 (successfully activated)"
+        );
+        assert_eq!(
+            messages[2].to_string(),
+            "DiagnosticMessage\u{4}0x0E80\u{4}0x1001\u{4}ReadDataByIdentifier request\u{4}DID 0xF190 (VIN)"
+        );
+        assert_eq!(messages[3].details, "ack 0x00");
+        assert_eq!(
+            messages[4].service,
+            "ReadDataByIdentifier negative response"
+        );
+        assert_eq!(messages[4].details, "NRC 0x31 (requestOutOfRange)");
+        let mut raw = Vec::new();
+        messages[2].to_writer(&mut raw).unwrap();
+        assert_eq!(raw.len(), HEADER_LEN + 7);
+        assert!(matches!(
+            parser.parse_item(&[0x02, 0xFD, 0x12, 0x34, 0, 0, 0, 0], None),
+            Err(Error::Parse(_))
+        ));
+    }
+}
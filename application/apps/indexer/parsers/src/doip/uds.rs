@@ -0,0 +1,304 @@
+//! # Unified diagnostic services
+//!
+//! Describes the UDS (ISO 14229-1) requests, positive and negative responses
+//! carried by diagnostic messages: the service, its sub-function and the
+//! well-known parameters like data identifiers, routine identifiers and
+//! negative response codes.
+use crate::doip::{
+    did::{DidNames, standard_did_name},
+    hex,
+};
+
+/// Service id of negative responses.
+const NEGATIVE_RESPONSE: u8 = 0x7F;
+/// Offset of the service id of positive responses.
+const POSITIVE_RESPONSE_OFFSET: u8 = 0x40;
+/// Bit of sub-functions to suppress the positive response.
+const SUPPRESS_POSITIVE_RESPONSE: u8 = 0x80;
+
+/// Returns the name of a UDS service.
+pub fn service_name(sid: u8) -> Option<&'static str> {
+    Some(match sid {
+        0x10 => "DiagnosticSessionControl",
+        0x11 => "ECUReset",
+        0x14 => "ClearDiagnosticInformation",
+        0x19 => "ReadDTCInformation",
+        0x22 => "ReadDataByIdentifier",
+        0x23 => "ReadMemoryByAddress",
+        0x24 => "ReadScalingDataByIdentifier",
+        0x27 => "SecurityAccess",
+        0x28 => "CommunicationControl",
+        0x29 => "Authentication",
+        0x2A => "ReadDataByPeriodicIdentifier",
+        0x2C => "DynamicallyDefineDataIdentifier",
+        0x2E => "WriteDataByIdentifier",
+        0x2F => "InputOutputControlByIdentifier",
+        0x31 => "RoutineControl",
+        0x34 => "RequestDownload",
+        0x35 => "RequestUpload",
+        0x36 => "TransferData",
+        0x37 => "RequestTransferExit",
+        0x38 => "RequestFileTransfer",
+        0x3D => "WriteMemoryByAddress",
+        0x3E => "TesterPresent",
+        0x83 => "AccessTimingParameter",
+        0x84 => "SecuredDataTransmission",
+        0x85 => "ControlDTCSetting",
+        0x86 => "ResponseOnEvent",
+        0x87 => "LinkControl",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a negative response code.
+pub fn nrc_name(nrc: u8) -> Option<&'static str> {
+    Some(match nrc {
+        0x10 => "generalReject",
+        0x11 => "serviceNotSupported",
+        0x12 => "subFunctionNotSupported",
+        0x13 => "incorrectMessageLengthOrInvalidFormat",
+        0x14 => "responseTooLong",
+        0x21 => "busyRepeatRequest",
+        0x22 => "conditionsNotCorrect",
+        0x24 => "requestSequenceError",
+        0x25 => "noResponseFromSubnetComponent",
+        0x26 => "failurePreventsExecutionOfRequestedAction",
+        0x31 => "requestOutOfRange",
+        0x33 => "securityAccessDenied",
+        0x34 => "authenticationRequired",
+        0x35 => "invalidKey",
+        0x36 => "exceededNumberOfAttempts",
+        0x37 => "requiredTimeDelayNotExpired",
+        0x70 => "uploadDownloadNotAccepted",
+        0x71 => "transferDataSuspended",
+        0x72 => "generalProgrammingFailure",
+        0x73 => "wrongBlockSequenceCounter",
+        0x78 => "requestCorrectlyReceivedResponsePending",
+        0x7E => "subFunctionNotSupportedInActiveSession",
+        0x7F => "serviceNotSupportedInActiveSession",
+        0x81 => "rpmTooHigh",
+        0x82 => "rpmTooLow",
+        0x83 => "engineIsRunning",
+        0x84 => "engineIsNotRunning",
+        0x85 => "engineRunTimeTooLow",
+        0x86 => "temperatureTooHigh",
+        0x87 => "temperatureTooLow",
+        0x88 => "vehicleSpeedTooHigh",
+        0x89 => "vehicleSpeedTooLow",
+        0x8A => "throttlePedalTooHigh",
+        0x8B => "throttlePedalTooLow",
+        0x8C => "transmissionRangeNotInNeutral",
+        0x8D => "transmissionRangeNotInGear",
+        0x8F => "brakeSwitchNotClosed",
+        0x90 => "shifterLeverNotInPark",
+        0x91 => "torqueConverterClutchLocked",
+        0x92 => "voltageTooHigh",
+        0x93 => "voltageTooLow",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a sub-function of the given service.
+fn sub_function_name(sid: u8, sub_function: u8) -> Option<String> {
+    let name = match (sid, sub_function) {
+        (0x10, 0x01) => "defaultSession",
+        (0x10, 0x02) => "programmingSession",
+        (0x10, 0x03) => "extendedDiagnosticSession",
+        (0x10, 0x04) => "safetySystemDiagnosticSession",
+        (0x11, 0x01) => "hardReset",
+        (0x11, 0x02) => "keyOffOnReset",
+        (0x11, 0x03) => "softReset",
+        (0x11, 0x04) => "enableRapidPowerShutDown",
+        (0x11, 0x05) => "disableRapidPowerShutDown",
+        (0x27, level) if level % 2 == 1 => return Some(format!("requestSeed level {level}")),
+        (0x27, level) if level != 0 => return Some(format!("sendKey level {}", level - 1)),
+        (0x28, 0x00) => "enableRxAndTx",
+        (0x28, 0x01) => "enableRxAndDisableTx",
+        (0x28, 0x02) => "disableRxAndEnableTx",
+        (0x28, 0x03) => "disableRxAndTx",
+        (0x31, 0x01) => "startRoutine",
+        (0x31, 0x02) => "stopRoutine",
+        (0x31, 0x03) => "requestRoutineResults",
+        (0x3E, 0x00) => "zeroSubFunction",
+        (0x85, 0x01) => "on",
+        (0x85, 0x02) => "off",
+        _ => return None,
+    };
+    Some(name.to_owned())
+}
+
+/// Services with a sub-function as first parameter.
+fn has_sub_function(sid: u8) -> bool {
+    matches!(
+        sid,
+        0x10 | 0x11 | 0x19 | 0x27 | 0x28 | 0x29 | 0x2C | 0x31 | 0x3E | 0x83 | 0x85 | 0x86 | 0x87
+    )
+}
+
+/// The decoded service of a UDS message.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UdsService {
+    /// The service name and kind of the message, e.g. `ECUReset request`.
+    pub service: String,
+    /// The decoded parameters of the message.
+    pub details: String,
+}
+
+/// Describes the given UDS message.
+pub fn describe(data: &[u8], dids: Option<&DidNames>) -> Option<UdsService> {
+    let (&sid, params) = data.split_first()?;
+    if sid == NEGATIVE_RESPONSE {
+        let (request_sid, nrc) = match params {
+            [request_sid, nrc, ..] => (*request_sid, *nrc),
+            _ => return None,
+        };
+        return Some(UdsService {
+            service: format!("{} negative response", name(request_sid)),
+            details: format!("NRC 0x{nrc:02X} ({})", nrc_name(nrc).unwrap_or("reserved")),
+        });
+    }
+    let (request_sid, kind) = match sid.checked_sub(POSITIVE_RESPONSE_OFFSET) {
+        Some(request_sid) if service_name(request_sid).is_some() => (request_sid, "response"),
+        _ if service_name(sid).is_some() => (sid, "request"),
+        _ => return None,
+    };
+    let is_request = kind == "request";
+    let mut details = Vec::new();
+    let mut params = params;
+    if has_sub_function(request_sid)
+        && let Some((&sub_function, rest)) = params.split_first()
+    {
+        let value = sub_function & !SUPPRESS_POSITIVE_RESPONSE;
+        details.push(match sub_function_name(request_sid, value) {
+            Some(name) => name,
+            None => format!("sub-function 0x{value:02X}"),
+        });
+        if is_request && sub_function & SUPPRESS_POSITIVE_RESPONSE != 0 {
+            details.push("suppressPosRspMsg".to_owned());
+        }
+        params = rest;
+    }
+    match request_sid {
+        // Requests may read several DIDs at once, responses contain the data.
+        0x22 if is_request => {
+            for did in params.chunks_exact(2) {
+                details.push(did_string(u16::from_be_bytes([did[0], did[1]]), dids));
+            }
+            params = &params[params.len() - params.len() % 2..];
+        }
+        0x22 | 0x24 | 0x2E | 0x2F => {
+            if let [high, low, rest @ ..] = params {
+                details.push(did_string(u16::from_be_bytes([*high, *low]), dids));
+                params = rest;
+            }
+        }
+        0x31 => {
+            if let [high, low, rest @ ..] = params {
+                details.push(format!(
+                    "routine 0x{:04X}",
+                    u16::from_be_bytes([*high, *low])
+                ));
+                params = rest;
+            }
+        }
+        0x36 | 0x37 if !params.is_empty() => {
+            details.push(format!("block {}", params[0]));
+            params = &params[1..];
+        }
+        _ => {}
+    }
+    if !params.is_empty() {
+        details.push(data_string(params));
+    }
+    Some(UdsService {
+        service: format!("{} {kind}", name(request_sid)),
+        details: details.join(" "),
+    })
+}
+
+fn name(sid: u8) -> String {
+    service_name(sid)
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("0x{sid:02X}"))
+}
+
+fn did_string(did: u16, dids: Option<&DidNames>) -> String {
+    let name = match dids {
+        Some(dids) => dids.name(did),
+        None => standard_did_name(did),
+    };
+    match name {
+        Some(name) => format!("DID 0x{did:04X} ({name})"),
+        None => format!("DID 0x{did:04X}"),
+    }
+}
+
+/// Formats data as hex, printable text is added in quotes.
+fn data_string(data: &[u8]) -> String {
+    let printable = data.len() > 1
+        && data
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ');
+    if printable {
+        format!("[{}] \"{}\"", hex(data), String::from_utf8_lossy(data))
+    } else {
+        format!("[{}]", hex(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(data: &[u8]) -> (String, String) {
+        let service = describe(data, None).unwrap();
+        (service.service, service.details)
+    }
+
+    #[test]
+    fn requests_and_responses() {
+        assert_eq!(
+            details(&[0x10, 0x03]),
+            (
+                "DiagnosticSessionControl request".into(),
+                "extendedDiagnosticSession".into()
+            )
+        );
+        assert_eq!(
+            details(&[0x3E, 0x80]),
+            (
+                "TesterPresent request".into(),
+                "zeroSubFunction suppressPosRspMsg".into()
+            )
+        );
+        assert_eq!(
+            details(&[0x22, 0xF1, 0x90, 0x01, 0x00]),
+            (
+                "ReadDataByIdentifier request".into(),
+                "DID 0xF190 (VIN) DID 0x0100".into()
+            )
+        );
+        assert_eq!(
+            details(&[0x62, 0xF1, 0x90, b'W', b'V', b'W']),
+            (
+                "ReadDataByIdentifier response".into(),
+                "DID 0xF190 (VIN) [57 56 57] \"WVW\"".into()
+            )
+        );
+        assert_eq!(
+            details(&[0x71, 0x01, 0x02, 0x03, 0x00]),
+            (
+                "RoutineControl response".into(),
+                "startRoutine routine 0x0203 [00]".into()
+            )
+        );
+        assert_eq!(
+            details(&[0x7F, 0x27, 0x35]),
+            (
+                "SecurityAccess negative response".into(),
+                "NRC 0x35 (invalidKey)".into()
+            )
+        );
+        assert_eq!(describe(&[0x02, 0x01], None), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! # systemd journal parser
+//!
+//! Parses entries in the journal export format, as provided by the journal
+//! byte source or written by `journalctl -o export`:
+//!
+//! ```text
+//! __REALTIME_TIMESTAMP=1710692018811000
+//! _SYSTEMD_UNIT=sshd.service
+//! MESSAGE=Accepted publickey for root
+//!
+//! ```
+//!
+//! Values containing line breaks are written as field name, followed by the
+//! little endian 64 bit length and the value itself.
+use crate::{
+    Error, LogMessage, ParseOutput, SingleParser,
+    separators::{COLUMN_SEP, LINE_SEP},
+};
+use chrono::DateTime;
+use serde::Serialize;
+use std::{fmt, io::Write};
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct JournalMessage {
+    /// The formatted realtime of the entry in UTC.
+    pub time: String,
+    /// The realtime in milliseconds since the epoch.
+    pub timestamp: Option<i64>,
+    /// `_SYSTEMD_UNIT`
+    pub unit: String,
+    /// `PRIORITY`
+    pub priority: String,
+    /// `_PID`
+    pub pid: String,
+    /// `SYSLOG_IDENTIFIER`
+    pub identifier: String,
+    /// `MESSAGE`
+    pub message: String,
+    /// The original bytes of the entry.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for JournalMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}",
+            self.time, self.unit, self.priority, self.pid, self.identifier, self.message
+        )
+    }
+}
+
+impl LogMessage for JournalMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+impl JournalMessage {
+    fn set(&mut self, name: &[u8], value: &[u8]) {
+        let text = || String::from_utf8_lossy(value).replace('\n', LINE_SEP);
+        match name {
+            b"__REALTIME_TIMESTAMP" => {
+                let Some(datetime) = std::str::from_utf8(value)
+                    .ok()
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .and_then(DateTime::from_timestamp_micros)
+                else {
+                    return;
+                };
+                self.timestamp = Some(datetime.timestamp_millis());
+                self.time = datetime.format("%Y-%m-%d %H:%M:%S%.6f").to_string();
+            }
+            b"_SYSTEMD_UNIT" => self.unit = text(),
+            b"PRIORITY" => self.priority = text(),
+            b"_PID" => self.pid = text(),
+            b"SYSLOG_IDENTIFIER" => self.identifier = text(),
+            b"MESSAGE" => self.message = text(),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct JournalParser;
+
+impl JournalParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SingleParser for JournalParser {
+    type Output = JournalMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<JournalMessage>, Error> {
+        let mut msg = JournalMessage::default();
+        let mut pos = 0;
+        loop {
+            let Some(len) = memchr::memchr(b'\n', &input[pos..]) else {
+                return Err(Error::Incomplete);
+            };
+            let line = &input[pos..pos + len];
+            pos += len + 1;
+            if line.is_empty() {
+                break;
+            }
+            if let Some(sep) = memchr::memchr(b'=', line) {
+                msg.set(&line[..sep], &line[sep + 1..]);
+                continue;
+            }
+            // Binary value
+            if input.len() < pos + 8 {
+                return Err(Error::Incomplete);
+            }
+            let mut size = [0u8; 8];
+            size.copy_from_slice(&input[pos..pos + 8]);
+            let size = u64::from_le_bytes(size) as usize;
+            let start = pos + 8;
+            let Some(end) = start.checked_add(size) else {
+                return Err(Error::Parse(format!("Invalid journal value size {size}")));
+            };
+            if input.len() <= end {
+                return Err(Error::Incomplete);
+            }
+            if input[end] != b'\n' {
+                return Err(Error::Parse(
+                    "Missing line break after journal value".into(),
+                ));
+            }
+            msg.set(line, &input[start..end]);
+            pos = end + 1;
+        }
+        // Empty lines between entries.
+        if pos == 1 {
+            return Ok(ParseOutput::new(pos, None));
+        }
+        msg.raw = input[..pos].to_vec();
+        Ok(ParseOutput::new(pos, Some(msg.into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseYield, Parser};
+
+    #[test]
+    fn export_entries() {
+        let mut input = b"__REALTIME_TIMESTAMP=1710692018811123\n\
+            _PID=712\n\
+            _SYSTEMD_UNIT=sshd.service\n\
+            PRIORITY=6\n\
+            SYSLOG_IDENTIFIER=sshd\n\
+            MESSAGE=Accepted publickey for root\n\
+            \n\
+            __REALTIME_TIMESTAMP=1710692019000000\n\
+            MESSAGE\n"
+            .to_vec();
+        input.extend_from_slice(&12u64.to_le_bytes());
+        input.extend_from_slice(b"first\nsecond\n\n");
+        let mut parser = JournalParser::new();
+        let messages: Vec<JournalMessage> = parser
+            .parse(&input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].timestamp, Some(1_710_692_018_811));
+        assert_eq!(
+            messages[0].to_string(),
+            "2024-03-17 16:13:38.811123\u{4}sshd.service\u{4}6\u{4}712\u{4}sshd\u{4}Accepted publickey for root"
+        );
+        assert_eq!(messages[1].message, "first\u{6}second");
+        assert_eq!(messages[1].unit, "");
+        let mut raw = Vec::new();
+        messages[1].to_writer(&mut raw).unwrap();
+        assert_eq!(raw.len(), 38 + 8 + 8 + 12 + 2);
+        assert!(matches!(
+            parser.parse_item(b"MESSAGE=incomplete\n", None),
+            Err(Error::Incomplete)
+        ));
+    }
+}
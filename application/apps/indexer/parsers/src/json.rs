@@ -0,0 +1,353 @@
+//! # JSON Lines parser
+//!
+//! Parses logs with one JSON object per line (e.g. tracing-subscriber, bunyan,
+//! zap or OpenTelemetry exporters). Nested objects are flattened to dotted
+//! paths (`span.name`). The configured fields become columns, all other fields
+//! are shown as `path=value` pairs in the last column. The value of the level
+//! field is shown normalized (e.g. `ERROR` for `error` or bunyan's `50`). Lines
+//! which aren't JSON objects are shown unchanged in the last column.
+use crate::{
+    Error, LogMessage, ParseOutput, SingleParser,
+    separators::{COLUMN_SEP, LINE_SEP},
+    text::pattern::{next_line, normalize_severity, parse_timestamp},
+};
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::{fmt, io::Write};
+
+/// Fields checked for the timestamp if no timestamp field is configured.
+const TIMESTAMP_FIELDS: &[&str] = &[
+    "timestamp",
+    "time",
+    "ts",
+    "@timestamp",
+    "timeUnixNano",
+    "date",
+];
+/// Fields checked for the level if no level field is configured.
+const LEVEL_FIELDS: &[&str] = &[
+    "level",
+    "severity",
+    "severityText",
+    "lvl",
+    "levelname",
+    "@l",
+];
+/// Name of the column containing the fields which aren't projected.
+const REMAINING_COLUMN: &str = "remaining";
+
+/// Configuration of the [`JsonParser`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JsonConfig {
+    /// Dotted paths of the fields shown as columns.
+    pub fields: Vec<String>,
+    /// Dotted path of the timestamp field. Detected if not defined.
+    pub timestamp_field: Option<String>,
+    /// `chrono` format of string timestamps which aren't RFC 3339.
+    pub timestamp_format: Option<String>,
+    /// Dotted path of the level field, its value is shown normalized. Detected if not defined.
+    pub level_field: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct JsonMessage {
+    /// The values of the configured fields followed by the remaining fields.
+    pub columns: Vec<String>,
+    /// The timestamp in milliseconds since the epoch, if it could be parsed.
+    timestamp: Option<i64>,
+    /// The original bytes of the line without the line break.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for JsonMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.columns.join(COLUMN_SEP))
+    }
+}
+
+impl LogMessage for JsonMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+pub struct JsonParser {
+    config: JsonConfig,
+    /// Year of timestamps, which don't contain it.
+    year: i32,
+}
+
+impl JsonParser {
+    /// Creates the parser. Timestamps without year are parsed in the current year.
+    pub fn new(config: JsonConfig) -> Self {
+        Self {
+            config,
+            year: Utc::now().year(),
+        }
+    }
+
+    /// Returns the names of the columns.
+    pub fn columns(&self) -> Vec<&str> {
+        self.config
+            .fields
+            .iter()
+            .map(String::as_str)
+            .chain([REMAINING_COLUMN])
+            .collect()
+    }
+
+    fn message(&self, line: String, raw: &[u8]) -> JsonMessage {
+        let Ok(Value::Object(object)) = serde_json::from_str::<Value>(&line) else {
+            let mut columns = vec![String::new(); self.config.fields.len()];
+            columns.push(line);
+            return JsonMessage {
+                columns,
+                timestamp: None,
+                raw: raw.to_vec(),
+            };
+        };
+        let mut fields = Vec::new();
+        flatten("", Value::Object(object), &mut fields);
+        let position = |path: &str| fields.iter().position(|(name, _)| name == path);
+        let detect = |configured: &Option<String>, candidates: &[&str]| match configured {
+            Some(path) => position(path.as_str()),
+            None => candidates.iter().find_map(|path| position(path)),
+        };
+        let timestamp = detect(&self.config.timestamp_field, TIMESTAMP_FIELDS)
+            .and_then(|idx| self.timestamp(&fields[idx].1));
+        if let Some(idx) = detect(&self.config.level_field, LEVEL_FIELDS) {
+            fields[idx].1 = level_severity(&fields[idx].1);
+        }
+        let find = |path: &str| {
+            fields
+                .iter()
+                .find(|(name, _)| name == path)
+                .map(|(_, value)| value.as_str())
+        };
+        let mut columns: Vec<String> = self
+            .config
+            .fields
+            .iter()
+            .map(|path| find(path.as_str()).unwrap_or_default().to_owned())
+            .collect();
+        columns.push(
+            fields
+                .iter()
+                .filter(|(name, _)| !self.config.fields.contains(name))
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<String>>()
+                .join(" "),
+        );
+        JsonMessage {
+            columns,
+            timestamp,
+            raw: raw.to_vec(),
+        }
+    }
+
+    fn timestamp(&self, value: &str) -> Option<i64> {
+        if let Ok(number) = value.parse::<f64>() {
+            return Some(epoch_millis(number));
+        }
+        if let Some(format) = &self.config.timestamp_format {
+            return parse_timestamp(value, format, self.year);
+        }
+        DateTime::parse_from_rfc3339(value)
+            .map(|datetime| datetime.timestamp_millis())
+            .or_else(|_| {
+                NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                    .map(|datetime| datetime.and_utc().timestamp_millis())
+            })
+            .ok()
+    }
+}
+
+/// Appends all fields of the given value with their dotted path to `fields`. Line
+/// breaks in strings (e.g. stack traces) are replaced with [`LINE_SEP`], so the
+/// message still takes a single row.
+fn flatten(path: &str, value: Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                let path = if path.is_empty() {
+                    key
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(&path, value, fields);
+            }
+        }
+        Value::String(value) => fields.push((path.to_owned(), value.replace('\n', LINE_SEP))),
+        value => fields.push((path.to_owned(), value.to_string())),
+    }
+}
+
+/// Converts an epoch timestamp in seconds, milliseconds, microseconds or
+/// nanoseconds to milliseconds.
+fn epoch_millis(value: f64) -> i64 {
+    let millis = match value.abs() {
+        v if v < 1e11 => value * 1e3,
+        v if v < 1e14 => value,
+        v if v < 1e17 => value / 1e3,
+        _ => value / 1e6,
+    };
+    millis.round() as i64
+}
+
+/// Normalizes a textual or a numeric (bunyan) level.
+fn level_severity(value: &str) -> String {
+    match value.parse::<u8>() {
+        Ok(level) => String::from(match level {
+            0..=10 => "TRACE",
+            11..=20 => "DEBUG",
+            21..=30 => "INFO",
+            31..=40 => "WARN",
+            41..=50 => "ERROR",
+            _ => "FATAL",
+        }),
+        Err(_) => normalize_severity(value),
+    }
+}
+
+impl SingleParser for JsonParser {
+    type Output = JsonMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<JsonMessage>, Error> {
+        let Some((line, consumed)) = next_line(input, false) else {
+            return Ok(ParseOutput::new(input.len(), None));
+        };
+        let raw = &input[..consumed];
+        let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+        Ok(ParseOutput::new(
+            consumed,
+            Some(self.message(line, raw).into()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogMessage, ParseYield, Parser};
+
+    fn raw(msg: &JsonMessage) -> Vec<u8> {
+        let mut raw = Vec::new();
+        msg.to_writer(&mut raw).unwrap();
+        raw
+    }
+
+    fn parse_all(parser: &mut JsonParser, input: &[u8]) -> Vec<JsonMessage> {
+        parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn project_and_flatten_fields() {
+        let mut parser = JsonParser::new(JsonConfig {
+            fields: vec![
+                String::from("level"),
+                String::from("fields.message"),
+                String::from("span.name"),
+            ],
+            ..Default::default()
+        });
+        assert_eq!(
+            parser.columns(),
+            vec!["level", "fields.message", "span.name", "remaining"]
+        );
+        let input = b"{\"timestamp\":\"2024-03-17T16:13:38.811Z\",\"level\":\"WARN\",\"fields\":{\"message\":\"slow\",\"ms\":120},\"span\":{\"name\":\"req\",\"ids\":[1,2]}}\n\
+            not json\r\n\
+            {\"time\":\"2024-03-17T16:13:38.811+01:00\",\"level\":50,\"msg\":\"failed\",\"ctx\":{}}\n";
+        let messages = parse_all(&mut parser, input);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].columns[..3], ["WARN", "slow", "req"]);
+        // The order of the remaining fields depends on the features of `serde_json`.
+        let mut remaining: Vec<&str> = messages[0].columns[3].split(' ').collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                "fields.ms=120",
+                "span.ids=[1,2]",
+                "timestamp=2024-03-17T16:13:38.811Z"
+            ]
+        );
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_018_811));
+
+        assert_eq!(messages[1].to_string(), "\u{4}\u{4}\u{4}not json");
+        assert_eq!(messages[1].timestamp(), None);
+        assert_eq!(raw(&messages[1]), b"not json\r");
+
+        assert_eq!(messages[2].columns[0], "ERROR");
+        assert_eq!(messages[2].timestamp(), Some(1_710_688_418_811));
+        assert_eq!(
+            raw(&messages[2]),
+            b"{\"time\":\"2024-03-17T16:13:38.811+01:00\",\"level\":50,\"msg\":\"failed\",\"ctx\":{}}"
+        );
+    }
+
+    #[test]
+    fn multiline_strings() {
+        let mut parser = JsonParser::new(JsonConfig {
+            fields: vec![String::from("msg"), String::from("err.stack")],
+            ..Default::default()
+        });
+        let input = b"{\"msg\":\"failed\",\"err\":{\"stack\":\"Error: failed\\n    at main (app.js:1:1)\"}}\n";
+        let messages = parse_all(&mut parser, input);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].columns[1],
+            "Error: failed\u{6}    at main (app.js:1:1)"
+        );
+        assert!(!messages[0].to_string().contains('\n'));
+    }
+
+    #[test]
+    fn configured_and_epoch_timestamps() {
+        let mut parser = JsonParser::new(JsonConfig::default());
+        let messages = parse_all(
+            &mut parser,
+            b"{\"ts\":1710692018.811,\"level\":\"info\"}\n\
+            {\"timeUnixNano\":\"1710692018811000000\",\"severityText\":\"Debug\"}\n",
+        );
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_018_811));
+        // Levels are shown normalized among the remaining fields.
+        assert!(messages[0].columns[0].contains("level=INFO"));
+        assert_eq!(messages[1].timestamp(), Some(1_710_692_018_811));
+        assert!(messages[1].columns[0].contains("severityText=DEBUG"));
+
+        let mut parser = JsonParser::new(JsonConfig {
+            fields: vec![],
+            timestamp_field: Some(String::from("meta.at")),
+            timestamp_format: Some(String::from("%d.%m.%Y %H:%M:%S")),
+            level_field: Some(String::from("meta.prio")),
+        });
+        let messages = parse_all(
+            &mut parser,
+            b"{\"time\":0,\"meta\":{\"at\":\"17.03.2024 16:13:38\",\"prio\":\"E\"}}",
+        );
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_018_000));
+        assert!(messages[0].columns[0].contains("meta.prio=ERROR"));
+        assert_eq!(
+            raw(&messages[0]),
+            "{\"time\":0,\"meta\":{\"at\":\"17.03.2024 16:13:38\",\"prio\":\"E\"}}".as_bytes()
+        );
+    }
+}
@@ -1,5 +1,12 @@
 #![deny(unused_crate_dependencies)]
+pub mod can;
 pub mod dlt;
+pub mod doip;
+pub mod journal;
+pub mod json;
+pub mod logcat;
+pub mod protobuf;
+pub mod separators;
 pub mod someip;
 pub mod text;
 use serde::Serialize;
@@ -120,6 +127,12 @@ pub trait LogMessage: Display + Serialize {
     /// Serializes a message directly into a Writer
     /// returns the size of the serialized message
     fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error>;
+
+    /// Returns the timestamp of the message in milliseconds since the epoch, if the
+    /// parser has detected one.
+    fn timestamp(&self) -> Option<i64> {
+        None
+    }
 }
 
 /// A trait for parsers that extract at most one item per call.
@@ -0,0 +1,386 @@
+//! # Android logcat parser
+//!
+//! Parses the binary `logger_entry` v1 to v4 records written by `logcat -B`
+//! and the `threadtime` text format, e.g. as part of bugreports:
+//!
+//! ```text
+//! 03-17 16:13:38.811  1702  2395 E WindowManager: Exception thrown
+//! ```
+//!
+//! Text lines which aren't logcat records (e.g. other sections of bugreports)
+//! are skipped. The `threadtime` format has no year and is written in the
+//! local time of the device, both can be configured.
+use crate::{
+    Error, LogMessage, ParseOutput, SingleParser,
+    separators::{COLUMN_SEP, LINE_SEP},
+    text::pattern::next_line,
+};
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::{fmt, io::Write};
+
+/// Header length of `logger_entry` v1, which has no header size field.
+const V1_HEADER_LEN: usize = 20;
+/// Maximal payload length of `logger_entry` v4.
+const MAX_PAYLOAD_LEN: usize = 5 * 1024;
+/// Header length of `logger_entry` v2 (with the effective user id) and v3 (with
+/// the log id); both versions have the same size.
+const V2_V3_HEADER_LEN: usize = 24;
+/// Header length of `logger_entry` v4, which has the log id and the user id.
+const V4_HEADER_LEN: usize = 28;
+/// Offset of the log id in v3 and v4, or of the effective user id in v2.
+const LOG_ID_OFFSET: usize = 20;
+/// Log buffers with binary payload: events, stats and security.
+const BINARY_LOG_IDS: [u32; 3] = [2, 5, 6];
+
+lazy_static! {
+    static ref THREADTIME: Regex = Regex::new(
+        r"^(?:(?P<year>\d{4})-)?(?P<time>\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3,9})\s+(?:\S+\s+)??(?P<pid>\d+)\s+(?P<tid>\d+)\s+(?P<priority>[VDIWEFAS])\s(?P<tag>.*?)\s*: (?P<message>.*)$"
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogcatFormat {
+    /// Binary `logger_entry` records (`logcat -B`).
+    Binary,
+    /// `threadtime` text format.
+    Threadtime,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct LogcatMessage {
+    /// The formatted time in the configured time zone.
+    pub time: String,
+    /// The timestamp in milliseconds since the epoch.
+    pub timestamp: i64,
+    pub pid: i32,
+    pub tid: u32,
+    /// The priority as letter (e.g. `E`).
+    pub priority: char,
+    pub tag: String,
+    pub message: String,
+    /// The original bytes of the record.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for LogcatMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}{COLUMN_SEP}{}",
+            self.time, self.pid, self.tid, self.priority, self.tag, self.message
+        )
+    }
+}
+
+impl LogMessage for LogcatMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        Some(self.timestamp)
+    }
+}
+
+pub struct LogcatParser {
+    format: LogcatFormat,
+    tz: Option<Tz>,
+    /// Year of `threadtime` records.
+    year: i32,
+}
+
+impl LogcatParser {
+    /// Creates a parser for the given format. Records without year are assigned to
+    /// `year` or the current year, text records are read in the time zone `tz` or UTC.
+    pub fn new(format: LogcatFormat, tz: Option<&str>, year: Option<i32>) -> Result<Self, Error> {
+        let tz = tz
+            .map(|tz| {
+                tz.parse::<Tz>()
+                    .map_err(|e| Error::Unrecoverable(format!("Invalid time zone {tz}: {e}")))
+            })
+            .transpose()?;
+        Ok(Self {
+            format,
+            tz,
+            year: year.unwrap_or_else(|| Utc::now().year()),
+        })
+    }
+
+    fn format_time(&self, datetime: DateTime<Utc>) -> String {
+        const FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+        match &self.tz {
+            Some(tz) => datetime.with_timezone(tz).format(FORMAT).to_string(),
+            None => datetime.format(FORMAT).to_string(),
+        }
+    }
+
+    fn binary(&self, input: &[u8]) -> Result<ParseOutput<LogcatMessage>, Error> {
+        if input.len() < V1_HEADER_LEN {
+            return Err(Error::Incomplete);
+        }
+        let u16_at = |offset: usize| u16::from_le_bytes([input[offset], input[offset + 1]]);
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes([
+                input[offset],
+                input[offset + 1],
+                input[offset + 2],
+                input[offset + 3],
+            ])
+        };
+        let len = u16_at(0) as usize;
+        let header_len = match u16_at(2) as usize {
+            0 => V1_HEADER_LEN,
+            size @ (V1_HEADER_LEN | V2_V3_HEADER_LEN | V4_HEADER_LEN) => size,
+            size => return Err(Error::Parse(format!("Invalid logcat header size {size}"))),
+        };
+        if len == 0 || len > MAX_PAYLOAD_LEN {
+            return Err(Error::Parse(format!("Invalid logcat payload length {len}")));
+        }
+        if input.len() < header_len + len {
+            return Err(Error::Incomplete);
+        }
+        let payload = &input[header_len..header_len + len];
+        let (priority, tag, message) = match header_len {
+            V4_HEADER_LEN if BINARY_LOG_IDS.contains(&u32_at(LOG_ID_OFFSET)) => {
+                binary_payload(payload)
+            }
+            // The field is the log id in v3, but the effective user id in v2. The user
+            // id can't be told from a log id of binary buffer, so such entry is binary
+            // only if its payload isn't a valid text entry.
+            V2_V3_HEADER_LEN if BINARY_LOG_IDS.contains(&u32_at(LOG_ID_OFFSET)) => {
+                text_payload(payload).unwrap_or_else(|_| binary_payload(payload))
+            }
+            _ => text_payload(payload)?,
+        };
+        let datetime = DateTime::from_timestamp(u32_at(12) as i64, u32_at(16))
+            .ok_or_else(|| Error::Parse(String::from("Invalid logcat timestamp")))?;
+        let msg = LogcatMessage {
+            time: self.format_time(datetime),
+            timestamp: datetime.timestamp_millis(),
+            pid: u32_at(4) as i32,
+            tid: u32_at(8),
+            priority,
+            tag,
+            message,
+            raw: input[..header_len + len].to_vec(),
+        };
+        Ok(ParseOutput::new(header_len + len, Some(msg.into())))
+    }
+
+    fn threadtime(&self, line: &str) -> Option<LogcatMessage> {
+        let caps = THREADTIME.captures(line)?;
+        let year = caps
+            .name("year")
+            .and_then(|year| year.as_str().parse().ok())
+            .unwrap_or(self.year);
+        let naive = NaiveDateTime::parse_from_str(
+            &format!("{year}-{}", &caps["time"]),
+            "%Y-%m-%d %H:%M:%S%.f",
+        )
+        .ok()?;
+        let datetime = match &self.tz {
+            Some(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()?
+                .with_timezone(&Utc),
+            None => naive.and_utc(),
+        };
+        Some(LogcatMessage {
+            time: self.format_time(datetime),
+            timestamp: datetime.timestamp_millis(),
+            pid: caps["pid"].parse().ok()?,
+            tid: caps["tid"].parse().ok()?,
+            priority: caps["priority"].chars().next()?,
+            tag: caps["tag"].to_owned(),
+            message: caps["message"].to_owned(),
+            raw: line.as_bytes().to_vec(),
+        })
+    }
+}
+
+/// Returns the letter of the given priority.
+fn priority(value: u8) -> char {
+    match value {
+        2 => 'V',
+        3 => 'D',
+        4 => 'I',
+        5 => 'W',
+        6 => 'E',
+        7 => 'F',
+        8 => 'S',
+        _ => '?',
+    }
+}
+
+/// Parses `priority: u8, tag: \0 terminated, message: \0 terminated`.
+fn text_payload(payload: &[u8]) -> Result<(char, String, String), Error> {
+    let (&prio, rest) = payload
+        .split_first()
+        .ok_or_else(|| Error::Parse(String::from("Empty logcat payload")))?;
+    if prio > 8 {
+        return Err(Error::Parse(format!("Invalid logcat priority {prio}")));
+    }
+    let mut parts = rest.splitn(2, |b| *b == 0);
+    let tag = String::from_utf8_lossy(parts.next().unwrap_or_default()).into_owned();
+    let message = parts.next().unwrap_or_default();
+    let message = message.strip_suffix(&[0]).unwrap_or(message);
+    let message = String::from_utf8_lossy(message)
+        .trim_end_matches('\n')
+        .replace('\n', LINE_SEP);
+    Ok((priority(prio), tag, message))
+}
+
+/// Binary buffers start with the numeric tag of the event, the data is shown as hex.
+fn binary_payload(payload: &[u8]) -> (char, String, String) {
+    let tag = match payload.get(..4) {
+        Some(tag) => u32::from_le_bytes([tag[0], tag[1], tag[2], tag[3]]).to_string(),
+        None => String::new(),
+    };
+    let data = payload
+        .get(4..)
+        .unwrap_or_default()
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<String>>()
+        .join(" ");
+    ('I', tag, data)
+}
+
+impl SingleParser for LogcatParser {
+    type Output = LogcatMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<LogcatMessage>, Error> {
+        match self.format {
+            LogcatFormat::Binary => self.binary(input),
+            LogcatFormat::Threadtime => {
+                let Some((line, consumed)) = next_line(input, false) else {
+                    return Ok(ParseOutput::new(input.len(), None));
+                };
+                Ok(ParseOutput::new(
+                    consumed,
+                    self.threadtime(&line).map(|msg| msg.into()),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseYield, Parser};
+
+    fn parse_all(parser: &mut LogcatParser, input: &[u8]) -> Vec<LogcatMessage> {
+        parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn entry(header_len: u16, log_id: u32, payload: &[u8]) -> Vec<u8> {
+        let mut entry = Vec::new();
+        entry.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        entry.extend_from_slice(&header_len.to_le_bytes());
+        entry.extend_from_slice(&1702i32.to_le_bytes());
+        entry.extend_from_slice(&2395u32.to_le_bytes());
+        entry.extend_from_slice(&1_710_692_018u32.to_le_bytes());
+        entry.extend_from_slice(&811_000_000u32.to_le_bytes());
+        if header_len >= 24 {
+            entry.extend_from_slice(&log_id.to_le_bytes());
+        }
+        if header_len == 28 {
+            entry.extend_from_slice(&1000u32.to_le_bytes());
+        }
+        entry.extend_from_slice(payload);
+        entry
+    }
+
+    #[test]
+    fn binary_entries() {
+        let mut input = entry(0, 0, b"\x06WindowManager\0Exception\nat Foo\n\0");
+        input.extend(entry(24, 0, b"\x04ActivityManager\0Start\0"));
+        input.extend(entry(28, 2, &[0x10, 0x27, 0, 0, 0x01, 0x02]));
+        let mut parser = LogcatParser::new(LogcatFormat::Binary, None, None).unwrap();
+        let messages = parse_all(&mut parser, &input);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0].to_string(),
+            "2024-03-17 16:13:38.811\u{4}1702\u{4}2395\u{4}E\u{4}WindowManager\u{4}Exception\u{6}at Foo"
+        );
+        assert_eq!(messages[0].timestamp, 1_710_692_018_811);
+        assert_eq!(messages[1].priority, 'I');
+        assert_eq!(messages[1].message, "Start");
+        assert_eq!(
+            (messages[2].tag.as_str(), messages[2].message.as_str()),
+            ("10000", "01 02")
+        );
+
+        let mut parser = LogcatParser::new(LogcatFormat::Binary, None, None).unwrap();
+        assert!(matches!(
+            parser.parse_item(&entry(24, 0, b"\x04Tag\0Msg\0")[..30], None),
+            Err(Error::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn v2_entries() {
+        // Effective user ids of v2 entries are at the offset of log ids of v3
+        let mut input = entry(24, 1000, b"\x04ActivityManager\0Start\0");
+        input.extend(entry(24, 2, b"\x03bin\0Started by user 2\0"));
+        // Binary entry of v3
+        input.extend(entry(24, 2, &[0x10, 0x27, 0, 0, 0x01, 0x02]));
+        let mut parser = LogcatParser::new(LogcatFormat::Binary, None, None).unwrap();
+        let messages = parse_all(&mut parser, &input);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            (messages[0].priority, messages[0].tag.as_str()),
+            ('I', "ActivityManager")
+        );
+        assert_eq!(
+            (messages[1].priority, messages[1].message.as_str()),
+            ('D', "Started by user 2")
+        );
+        assert_eq!(
+            (messages[2].tag.as_str(), messages[2].message.as_str()),
+            ("10000", "01 02")
+        );
+    }
+
+    #[test]
+    fn threadtime_lines() {
+        let mut parser =
+            LogcatParser::new(LogcatFormat::Threadtime, Some("Europe/Berlin"), Some(2023)).unwrap();
+        let messages = parse_all(
+            &mut parser,
+            b"== dumpstate: 2024-03-17 16:13:40\n\
+            --------- beginning of main\n\
+            03-17 16:13:38.811  1702  2395 E WindowManager: Exception thrown\n\
+            2024-03-17 16:13:38.811  1000  1702  2395 W Audio Flinger   : underrun\n",
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0].to_string(),
+            "2023-03-17 16:13:38.811\u{4}1702\u{4}2395\u{4}E\u{4}WindowManager\u{4}Exception thrown"
+        );
+        assert_eq!(messages[0].timestamp, 1_679_066_018_811);
+        assert_eq!(messages[1].timestamp, 1_710_688_418_811);
+        assert_eq!(messages[1].tag, "Audio Flinger");
+        assert_eq!(messages[1].pid, 1702);
+        assert!(LogcatParser::new(LogcatFormat::Threadtime, Some("Mars/Base"), None).is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! # Protobuf parser
+//!
+//! Parses streams of length delimited Protocol Buffers messages of one message
+//! type, e.g. written with `writeDelimitedTo` or sent over TCP and UDP, as well
+//! as UDP datagrams carrying one message each. The message types are described by `.proto` files, which are compiled at
+//! runtime, or by descriptor sets (`protoc --descriptor_set_out`).
+//!
+//! The fields of a message are either shown as columns, nested messages and
+//! repeated fields as JSON, or the whole message is shown as JSON.
+use crate::{
+    Error, LogMessage, ParseOutput, SingleParser,
+    doip::hex,
+    separators::{COLUMN_SEP, LINE_SEP},
+};
+use prost_reflect::{DescriptorPool, DynamicMessage, Kind, MapKey, MessageDescriptor, Value};
+use serde::Serialize;
+use std::{
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Maximal accepted message length, larger values are treated as invalid frame.
+const MAX_MESSAGE_LEN: usize = 0x100_0000;
+/// Maximal length of a varint.
+const MAX_VARINT_LEN: usize = 10;
+/// Name of the column of messages shown as JSON.
+const JSON_COLUMN: &str = "message";
+
+/// Framing of the messages in the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// Messages are prefixed with their length as varint.
+    Varint,
+    /// Messages are prefixed with their length as 32 bit big endian integer.
+    Fixed32Be,
+    /// Messages are prefixed with their length as 32 bit little endian integer.
+    Fixed32Le,
+    /// Messages have no prefix, each datagram is one message. The whole input
+    /// is taken as message, so it's suitable for UDP sources only.
+    Datagram,
+}
+
+/// Presentation of the decoded messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rendering {
+    /// Each field of the message is a column.
+    Columns,
+    /// The message is shown as JSON.
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ProtobufMessage {
+    /// The rendered fields of the message.
+    pub columns: Vec<String>,
+    /// The original bytes of the message including its length prefix.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for ProtobufMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.columns.join(COLUMN_SEP))
+    }
+}
+
+impl LogMessage for ProtobufMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+}
+
+pub struct ProtobufParser {
+    descriptor: MessageDescriptor,
+    framing: Framing,
+    rendering: Rendering,
+}
+
+impl ProtobufParser {
+    /// Creates a parser for messages of the given type. The name may omit the
+    /// package if it is unique within the loaded files.
+    pub fn new(
+        paths: &[PathBuf],
+        message_type: &str,
+        framing: Framing,
+        rendering: Rendering,
+    ) -> Result<Self, Error> {
+        let pool = load_descriptors(paths)?;
+        let descriptor = match pool.get_message_by_name(message_type) {
+            Some(descriptor) => descriptor,
+            None => {
+                let mut candidates = pool
+                    .all_messages()
+                    .filter(|descriptor| descriptor.name() == message_type);
+                match (candidates.next(), candidates.next()) {
+                    (Some(descriptor), None) => descriptor,
+                    (Some(_), Some(_)) => {
+                        return Err(Error::Unrecoverable(format!(
+                            "Protobuf message type {message_type} is ambiguous, use its full name"
+                        )));
+                    }
+                    _ => {
+                        return Err(Error::Unrecoverable(format!(
+                            "Protobuf message type {message_type} isn't defined"
+                        )));
+                    }
+                }
+            }
+        };
+        Ok(Self {
+            descriptor,
+            framing,
+            rendering,
+        })
+    }
+
+    /// Returns the names of the columns.
+    pub fn columns(&self) -> Vec<String> {
+        match self.rendering {
+            Rendering::Columns => self
+                .descriptor
+                .fields()
+                .map(|field| field.name().to_owned())
+                .collect(),
+            Rendering::Json => vec![JSON_COLUMN.to_owned()],
+        }
+    }
+
+    /// Returns the length of the frame prefix and of the message.
+    fn frame(&self, input: &[u8]) -> Result<(usize, usize), Error> {
+        let (prefix, len) = match self.framing {
+            Framing::Varint => {
+                let mut len = 0u64;
+                let mut prefix = None;
+                for (i, byte) in input.iter().take(MAX_VARINT_LEN).enumerate() {
+                    len |= u64::from(byte & 0x7F) << (7 * i);
+                    if byte & 0x80 == 0 {
+                        prefix = Some(i + 1);
+                        break;
+                    }
+                }
+                match prefix {
+                    Some(prefix) => (prefix, len),
+                    None if input.len() < MAX_VARINT_LEN => return Err(Error::Incomplete),
+                    None => return Err(Error::Parse("Invalid varint length".to_owned())),
+                }
+            }
+            Framing::Fixed32Be | Framing::Fixed32Le => {
+                let Some(bytes) = input.first_chunk::<4>() else {
+                    return Err(Error::Incomplete);
+                };
+                let len = if self.framing == Framing::Fixed32Be {
+                    u32::from_be_bytes(*bytes)
+                } else {
+                    u32::from_le_bytes(*bytes)
+                };
+                (4, u64::from(len))
+            }
+            Framing::Datagram if input.is_empty() => return Err(Error::Incomplete),
+            Framing::Datagram => (0, input.len() as u64),
+        };
+        match usize::try_from(len) {
+            Ok(len) if len <= MAX_MESSAGE_LEN => Ok((prefix, len)),
+            _ => Err(Error::Parse(format!(
+                "Invalid protobuf message length {len}"
+            ))),
+        }
+    }
+
+    fn render(&self, msg: &DynamicMessage) -> Vec<String> {
+        match self.rendering {
+            Rendering::Columns => self
+                .descriptor
+                .fields()
+                .map(|field| render_value(&msg.get_field(&field), &field.kind()))
+                .collect(),
+            Rendering::Json => vec![json(msg)],
+        }
+    }
+}
+
+impl SingleParser for ProtobufParser {
+    type Output = ProtobufMessage;
+
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<ParseOutput<ProtobufMessage>, Error> {
+        let (prefix, len) = self.frame(input)?;
+        if input.len() < prefix + len {
+            return Err(Error::Incomplete);
+        }
+        let payload = &input[prefix..prefix + len];
+        let columns = match DynamicMessage::decode(self.descriptor.clone(), payload) {
+            Ok(msg) => self.render(&msg),
+            Err(err) => {
+                let mut columns = vec![String::new(); self.columns().len().max(1)];
+                if let Some(last) = columns.last_mut() {
+                    *last = format!("invalid message ({err}) [{}]", hex(payload));
+                }
+                columns
+            }
+        };
+        let msg = ProtobufMessage {
+            columns,
+            raw: input[..prefix + len].to_vec(),
+        };
+        Ok(ParseOutput::new(prefix + len, Some(msg.into())))
+    }
+}
+
+/// Loads the descriptors of `.proto` files and of descriptor sets. Imports of
+/// `.proto` files are resolved relative to the directories of the files.
+fn load_descriptors(paths: &[PathBuf]) -> Result<DescriptorPool, Error> {
+    let (protos, sets): (Vec<&PathBuf>, Vec<&PathBuf>) = paths.iter().partition(|path| {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("proto"))
+    });
+    let mut pool = if protos.is_empty() {
+        DescriptorPool::new()
+    } else {
+        let mut includes: Vec<&Path> = protos.iter().filter_map(|path| path.parent()).collect();
+        includes.sort();
+        includes.dedup();
+        let mut compiler = protox::Compiler::new(includes)
+            .map_err(|e| Error::Unrecoverable(format!("Fail to compile proto files: {e}")))?;
+        compiler.include_imports(true);
+        compiler
+            .open_files(protos)
+            .map_err(|e| Error::Unrecoverable(format!("Fail to compile proto files: {e}")))?;
+        compiler.descriptor_pool()
+    };
+    for path in sets {
+        let bytes = fs::read(path).map_err(|e| {
+            Error::Unrecoverable(format!(
+                "Fail to read descriptor set {}: {e}",
+                path.display()
+            ))
+        })?;
+        pool.decode_file_descriptor_set(bytes.as_slice())
+            .map_err(|e| {
+                Error::Unrecoverable(format!("Invalid descriptor set {}: {e}", path.display()))
+            })?;
+    }
+    Ok(pool)
+}
+
+fn json(msg: &DynamicMessage) -> String {
+    serde_json::to_string(msg).unwrap_or_else(|e| format!("invalid message ({e})"))
+}
+
+/// Renders a field value. Enums are shown by their name, bytes as hex and
+/// nested messages as JSON. Line breaks in strings are replaced with [`LINE_SEP`],
+/// so the message still takes a single row.
+fn render_value(value: &Value, kind: &Kind) -> String {
+    match value {
+        Value::Bool(value) => value.to_string(),
+        Value::I32(value) => value.to_string(),
+        Value::I64(value) => value.to_string(),
+        Value::U32(value) => value.to_string(),
+        Value::U64(value) => value.to_string(),
+        Value::F32(value) => value.to_string(),
+        Value::F64(value) => value.to_string(),
+        Value::String(value) => value.replace('\n', LINE_SEP),
+        Value::Bytes(value) => hex(value),
+        Value::EnumNumber(number) => kind
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*number))
+            .map(|value| value.name().to_owned())
+            .unwrap_or_else(|| number.to_string()),
+        Value::Message(msg) => json(msg),
+        Value::List(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(|value| render_value(value, kind))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Value::Map(entries) => {
+            let value_kind = kind
+                .as_message()
+                .map(|entry| entry.map_entry_value_field().kind())
+                .unwrap_or_else(|| kind.clone());
+            let mut entries: Vec<String> = entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        MapKey::Bool(key) => key.to_string(),
+                        MapKey::I32(key) => key.to_string(),
+                        MapKey::I64(key) => key.to_string(),
+                        MapKey::U32(key) => key.to_string(),
+                        MapKey::U64(key) => key.to_string(),
+                        MapKey::String(key) => key.replace('\n', LINE_SEP),
+                    };
+                    format!("{key}: {}", render_value(value, &value_kind))
+                })
+                .collect();
+            // Entries of maps have no order.
+            entries.sort();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseYield, Parser};
+
+    const PROTO: &str = r#"
+        syntax = "proto3";
+        package my_package;
+
+        enum Event {
+          EVENT_A = 0;
+          EVENT_B = 1;
+        }
+
+        message Request {
+          uint64 id = 1;
+          string str = 2;
+          Event event = 3;
+          repeated uint32 values = 4;
+        }
+    "#;
+
+    fn parser(framing: Framing, rendering: Rendering) -> ProtobufParser {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("request.proto");
+        fs::write(&path, PROTO).unwrap();
+        ProtobufParser::new(&[path], "Request", framing, rendering).unwrap()
+    }
+
+    fn parse_all(parser: &mut ProtobufParser, input: &[u8]) -> Vec<String> {
+        parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delimited_messages() {
+        // id: 1, str: "hi", event: EVENT_B, values: [3, 4]
+        let msg = [
+            0x08, 0x01, 0x12, 0x02, b'h', b'i', 0x18, 0x01, 0x22, 0x02, 0x03, 0x04,
+        ];
+        let mut input = vec![msg.len() as u8];
+        input.extend_from_slice(&msg);
+        // An empty message with defaults only.
+        input.push(0x00);
+
+        let mut columns = parser(Framing::Varint, Rendering::Columns);
+        assert_eq!(columns.columns(), vec!["id", "str", "event", "values"]);
+        assert_eq!(
+            parse_all(&mut columns, &input),
+            vec![
+                "1\u{4}hi\u{4}EVENT_B\u{4}[3, 4]",
+                "0\u{4}\u{4}EVENT_A\u{4}[]"
+            ]
+        );
+        assert!(matches!(
+            columns.parse_item(&input[..5], None),
+            Err(Error::Incomplete)
+        ));
+
+        let mut json = parser(Framing::Fixed32Be, Rendering::Json);
+        let mut input = (msg.len() as u32).to_be_bytes().to_vec();
+        input.extend_from_slice(&msg);
+        assert_eq!(
+            parse_all(&mut json, &input),
+            vec![r#"{"id":"1","str":"hi","event":"EVENT_B","values":[3,4]}"#]
+        );
+        assert!(ProtobufParser::new(&[], "Request", Framing::Varint, Rendering::Json).is_err());
+    }
+
+    #[test]
+    fn multiline_strings() {
+        // id: 2, str: "a\nb"
+        let msg = [0x08, 0x02, 0x12, 0x03, b'a', b'\n', b'b'];
+        let mut datagram = parser(Framing::Datagram, Rendering::Columns);
+        assert_eq!(
+            parse_all(&mut datagram, &msg),
+            vec!["2\u{4}a\u{6}b\u{4}EVENT_A\u{4}[]"]
+        );
+    }
+
+    #[test]
+    fn datagrams() {
+        let msg = [0x08, 0x01, 0x12, 0x02, b'h', b'i'];
+        let mut datagram = parser(Framing::Datagram, Rendering::Columns);
+        let output = datagram.parse_item(&msg, None).unwrap();
+        assert_eq!(output.consumed, msg.len());
+        assert_eq!(
+            parse_all(&mut datagram, &msg),
+            vec!["1\u{4}hi\u{4}EVENT_A\u{4}[]"]
+        );
+        assert!(matches!(
+            datagram.parse_item(&[], None),
+            Err(Error::Incomplete)
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+//! Separators shared by the parsers, which render messages as columns.
+//!
+//! Columns of a message are joined with [`COLUMN_SEP`]; line breaks inside of a
+//! column are replaced with [`LINE_SEP`], so every message takes exactly one row of
+//! the session file.
+
+/// Separator used between the columns of a message.
+pub const COLUMN_SEP: &str = "\u{0004}";
+/// Separator used between the lines of a multi-line column.
+pub const LINE_SEP: &str = "\u{0006}";
@@ -0,0 +1,583 @@
+//! Verification of AUTOSAR E2E (end-to-end) protected SOME/IP payloads.
+//!
+//! Supported are the profiles 1, 2, 4, 5 and 7. A protection is configured per
+//! data ID and bound to a SOME/IP message by its service- and method-id. The
+//! verifier checks the CRC of each protected payload and keeps track of the
+//! counter per data ID to detect repeated and lost messages.
+//!
+//! Protections are read from a JSON list of [`E2EProtection`] or from the
+//! protection sets of an ARXML file. The E2E settings of FIBEX files aren't read;
+//! they have to be converted to JSON.
+mod arxml;
+
+use crate::Error;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+/// The supported E2E profiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum E2EProfile {
+    /// CRC8 (SAE J1850) and 4-bit counter, data ID as part of the CRC.
+    P1,
+    /// CRC8H2F and 4-bit counter, data ID taken from a list indexed by the counter.
+    P2,
+    /// CRC32P4, 16-bit counter, explicit length and data ID.
+    P4,
+    /// CRC16 (CCITT-FALSE) and 8-bit counter, data ID as part of the CRC.
+    P5,
+    /// CRC64 (ECMA-182), 32-bit counter, explicit length and data ID.
+    P7,
+}
+
+impl E2EProfile {
+    /// Returns the size of the E2E header in bytes.
+    fn header_len(&self) -> usize {
+        match self {
+            E2EProfile::P1 | E2EProfile::P2 => 2,
+            E2EProfile::P4 => 12,
+            E2EProfile::P5 => 3,
+            E2EProfile::P7 => 20,
+        }
+    }
+
+    /// Returns the highest counter value before the counter wraps around.
+    fn max_counter(&self) -> u64 {
+        match self {
+            E2EProfile::P1 => 0x0E,
+            E2EProfile::P2 => 0x0F,
+            E2EProfile::P4 => 0xFFFF,
+            E2EProfile::P5 => 0xFF,
+            E2EProfile::P7 => 0xFFFF_FFFF,
+        }
+    }
+}
+
+/// The E2E protection of a single data ID.
+#[derive(Debug, Clone, Deserialize)]
+pub struct E2EProtection {
+    /// The service-id of the protected SOME/IP message.
+    pub service_id: u16,
+    /// The method-id (or event-id) of the protected SOME/IP message.
+    pub method_id: u16,
+    /// The E2E profile.
+    pub profile: E2EProfile,
+    /// The data ID of the protected data.
+    pub data_id: u32,
+    /// The list of data IDs indexed by the counter (profile 2 only).
+    #[serde(default)]
+    pub data_id_list: Vec<u8>,
+    /// The offset of the E2E header within the payload in bytes.
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// The E2E configuration of a SOME/IP trace.
+#[derive(Debug, Clone, Default)]
+pub struct E2EConfig {
+    protections: HashMap<(u16, u16), E2EProtection>,
+}
+
+impl E2EConfig {
+    /// Creates a new configuration from the given protections.
+    pub fn new(protections: Vec<E2EProtection>) -> Self {
+        E2EConfig {
+            protections: protections
+                .into_iter()
+                .map(|p| ((p.service_id, p.method_id), p))
+                .collect(),
+        }
+    }
+
+    /// Reads the configuration from a JSON file containing a list of protections
+    /// or from an ARXML file. Other XML files (FIBEX) are rejected.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::Unrecoverable(format!(
+                "Fail to read E2E config {}: {e}",
+                path.to_string_lossy()
+            ))
+        })?;
+        let protections: Vec<E2EProtection> = if content.trim_start().starts_with('<') {
+            arxml::protections(&content).map_err(|e| {
+                Error::Unrecoverable(format!(
+                    "Invalid E2E config {} (only JSON and ARXML are supported): {e}",
+                    path.to_string_lossy()
+                ))
+            })?
+        } else {
+            serde_json::from_str(&content).map_err(|e| {
+                Error::Unrecoverable(format!(
+                    "Invalid E2E config {}: {e}",
+                    path.to_string_lossy()
+                ))
+            })?
+        };
+        Ok(E2EConfig::new(protections))
+    }
+
+    /// Returns the protection of the given message if any.
+    pub fn get(&self, service_id: u16, method_id: u16) -> Option<&E2EProtection> {
+        self.protections.get(&(service_id, method_id))
+    }
+
+    /// Returns true if no protection is configured.
+    pub fn is_empty(&self) -> bool {
+        self.protections.is_empty()
+    }
+}
+
+/// The result of the CRC check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrcStatus {
+    /// The CRC matches the data.
+    Ok,
+    /// The CRC doesn't match the data.
+    Wrong,
+    /// The data ID in the header doesn't match the configuration.
+    WrongDataId,
+    /// The length in the header doesn't match the payload.
+    WrongLength,
+    /// The payload is too short to contain the E2E header.
+    NoData,
+}
+
+impl fmt::Display for CrcStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CRC:{}",
+            match self {
+                CrcStatus::Ok => "OK",
+                CrcStatus::Wrong => "WRONG",
+                CrcStatus::WrongDataId => "WRONG_DATA_ID",
+                CrcStatus::WrongLength => "WRONG_LENGTH",
+                CrcStatus::NoData => "NO_DATA",
+            }
+        )
+    }
+}
+
+/// The result of the counter check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterStatus {
+    /// The first message of the data ID.
+    Initial,
+    /// The counter has been incremented by one.
+    Ok,
+    /// The counter is the same as of the previous message.
+    Repeated,
+    /// The given number of messages has been lost since the previous message.
+    Lost(u64),
+    /// The counter has not been checked as the CRC check failed.
+    Unchecked,
+}
+
+impl fmt::Display for CounterStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterStatus::Initial => write!(f, "CNT:INITIAL"),
+            CounterStatus::Ok => write!(f, "CNT:OK"),
+            CounterStatus::Repeated => write!(f, "CNT:REPEATED"),
+            CounterStatus::Lost(n) => write!(f, "CNT:LOST {n}"),
+            CounterStatus::Unchecked => write!(f, "CNT:-"),
+        }
+    }
+}
+
+/// The result of the E2E check of a single message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct E2EStatus {
+    /// The status of the CRC check.
+    pub crc: CrcStatus,
+    /// The status of the counter check.
+    pub counter: CounterStatus,
+}
+
+/// Verifies E2E protected payloads and tracks the counter state per data ID.
+#[derive(Debug, Default)]
+pub struct E2EVerifier {
+    config: E2EConfig,
+    counters: HashMap<u32, u64>,
+}
+
+impl E2EVerifier {
+    /// Creates a new verifier for the given configuration.
+    pub fn new(config: E2EConfig) -> Self {
+        E2EVerifier {
+            config,
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Verifies the payload of the given message, returns `None` if the message is not protected.
+    pub fn verify(&mut self, service_id: u16, method_id: u16, payload: &[u8]) -> Option<E2EStatus> {
+        let protection = self.config.get(service_id, method_id)?;
+        let (crc, counter) = check(protection, payload);
+        let counter = match (crc, counter) {
+            (CrcStatus::Ok, Some(counter)) => {
+                let max = protection.profile.max_counter();
+                match self.counters.insert(protection.data_id, counter) {
+                    None => CounterStatus::Initial,
+                    Some(previous) => match (counter + max + 1 - previous) % (max + 1) {
+                        0 => CounterStatus::Repeated,
+                        1 => CounterStatus::Ok,
+                        delta => CounterStatus::Lost(delta - 1),
+                    },
+                }
+            }
+            _ => CounterStatus::Unchecked,
+        };
+        Some(E2EStatus { crc, counter })
+    }
+}
+
+/// Checks the CRC of the payload and returns the status together with the received counter.
+fn check(protection: &E2EProtection, payload: &[u8]) -> (CrcStatus, Option<u64>) {
+    let offset = protection.offset;
+    if offset
+        .checked_add(protection.profile.header_len())
+        .is_none_or(|end| payload.len() < end)
+    {
+        return (CrcStatus::NoData, None);
+    }
+    let header = &payload[offset..];
+    let data_id = protection.data_id;
+    match protection.profile {
+        E2EProfile::P1 => {
+            let counter = (header[1] & 0x0F) as u64;
+            let crc = CRC8_SAE_J1850.checksum(&[
+                &[data_id as u8, (data_id >> 8) as u8],
+                &payload[..offset],
+                &payload[offset + 1..],
+            ]);
+            (crc_status(crc == header[0] as u64), Some(counter))
+        }
+        E2EProfile::P2 => {
+            let counter = (header[1] & 0x0F) as u64;
+            let Some(list_id) = protection.data_id_list.get(counter as usize) else {
+                return (CrcStatus::WrongDataId, None);
+            };
+            let crc = CRC8_H2F.checksum(&[&payload[offset + 1..], &[*list_id]]);
+            (crc_status(crc == header[0] as u64), Some(counter))
+        }
+        E2EProfile::P4 => {
+            let length = u16::from_be_bytes([header[0], header[1]]) as usize;
+            let counter = u16::from_be_bytes([header[2], header[3]]) as u64;
+            let received_id = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+            let received_crc = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+            if length != payload.len() {
+                return (CrcStatus::WrongLength, None);
+            }
+            if received_id != data_id {
+                return (CrcStatus::WrongDataId, None);
+            }
+            let crc = CRC32_P4.checksum(&[&payload[..offset + 8], &payload[offset + 12..]]);
+            (crc_status(crc == received_crc as u64), Some(counter))
+        }
+        E2EProfile::P5 => {
+            let received_crc = u16::from_le_bytes([header[0], header[1]]);
+            let counter = header[2] as u64;
+            let crc = CRC16_CCITT_FALSE.checksum(&[
+                &payload[..offset],
+                &payload[offset + 2..],
+                &[data_id as u8, (data_id >> 8) as u8],
+            ]);
+            (crc_status(crc == received_crc as u64), Some(counter))
+        }
+        E2EProfile::P7 => {
+            let mut crc_bytes = [0u8; 8];
+            crc_bytes.copy_from_slice(&header[..8]);
+            let received_crc = u64::from_be_bytes(crc_bytes);
+            let length = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+            let counter = u32::from_be_bytes([header[12], header[13], header[14], header[15]]);
+            let received_id = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+            if length as usize != payload.len() {
+                return (CrcStatus::WrongLength, None);
+            }
+            if received_id != data_id {
+                return (CrcStatus::WrongDataId, None);
+            }
+            let crc = CRC64_P7.checksum(&[&payload[..offset], &payload[offset + 8..]]);
+            (crc_status(crc == received_crc), Some(counter as u64))
+        }
+    }
+}
+
+fn crc_status(valid: bool) -> CrcStatus {
+    if valid {
+        CrcStatus::Ok
+    } else {
+        CrcStatus::Wrong
+    }
+}
+
+/// Parameters of a CRC algorithm.
+struct Crc {
+    width: u32,
+    poly: u64,
+    init: u64,
+    reflected: bool,
+    xor_out: u64,
+}
+
+const CRC8_SAE_J1850: Crc = Crc {
+    width: 8,
+    poly: 0x1D,
+    init: 0xFF,
+    reflected: false,
+    xor_out: 0xFF,
+};
+
+const CRC8_H2F: Crc = Crc {
+    width: 8,
+    poly: 0x2F,
+    init: 0xFF,
+    reflected: false,
+    xor_out: 0xFF,
+};
+
+const CRC16_CCITT_FALSE: Crc = Crc {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    reflected: false,
+    xor_out: 0x0000,
+};
+
+const CRC32_P4: Crc = Crc {
+    width: 32,
+    poly: 0xF4AC_FB13,
+    init: 0xFFFF_FFFF,
+    reflected: true,
+    xor_out: 0xFFFF_FFFF,
+};
+
+const CRC64_P7: Crc = Crc {
+    width: 64,
+    poly: 0x42F0_E1EB_A9EA_3693,
+    init: u64::MAX,
+    reflected: true,
+    xor_out: u64::MAX,
+};
+
+impl Crc {
+    fn mask(&self) -> u64 {
+        if self.width == 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.width) - 1
+        }
+    }
+
+    /// Calculates the checksum over the given chunks of data.
+    fn checksum(&self, chunks: &[&[u8]]) -> u64 {
+        let mask = self.mask();
+        let mut crc = self.init;
+        let bytes = chunks.iter().flat_map(|chunk| chunk.iter());
+        if self.reflected {
+            let poly = self.poly.reverse_bits() >> (64 - self.width);
+            for byte in bytes {
+                crc ^= *byte as u64;
+                for _ in 0..8 {
+                    crc = if crc & 1 == 1 {
+                        (crc >> 1) ^ poly
+                    } else {
+                        crc >> 1
+                    };
+                }
+            }
+        } else {
+            let top = 1u64 << (self.width - 1);
+            for byte in bytes {
+                crc ^= (*byte as u64) << (self.width - 8);
+                for _ in 0..8 {
+                    crc = if crc & top != 0 {
+                        (crc << 1) ^ self.poly
+                    } else {
+                        crc << 1
+                    };
+                    crc &= mask;
+                }
+            }
+        }
+        (crc ^ self.xor_out) & mask
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn crc_check_values() {
+        assert_eq!(CRC8_SAE_J1850.checksum(&[CHECK]), 0x4B);
+        assert_eq!(CRC8_H2F.checksum(&[CHECK]), 0xDF);
+        assert_eq!(CRC16_CCITT_FALSE.checksum(&[CHECK]), 0x29B1);
+        assert_eq!(CRC32_P4.checksum(&[CHECK]), 0x1697_D06A);
+        assert_eq!(CRC64_P7.checksum(&[CHECK]), 0x995D_C9BB_DF19_39FA);
+        assert_eq!(
+            CRC32_P4.checksum(&[&CHECK[..4], &CHECK[4..]]),
+            CRC32_P4.checksum(&[CHECK])
+        );
+    }
+
+    fn protection(profile: E2EProfile) -> E2EProtection {
+        E2EProtection {
+            service_id: 1,
+            method_id: 2,
+            profile,
+            data_id: 0x1234,
+            data_id_list: (0..16).collect(),
+            offset: 0,
+        }
+    }
+
+    fn p4_payload(counter: u16, data: &[u8]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&((12 + data.len()) as u16).to_be_bytes());
+        payload.extend_from_slice(&counter.to_be_bytes());
+        payload.extend_from_slice(&0x1234u32.to_be_bytes());
+        payload.extend_from_slice(&[0, 0, 0, 0]);
+        payload.extend_from_slice(data);
+        let crc = CRC32_P4.checksum(&[&payload[..8], &payload[12..]]) as u32;
+        payload[8..12].copy_from_slice(&crc.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn verify_p4_counter() {
+        let mut verifier = E2EVerifier::new(E2EConfig::new(vec![protection(E2EProfile::P4)]));
+        let mut status = |payload: Vec<u8>| verifier.verify(1, 2, &payload).unwrap();
+
+        assert_eq!(
+            status(p4_payload(1, &[1, 2])).counter,
+            CounterStatus::Initial
+        );
+        assert_eq!(status(p4_payload(2, &[1, 2])).counter, CounterStatus::Ok);
+        assert_eq!(
+            status(p4_payload(2, &[1, 2])).counter,
+            CounterStatus::Repeated
+        );
+        assert_eq!(
+            status(p4_payload(5, &[1, 2])).counter,
+            CounterStatus::Lost(2)
+        );
+        assert_eq!(status(p4_payload(0xFFFF, &[1, 2])).crc, CrcStatus::Ok);
+        assert_eq!(status(p4_payload(0, &[1, 2])).counter, CounterStatus::Ok);
+
+        let mut corrupted = p4_payload(1, &[1, 2]);
+        corrupted[13] = 3;
+        assert_eq!(
+            status(corrupted),
+            E2EStatus {
+                crc: CrcStatus::Wrong,
+                counter: CounterStatus::Unchecked,
+            }
+        );
+        assert_eq!(status(vec![0; 4]).crc, CrcStatus::NoData);
+
+        let mut overflowing = protection(E2EProfile::P4);
+        overflowing.offset = usize::MAX;
+        let mut verifier = E2EVerifier::new(E2EConfig::new(vec![overflowing]));
+        assert_eq!(
+            verifier.verify(1, 2, &p4_payload(1, &[1, 2])).unwrap().crc,
+            CrcStatus::NoData
+        );
+    }
+
+    const ARXML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AUTOSAR xmlns="http://autosar.org/schema/r4.0">
+  <AR-PACKAGES>
+    <AR-PACKAGE>
+      <SHORT-NAME>System</SHORT-NAME>
+      <ELEMENTS>
+        <END-TO-END-PROTECTION-SET>
+          <SHORT-NAME>E2E</SHORT-NAME>
+          <END-TO-END-PROTECTIONS>
+            <END-TO-END-PROTECTION>
+              <SHORT-NAME>Speed</SHORT-NAME>
+              <END-TO-END-PROFILE>
+                <CATEGORY>PROFILE_02</CATEGORY>
+                <CRC-OFFSET>0</CRC-OFFSET>
+                <DATA-IDS>
+                  <DATA-ID>10</DATA-ID>
+                  <DATA-ID>11</DATA-ID>
+                </DATA-IDS>
+              </END-TO-END-PROFILE>
+              <END-TO-END-PROTECTION-I-SIGNAL-I-PDUS>
+                <END-TO-END-PROTECTION-I-SIGNAL-I-PDU>
+                  <DATA-OFFSET>16</DATA-OFFSET>
+                  <I-SIGNAL-I-PDU-REF DEST="I-SIGNAL-I-PDU">/System/Speed</I-SIGNAL-I-PDU-REF>
+                </END-TO-END-PROTECTION-I-SIGNAL-I-PDU>
+              </END-TO-END-PROTECTION-I-SIGNAL-I-PDUS>
+            </END-TO-END-PROTECTION>
+          </END-TO-END-PROTECTIONS>
+        </END-TO-END-PROTECTION-SET>
+        <ETHERNET-CLUSTER>
+          <SHORT-NAME>Ethernet</SHORT-NAME>
+          <PDU-TRIGGERINGS>
+            <PDU-TRIGGERING>
+              <SHORT-NAME>SpeedTriggering</SHORT-NAME>
+              <I-PDU-REF DEST="I-SIGNAL-I-PDU">/System/Speed</I-PDU-REF>
+            </PDU-TRIGGERING>
+          </PDU-TRIGGERINGS>
+          <SOCKET-CONNECTION-IPDU-IDENTIFIER>
+            <HEADER-ID>17006597</HEADER-ID>
+            <PDU-TRIGGERING-REF DEST="PDU-TRIGGERING">/System/Ethernet/SpeedTriggering</PDU-TRIGGERING-REF>
+          </SOCKET-CONNECTION-IPDU-IDENTIFIER>
+        </ETHERNET-CLUSTER>
+      </ELEMENTS>
+    </AR-PACKAGE>
+  </AR-PACKAGES>
+</AUTOSAR>"#;
+
+    #[test]
+    fn read_config_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("e2e.xml");
+        fs::write(&path, "<?xml version=\"1.0\"?><FIBEX/>").unwrap();
+        assert!(E2EConfig::from_file(&path).is_err());
+        let path = dir.path().join("e2e.arxml");
+        fs::write(&path, ARXML).unwrap();
+        let config = E2EConfig::from_file(&path).unwrap();
+        let protection = config.get(0x0103, 0x8005).unwrap();
+        assert_eq!(protection.profile, E2EProfile::P2);
+        assert_eq!(protection.data_id, 10);
+        assert_eq!(protection.data_id_list, vec![10, 11]);
+        assert_eq!(protection.offset, 2);
+        let path = dir.path().join("e2e.json");
+        fs::write(
+            &path,
+            r#"[{"service_id": 1, "method_id": 2, "profile": "P4", "data_id": 4660}]"#,
+        )
+        .unwrap();
+        let config = E2EConfig::from_file(&path).unwrap();
+        assert_eq!(config.get(1, 2).map(|p| p.data_id), Some(0x1234));
+    }
+
+    #[test]
+    fn verify_p1_p2_p5() {
+        let data = [0x00, 0x03, 0xAA, 0xBB];
+
+        let mut p1 = data;
+        p1[0] = CRC8_SAE_J1850.checksum(&[&[0x34, 0x12], &p1[1..]]) as u8;
+        let mut verifier = E2EVerifier::new(E2EConfig::new(vec![protection(E2EProfile::P1)]));
+        assert_eq!(verifier.verify(1, 2, &p1).unwrap().crc, CrcStatus::Ok);
+        assert!(verifier.verify(1, 3, &p1).is_none());
+
+        let mut p2 = data;
+        p2[0] = CRC8_H2F.checksum(&[&p2[1..], &[3]]) as u8;
+        let mut verifier = E2EVerifier::new(E2EConfig::new(vec![protection(E2EProfile::P2)]));
+        assert_eq!(verifier.verify(1, 2, &p2).unwrap().crc, CrcStatus::Ok);
+
+        let mut p5 = [0x00, 0x00, 0x07, 0xAA, 0xBB];
+        let crc = CRC16_CCITT_FALSE.checksum(&[&p5[2..], &[0x34, 0x12]]) as u16;
+        p5[..2].copy_from_slice(&crc.to_le_bytes());
+        let mut verifier = E2EVerifier::new(E2EConfig::new(vec![protection(E2EProfile::P5)]));
+        assert_eq!(verifier.verify(1, 2, &p5).unwrap().crc, CrcStatus::Ok);
+        p5[4] = 0;
+        assert_eq!(verifier.verify(1, 2, &p5).unwrap().crc, CrcStatus::Wrong);
+    }
+}
@@ -0,0 +1,223 @@
+//! Reading of E2E protections from AUTOSAR (ARXML) system descriptions.
+//!
+//! Protections are taken from the `END-TO-END-PROTECTION` elements of protection
+//! sets. A protection refers to the protected I-PDUs; those are bound to SOME/IP
+//! messages by the `SOCKET-CONNECTION-IPDU-IDENTIFIER` elements, whose header-id is
+//! the message-id (service-id and method-id) of the PDU triggering the I-PDU.
+//!
+//! Offsets in ARXML are given in bits: the header starts at the `DATA-OFFSET` of the
+//! protected PDU plus the `CRC-OFFSET` (profiles 1 and 2) or the `OFFSET` (profiles
+//! 4, 5 and 7) of the profile.
+use super::{E2EProfile, E2EProtection};
+use crate::Error;
+use quick_xml::{Reader, events::Event};
+use std::collections::HashMap;
+
+/// An element of the XML document.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns the text of the element at the given path of child names.
+    fn value(&self, path: &[&str]) -> Option<&str> {
+        path.iter()
+            .try_fold(self, |element, name| element.child(name))
+            .map(|element| element.text.trim())
+    }
+
+    fn short_name(&self) -> Option<&str> {
+        self.value(&["SHORT-NAME"])
+    }
+
+    /// Collects all descendants with the given name.
+    fn descendants<'a>(&'a self, name: &str, found: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            }
+            child.descendants(name, found);
+        }
+    }
+}
+
+/// Elements, which are needed to resolve protections.
+#[derive(Default)]
+struct Elements<'a> {
+    protections: Vec<&'a Element>,
+    /// PDU triggerings by their reference path.
+    triggerings: HashMap<String, &'a Element>,
+    identifiers: Vec<&'a Element>,
+}
+
+impl<'a> Elements<'a> {
+    /// Walks the document, keeping track of the reference path of elements.
+    fn collect(&mut self, element: &'a Element, path: &str) {
+        let path = match element.short_name() {
+            Some(name) => format!("{path}/{name}"),
+            None => path.to_owned(),
+        };
+        match element.name.as_str() {
+            "END-TO-END-PROTECTION" => self.protections.push(element),
+            "PDU-TRIGGERING" => {
+                self.triggerings.insert(path.clone(), element);
+            }
+            "SOCKET-CONNECTION-IPDU-IDENTIFIER" => self.identifiers.push(element),
+            _ => {}
+        }
+        for child in &element.children {
+            self.collect(child, &path);
+        }
+    }
+}
+
+/// Reads the E2E protections of SOME/IP messages from the ARXML document.
+pub(super) fn protections(content: &str) -> Result<Vec<E2EProtection>, Error> {
+    let root = parse(content)?;
+    if root.name != "AUTOSAR" {
+        return Err(Error::Unrecoverable(format!(
+            "Unsupported E2E config: expected ARXML (AUTOSAR), found <{}>",
+            root.name
+        )));
+    }
+    let mut elements = Elements::default();
+    elements.collect(&root, "");
+    // Message-ids of I-PDUs by the reference path of I-PDUs.
+    let mut messages: HashMap<&str, Vec<u32>> = HashMap::new();
+    for identifier in &elements.identifiers {
+        let Some(header_id) = identifier
+            .value(&["HEADER-ID"])
+            .and_then(|id| id.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if let Some(pdu) = identifier
+            .value(&["PDU-TRIGGERING-REF"])
+            .and_then(|path| elements.triggerings.get(path))
+            .and_then(|triggering| triggering.value(&["I-PDU-REF"]))
+        {
+            messages.entry(pdu).or_default().push(header_id);
+        }
+    }
+    let mut protections = Vec::new();
+    for protection in &elements.protections {
+        let Some(profile) = protection.child("END-TO-END-PROFILE") else {
+            continue;
+        };
+        let category = profile.value(&["CATEGORY"]).unwrap_or_default();
+        let e2e_profile = match category
+            .trim_start_matches("PROFILE")
+            .trim_start_matches('_')
+            .trim_start_matches('0')
+        {
+            "1" => E2EProfile::P1,
+            "2" => E2EProfile::P2,
+            "4" => E2EProfile::P4,
+            "5" => E2EProfile::P5,
+            "7" => E2EProfile::P7,
+            _ => {
+                return Err(Error::Unrecoverable(format!(
+                    "Unsupported E2E profile {category} of protection {}",
+                    protection.short_name().unwrap_or_default()
+                )));
+            }
+        };
+        let mut ids = Vec::new();
+        profile.descendants("DATA-ID", &mut ids);
+        let data_ids: Vec<u32> = ids
+            .iter()
+            .filter_map(|id| id.text.trim().parse::<u32>().ok())
+            .collect();
+        let bits = |name: &str| {
+            profile
+                .value(&[name])
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(0)
+        };
+        let header_offset = match e2e_profile {
+            E2EProfile::P1 | E2EProfile::P2 => bits("CRC-OFFSET"),
+            E2EProfile::P4 | E2EProfile::P5 | E2EProfile::P7 => bits("OFFSET"),
+        };
+        let mut pdus = Vec::new();
+        protection.descendants("END-TO-END-PROTECTION-I-SIGNAL-I-PDU", &mut pdus);
+        for pdu in pdus {
+            let data_offset = pdu
+                .value(&["DATA-OFFSET"])
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(0);
+            let Some(ids) = pdu
+                .value(&["I-SIGNAL-I-PDU-REF"])
+                .and_then(|path| messages.get(path))
+            else {
+                continue;
+            };
+            for id in ids {
+                protections.push(E2EProtection {
+                    service_id: (id >> 16) as u16,
+                    method_id: *id as u16,
+                    profile: e2e_profile,
+                    data_id: data_ids.first().copied().unwrap_or_default(),
+                    data_id_list: match e2e_profile {
+                        E2EProfile::P2 => data_ids.iter().map(|id| *id as u8).collect(),
+                        _ => Vec::new(),
+                    },
+                    offset: (data_offset + header_offset) / 8,
+                });
+            }
+        }
+    }
+    Ok(protections)
+}
+
+/// Parses the XML document into a tree of elements; namespace prefixes are dropped.
+fn parse(content: &str) -> Result<Element, Error> {
+    let invalid = |e: quick_xml::Error| Error::Unrecoverable(format!("Invalid ARXML: {e}"));
+    let name = |name: &[u8]| String::from_utf8_lossy(name).into_owned();
+    let mut reader = Reader::from_str(content);
+    let mut stack = vec![Element::default()];
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(start) => stack.push(Element {
+                name: name(start.local_name().as_ref()),
+                ..Default::default()
+            }),
+            Event::Empty(empty) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Element {
+                        name: name(empty.local_name().as_ref()),
+                        ..Default::default()
+                    });
+                }
+            }
+            Event::Text(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text.unescape().map_err(invalid)?);
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop();
+                match (element, stack.last_mut()) {
+                    (Some(element), Some(parent)) => parent.children.push(element),
+                    _ => return Err(Error::Unrecoverable(String::from("Invalid ARXML"))),
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    match (stack.pop(), stack.is_empty()) {
+        (Some(document), true) => document
+            .children
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Unrecoverable(String::from("Empty ARXML"))),
+        _ => Err(Error::Unrecoverable(String::from("Invalid ARXML"))),
+    }
+}
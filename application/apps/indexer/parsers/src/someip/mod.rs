@@ -1,3 +1,5 @@
+pub mod e2e;
+
 use crate::{Error, LogMessage, ParseOutput, ParseYield, SingleParser};
 use std::{
     borrow::Cow,
@@ -21,6 +23,8 @@ use log::{debug, error};
 use regex::Regex;
 use serde::Serialize;
 
+use self::e2e::{E2EConfig, E2EStatus, E2EVerifier};
+
 /// Marker for a column separator in the output string.
 const COLUMN_SEP: &str = "\u{0004}"; // EOT
 /// Marker for a newline in the output string.
@@ -188,6 +192,7 @@ impl FibexTypeCache {
 /// A parser for SOME/IP log messages.
 pub struct SomeipParser {
     fibex_metadata: Option<FibexMetadata>,
+    e2e: Option<E2EVerifier>,
 }
 
 impl Default for SomeipParser {
@@ -201,6 +206,7 @@ impl SomeipParser {
     pub fn new() -> Self {
         SomeipParser {
             fibex_metadata: None,
+            e2e: None,
         }
     }
 
@@ -208,12 +214,21 @@ impl SomeipParser {
     pub fn from_fibex_files(paths: Vec<PathBuf>) -> Self {
         SomeipParser {
             fibex_metadata: FibexMetadata::from_fibex_files(paths),
+            e2e: None,
         }
     }
 
+    /// Enables the verification of E2E protected messages with the given configuration.
+    #[must_use]
+    pub fn with_e2e_config(mut self, config: E2EConfig) -> Self {
+        self.e2e = Some(E2EVerifier::new(config));
+        self
+    }
+
     /// Parses a SOME/IP message (header and payload) from the given input.
     pub(crate) fn parse_message(
         fibex_metadata: Option<&FibexMetadata>,
+        e2e: Option<&mut E2EVerifier>,
         input: &[u8],
         timestamp: Option<u64>,
     ) -> Result<(usize, SomeipLogMessage), Error> {
@@ -238,6 +253,13 @@ impl SomeipParser {
             Ok(Message::Rpc(header, payload)) => {
                 let len = header.message_len();
                 debug!("at {time} : RPC Message ({len:?} bytes)");
+                let e2e_status = e2e.and_then(|verifier| {
+                    verifier.verify(
+                        header.message_id.service_id,
+                        header.message_id.method_id,
+                        &payload[..],
+                    )
+                });
                 Ok((
                     if input.len() - len < Header::LENGTH {
                         input.len()
@@ -245,7 +267,7 @@ impl SomeipParser {
                         len
                     },
                     SomeipLogMessage::from(
-                        rpc_message_string(fibex_metadata, &header, &payload),
+                        rpc_message_string(fibex_metadata, &header, &payload, e2e_status),
                         input[..len].to_vec(),
                     ),
                 ))
@@ -304,8 +326,13 @@ impl SingleParser for SomeipParser {
         input: &[u8],
         timestamp: Option<u64>,
     ) -> Result<ParseOutput<SomeipLogMessage>, Error> {
-        SomeipParser::parse_message(self.fibex_metadata.as_ref(), input, timestamp)
-            .map(|(rest, message)| ParseOutput::new(rest, Some(ParseYield::from(message))))
+        SomeipParser::parse_message(
+            self.fibex_metadata.as_ref(),
+            self.e2e.as_mut(),
+            input,
+            timestamp,
+        )
+        .map(|(rest, message)| ParseOutput::new(rest, Some(ParseYield::from(message))))
     }
 }
 
@@ -432,9 +459,10 @@ fn rpc_message_string(
     fibex_metadata: Option<&FibexMetadata>,
     header: &Header,
     payload: &RpcPayload,
+    e2e_status: Option<E2EStatus>,
 ) -> String {
     format!(
-        "RPC{COLUMN_SEP}{}{COLUMN_SEP}{}",
+        "RPC{COLUMN_SEP}{}{COLUMN_SEP}{}{}",
         header_string(header),
         match fibex_metadata {
             None => {
@@ -487,6 +515,13 @@ fn rpc_message_string(
                     }
                 }
             }
+        },
+        match e2e_status {
+            None => Cow::Borrowed(""),
+            Some(status) => Cow::Owned(format!(
+                "{COLUMN_SEP}{}{COLUMN_SEP}{}",
+                status.crc, status.counter
+            )),
         }
     )
 }
@@ -524,6 +559,28 @@ impl fmt::Debug for SomeipLogMessage {
     }
 }
 
+/// Labels of the header columns, which are following the message type column.
+const HEADER_LABELS: [&str; 8] = [
+    "SERV", "METH", "LENG", "CLID", "SEID", "IVER", "MSTP", "RETC",
+];
+
+/// Splits the SOME/IP message columns and labels the header columns the same way
+/// as [`merge_columns`] does (e.g. `SERV:123`).
+pub(crate) fn labeled_columns(columns: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    columns
+        .split(COLUMN_SEP)
+        .enumerate()
+        .map(|(index, column)| {
+            match index
+                .checked_sub(1)
+                .and_then(|index| HEADER_LABELS.get(index))
+            {
+                Some(label) => Cow::Owned(format!("{label}:{column}")),
+                None => Cow::Borrowed(column),
+            }
+        })
+}
+
 /// Merges the SOME/IP message columns to a single column with additional info.
 fn merge_columns(columns: &str) -> Cow<'_, str> {
     lazy_static! {
@@ -531,7 +588,13 @@ fn merge_columns(columns: &str) -> Cow<'_, str> {
             &format!("(SD|RPC){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(\\d+){COLUMN_SEP}(.*)")
             ).unwrap();
     }
-    REGEX.replace(columns, "${1} SERV:${2} METH:${3} LENG:${4} CLID:${5} SEID:${6} IVER:${7} MSTP:${8} RETC:${9} ${10}")
+    let merged = REGEX.replace(columns, "${1} SERV:${2} METH:${3} LENG:${4} CLID:${5} SEID:${6} IVER:${7} MSTP:${8} RETC:${9} ${10}");
+    if merged.contains(COLUMN_SEP) {
+        // Additional columns (e.g. E2E status) following the payload.
+        Cow::Owned(merged.replace(COLUMN_SEP, " "))
+    } else {
+        merged
+    }
 }
 
 #[cfg(test)]
@@ -711,6 +774,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -772,6 +836,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -804,6 +869,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -836,6 +902,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -868,6 +935,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -900,6 +968,7 @@ mod test {
         let fibex_metadata = test_metadata();
         let mut parser = SomeipParser {
             fibex_metadata: Some(fibex_metadata),
+            e2e: None,
         };
 
         let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
@@ -919,6 +988,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_rpc_message_e2e() {
+        let input: &[u8] = &[
+            0x01, 0x03, 0x80, 0x05, // serviceId(u16), methodId(u16)
+            0x00, 0x00, 0x00, 0x0A, // length(u32)
+            0x00, 0x01, 0x00, 0x02, // clientId(u16), sessionId(u16)
+            0x01, 0x01, 0x02, 0x00, // proto(u8), version(u8), messageType,(u8) returnCode(u8)
+            0xEE, 0x01, // payload([crc(u8), counter(u8)])
+        ];
+
+        let mut parser =
+            SomeipParser::new().with_e2e_config(E2EConfig::new(vec![e2e::E2EProtection {
+                service_id: 259,
+                method_id: 32773,
+                profile: e2e::E2EProfile::P1,
+                data_id: 0x1234,
+                data_id_list: Vec::new(),
+                offset: 0,
+            }]));
+
+        let expected = ["CRC:OK\u{4}CNT:INITIAL", "CRC:OK\u{4}CNT:REPEATED"];
+        for e2e_columns in expected {
+            let ParseOutput { consumed, message } = parser.parse_item(input, None).unwrap();
+            assert_eq!(consumed, input.len());
+
+            if let ParseYield::Message(item) = message.unwrap() {
+                assert_eq!(
+                    format!(
+                        "RPC\u{4}259\u{4}32773\u{4}10\u{4}1\u{4}2\u{4}1\u{4}2\u{4}0\u{4}[EE, 01]\u{4}{e2e_columns}"
+                    ),
+                    format!("{}", item)
+                );
+            } else {
+                panic!("unexpected parse yield");
+            }
+        }
+
+        let mut corrupted = input.to_vec();
+        corrupted[17] = 0x02;
+        let ParseOutput { message, .. } = parser.parse_item(&corrupted, None).unwrap();
+        if let ParseYield::Message(item) = message.unwrap() {
+            assert_eq!(
+                "RPC SERV:259 METH:32773 LENG:10 CLID:1 SEID:2 IVER:1 MSTP:2 RETC:0 [EE, 02] CRC:WRONG CNT:-",
+                &format!("{:?}", item)
+            );
+        } else {
+            panic!("unexpected parse yield");
+        }
+    }
+
     #[test]
     fn parse_empty_sd_message() {
         let input: &[u8] = &[
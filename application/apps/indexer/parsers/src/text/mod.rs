@@ -1,3 +1,6 @@
+pub mod multiline;
+pub mod pattern;
+
 use crate::{Error, LogMessage, ParseOutput, ParseYield, SingleParser};
 use serde::Serialize;
 use std::{fmt, io::Write};
@@ -0,0 +1,326 @@
+//! # Multi-line text parser
+//!
+//! Folds continuation lines (e.g. stack traces or pretty printed JSON) into
+//! the message they belong to. The lines of a message are joined with
+//! [`LINE_SEP`], so they are still shown on separate lines, while the raw
+//! content keeps the original bytes.
+//!
+//! A message reaching the end of the loaded data may still be continued by the
+//! lines, which aren't loaded yet. Such message is held back until more data is
+//! loaded; if the same data is provided again (nothing was loaded in between),
+//! the end of the source is reached and the message is complete. The folding is
+//! shared with [`crate::text::pattern::TextPatternParser`].
+use crate::{
+    Error, LogMessage, ParseOutput, ParseYield, Parser, separators::LINE_SEP,
+    text::pattern::next_line,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::{fmt, io::Write};
+
+/// Maximal number of lines of a message if not configured.
+pub const DEFAULT_MAX_LINES: usize = 1000;
+
+lazy_static! {
+    /// Common timestamp prefixes: ISO 8601 and similar dates, syslog, logcat,
+    /// plain times and kernel uptimes.
+    static ref TIMESTAMP_PREFIX: Regex = Regex::new(
+        r"^\[?\s*(?:\d{4}[-/.]\d{2}[-/.]\d{2}[ T]\d{2}:\d{2}|\d{2}[-/.]\d{2}[-/.]\d{4} \d{2}:\d{2}|\d{2}-\d{2} \d{2}:\d{2}:\d{2}|(?:[A-Z][a-z]{2} )?[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}|\d{2}:\d{2}:\d{2}|\d+\.\d+\])"
+    )
+    .unwrap();
+    /// Headers of Java exceptions, e.g. `java.lang.IllegalStateException: boom`.
+    static ref EXCEPTION_HEADER: Regex =
+        Regex::new(r"^(?:[A-Za-z_$][\w$]*\.)+[\w$]*(?:Exception|Error|Throwable)(?::|$)").unwrap();
+}
+
+/// Rules to detect lines continuing the previous message.
+#[derive(Debug, Clone)]
+pub enum ContinuationRule {
+    /// Indented lines, headers and `Caused by:` lines of Java exceptions.
+    LeadingWhitespace,
+    /// Lines which don't start with a timestamp.
+    NoTimestamp,
+    /// Lines matching the given regular expression.
+    Pattern(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct MultilineMessage {
+    /// The number of folded lines.
+    pub lines: usize,
+    content: String,
+    /// Original bytes of the lines without the last line break.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for MultilineMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.content)
+    }
+}
+
+impl LogMessage for MultilineMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+}
+
+enum Rule {
+    LeadingWhitespace,
+    NoTimestamp,
+    Pattern(Regex),
+}
+
+impl Rule {
+    fn is_continuation(&self, line: &str) -> bool {
+        match self {
+            Rule::LeadingWhitespace => {
+                line.starts_with([' ', '\t'])
+                    || line.starts_with("Caused by:")
+                    || EXCEPTION_HEADER.is_match(line)
+            }
+            Rule::NoTimestamp => !TIMESTAMP_PREFIX.is_match(line),
+            Rule::Pattern(regex) => regex.is_match(line),
+        }
+    }
+}
+
+pub struct MultilineTextParser {
+    rule: Rule,
+    max_lines: usize,
+    /// Length of the data, which was provided with the last call, if the only
+    /// message of it was held back.
+    held: Option<usize>,
+}
+
+impl MultilineTextParser {
+    /// Creates a parser folding at most `max_lines` lines into one message.
+    pub fn new(rule: ContinuationRule, max_lines: Option<usize>) -> Result<Self, Error> {
+        let rule = match rule {
+            ContinuationRule::LeadingWhitespace => Rule::LeadingWhitespace,
+            ContinuationRule::NoTimestamp => Rule::NoTimestamp,
+            ContinuationRule::Pattern(pattern) => {
+                Rule::Pattern(Regex::new(&pattern).map_err(|e| {
+                    Error::Unrecoverable(format!("Invalid continuation pattern: {e}"))
+                })?)
+            }
+        };
+        Ok(Self {
+            rule,
+            max_lines: max_lines.unwrap_or(DEFAULT_MAX_LINES).max(1),
+            held: None,
+        })
+    }
+}
+
+impl Parser for MultilineTextParser {
+    type Output = MultilineMessage;
+
+    fn parse(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<impl Iterator<Item = ParseOutput<MultilineMessage>>, Error> {
+        let outputs = fold(
+            input,
+            &mut self.held,
+            self.max_lines,
+            |line| self.rule.is_continuation(line),
+            |lines, raw| MultilineMessage {
+                lines: lines.len(),
+                content: lines.join(LINE_SEP),
+                raw: raw.to_vec(),
+            },
+        );
+        Ok(outputs.into_iter())
+    }
+}
+
+/// Returns the lines of the next message, the number of consumed bytes and whether
+/// the message can be continued by the data after the input.
+fn next_message(
+    input: &[u8],
+    max_lines: usize,
+    is_continuation: &impl Fn(&str) -> bool,
+) -> Option<(Vec<String>, usize, bool)> {
+    let (line, mut consumed) = next_line(input, false)?;
+    let mut lines = vec![line];
+    let mut open = !input[..consumed].ends_with(b"\n");
+    while !open && lines.len() < max_lines {
+        let Some((line, len)) = next_line(&input[consumed..], true) else {
+            open = true;
+            break;
+        };
+        if !is_continuation(&line) {
+            break;
+        }
+        lines.push(line);
+        consumed += len;
+    }
+    Some((lines, consumed, open))
+}
+
+/// Splits the input into messages, folding continuation lines into the message they
+/// belong to. A message, which can be continued by the data after the input, is held
+/// back, unless the same data is provided again.
+///
+/// # Arguments
+///
+/// * `input` - Loaded data.
+/// * `held` - Length of the data of the last call, if its only message was held back.
+/// * `max_lines` - Maximal number of lines of a message.
+/// * `is_continuation` - Checks whether the line continues the previous one.
+/// * `message` - Creates the message of the lines (with line breaks removed) and the
+///   original bytes of them without the last line break.
+pub(crate) fn fold<'a, T>(
+    input: &'a [u8],
+    held: &mut Option<usize>,
+    max_lines: usize,
+    is_continuation: impl Fn(&str) -> bool,
+    message: impl Fn(Vec<String>, &'a [u8]) -> T,
+) -> Vec<ParseOutput<T>> {
+    // The same data is provided again, so the source has no more data
+    let eof = held.take() == Some(input.len());
+    let mut outputs = Vec::new();
+    let mut offset = 0;
+    while let Some((lines, consumed, open)) =
+        next_message(&input[offset..], max_lines, &is_continuation)
+    {
+        if open && !(eof && outputs.is_empty()) {
+            if outputs.is_empty() {
+                *held = Some(input.len());
+            }
+            break;
+        }
+        let raw = &input[offset..offset + consumed];
+        let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+        outputs.push(ParseOutput::new(
+            consumed,
+            Some(ParseYield::Message(message(lines, raw))),
+        ));
+        offset += consumed;
+    }
+    outputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseYield;
+
+    fn parse_next(parser: &mut MultilineTextParser, input: &[u8]) -> Vec<(usize, Vec<u8>)> {
+        parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => {
+                    let mut raw = Vec::new();
+                    msg.to_writer(&mut raw).unwrap();
+                    Some((out.consumed, raw))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Parses the input as the whole content of the source.
+    fn parse_all(parser: &mut MultilineTextParser, input: &[u8]) -> Vec<String> {
+        let mut messages = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let outputs: Vec<_> = parser.parse(rest, None).unwrap().collect();
+            rest = &rest[outputs.iter().map(|out| out.consumed).sum::<usize>()..];
+            messages.extend(outputs.into_iter().filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg.to_string()),
+                _ => None,
+            }));
+        }
+        messages
+    }
+
+    #[test]
+    fn java_stack_trace() {
+        let mut parser =
+            MultilineTextParser::new(ContinuationRule::LeadingWhitespace, None).unwrap();
+        let input = b"12:00:01 ERROR Request failed\n\
+            java.lang.IllegalStateException: boom\n\
+            \tat com.example.Service.run(Service.java:42)\n\
+            Caused by: java.io.IOException: closed\n\
+            \t... 12 more\n\
+            12:00:02 INFO Recovered\n";
+        assert_eq!(
+            parse_all(&mut parser, input),
+            vec![
+                "12:00:01 ERROR Request failed\u{6}java.lang.IllegalStateException: boom\u{6}\tat com.example.Service.run(Service.java:42)\u{6}Caused by: java.io.IOException: closed\u{6}\t... 12 more",
+                "12:00:02 INFO Recovered",
+            ]
+        );
+    }
+
+    #[test]
+    fn python_traceback_and_json() {
+        let mut parser = MultilineTextParser::new(ContinuationRule::NoTimestamp, Some(3)).unwrap();
+        let input = b"2024-03-17 16:13:38,811 ERROR failed\n\
+            Traceback (most recent call last):\n  \
+              File \"app.py\", line 3, in <module>\n\
+            ValueError: bad value\n\
+            Mar 17 16:13:39 host app[12]: payload {\n  \
+              \"id\": 1\n\
+            }\n";
+        let messages = parse_all(&mut parser, input);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].matches(LINE_SEP).count(), 2);
+        assert_eq!(messages[1], "ValueError: bad value");
+        assert_eq!(
+            messages[2],
+            "Mar 17 16:13:39 host app[12]: payload {\u{6}  \"id\": 1\u{6}}"
+        );
+        let mut out = parser.parse(input, None).unwrap().next().unwrap();
+        let mut raw = Vec::new();
+        if let Some(ParseYield::Message(msg)) = out.message.take() {
+            assert_eq!(msg.lines, 3);
+            msg.to_writer(&mut raw).unwrap();
+        }
+        assert_eq!(raw, &input[..out.consumed - 1]);
+        assert!(MultilineTextParser::new(ContinuationRule::Pattern("(".into()), None).is_err());
+    }
+
+    #[test]
+    fn continuation_after_loaded_data() {
+        let mut parser =
+            MultilineTextParser::new(ContinuationRule::LeadingWhitespace, None).unwrap();
+        let loaded = b"12:00:01 ERROR Request failed\n\tat a\n\tat";
+        // The only message can be continued, so it's held back
+        assert!(parse_next(&mut parser, loaded).is_empty());
+        let loaded = b"12:00:01 ERROR Request failed\n\tat a\n\tat b\n12:00:02 INFO Recovered\n";
+        let messages = parse_next(&mut parser, loaded);
+        assert_eq!(
+            messages,
+            vec![(
+                42,
+                b"12:00:01 ERROR Request failed\n\tat a\n\tat b".to_vec()
+            )]
+        );
+        let rest = &loaded[42..];
+        assert!(parse_next(&mut parser, rest).is_empty());
+        // Nothing was loaded since the last call
+        assert_eq!(
+            parse_next(&mut parser, rest),
+            vec![(24, b"12:00:02 INFO Recovered".to_vec())]
+        );
+    }
+
+    #[test]
+    fn original_bytes() {
+        let mut parser = MultilineTextParser::new(ContinuationRule::NoTimestamp, None).unwrap();
+        let input = b"12:00:01 first\r\n  invalid \xff\r\n12:00:02 second\n";
+        let messages = parse_next(&mut parser, input);
+        assert_eq!(
+            messages,
+            vec![(29, b"12:00:01 first\r\n  invalid \xff\r".to_vec())]
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! # Regex based text parser
+//!
+//! Splits plain text logs into columns using the named groups of a regular
+//! expression. One of the groups can be marked as timestamp (parsed with a
+//! `chrono` format string) and one as severity, which is shown normalized (e.g.
+//! `ERROR` for `E`). Lines which belong to the previous message (e.g. stack
+//! traces) can be joined with it; they are folded the same way as by
+//! [`crate::text::multiline::MultilineTextParser`].
+use crate::{
+    Error, LogMessage, ParseOutput, Parser,
+    separators::{COLUMN_SEP, LINE_SEP},
+    text::multiline::{DEFAULT_MAX_LINES, fold},
+};
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use memchr::memchr;
+use regex::Regex;
+use serde::Serialize;
+use std::{fmt, io::Write};
+
+/// Ready-made configurations for common log formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPatternPreset {
+    /// log4j with the pattern `%d{ISO8601} [%t] %-5p %c - %m%n`.
+    Log4j,
+    /// Android logcat with the `threadtime` format.
+    LogcatThreadtime,
+    /// nginx access log with the `combined` format.
+    Nginx,
+    /// Python logging with the format `%(asctime)s - %(name)s - %(levelname)s - %(message)s`.
+    PythonLogging,
+}
+
+/// Configuration of the [`TextPatternParser`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextPatternConfig {
+    /// Regular expression matching a log line. Each named group becomes a column.
+    pub pattern: String,
+    /// Name of the group containing the timestamp.
+    pub timestamp_group: Option<String>,
+    /// `chrono` format of the timestamp. Formats without a year are parsed in `year`.
+    pub timestamp_format: Option<String>,
+    /// Year of timestamps, which don't contain it. The current year is used if not defined.
+    pub year: Option<i32>,
+    /// Name of the group containing the severity. Its column shows the normalized severity.
+    pub severity_group: Option<String>,
+    /// Regular expression matching lines which continue the previous message.
+    pub continuation_pattern: Option<String>,
+    /// Appends lines which don't match `pattern` to the previous message.
+    pub join_unmatched: bool,
+}
+
+impl TextPatternConfig {
+    pub fn preset(preset: TextPatternPreset) -> Self {
+        let (pattern, timestamp_format, severity_group) = match preset {
+            TextPatternPreset::Log4j => (
+                r"^(?P<timestamp>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3}) \[(?P<thread>[^\]]*)\] (?P<level>[A-Z]+)\s+(?P<logger>\S+) - (?P<message>.*)$",
+                "%Y-%m-%d %H:%M:%S,%3f",
+                Some("level"),
+            ),
+            TextPatternPreset::LogcatThreadtime => (
+                r"^(?P<timestamp>\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3})\s+(?P<pid>\d+)\s+(?P<tid>\d+) (?P<level>[VDIWEFS]) (?P<tag>.*?)\s*: (?P<message>.*)$",
+                "%m-%d %H:%M:%S%.3f",
+                Some("level"),
+            ),
+            TextPatternPreset::Nginx => (
+                r#"^(?P<remote_addr>\S+) - (?P<remote_user>\S+) \[(?P<timestamp>[^\]]+)\] "(?P<request>[^"]*)" (?P<status>\d{3}) (?P<body_bytes_sent>\d+|-) "(?P<http_referer>[^"]*)" "(?P<http_user_agent>[^"]*)"$"#,
+                "%d/%b/%Y:%H:%M:%S %z",
+                None,
+            ),
+            TextPatternPreset::PythonLogging => (
+                r"^(?P<timestamp>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3}) - (?P<logger>.*?) - (?P<level>[A-Z]+) - (?P<message>.*)$",
+                "%Y-%m-%d %H:%M:%S,%3f",
+                Some("level"),
+            ),
+        };
+        Self {
+            pattern: pattern.to_owned(),
+            timestamp_group: Some(String::from("timestamp")),
+            timestamp_format: Some(timestamp_format.to_owned()),
+            year: None,
+            severity_group: severity_group.map(String::from),
+            continuation_pattern: None,
+            // Stack traces and tracebacks don't match the pattern of a log line.
+            join_unmatched: preset != TextPatternPreset::Nginx,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct TextPatternMessage {
+    /// The values of the columns in the order of the named groups.
+    pub columns: Vec<String>,
+    /// The timestamp in milliseconds since the epoch, if it could be parsed.
+    timestamp: Option<i64>,
+    /// The original bytes of the lines without the last line break.
+    #[serde(skip)]
+    raw: Vec<u8>,
+}
+
+impl fmt::Display for TextPatternMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.columns.join(COLUMN_SEP))
+    }
+}
+
+impl LogMessage for TextPatternMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.raw)?;
+        Ok(self.raw.len())
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+pub struct TextPatternParser {
+    regex: Regex,
+    continuation: Option<Regex>,
+    /// Indexes of the named groups and their names.
+    columns: Vec<(usize, String)>,
+    /// Column index and format of the timestamp.
+    timestamp: Option<(usize, String)>,
+    /// Year of timestamps, which don't contain it.
+    year: i32,
+    /// Column index of the severity.
+    severity: Option<usize>,
+    join_unmatched: bool,
+    /// Length of the data, which was provided with the last call, if the only
+    /// message of it was held back.
+    held: Option<usize>,
+}
+
+impl TextPatternParser {
+    pub fn new(config: TextPatternConfig) -> Result<Self, Error> {
+        let regex = Regex::new(&config.pattern)
+            .map_err(|e| Error::Unrecoverable(format!("Invalid pattern: {e}")))?;
+        let continuation = config
+            .continuation_pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| Error::Unrecoverable(format!("Invalid continuation pattern: {e}")))?;
+        let columns: Vec<(usize, String)> = regex
+            .capture_names()
+            .enumerate()
+            .filter_map(|(idx, name)| name.map(|name| (idx, name.to_owned())))
+            .collect();
+        if columns.is_empty() {
+            return Err(Error::Unrecoverable(String::from(
+                "Pattern doesn't contain any named group",
+            )));
+        }
+        let column = |group: &str| {
+            columns
+                .iter()
+                .position(|(_, name)| name == group)
+                .ok_or_else(|| Error::Unrecoverable(format!("Pattern has no group \"{group}\"")))
+        };
+        let timestamp = match (&config.timestamp_group, config.timestamp_format) {
+            (Some(group), Some(format)) => Some((column(group.as_str())?, format)),
+            (Some(_), None) => {
+                return Err(Error::Unrecoverable(String::from(
+                    "Timestamp format isn't defined",
+                )));
+            }
+            _ => None,
+        };
+        let severity = config.severity_group.as_deref().map(column).transpose()?;
+        Ok(Self {
+            regex,
+            continuation,
+            columns,
+            timestamp,
+            year: config.year.unwrap_or_else(|| Utc::now().year()),
+            severity,
+            join_unmatched: config.join_unmatched,
+            held: None,
+        })
+    }
+
+    /// Returns the names of the columns.
+    pub fn columns(&self) -> Vec<&str> {
+        self.columns.iter().map(|(_, name)| name.as_str()).collect()
+    }
+
+    /// Creates the message of the first line, the continuation lines are appended to
+    /// the last column.
+    fn message(&self, lines: Vec<String>, raw: &[u8]) -> TextPatternMessage {
+        let mut lines = lines.into_iter();
+        let line = lines.next().unwrap_or_default();
+        let (mut columns, timestamp) = match self.regex.captures(&line) {
+            Some(captures) => {
+                let mut columns: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|(idx, _)| {
+                        captures
+                            .get(*idx)
+                            .map(|m| m.as_str().to_owned())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                let timestamp = self
+                    .timestamp
+                    .as_ref()
+                    .and_then(|(col, format)| parse_timestamp(&columns[*col], format, self.year));
+                if let Some(col) = self.severity {
+                    columns[col] = normalize_severity(&columns[col]);
+                }
+                (columns, timestamp)
+            }
+            None => {
+                // Lines which don't match are shown in the last column.
+                let mut columns = vec![String::new(); self.columns.len()];
+                if let Some(last) = columns.last_mut() {
+                    *last = line.clone();
+                }
+                (columns, None)
+            }
+        };
+        if let Some(last) = columns.last_mut() {
+            for line in lines {
+                last.push_str(LINE_SEP);
+                last.push_str(&line);
+            }
+        }
+        TextPatternMessage {
+            columns,
+            timestamp,
+            raw: raw.to_vec(),
+        }
+    }
+
+    fn is_continuation(&self, line: &str) -> bool {
+        self.continuation
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(line))
+            || (self.join_unmatched && !self.regex.is_match(line))
+    }
+}
+
+/// Returns the next line and the number of consumed bytes. The line is only
+/// returned if it's terminated by a line break, unless `complete` isn't required.
+pub(crate) fn next_line(input: &[u8], complete: bool) -> Option<(String, usize)> {
+    let (line, consumed) = match memchr(b'\n', input) {
+        Some(len) => (&input[..len], len + 1),
+        None if !complete && !input.is_empty() => (input, input.len()),
+        None => return None,
+    };
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    Some((String::from_utf8_lossy(line).into_owned(), consumed))
+}
+
+/// Parses the timestamp with the `chrono` format into milliseconds since the epoch. Formats
+/// without a timezone are parsed as UTC, formats without a year - in the given year.
+pub(crate) fn parse_timestamp(value: &str, format: &str, year: i32) -> Option<i64> {
+    if let Ok(datetime) = DateTime::parse_from_str(value, format) {
+        return Some(datetime.timestamp_millis());
+    }
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+        return Some(datetime.and_utc().timestamp_millis());
+    }
+    NaiveDateTime::parse_from_str(&format!("{year} {value}"), &format!("%Y {format}"))
+        .ok()
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+}
+
+pub(crate) fn normalize_severity(value: &str) -> String {
+    let value = value.trim().to_ascii_uppercase();
+    match value.as_str() {
+        "F" | "FATAL" | "CRITICAL" | "CRIT" | "ALERT" | "EMERG" => String::from("FATAL"),
+        "E" | "ERROR" | "ERR" => String::from("ERROR"),
+        "W" | "WARN" | "WARNING" => String::from("WARN"),
+        "I" | "INFO" | "NOTICE" => String::from("INFO"),
+        "D" | "DEBUG" => String::from("DEBUG"),
+        "V" | "VERBOSE" | "T" | "TRACE" => String::from("TRACE"),
+        _ => value,
+    }
+}
+
+impl Parser for TextPatternParser {
+    type Output = TextPatternMessage;
+
+    fn parse(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<impl Iterator<Item = ParseOutput<TextPatternMessage>>, Error> {
+        let max_lines = if self.continuation.is_some() || self.join_unmatched {
+            DEFAULT_MAX_LINES
+        } else {
+            1
+        };
+        let mut held = self.held.take();
+        let outputs = fold(
+            input,
+            &mut held,
+            max_lines,
+            |line| self.is_continuation(line),
+            |lines, raw| self.message(lines, raw),
+        );
+        self.held = held;
+        Ok(outputs.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseYield, Parser};
+    use chrono::NaiveDate;
+
+    fn parse_next(parser: &mut TextPatternParser, input: &[u8]) -> Vec<(usize, Vec<u8>)> {
+        parser
+            .parse(input, None)
+            .unwrap()
+            .filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => {
+                    let mut raw = Vec::new();
+                    msg.to_writer(&mut raw).unwrap();
+                    Some((out.consumed, raw))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Parses the input as the whole content of the source.
+    fn parse_all(parser: &mut TextPatternParser, input: &[u8]) -> Vec<TextPatternMessage> {
+        let mut messages = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let outputs: Vec<_> = parser.parse(rest, None).unwrap().collect();
+            rest = &rest[outputs.iter().map(|out| out.consumed).sum::<usize>()..];
+            messages.extend(outputs.into_iter().filter_map(|out| match out.message {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            }));
+        }
+        messages
+    }
+
+    #[test]
+    fn log4j_with_stack_trace() {
+        let mut parser =
+            TextPatternParser::new(TextPatternConfig::preset(TextPatternPreset::Log4j)).unwrap();
+        assert_eq!(
+            parser.columns(),
+            vec!["timestamp", "thread", "level", "logger", "message"]
+        );
+        let input = b"2024-03-17 16:13:38,811 [main] INFO  com.example.App - Started\n\
+            2024-03-17 16:13:39,002 [main] ERROR com.example.App - Failed\n\
+            java.lang.IllegalStateException: boom\n\
+            \tat com.example.App.main(App.java:10)\n\
+            2024-03-17 16:13:40,000 [worker-1] W com.example.Job - Slow\r\n";
+        let messages = parse_all(&mut parser, input);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0].to_string(),
+            "2024-03-17 16:13:38,811\u{4}main\u{4}INFO\u{4}com.example.App\u{4}Started"
+        );
+        assert_eq!(messages[0].timestamp(), Some(1_710_692_018_811));
+        assert_eq!(
+            messages[1].columns[4],
+            "Failed\u{6}java.lang.IllegalStateException: boom\u{6}\tat com.example.App.main(App.java:10)"
+        );
+        let mut raw = Vec::new();
+        messages[1].to_writer(&mut raw).unwrap();
+        assert_eq!(
+            raw,
+            b"2024-03-17 16:13:39,002 [main] ERROR com.example.App - Failed\n\
+            java.lang.IllegalStateException: boom\n\
+            \tat com.example.App.main(App.java:10)"
+        );
+        assert_eq!(messages[2].columns[2], "WARN");
+        assert_eq!(messages[2].columns[4], "Slow");
+    }
+
+    #[test]
+    fn logcat_nginx_and_python() {
+        let mut parser = TextPatternParser::new(TextPatternConfig {
+            year: Some(2023),
+            ..TextPatternConfig::preset(TextPatternPreset::LogcatThreadtime)
+        })
+        .unwrap();
+        let messages = parse_all(
+            &mut parser,
+            b"03-17 16:13:38.811  1702  2395 E WindowManager: Exception thrown\n",
+        );
+        assert_eq!(
+            messages[0].columns,
+            vec![
+                "03-17 16:13:38.811",
+                "1702",
+                "2395",
+                "ERROR",
+                "WindowManager",
+                "Exception thrown"
+            ]
+        );
+        let expected = NaiveDate::from_ymd_opt(2023, 3, 17)
+            .and_then(|date| date.and_hms_milli_opt(16, 13, 38, 811))
+            .map(|datetime| datetime.and_utc().timestamp_millis());
+        assert_eq!(messages[0].timestamp(), expected);
+
+        let mut parser =
+            TextPatternParser::new(TextPatternConfig::preset(TextPatternPreset::Nginx)).unwrap();
+        let messages = parse_all(
+            &mut parser,
+            b"127.0.0.1 - - [17/Mar/2024:16:13:38 +0100] \"GET / HTTP/1.1\" 200 612 \"-\" \"curl/8.0\"\n\
+            not an access log line\n",
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].columns[3], "GET / HTTP/1.1");
+        assert_eq!(messages[0].timestamp(), Some(1_710_688_418_000));
+        assert_eq!(messages[1].columns[7], "not an access log line");
+        assert!(messages[1].columns[..7].iter().all(String::is_empty));
+
+        let mut parser =
+            TextPatternParser::new(TextPatternConfig::preset(TextPatternPreset::PythonLogging))
+                .unwrap();
+        let messages = parse_all(
+            &mut parser,
+            b"2024-03-17 16:13:38,811 - app.db - WARNING - Retry\n\
+            Traceback (most recent call last):\n\
+            2024-03-17 16:13:39,811 - app - INFO - Done\n",
+        );
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0].to_string(),
+            "2024-03-17 16:13:38,811\u{4}app.db\u{4}WARN\u{4}Retry\u{6}Traceback (most recent call last):"
+        );
+        let mut raw = Vec::new();
+        messages[0].to_writer(&mut raw).unwrap();
+        assert!(raw.starts_with(b"2024-03-17 16:13:38,811 - app.db - WARNING - Retry\n"));
+    }
+
+    #[test]
+    fn custom_continuation_and_invalid_config() {
+        let mut parser = TextPatternParser::new(TextPatternConfig {
+            pattern: String::from(r"^(?P<level>\w+): (?P<message>.*)$"),
+            timestamp_group: None,
+            timestamp_format: None,
+            year: None,
+            severity_group: Some(String::from("level")),
+            continuation_pattern: Some(String::from(r"^\s+")),
+            join_unmatched: false,
+        })
+        .unwrap();
+        let messages = parse_all(&mut parser, b"error: one\n  two\nthree\n");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].columns, vec!["ERROR", "one\u{6}  two"]);
+        assert_eq!(messages[1].columns, vec!["", "three"]);
+
+        let config = |pattern: &str, timestamp_group: Option<&str>| TextPatternConfig {
+            pattern: pattern.to_owned(),
+            timestamp_group: timestamp_group.map(String::from),
+            timestamp_format: Some(String::from("%s")),
+            year: None,
+            severity_group: None,
+            continuation_pattern: None,
+            join_unmatched: false,
+        };
+        assert!(TextPatternParser::new(config(r"(?P<a>", None)).is_err());
+        assert!(TextPatternParser::new(config(r"^.*$", None)).is_err());
+        assert!(TextPatternParser::new(config(r"^(?P<a>.*)$", Some("b"))).is_err());
+    }
+
+    #[test]
+    fn continuation_after_loaded_data() {
+        let mut parser =
+            TextPatternParser::new(TextPatternConfig::preset(TextPatternPreset::Log4j)).unwrap();
+        let first = b"2024-03-17 16:13:39,002 [main] ERROR com.example.App - Failed\n";
+        let loaded = [&first[..], b"\tat a\n\tat"].concat();
+        // The only message can be continued, so it's held back
+        assert!(parse_next(&mut parser, &loaded).is_empty());
+        let loaded = [
+            &first[..],
+            b"\tat a\r\n\tat \xff\n2024-03-17 16:13:40,000 [main] INFO com.example.App - Done\n",
+        ]
+        .concat();
+        let messages = parse_next(&mut parser, &loaded);
+        let len = first.len() + 13;
+        assert_eq!(
+            messages,
+            vec![(len, [&first[..], b"\tat a\r\n\tat \xff"].concat())]
+        );
+        let rest = &loaded[len..];
+        assert!(parse_next(&mut parser, rest).is_empty());
+        // Nothing was loaded since the last call
+        assert_eq!(parse_next(&mut parser, rest).len(), 1);
+    }
+}
@@ -247,6 +247,7 @@ pub async fn load_plugin(
         PluginMetadata {
             title: dir_name.into(),
             description: None,
+            detector: None,
         }
     });
 
@@ -32,6 +32,7 @@ fn create_manager() -> PluginsManager {
             metadata: PluginMetadata {
                 title: "parser_1".into(),
                 description: None,
+                detector: None,
             },
             readme_path: Some(PARSER_README_PATH.into()),
         }
@@ -51,6 +52,7 @@ fn create_manager() -> PluginsManager {
             metadata: PluginMetadata {
                 title: "parser_2".into(),
                 description: None,
+                detector: None,
             },
             readme_path: None,
         }
@@ -68,6 +70,7 @@ fn create_manager() -> PluginsManager {
             metadata: PluginMetadata {
                 title: "source_1".into(),
                 description: None,
+                detector: None,
             },
             readme_path: Some(SOURCE_README_PATH.into()),
         }
@@ -85,6 +88,7 @@ fn create_manager() -> PluginsManager {
             metadata: PluginMetadata {
                 title: "source_2".into(),
                 description: None,
+                detector: None,
             },
             readme_path: None,
         }
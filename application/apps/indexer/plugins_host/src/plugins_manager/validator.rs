@@ -7,7 +7,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use stypes::{ColumnsRenderOptions, PluginInfo, PluginMetadata, RenderOptions};
+use stypes::{
+    ColumnsRenderOptions, PluginFormatDetector, PluginInfo, PluginMetadata, RenderOptions,
+};
 
 use crate::plugins_manager::paths::extract_plugin_file_paths;
 
@@ -25,6 +27,9 @@ const MAX_DESCRIPTION_TEXT_LENGTH: usize = 10 * 1024;
 /// Maximum length of collections provided by plugins before considered malicious.
 const MAX_COLLECTIONS_LENGTH: usize = 100;
 
+/// Maximum length in bytes of file signatures provided by plugins.
+const MAX_SIGNATURE_LENGTH: usize = 64;
+
 /// Scans and validates the plugin directory, ensuring all required files exist  
 /// and collecting paths of relevant plugin-related files (both mandatory and optional).
 ///
@@ -181,7 +186,11 @@ pub fn validate_plugin_info(info: &PluginInfo) -> Result<(), String> {
 
 pub fn validate_plugins_metadata(metadata: &PluginMetadata) -> Result<(), String> {
     // Note: Pattern match is reminder to validate newly added items.
-    let PluginMetadata { title, description } = metadata;
+    let PluginMetadata {
+        title,
+        description,
+        detector,
+    } = metadata;
 
     if title.len() > MAX_TITLE_TEXT_LENGTH {
         return Err(format!(
@@ -198,6 +207,38 @@ pub fn validate_plugins_metadata(metadata: &PluginMetadata) -> Result<(), String
         ));
     }
 
+    if let Some(detector) = detector {
+        let PluginFormatDetector {
+            extensions,
+            signatures,
+        } = detector;
+
+        if extensions.len() > MAX_COLLECTIONS_LENGTH || signatures.len() > MAX_COLLECTIONS_LENGTH {
+            return Err(format!(
+                "Plugin detector has more than {MAX_COLLECTIONS_LENGTH} extensions or signatures"
+            ));
+        }
+
+        if extensions.iter().any(|ext| ext.len() > MAX_ID_TEXT_LENGTH) {
+            return Err(format!(
+                "Plugin detector extension is longer than {MAX_ID_TEXT_LENGTH} bytes"
+            ));
+        }
+
+        for signature in signatures {
+            let valid = !signature.is_empty()
+                && signature.len() % 2 == 0
+                && signature.len() <= MAX_SIGNATURE_LENGTH * 2
+                && signature.chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(format!(
+                    "Plugin detector signature '{signature}' isn't a hex string of up to \
+                    {MAX_SIGNATURE_LENGTH} bytes"
+                ));
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -524,6 +565,7 @@ mod tests {
         let valid_meta = PluginMetadata {
             title: String::from("Title_1"),
             description: Some(String::from("Description_1")),
+            detector: None,
         };
 
         assert!(validate_plugins_metadata(&valid_meta).is_ok());
@@ -531,17 +573,42 @@ mod tests {
         let valid_meta = PluginMetadata {
             title: String::from("Title_1"),
             description: None,
+            detector: None,
         };
 
         assert!(validate_plugins_metadata(&valid_meta).is_ok());
     }
 
+    #[test]
+    fn metadata_detector() {
+        let mut meta = PluginMetadata {
+            title: String::from("Title_1"),
+            description: None,
+            detector: Some(PluginFormatDetector {
+                extensions: vec![String::from("dlt")],
+                signatures: vec![String::from("444C5401")],
+            }),
+        };
+
+        assert!(validate_plugins_metadata(&meta).is_ok());
+
+        let too_long = "00".repeat(MAX_SIGNATURE_LENGTH + 1);
+        for signature in ["", "444C540", "444C54ZZ", too_long.as_str()] {
+            meta.detector = Some(PluginFormatDetector {
+                extensions: Vec::new(),
+                signatures: vec![signature.to_owned()],
+            });
+            assert!(validate_plugins_metadata(&meta).is_err());
+        }
+    }
+
     #[test]
     fn invalid_metadata_title_fail() {
         let title = get_too_long(MAX_TITLE_TEXT_LENGTH);
         let invalid_meta = PluginMetadata {
             title,
             description: Some(String::from("Description_1")),
+            detector: None,
         };
 
         assert!(validate_plugins_metadata(&invalid_meta).is_err());
@@ -553,6 +620,7 @@ mod tests {
         let invalid_meta = PluginMetadata {
             title: String::from("Title_1"),
             description: Some(description),
+            detector: None,
         };
 
         assert!(validate_plugins_metadata(&invalid_meta).is_err());
@@ -1,17 +1,20 @@
 //! Includes the functions to export logs from files in ray binary format.
 
 use std::{
+    fs::File,
     io::{BufWriter, Write},
+    ops::Range,
     path::Path,
 };
 
 use indexer_base::config::IndexSection;
 use parsers::{LogMessage, ParseYield, Parser};
-use sources::ByteSource;
+use sources::{ByteSource, encoding::copy_lines};
+use stypes::TextEncoding;
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 
-use crate::producer::{GeneralLogCollector, MessageProducer, ProduceError, ProduceSummary};
+use crate::producer::{LogRecordsCollector, MessageProducer, ProduceError, ProduceSummary};
 
 #[derive(Error, Debug)]
 pub enum ExportError {
@@ -25,6 +28,38 @@ pub enum ExportError {
     Producer(ProduceError),
 }
 
+/// Collects messages along with positions of bytes in the source, which messages have been
+/// parsed from.
+struct SpansCollector<T> {
+    messages: Vec<(T, Range<u64>)>,
+}
+
+impl<T: LogMessage> LogRecordsCollector<T> for SpansCollector<T> {
+    fn append(&mut self, log_record: ParseYield<T>) {
+        self.append_at(log_record, 0, 0);
+    }
+
+    fn append_at(&mut self, log_record: ParseYield<T>, offset: u64, consumed: usize) {
+        let span = offset..offset + consumed as u64;
+        match log_record {
+            ParseYield::Message(msg) | ParseYield::MessageAndAttachment((msg, _)) => {
+                self.messages.push((msg, span));
+            }
+            ParseYield::Attachment(_) => {}
+        }
+    }
+}
+
+fn open_destination(destination_path: &Path) -> Result<File, ExportError> {
+    Ok(if destination_path.exists() {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(destination_path)?
+    } else {
+        File::create(destination_path)?
+    })
+}
+
 /// Exporting data as raw into a given destination. Would be exported only data
 /// defined in selections as indexes of rows (messages).
 ///
@@ -41,12 +76,13 @@ pub enum ExportError {
 ///   are processed; in "false" will stop listening to a stream as soon as all ranges
 ///   are processed. It should be used in "true" for example if exporting applied
 ///   to concatenated files.
+/// * `text_file` - in "true" messages are written line by line.
 /// * `cancel` - cancellation token to stop operation
 ///
 /// # Errors
 /// In case of cancellation will return ExportError::Cancelled
 pub async fn export_raw<P, D>(
-    mut producer: MessageProducer<P, D>,
+    producer: MessageProducer<P, D>,
     destination_path: &Path,
     sections: &Vec<IndexSection>,
     read_to_end: bool,
@@ -58,18 +94,95 @@ where
     D: ByteSource,
 {
     trace!("export_raw, sections: {sections:?}");
+    let mut out_writer = BufWriter::new(open_destination(destination_path)?);
+    let read = select_messages(producer, sections, read_to_end, cancel, |msg, _| {
+        msg.to_writer(&mut out_writer)?;
+        if text_file {
+            out_writer.write_all("\n".as_bytes())?;
+        }
+        Ok(())
+    })
+    .await?;
+    out_writer.flush()?;
+    Ok(read)
+}
+
+/// Exporting messages of a text file as raw into a given destination. Messages are parsed
+/// from the decoded (UTF-8) content of the file, so unlike [`export_raw`] the messages
+/// aren't written; the lines of the file, which messages have been parsed from, are copied
+/// as they are: in the original encoding, with the original line breaks and invalid
+/// sequences.
+///
+/// Returns usize - count of read messages (not exported, but read messages)
+///
+/// # Arguments
+/// * `source_path` - path to the text file, which is the source of the producer
+/// * `text_encoding` - encoding of the text file
+///
+/// Other arguments are the same as for [`export_raw`].
+///
+/// # Errors
+/// In case of cancellation will return ExportError::Cancelled
+pub async fn export_text_raw<P, D>(
+    producer: MessageProducer<P, D>,
+    source_path: &Path,
+    text_encoding: &TextEncoding,
+    destination_path: &Path,
+    sections: &Vec<IndexSection>,
+    read_to_end: bool,
+    cancel: &CancellationToken,
+) -> Result<usize, ExportError>
+where
+    P: Parser,
+    D: ByteSource,
+{
+    trace!("export_text_raw, sections: {sections:?}");
+    let mut spans: Vec<Range<u64>> = Vec::new();
+    let read = select_messages(producer, sections, read_to_end, cancel, |_, span| {
+        match spans.last_mut() {
+            Some(last) if last.end == span.start => last.end = span.end,
+            _ => spans.push(span),
+        }
+        Ok(())
+    })
+    .await?;
+    if cancel.is_cancelled() {
+        return Err(ExportError::Cancelled);
+    }
+    let mut out_writer = BufWriter::new(open_destination(destination_path)?);
+    copy_lines(
+        File::open(source_path)?,
+        text_encoding,
+        &spans,
+        &mut out_writer,
+    )?;
+    out_writer.flush()?;
+    Ok(read)
+}
+
+/// Iterates messages of the producer and calls `write` for messages in `sections` along
+/// with positions of bytes in the source, which messages have been parsed from.
+///
+/// Returns usize - count of read messages; if `sections` are empty - count of written
+/// messages.
+async fn select_messages<P, D, F>(
+    mut producer: MessageProducer<P, D>,
+    sections: &Vec<IndexSection>,
+    read_to_end: bool,
+    cancel: &CancellationToken,
+    mut write: F,
+) -> Result<usize, ExportError>
+where
+    P: Parser,
+    D: ByteSource,
+    F: FnMut(P::Output, Range<u64>) -> Result<(), ExportError>,
+{
     if !sections_valid(sections) {
         return Err(ExportError::Config("Invalid sections".to_string()));
     }
-    let out_file = if destination_path.exists() {
-        std::fs::OpenOptions::new()
-            .append(true)
-            .open(destination_path)?
-    } else {
-        std::fs::File::create(destination_path)?
+    let mut collector: SpansCollector<P::Output> = SpansCollector {
+        messages: Vec::new(),
     };
-    let mut out_writer = BufWriter::new(out_file);
-    let mut collector: GeneralLogCollector<P::Output> = GeneralLogCollector::default();
     let mut section_index = 0usize;
     let mut current_index = 0usize;
     let mut inside = false;
@@ -83,24 +196,9 @@ where
             }
             match producer.produce_next(&mut collector).await {
                 Ok(ProduceSummary::Processed { .. }) => {
-                    for item in collector.get_records().drain(..) {
-                        let written = match item {
-                            ParseYield::Message(msg) => {
-                                msg.to_writer(&mut out_writer)?;
-                                true
-                            }
-                            ParseYield::MessageAndAttachment((msg, _)) => {
-                                msg.to_writer(&mut out_writer)?;
-                                true
-                            }
-                            _ => false,
-                        };
-                        if written && text_file {
-                            out_writer.write_all("\n".as_bytes())?;
-                        }
-                        if written {
-                            exported += 1;
-                        }
+                    for (msg, span) in collector.messages.drain(..) {
+                        write(msg, span)?;
+                        exported += 1;
                     }
                 }
                 Ok(ProduceSummary::Done { .. } | ProduceSummary::NoBytesAvailable { .. }) => {
@@ -122,7 +220,7 @@ where
 
         match producer.produce_next(&mut collector).await {
             Ok(ProduceSummary::Processed { .. }) => {
-                for item in collector.get_records().drain(..) {
+                for (msg, span) in collector.messages.drain(..) {
                     if !inside {
                         if sections[section_index].first_line == current_index {
                             inside = true;
@@ -140,26 +238,10 @@ where
                             inside = true;
                         }
                     }
-                    let written = match item {
-                        ParseYield::Message(msg) => {
-                            if inside {
-                                msg.to_writer(&mut out_writer)?;
-                            }
-                            current_index += 1;
-                            inside
-                        }
-                        ParseYield::MessageAndAttachment((msg, _)) => {
-                            if inside {
-                                msg.to_writer(&mut out_writer)?;
-                            }
-                            current_index += 1;
-                            inside
-                        }
-                        _ => false,
-                    };
-                    if written && text_file {
-                        out_writer.write_all("\n".as_bytes())?;
+                    if inside {
+                        write(msg, span)?;
                     }
+                    current_index += 1;
                 }
             }
             Ok(ProduceSummary::Done { .. } | ProduceSummary::NoBytesAvailable { .. }) => {
@@ -267,13 +267,28 @@ impl SearchMap {
     /// In that case nearest for 310 will be 300
     /// Returns None if there are no search results
     pub fn nearest_to(&self, position_in_stream: u64) -> Option<stypes::NearestPosition> {
+        self.nearest_to_visible(position_in_stream, |_| true)
+    }
+
+    /// Same as `nearest_to`, but considers only matches on rows for which `is_visible`
+    /// returns `true`. Used when some rows of stream are hidden from the view.
+    pub fn nearest_to_visible(
+        &self,
+        position_in_stream: u64,
+        is_visible: impl Fn(u64) -> bool,
+    ) -> Option<stypes::NearestPosition> {
         if self.matches.is_empty() {
             None
         } else {
             let mut distance: i64 = i64::MAX;
             let mut index: u64 = 0;
             let mut position: u64 = 0;
-            for (position_in_search, filter_match) in self.matches.iter().enumerate() {
+            for (position_in_search, filter_match) in self
+                .matches
+                .iter()
+                .enumerate()
+                .filter(|(_, filter_match)| is_visible(filter_match.index))
+            {
                 let diff = (position_in_stream as i64 - filter_match.index as i64).abs();
                 if diff < distance {
                     distance = diff;
@@ -299,6 +314,11 @@ impl SearchMap {
         self.matches.len()
     }
 
+    /// Returns all matches in the search results.
+    pub fn matches(&self) -> &[stypes::FilterMatch] {
+        &self.matches
+    }
+
     pub fn get_stats(&self) -> HashMap<String, u64> {
         self.stats.stats.clone()
     }
@@ -313,6 +333,27 @@ impl SearchMap {
         }
     }
 
+    /// Returns ranges of rows around matches: each match along with `before` rows before it
+    /// and `after` rows after it. Overlapping and adjacent ranges are merged; ranges are
+    /// limited with the length of stream.
+    pub fn context_ranges(&self, before: u64, after: u64) -> Vec<RangeInclusive<u64>> {
+        let mut ranges: Vec<RangeInclusive<u64>> = Vec::new();
+        for m in self.matches.iter().filter(|m| m.index < self.stream_len) {
+            let start = m.index.saturating_sub(before);
+            let end = m
+                .index
+                .saturating_add(after)
+                .min(self.stream_len.saturating_sub(1));
+            match ranges.last_mut() {
+                Some(last) if start <= last.end().saturating_add(1) => {
+                    *last = *last.start()..=end.max(*last.end());
+                }
+                _ => ranges.push(start..=end),
+            }
+        }
+        ranges
+    }
+
     pub fn len(&self) -> usize {
         self.matches.len()
     }
@@ -510,3 +551,33 @@ fn test_scaled_map() {
     assert_eq!(scaled[8], vec![(0, 1), (1, 2), (2, 1), (3, 1)]);
     assert_eq!(scaled[9], vec![(2, 1), (3, 1)]);
 }
+
+#[test]
+fn test_context_ranges() {
+    let mut map: SearchMap = SearchMap::new();
+    map.set(
+        Some(vec![
+            stypes::FilterMatch::new(1, vec![0]),
+            stypes::FilterMatch::new(10, vec![0]),
+            stypes::FilterMatch::new(14, vec![1]),
+            stypes::FilterMatch::new(22, vec![0]),
+            stypes::FilterMatch::new(40, vec![0]),
+            stypes::FilterMatch::new(48, vec![0]),
+        ]),
+        None,
+    );
+    map.set_stream_len(50);
+    assert_eq!(
+        map.context_ranges(0, 0),
+        vec![1..=1, 10..=10, 14..=14, 22..=22, 40..=40, 48..=48]
+    );
+    assert_eq!(
+        map.context_ranges(3, 2),
+        vec![0..=3, 7..=16, 19..=24, 37..=42, 45..=49]
+    );
+    // Adjacent ranges are merged
+    assert_eq!(map.context_ranges(2, 5)[..2], [0..=6, 8..=27]);
+    // Matches out of stream are ignored
+    map.set_stream_len(45);
+    assert_eq!(map.context_ranges(0, 10).last(), Some(&(40..=44)));
+}
@@ -5,6 +5,13 @@ use parsers::{LogMessage, ParseYield};
 pub trait LogRecordsCollector<T: LogMessage> {
     /// Append the provided `log_record`.
     fn append(&mut self, log_record: ParseYield<T>);
+
+    /// Append the provided `log_record`, which has been parsed from `consumed` bytes of the
+    /// byte source starting at `offset`. Collectors, which don't need positions of records
+    /// in the source, can rely on the default implementation.
+    fn append_at(&mut self, log_record: ParseYield<T>, _offset: u64, _consumed: usize) {
+        self.append(log_record);
+    }
 }
 
 /// General purpose [`LogRecordsCollector`] which just collect the provided logs
@@ -130,6 +130,8 @@ impl<P: Parser, D: ByteSource> MessageProducer<P, D> {
 
             let mut bytes_consumed = 0;
             let mut messages_count = 0;
+            // Position of the loaded bytes in the content of byte source.
+            let offset = self.total_loaded.saturating_sub(available) as u64;
 
             match self
                 .parser
@@ -141,9 +143,9 @@ impl<P: Parser, D: ByteSource> MessageProducer<P, D> {
                             debug!(
                             "Extracted a valid message, consumed {consumed} bytes (total used {total_used_bytes} bytes)"
                             );
+                            collector.append_at(m, offset + bytes_consumed as u64, consumed);
                             bytes_consumed += consumed;
                             messages_count += 1;
-                            collector.append(m);
                         }
                         ParseOutput{consumed: skipped, message: None} => {
                             bytes_consumed += skipped;
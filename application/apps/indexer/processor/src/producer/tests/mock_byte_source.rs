@@ -139,8 +139,12 @@ impl ByteSource for MockByteSource {
         let bytes = match &msg {
             stypes::SdeRequest::WriteText(text) => text.as_bytes(),
             stypes::SdeRequest::WriteBytes(bytes) => bytes,
+            stypes::SdeRequest::DltControl(_) => return Err(Error::NotSupported),
         };
-        Ok(stypes::SdeResponse { bytes: bytes.len() })
+        Ok(stypes::SdeResponse {
+            bytes: bytes.len(),
+            response: None,
+        })
     }
 }
 
@@ -228,7 +232,10 @@ async fn test_mock_byte_source_income() {
     // Byte income should succeed producing a response with the length of the provided bytes.
     assert!(matches!(
         byte_income_res,
-        Ok(stypes::SdeResponse { bytes: BYTES_LEN })
+        Ok(stypes::SdeResponse {
+            bytes: BYTES_LEN,
+            response: None
+        })
     ));
 
     // *** Text Tests ***
@@ -242,7 +249,10 @@ async fn test_mock_byte_source_income() {
     // Text income should succeed producing a response wit the length of the provided text bytes.
     assert!(matches!(
         text_income_res,
-        Ok(stypes::SdeResponse { bytes: TEXT_LEN })
+        Ok(stypes::SdeResponse {
+            bytes: TEXT_LEN,
+            response: None
+        })
     ));
 }
 
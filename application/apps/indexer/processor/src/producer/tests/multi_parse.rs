@@ -519,3 +519,50 @@ async fn success_parse_error_success_err_skipped_bytes() {
         }
     }
 }
+
+/// Collects positions of parsed messages in the byte source.
+#[derive(Default)]
+struct PositionsCollector {
+    positions: Vec<(usize, u64, usize)>,
+}
+
+impl LogRecordsCollector<MockMessage> for PositionsCollector {
+    fn append(&mut self, _log_record: ParseYield<MockMessage>) {
+        panic!("Positions of records should be provided");
+    }
+
+    fn append_at(&mut self, log_record: ParseYield<MockMessage>, offset: u64, consumed: usize) {
+        if let ParseYield::Message(msg) = log_record {
+            self.positions.push((msg.content, offset, consumed));
+        }
+    }
+}
+
+#[tokio::test]
+async fn parse_items_positions() {
+    let parser = MockParser::new([
+        Ok(vec![
+            MockParseSeed::new(4, Some(ParseYield::Message(MockMessage::from(1)))),
+            MockParseSeed::new(6, Some(ParseYield::Message(MockMessage::from(2)))),
+        ]),
+        Ok(vec![
+            MockParseSeed::new(3, None),
+            MockParseSeed::new(5, Some(ParseYield::Message(MockMessage::from(3)))),
+        ]),
+    ]);
+    let source = MockByteSource::new(
+        0,
+        [
+            Ok(Some(MockReloadSeed::new(20, 0))),
+            Ok(Some(MockReloadSeed::new(0, 0))),
+        ],
+    );
+
+    let mut producer = MessageProducer::new(parser, source);
+    let mut collector = PositionsCollector::default();
+    producer.produce_next(&mut collector).await.unwrap();
+    producer.produce_next(&mut collector).await.unwrap();
+
+    // Skipped bytes are taken into account
+    assert_eq!(collector.positions, vec![(1, 0, 4), (2, 4, 6), (3, 13, 5)]);
+}
@@ -1,16 +1,33 @@
 //! Includes Extractor operations which are used in charts.
 
-use crate::search::{error::SearchError, filter, filter::SearchFilter};
+use crate::search::{error::SearchError, filter, filter::SearchFilter, scope::ScopeMatcher};
 use grep_regex::RegexMatcher;
 use grep_searcher::{Searcher, sinks::UTF8};
 use itertools::Itertools;
 use regex::Regex;
 use std::{path::PathBuf, str::FromStr};
 
-fn get_extracted_value(index: u64, input: &str, filters: &[Regex]) -> stypes::ExtractedMatchValue {
+fn get_extracted_value(
+    index: u64,
+    input: &str,
+    filters: &[SearchFilter],
+    regexs: &[Regex],
+) -> stypes::ExtractedMatchValue {
     let mut values: Vec<(usize, Vec<String>)> = vec![];
-    for (filter_index, filter) in filters.iter().enumerate() {
-        for caps in filter.captures_iter(input) {
+    for (filter_index, (filter, re)) in filters.iter().zip(regexs.iter()).enumerate() {
+        // Regex of approximate filter only preselects rows
+        if !filter.is_match(re, input) {
+            continue;
+        }
+        // Values are extracted only from selected columns, if filter is scoped
+        let subjects: Vec<&str> = match filter.get_columns() {
+            Some(scope) => scope.cells(input).collect(),
+            None => vec![input],
+        };
+        for caps in subjects
+            .into_iter()
+            .flat_map(|subject| re.captures_iter(subject))
+        {
             // Element on 0 always is the whole match. Here we don't need it
             let matches: Vec<String> = caps
                 .iter()
@@ -30,11 +47,24 @@ fn get_extracted_value(index: u64, input: &str, filters: &[Regex]) -> stypes::Ex
 pub struct MatchesExtractor {
     pub file_path: PathBuf,
     filters: Vec<SearchFilter>,
+    /// Restricts extracting to some rows; all rows are used if not defined.
+    scope: Option<ScopeMatcher>,
 }
 
 impl MatchesExtractor {
     pub fn new(file_path: PathBuf, filters: Vec<SearchFilter>) -> Self {
-        Self { file_path, filters }
+        Self {
+            file_path,
+            filters,
+            scope: None,
+        }
+    }
+
+    /// Restricts extracting to rows in the scope.
+    #[must_use]
+    pub fn scope(mut self, scope: Option<ScopeMatcher>) -> Self {
+        self.scope = scope;
+        self
     }
 
     pub fn extract_matches(&self) -> Result<Vec<stypes::ExtractedMatchValue>, SearchError> {
@@ -43,8 +73,10 @@ impl MatchesExtractor {
                 "Cannot search without filters".to_owned(),
             ));
         }
-        let combined_regex: String =
-            format!("({})", self.filters.iter().map(filter::as_regex).join("|"));
+        let combined_regex: String = format!(
+            "({})",
+            self.filters.iter().map(filter::as_row_regex).join("|")
+        );
         let mut values: Vec<stypes::ExtractedMatchValue> = vec![];
         let mut regexs: Vec<Regex> = vec![];
         for filter in self.filters.iter() {
@@ -65,7 +97,18 @@ impl MatchesExtractor {
                 &regex_matcher,
                 &self.file_path,
                 UTF8(|lnum, line| {
-                    values.push(get_extracted_value(lnum - 1, line, &regexs));
+                    if self
+                        .scope
+                        .as_ref()
+                        .is_none_or(|scope| scope.contains(lnum - 1))
+                        && self
+                            .filters
+                            .iter()
+                            .zip(regexs.iter())
+                            .any(|(filter, re)| filter.is_match(re, line))
+                    {
+                        values.push(get_extracted_value(lnum - 1, line, &self.filters, &regexs));
+                    }
                     Ok(true)
                 }),
             )
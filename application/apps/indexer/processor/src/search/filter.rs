@@ -1,14 +1,83 @@
+use crate::search::fuzzy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+/// Restricts matching to some columns of a row.
+///
+/// Rows are split into columns with the same separator (`spliter`), which is used by export
+/// of selected columns. Filter matches, if it matches at least one of selected columns.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ColumnsScope {
+    /// Separator of columns in the session file.
+    pub spliter: String,
+    /// Indices of columns to search in.
+    pub columns: Vec<usize>,
+}
+
+impl ColumnsScope {
+    pub fn new(spliter: impl Into<String>, columns: Vec<usize>) -> Self {
+        Self {
+            spliter: spliter.into(),
+            columns,
+        }
+    }
+
+    /// Returns selected columns of the row. Columns which are missing in the row are skipped.
+    pub fn cells<'a>(&'a self, line: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.cells_with_offsets(line).map(|(_, cell)| cell)
+    }
+
+    /// Returns selected columns of the row along with their offsets (in bytes) in the row.
+    pub fn cells_with_offsets<'a>(
+        &'a self,
+        line: &'a str,
+    ) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+        line.split(self.spliter.as_str())
+            .scan(0, |offset, cell| {
+                let start = *offset;
+                *offset += cell.len() + self.spliter.len();
+                Some((start, cell))
+            })
+            .enumerate()
+            .filter(|(index, _)| self.columns.contains(index))
+            .map(|(_, cell)| cell)
+    }
+
+    /// Checks whether regex matches any of selected columns.
+    pub fn is_match(&self, re: &Regex, line: &str) -> bool {
+        self.cells(line).any(|cell| re.is_match(cell))
+    }
+
+    /// Returns description of error, if scope isn't valid.
+    pub fn error(&self) -> Option<String> {
+        if self.spliter.is_empty() {
+            Some(String::from("Columns separator is empty"))
+        } else if self.columns.is_empty() {
+            Some(String::from("No columns are selected"))
+        } else {
+            None
+        }
+    }
+}
+
 /// Represents the definitions of search filter.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SearchFilter {
     pub value: String,
     is_regex: bool,
     ignore_case: bool,
     is_word: bool,
+    /// Columns to search in; the whole row is used if not defined.
+    #[serde(default)]
+    columns: Option<ColumnsScope>,
+    /// Value is a wildcard pattern (see [`fuzzy::glob_as_regex`]).
+    #[serde(default)]
+    is_glob: bool,
+    /// Maximum count of edits (insertions, deletions or substitutions of chars) for
+    /// approximate matching of plain text; exact matching is used if not defined.
+    #[serde(default)]
+    distance: Option<u8>,
 }
 
 impl SearchFilter {
@@ -18,6 +87,9 @@ impl SearchFilter {
             is_regex,
             ignore_case,
             is_word,
+            columns: None,
+            is_glob: false,
+            distance: None,
         }
     }
 
@@ -27,6 +99,9 @@ impl SearchFilter {
             is_regex: false,
             ignore_case: false,
             is_word: false,
+            columns: None,
+            is_glob: false,
+            distance: None,
         }
     }
 
@@ -58,9 +133,95 @@ impl SearchFilter {
         self.is_word = word;
         self
     }
+
+    /// Restricts filter to the given columns of rows.
+    #[must_use]
+    pub fn columns(mut self, columns: Option<ColumnsScope>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Treats value as a wildcard pattern.
+    #[must_use]
+    pub fn glob(mut self, glob: bool) -> Self {
+        self.is_glob = glob;
+        self
+    }
+
+    /// Sets maximum edit distance for approximate matching.
+    #[must_use]
+    pub fn fuzzy(mut self, distance: Option<u8>) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn get_columns(&self) -> Option<&ColumnsScope> {
+        self.columns.as_ref()
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.is_regex
+    }
+
+    pub fn is_ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+
+    pub fn is_glob(&self) -> bool {
+        self.is_glob
+    }
+
+    pub fn get_distance(&self) -> Option<u8> {
+        self.distance
+    }
+
+    /// Checks whether regex of filter (see [`as_regex`]) matches the row considering
+    /// columns scope of filter. For approximate matching the regex only preselects rows,
+    /// which are checked then with the edit distance.
+    pub fn is_match(&self, re: &Regex, line: &str) -> bool {
+        let Some(distance) = self.distance else {
+            return match self.columns.as_ref() {
+                Some(scope) => scope.is_match(re, line),
+                None => re.is_match(line),
+            };
+        };
+        let is_match = |text: &str| {
+            re.is_match(text)
+                && fuzzy::is_approximate_match(&self.value, text, distance, self.ignore_case)
+        };
+        match self.columns.as_ref() {
+            Some(scope) => scope.cells(line).any(is_match),
+            None => is_match(line),
+        }
+    }
 }
 
 pub fn get_filter_error(filter: &SearchFilter) -> Option<String> {
+    if let Some(err) = filter.columns.as_ref().and_then(ColumnsScope::error) {
+        return Some(err);
+    }
+    if filter.is_glob && filter.is_regex {
+        return Some(String::from(
+            "Wildcards cannot be used with regular expression",
+        ));
+    }
+    if let Some(distance) = filter.distance {
+        if filter.is_regex || filter.is_glob {
+            return Some(String::from(
+                "Approximate matching can be used only with plain text",
+            ));
+        }
+        if filter.is_word {
+            return Some(String::from(
+                "Approximate matching cannot be used with whole word matching",
+            ));
+        }
+        if filter.value.chars().count() <= distance as usize {
+            return Some(format!(
+                "Filter should be longer than the edit distance ({distance})"
+            ));
+        }
+    }
     let regex_as_str = as_regex(filter);
     Regex::from_str(&regex_as_str).map_or_else(|err| Some(err.to_string()), |_| None)
 }
@@ -71,18 +232,80 @@ pub fn as_regex(filter: &SearchFilter) -> String {
     let ignore_case_end = if filter.ignore_case { "(?-i)" } else { "" };
     let subject = if filter.is_regex {
         filter.value.clone()
+    } else if filter.is_glob {
+        fuzzy::glob_as_regex(&filter.value)
+    } else if let Some(distance) = filter.distance {
+        format!(
+            "(?:{})",
+            fuzzy::pieces(&filter.value, distance)
+                .iter()
+                .map(|piece| regex::escape(piece))
+                .collect::<Vec<String>>()
+                .join("|")
+        )
     } else {
         regex::escape(&filter.value)
     };
     format!("{ignore_case_start}{word_marker}{subject}{word_marker}{ignore_case_end}",)
 }
 
+/// Checks whether regex has anchors (`^`, `$`, `\A`, `\z`). Applied to separate columns
+/// such regex can match, even if it doesn't match the whole row.
+/// Escaped characters (e.g. `\^`) and characters within classes (e.g. `[^a]`) aren't anchors.
+pub fn has_anchors(regex: &str) -> bool {
+    let mut chars = regex.chars();
+    let mut class = false;
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                if matches!(chars.next(), Some('A' | 'z')) && !class {
+                    return true;
+                }
+            }
+            '[' => class = true,
+            ']' => class = false,
+            '^' | '$' if !class => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns regex, which matches all rows where filter can match. It's used to select
+/// rows for further checking considering columns scope of filter.
+pub fn as_row_regex(filter: &SearchFilter) -> String {
+    if filter.columns.is_some() && filter.is_regex && has_anchors(&filter.value) {
+        // Any row
+        String::from("^")
+    } else {
+        as_regex(filter)
+    }
+}
+
 pub fn as_alias(filter: &SearchFilter) -> String {
     let word_marker = if filter.is_word { "1" } else { "0" };
     let ignore_case = if filter.ignore_case { "1" } else { "0" };
     let is_regex = if filter.is_regex { "1" } else { "0" };
-    format!(
+    let mut alias = format!(
         "{}:{}{}{}",
         filter.value, is_regex, ignore_case, word_marker
-    )
+    );
+    if filter.is_glob {
+        alias.push_str(":glob");
+    }
+    if let Some(distance) = filter.distance {
+        alias = format!("{alias}:~{distance}");
+    }
+    match filter.columns.as_ref() {
+        Some(scope) => format!(
+            "{alias}:{}",
+            scope
+                .columns
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        ),
+        None => alias,
+    }
 }
@@ -0,0 +1,258 @@
+//! Includes matching modes of search filters for users, which aren't familiar with regular
+//! expressions: wildcards (glob) and approximate matching.
+//!
+//! Both modes are based on regular expressions, so filters of these modes are working with
+//! all kinds of searches. Wildcards are converted into equivalent regular expressions.
+//! Approximate matching can't be expressed with a reasonable regular expression, so it's
+//! done in two steps: a regular expression selects rows, which might match (see [`pieces`]),
+//! and then rows are checked with [`is_approximate_match`].
+
+use std::ops::Range;
+
+/// Token of wildcard pattern.
+enum GlobToken {
+    /// Any sequence of chars (`*`)
+    Any,
+    /// Any single char (`?`)
+    AnyChar,
+    /// Set of chars (`[abc]`, `[a-z]`, `[!abc]`) as regular expression
+    Class(String),
+    Literal(char),
+}
+
+fn glob_tokens(glob: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                if !matches!(tokens.last(), Some(GlobToken::Any)) {
+                    tokens.push(GlobToken::Any);
+                }
+            }
+            '?' => tokens.push(GlobToken::AnyChar),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                tokens.push(GlobToken::Literal(chars[i]));
+            }
+            '[' => match class_end(&chars, i) {
+                Some(end) => {
+                    let mut class = String::from("[");
+                    let mut j = i + 1;
+                    if chars[j] == '!' {
+                        class.push('^');
+                        j += 1;
+                    }
+                    for c in &chars[j..end] {
+                        if matches!(c, '\\' | '[' | ']' | '&' | '~' | '^') {
+                            class.push('\\');
+                        }
+                        class.push(*c);
+                    }
+                    class.push(']');
+                    tokens.push(GlobToken::Class(class));
+                    i = end;
+                }
+                None => tokens.push(GlobToken::Literal('[')),
+            },
+            c => tokens.push(GlobToken::Literal(c)),
+        }
+        i += 1;
+    }
+    tokens
+}
+
+/// Returns position of `]` closing the set of chars opened at `open`. `]` right after
+/// `[` or `[!` belongs to the set.
+fn class_end(chars: &[char], open: usize) -> Option<usize> {
+    let mut first = open + 1;
+    if chars.get(first) == Some(&'!') {
+        first += 1;
+    }
+    if chars.get(first) == Some(&']') {
+        first += 1;
+    }
+    chars
+        .get(first..)?
+        .iter()
+        .position(|c| *c == ']')
+        .map(|pos| first + pos)
+}
+
+fn push_escaped(regex: &mut String, c: char) {
+    regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+}
+
+/// Converts wildcard pattern into regular expression. Supported wildcards: `*` - any
+/// sequence of chars, `?` - any single char, `[abc]`, `[a-z]` and `[!abc]` - sets of chars.
+/// Wildcards can be escaped with `\`. As plain text, pattern can match any part of a row.
+pub fn glob_as_regex(glob: &str) -> String {
+    let mut regex = String::new();
+    for token in glob_tokens(glob) {
+        match token {
+            GlobToken::Any => regex.push_str(".*"),
+            GlobToken::AnyChar => regex.push('.'),
+            GlobToken::Class(class) => regex.push_str(&class),
+            GlobToken::Literal(c) => push_escaped(&mut regex, c),
+        }
+    }
+    regex
+}
+
+/// Returns literal parts of wildcard pattern, which are present in every matched row.
+pub fn glob_literals(glob: &str) -> Vec<String> {
+    let mut literals = vec![String::new()];
+    for token in glob_tokens(glob) {
+        match token {
+            GlobToken::Literal(c) => {
+                if let Some(literal) = literals.last_mut() {
+                    literal.push(c);
+                }
+            }
+            _ => literals.push(String::new()),
+        }
+    }
+    literals.retain(|literal| !literal.is_empty());
+    literals
+}
+
+/// Splits pattern into `distance + 1` parts of (almost) equal length. Each edit changes
+/// at most one part, so any approximate match contains at least one part as is. Returns
+/// nothing, if pattern is too short for the given distance.
+pub fn pieces(pattern: &str, distance: u8) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let count = distance as usize + 1;
+    if chars.len() < count {
+        return Vec::new();
+    }
+    let (len, rest) = (chars.len() / count, chars.len() % count);
+    let mut pieces = Vec::with_capacity(count);
+    let mut start = 0;
+    for n in 0..count {
+        let end = start + len + usize::from(n < rest);
+        pieces.push(chars[start..end].iter().collect());
+        start = end;
+    }
+    pieces
+}
+
+fn fold(c: char, ignore_case: bool) -> char {
+    if ignore_case {
+        c.to_lowercase().next().unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// Column of the edit distance matrix (Sellers algorithm). Values are the smallest count
+/// of edits to match the beginning of pattern ending at the current position of text;
+/// along with the positions, where such matches start.
+struct Column {
+    costs: Vec<usize>,
+    starts: Vec<usize>,
+}
+
+impl Column {
+    fn new(len: usize, start: usize) -> Self {
+        Self {
+            costs: (0..=len).collect(),
+            starts: vec![start; len + 1],
+        }
+    }
+
+    /// Moves to the next char of text, which ends at `end`.
+    fn step(&mut self, pattern: &[char], c: char, end: usize) {
+        let (mut diag_cost, mut diag_start) = (self.costs[0], self.starts[0]);
+        self.costs[0] = 0;
+        self.starts[0] = end;
+        for i in 1..=pattern.len() {
+            let (prev_cost, prev_start) = (self.costs[i], self.starts[i]);
+            // Substitution (or exact match)
+            let mut cost = diag_cost + usize::from(pattern[i - 1] != c);
+            let mut start = diag_start;
+            // Extra char in text
+            if prev_cost + 1 < cost {
+                cost = prev_cost + 1;
+                start = prev_start;
+            }
+            // Missed char of pattern
+            if self.costs[i - 1] + 1 < cost {
+                cost = self.costs[i - 1] + 1;
+                start = self.starts[i - 1];
+            }
+            (diag_cost, diag_start) = (prev_cost, prev_start);
+            self.costs[i] = cost;
+            self.starts[i] = start;
+        }
+    }
+
+    fn cost(&self) -> usize {
+        self.costs[self.costs.len() - 1]
+    }
+
+    fn start(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+}
+
+/// Checks whether text has a part, which differs from pattern in at most `distance`
+/// edits (insertions, deletions or substitutions of chars).
+pub fn is_approximate_match(pattern: &str, text: &str, distance: u8, ignore_case: bool) -> bool {
+    let pattern: Vec<char> = pattern.chars().map(|c| fold(c, ignore_case)).collect();
+    let distance = distance as usize;
+    if pattern.len() <= distance {
+        return true;
+    }
+    let mut column = Column::new(pattern.len(), 0);
+    text.char_indices().any(|(pos, c)| {
+        column.step(&pattern, fold(c, ignore_case), pos + c.len_utf8());
+        column.cost() <= distance
+    })
+}
+
+/// Returns (byte) ranges of not overlapping parts of text, which differ from pattern in
+/// at most `distance` edits. Among overlapping matches the one with the fewest edits is
+/// taken.
+pub fn find_approximate(
+    pattern: &str,
+    text: &str,
+    distance: u8,
+    ignore_case: bool,
+) -> Vec<Range<usize>> {
+    let pattern: Vec<char> = pattern.chars().map(|c| fold(c, ignore_case)).collect();
+    let distance = distance as usize;
+    let mut found = Vec::new();
+    if pattern.len() <= distance {
+        return found;
+    }
+    let mut column = Column::new(pattern.len(), 0);
+    // The best match so far, which can be improved by following chars
+    let mut candidate: Option<(usize, Range<usize>)> = None;
+    for (pos, c) in text.char_indices() {
+        let end = pos + c.len_utf8();
+        let c = fold(c, ignore_case);
+        column.step(&pattern, c, end);
+        if column.cost() <= distance
+            && candidate
+                .as_ref()
+                .is_none_or(|(cost, _)| column.cost() < *cost)
+        {
+            candidate = Some((column.cost(), column.start()..end));
+            continue;
+        }
+        if let Some((_, range)) = candidate.take() {
+            // Searching continues after the match, which ends right before the current char
+            column = Column::new(pattern.len(), pos);
+            column.step(&pattern, c, end);
+            found.push(range);
+            if column.cost() <= distance {
+                candidate = Some((column.cost(), column.start()..end));
+            }
+        }
+    }
+    if let Some((_, range)) = candidate {
+        found.push(range);
+    }
+    found
+}
@@ -0,0 +1,102 @@
+//! Includes utilities for locating parts of rows matched by search filters.
+//! Locations are delivered to clients along with grabbed rows, so matches can be highlighted
+//! without running filters once again on the client side.
+
+use crate::search::{error::SearchError, filter, filter::SearchFilter, fuzzy};
+use regex::Regex;
+use std::{ops::Range, str::FromStr};
+
+/// Locates matches of filters in rows.
+#[derive(Debug, Default)]
+pub struct Highlighter {
+    /// Filters along with compiled regular expressions. The position of filter in the list is
+    /// used as its identifier, as in search results (`FilterMatch`).
+    filters: Vec<(SearchFilter, Regex)>,
+}
+
+impl Highlighter {
+    /// Creates a new `Highlighter` for the given filters.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - Filters in the same order as they are used in search.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` - If all regular expressions are successfully created.
+    /// * `Err(SearchError)` - If some regular expression cannot be compiled.
+    pub fn new(filters: &[SearchFilter]) -> Result<Self, SearchError> {
+        let mut compiled = Vec::with_capacity(filters.len());
+        for filter in filters.iter() {
+            let regex_as_str = filter::as_regex(filter);
+            let re = Regex::from_str(&regex_as_str).map_err(|err| {
+                SearchError::Regex(format!("Failed to create regex for {regex_as_str}: {err}"))
+            })?;
+            compiled.push((filter.clone(), re));
+        }
+        Ok(Self { filters: compiled })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Returns parts of the row matched by filters, considering columns scopes of filters.
+    /// Spans are ordered by position; positions are in characters, not bytes. Empty matches
+    /// are skipped.
+    pub fn spans(&self, line: &str) -> Vec<stypes::MatchSpan> {
+        let mut found: Vec<(u8, Range<usize>)> = Vec::new();
+        for (index, (filter, re)) in self.filters.iter().enumerate() {
+            let mut collect = |offset: usize, subject: &str| match filter.get_distance() {
+                Some(distance) => found.extend(
+                    fuzzy::find_approximate(
+                        &filter.value,
+                        subject,
+                        distance,
+                        filter.is_ignore_case(),
+                    )
+                    .into_iter()
+                    .map(|range| (index as u8, offset + range.start..offset + range.end)),
+                ),
+                None => found.extend(
+                    re.find_iter(subject)
+                        .filter(|m| !m.is_empty())
+                        .map(|m| (index as u8, offset + m.start()..offset + m.end())),
+                ),
+            };
+            match filter.get_columns() {
+                Some(scope) => scope
+                    .cells_with_offsets(line)
+                    .for_each(|(offset, cell)| collect(offset, cell)),
+                None => collect(0, line),
+            }
+        }
+        found.sort_by_key(|(filter, range)| (range.start, *filter));
+        if line.is_ascii() {
+            return found
+                .into_iter()
+                .map(|(filter, range)| stypes::MatchSpan {
+                    filter,
+                    start: range.start as u64,
+                    end: range.end as u64,
+                })
+                .collect();
+        }
+        // Spans are sorted by start, so characters before a span are counted from the
+        // start of the previous one.
+        let (mut bytes, mut chars) = (0, 0);
+        found
+            .into_iter()
+            .map(|(filter, range)| {
+                chars += line[bytes..range.start].chars().count();
+                bytes = range.start;
+                let len = line[range.clone()].chars().count();
+                stypes::MatchSpan {
+                    filter,
+                    start: chars as u64,
+                    end: (chars + len) as u64,
+                }
+            })
+            .collect()
+    }
+}
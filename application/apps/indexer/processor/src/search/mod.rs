@@ -1,4 +1,17 @@
 pub mod error;
 pub mod extractor;
 pub mod filter;
+pub mod fuzzy;
+pub mod highlight;
+pub mod ngrams;
+pub mod query;
+pub mod scope;
 pub mod searchers;
+#[cfg(test)]
+mod tests_fuzzy;
+#[cfg(test)]
+mod tests_highlight;
+#[cfg(test)]
+mod tests_ngrams;
+#[cfg(test)]
+mod tests_scope;
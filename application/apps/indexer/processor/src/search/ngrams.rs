@@ -0,0 +1,498 @@
+//! Includes the definitions of n-gram index of the session file.
+//!
+//! The index maps trigrams (sequences of 3 bytes) to the blocks of rows, which contain them.
+//! Searching with the index is done in two steps: blocks, where all trigrams of required
+//! literals of filters are present, are selected with the index; after that only selected
+//! blocks are scanned with regular search, which verifies each row. Blocks (instead of
+//! single rows) keep the index small enough to be built on huge sessions.
+//!
+//! The index is case-insensitive for ASCII: all bytes are lowercased before indexing.
+
+use crate::search::{error::SearchError, filter::SearchFilter, fuzzy};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+/// Count of rows in one block of the index.
+const BLOCK_ROWS: u64 = 256;
+
+/// Count of bytes at the beginning and at the end of the indexed content, which are used to
+/// check whether the persisted index still belongs to the file.
+const FINGERPRINT_LEN: u64 = 4096;
+
+const VERSION: u32 = 1;
+
+type Trigram = [u8; 3];
+
+/// Index, which is shared between the indexer (writer) and searchers (readers).
+pub type SharedNgramIndex = Arc<RwLock<NgramIndex>>;
+
+/// Part of the session file to search in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Position of the first row of the segment in the session.
+    pub row: u64,
+    /// Bytes of the segment in the session file.
+    pub bytes: Range<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Block {
+    /// Position of the first row of the block.
+    row: u64,
+    /// Offset of the first row of the block in the session file.
+    byte: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NgramIndex {
+    version: u32,
+    blocks: Vec<Block>,
+    /// Sorted identifiers of blocks, which contain trigram.
+    postings: HashMap<Trigram, Vec<u32>>,
+    /// Count of indexed rows.
+    rows: u64,
+    /// Count of indexed bytes of the session file.
+    bytes: u64,
+}
+
+impl Default for NgramIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NgramIndex {
+    pub fn new() -> Self {
+        Self {
+            version: VERSION,
+            blocks: Vec::new(),
+            postings: HashMap::new(),
+            rows: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Loads persisted index of the session file.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_file` - Path to the session file.
+    /// * `path` - Path to the index file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Self))` - If the index was persisted and it still matches the session file.
+    /// * `Ok(None)` - If there is no index; or the index is outdated (for example, the file
+    ///   was rewritten) or was written by another version.
+    /// * `Err(SearchError)` - If the index cannot be read.
+    pub fn load(session_file: &Path, path: &Path) -> Result<Option<Self>, SearchError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(path).map_err(|e| {
+            SearchError::IoOperation(format!("Could not open index file {path:?}: {e}"))
+        })?;
+        // Fingerprint of the indexed content is written before the index
+        let (persisted, index): (u64, Self) = match bincode::deserialize_from(BufReader::new(file))
+        {
+            Ok(content) => content,
+            Err(err) => {
+                log::warn!("Index file {path:?} will be ignored: {err}");
+                return Ok(None);
+            }
+        };
+        if index.version != VERSION {
+            return Ok(None);
+        }
+        let len = fs::metadata(session_file)
+            .map_err(|e| {
+                SearchError::IoOperation(format!(
+                    "Could not read metadata of {session_file:?}: {e}"
+                ))
+            })?
+            .len();
+        if len < index.bytes || fingerprint(session_file, index.bytes)? != persisted {
+            return Ok(None);
+        }
+        Ok(Some(index))
+    }
+
+    /// Persists the index of the session file. The index is written into temporary file
+    /// first, so the persisted index is never partially written.
+    ///
+    /// The index isn't changed by saving, so it can be saved while it's shared with
+    /// searchers.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_file` - Path to the session file.
+    /// * `path` - Path to the index file.
+    pub fn save(&self, session_file: &Path, path: &Path) -> Result<(), SearchError> {
+        let fingerprint = fingerprint(session_file, self.bytes)?;
+        let mut tmp = path.to_path_buf().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let file = File::create(&tmp).map_err(|e| {
+            SearchError::IoOperation(format!("Could not create index file {tmp:?}: {e}"))
+        })?;
+        let mut writer = BufWriter::new(file);
+        bincode::serialize_into(&mut writer, &(fingerprint, self))
+            .map_err(|e| e.to_string())
+            .and_then(|_| writer.flush().map_err(|e| e.to_string()))
+            .map_err(|e| {
+                SearchError::IoOperation(format!("Could not write index file {tmp:?}: {e}"))
+            })?;
+        fs::rename(&tmp, path).map_err(|e| {
+            SearchError::IoOperation(format!("Could not write index file {path:?}: {e}"))
+        })
+    }
+
+    /// Count of indexed rows.
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Count of indexed bytes of the session file.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Indexes rows of the session file, which were written after the last update.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_file` - Path to the session file.
+    /// * `to` - Offset in the session file to index up to (exclusive). Only complete rows
+    ///   are indexed.
+    /// * `max` - Maximum count of bytes to index at once. Allows to release the index
+    ///   between steps of indexing of a huge file.
+    ///
+    /// # Returns
+    ///
+    /// `true` if indexing was stopped because of `max` and there are rows before `to`,
+    /// which are still not indexed.
+    pub fn update(&mut self, session_file: &Path, to: u64, max: u64) -> Result<bool, SearchError> {
+        if to <= self.bytes {
+            return Ok(false);
+        }
+        let mut file = File::open(session_file).map_err(|e| {
+            SearchError::IoOperation(format!("Could not open file {session_file:?}: {e}"))
+        })?;
+        file.seek(SeekFrom::Start(self.bytes)).map_err(|e| {
+            SearchError::IoOperation(format!(
+                "Could not seek file {session_file:?} to {}: {e}",
+                self.bytes
+            ))
+        })?;
+        let mut reader = BufReader::new(file.take(to - self.bytes));
+        let mut line = Vec::new();
+        let mut indexed = 0;
+        while indexed < max {
+            line.clear();
+            let len = reader.read_until(b'\n', &mut line).map_err(|e| {
+                SearchError::IoOperation(format!("Could not read file {session_file:?}: {e}"))
+            })? as u64;
+            if len == 0 || line.last() != Some(&b'\n') {
+                // Incomplete row will be indexed with the next update
+                break;
+            }
+            self.add_row(&line);
+            self.bytes += len;
+            indexed += len;
+        }
+        Ok(indexed >= max && self.bytes < to)
+    }
+
+    /// Selects parts of the session file, where rows matching at least one of filters can
+    /// be found.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - Filters of search.
+    /// * `row` - Position of the row, which starts at `bytes.start`.
+    /// * `bytes` - Bytes of the session file to search in.
+    ///
+    /// # Returns
+    ///
+    /// Ordered segments to search in or `None` if the index cannot narrow down the search
+    /// (for example, a filter doesn't have literals long enough). Bytes after the indexed
+    /// content are always included.
+    pub fn segments(
+        &self,
+        filters: &[SearchFilter],
+        row: u64,
+        bytes: Range<u64>,
+    ) -> Option<Vec<Segment>> {
+        if filters.is_empty() {
+            return None;
+        }
+        let mut candidates = Vec::new();
+        for filter in filters {
+            candidates.extend(self.candidates(filter)?);
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        let covered = self.bytes.min(bytes.end);
+        if covered <= bytes.start {
+            return Some(vec![Segment { row, bytes }]);
+        }
+        let mut segments: Vec<Segment> = Vec::new();
+        let first = self
+            .blocks
+            .partition_point(|block| block.byte <= bytes.start)
+            .saturating_sub(1) as u32;
+        let from = candidates.partition_point(|id| *id < first);
+        for id in candidates[from..].iter() {
+            let block = self.blocks[*id as usize];
+            if block.byte >= covered {
+                break;
+            }
+            let end = self.block_end(*id).min(covered);
+            let segment = if block.byte < bytes.start {
+                Segment {
+                    row,
+                    bytes: bytes.start..end,
+                }
+            } else {
+                Segment {
+                    row: block.row,
+                    bytes: block.byte..end,
+                }
+            };
+            push_segment(&mut segments, segment);
+        }
+        if covered < bytes.end {
+            push_segment(
+                &mut segments,
+                Segment {
+                    row: self.rows,
+                    bytes: covered..bytes.end,
+                },
+            );
+        }
+        Some(segments)
+    }
+
+    fn add_row(&mut self, line: &[u8]) {
+        if self.rows % BLOCK_ROWS == 0 {
+            self.blocks.push(Block {
+                row: self.rows,
+                byte: self.bytes,
+            });
+        }
+        let id = (self.blocks.len() - 1) as u32;
+        let line = line.to_ascii_lowercase();
+        for trigram in line.windows(3) {
+            if trigram.contains(&b'\n') || trigram.contains(&b'\r') {
+                continue;
+            }
+            let blocks = self
+                .postings
+                .entry([trigram[0], trigram[1], trigram[2]])
+                .or_default();
+            if blocks.last() != Some(&id) {
+                blocks.push(id);
+            }
+        }
+        self.rows += 1;
+    }
+
+    fn block_end(&self, id: u32) -> u64 {
+        self.blocks
+            .get(id as usize + 1)
+            .map(|block| block.byte)
+            .unwrap_or(self.bytes)
+    }
+
+    /// Returns sorted identifiers of blocks, where the filter can match; `None` if all
+    /// blocks have to be checked.
+    fn candidates(&self, filter: &SearchFilter) -> Option<Vec<u32>> {
+        let trigrams = trigrams(filter);
+        if trigrams.is_empty() {
+            return None;
+        }
+        let mut postings = Vec::new();
+        for trigram in trigrams.iter() {
+            match self.postings.get(trigram) {
+                Some(blocks) => postings.push(blocks),
+                None => return Some(Vec::new()),
+            }
+        }
+        postings.sort_by_key(|blocks| blocks.len());
+        let mut candidates = postings[0].clone();
+        for blocks in postings[1..].iter() {
+            candidates.retain(|id| blocks.binary_search(id).is_ok());
+        }
+        Some(candidates)
+    }
+}
+
+fn push_segment(segments: &mut Vec<Segment>, segment: Segment) {
+    match segments.last_mut() {
+        Some(last) if last.bytes.end == segment.bytes.start => {
+            last.bytes.end = segment.bytes.end;
+        }
+        _ => segments.push(segment),
+    }
+}
+
+/// Returns trigrams, which are present in every row matched by the filter.
+fn trigrams(filter: &SearchFilter) -> Vec<Trigram> {
+    let mut trigrams = Vec::new();
+    for literal in required_literals(filter) {
+        let literal = literal.as_bytes().to_ascii_lowercase();
+        for trigram in literal.windows(3) {
+            // Unicode case folding matches some non-ASCII chars with ASCII letters (for
+            // example KELVIN SIGN with "k"), so such trigrams cannot be used without
+            // case sensitivity.
+            if filter.is_ignore_case()
+                && trigram
+                    .iter()
+                    .any(|b| !b.is_ascii() || *b == b'k' || *b == b's')
+            {
+                continue;
+            }
+            let trigram = [trigram[0], trigram[1], trigram[2]];
+            if !trigrams.contains(&trigram) {
+                trigrams.push(trigram);
+            }
+        }
+    }
+    trigrams
+}
+
+/// Returns literals, which are present in every row matched by the filter. Regular
+/// expressions are analyzed conservatively: nothing is returned for expressions with
+/// alternations, flags or escapes, which aren't supported here. Approximately matched
+/// rows can miss any part of filter, so nothing is returned for such filters.
+pub(crate) fn required_literals(filter: &SearchFilter) -> Vec<String> {
+    if filter.get_distance().is_some() {
+        return Vec::new();
+    }
+    if filter.is_glob() {
+        return fuzzy::glob_literals(&filter.value);
+    }
+    if !filter.is_regex() {
+        return vec![filter.value.clone()];
+    }
+    let pattern = &filter.value;
+    if pattern.contains("(?") {
+        return Vec::new();
+    }
+    let mut literals = Vec::new();
+    let mut current = String::new();
+    // Whether the last char of `current` is the last token of pattern
+    let mut last_literal = false;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_punctuation() || escaped == ' ' => {
+                    current.push(escaped);
+                    last_literal = true;
+                    continue;
+                }
+                Some('x' | 'u' | 'U' | 'p' | 'P') | None => return Vec::new(),
+                // Classes (\d, \w), anchors (\b), special chars (\n, \t)
+                Some(_) => {}
+            },
+            '|' => return Vec::new(),
+            '?' | '*' | '{' => {
+                // Previous token is optional or its count is unknown
+                if last_literal {
+                    current.pop();
+                }
+                if c == '{' && !chars.by_ref().any(|c| c == '}') {
+                    return Vec::new();
+                }
+            }
+            '[' => {
+                if !skip_class(&mut chars) {
+                    return Vec::new();
+                }
+            }
+            ')' => {
+                if matches!(chars.peek(), Some('?' | '*' | '{')) {
+                    // Group is optional, but its literals are already taken
+                    return Vec::new();
+                }
+            }
+            // Previous char is still required, but the next one doesn't follow it
+            '+' | '(' | '.' | '^' | '$' => {}
+            _ => {
+                current.push(c);
+                last_literal = true;
+                continue;
+            }
+        }
+        last_literal = false;
+        if !current.is_empty() {
+            literals.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        literals.push(current);
+    }
+    literals
+}
+
+// Skips class of chars ("[...]"). Returns `false` if class isn't closed.
+fn skip_class(chars: &mut std::iter::Peekable<std::str::Chars>) -> bool {
+    let mut depth = 1;
+    if chars.peek() == Some(&'^') {
+        chars.next();
+    }
+    if chars.peek() == Some(&']') {
+        chars.next();
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+// FNV-1a hash of the beginning and the end of the first `len` bytes of the file.
+fn fingerprint(session_file: &Path, len: u64) -> Result<u64, SearchError> {
+    let mut file = File::open(session_file).map_err(|e| {
+        SearchError::IoOperation(format!("Could not open file {session_file:?}: {e}"))
+    })?;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let head = len.min(FINGERPRINT_LEN);
+    let tail = len.saturating_sub(FINGERPRINT_LEN).max(head)..len;
+    for range in [0..head, tail] {
+        let mut buffer = Vec::new();
+        file.seek(SeekFrom::Start(range.start))
+            .and_then(|_| {
+                (&mut file)
+                    .take(range.end - range.start)
+                    .read_to_end(&mut buffer)
+            })
+            .map_err(|e| {
+                SearchError::IoOperation(format!("Could not read file {session_file:?}: {e}"))
+            })?;
+        for byte in buffer {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    Ok(hash)
+}
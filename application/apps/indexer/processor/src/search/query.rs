@@ -0,0 +1,514 @@
+//! Query expressions for regular searches.
+//!
+//! A query combines search terms with boolean operators and proximity conditions:
+//!
+//! ```text
+//! ERROR AND ECU1 AND NOT heartbeat
+//! (/time(out)?/i OR "connection lost") AND NOT "retry"w
+//! "request sent" FOLLOWED BY "response timeout" WITHIN 10
+//! ```
+//!
+//! Terms can be written as bare words, as quoted strings (`"..."`, with `\"` and `\\` escapes)
+//! or as regular expressions (`/.../`, with `\/` escape). Quoted strings and regular expressions
+//! accept trailing flags: `i` - ignore case, `w` - match whole words. Keywords (`AND`, `OR`, `NOT`,
+//! `FOLLOWED BY`, `WITHIN`) are case-insensitive; a term which equals to keyword has to be quoted.
+//!
+//! Operators by precedence (from the lowest): `OR`, `AND`, `NOT`, `FOLLOWED BY`.
+//!
+//! `A FOLLOWED BY B WITHIN N` matches the row where `B` matches, if `A` has matched on one of
+//! the previous `N` rows.
+//!
+//! A term can be restricted to columns of rows with prefix `#<columns>:`, for example
+//! `#1:/^DIAG$/ AND #5:timeout` or `#0,2:"ECU1"`. Such terms require a columns separator.
+//! `#` not followed by a list of columns and `:` is taken literally, e.g. `#define`.
+//!
+//! Each distinct term becomes a [`SearchFilter`]. Indexes of filters are used in search results
+//! (`FilterMatch`) and per-filter statistics are collected for them as for regular filters.
+
+use crate::search::{
+    error::SearchError,
+    filter::{ColumnsScope, SearchFilter},
+};
+use std::{iter::Peekable, str::CharIndices};
+
+/// Max count of terms in one query. Matched filters are reported as `u8` indexes.
+const MAX_TERMS: usize = u8::MAX as usize + 1;
+
+/// Compiled search query: list of terms and expression over them.
+#[derive(Debug, Clone)]
+pub struct Query {
+    filters: Vec<SearchFilter>,
+    expr: Expr,
+}
+
+/// Expression tree of query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Index of term (filter) in query.
+    Term(usize),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    /// `first` followed by `then` within `within` rows.
+    Followed {
+        first: Box<Expr>,
+        then: Box<Expr>,
+        within: u64,
+    },
+}
+
+impl Query {
+    /// Parses query expression.
+    ///
+    /// * `spliter` - separator of columns in the session file; required for terms restricted
+    ///   to columns.
+    pub fn parse(query: &str, spliter: Option<&str>) -> Result<Self, SearchError> {
+        let mut parser = Parser {
+            tokens: tokenize(query)?,
+            pos: 0,
+            filters: Vec::new(),
+            spliter: spliter.map(String::from),
+        };
+        if parser.tokens.is_empty() {
+            return Err(SearchError::Input(String::from("Query is empty")));
+        }
+        let expr = parser.or()?;
+        if let Some((token, pos)) = parser.tokens.get(parser.pos) {
+            return Err(SearchError::Input(format!(
+                "Unexpected {token} at position {pos}"
+            )));
+        }
+        for filter in parser.filters.iter() {
+            if !filter.valid() {
+                return Err(SearchError::Input(format!(
+                    "Invalid term: {}",
+                    filter.value
+                )));
+            }
+        }
+        Ok(Self {
+            filters: parser.filters,
+            expr,
+        })
+    }
+
+    /// Creates query which matches rows with at least one matching filter.
+    pub fn any_of(filters: Vec<SearchFilter>) -> Self {
+        Self {
+            expr: Expr::Or((0..filters.len()).map(Expr::Term).collect()),
+            filters,
+        }
+    }
+
+    pub fn filters(&self) -> &[SearchFilter] {
+        &self.filters
+    }
+
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    pub fn into_parts(self) -> (Vec<SearchFilter>, Expr) {
+        (self.filters, self.expr)
+    }
+}
+
+impl Expr {
+    /// Evaluates expression for row.
+    ///
+    /// * `hits` - flags of matched terms, indexed as query filters.
+    /// * `row` - number of row. Rows have to be evaluated in ascending order.
+    /// * `history` - rows, where first parts of `FOLLOWED BY` were matched last time. Should
+    ///   have a length of [`Expr::proximity_count`].
+    pub fn eval(&self, hits: &[bool], row: u64, history: &mut [Option<u64>]) -> bool {
+        let mut cursor = 0;
+        self.eval_at(hits, row, history, &mut cursor)
+    }
+
+    // Subexpressions are never short-circuited to keep the history of `FOLLOWED BY` up to date.
+    fn eval_at(
+        &self,
+        hits: &[bool],
+        row: u64,
+        history: &mut [Option<u64>],
+        cursor: &mut usize,
+    ) -> bool {
+        match self {
+            Expr::Term(index) => hits.get(*index).copied().unwrap_or(false),
+            Expr::Not(expr) => !expr.eval_at(hits, row, history, cursor),
+            Expr::And(exprs) => exprs.iter().fold(true, |matched, expr| {
+                expr.eval_at(hits, row, history, cursor) && matched
+            }),
+            Expr::Or(exprs) => exprs.iter().fold(false, |matched, expr| {
+                expr.eval_at(hits, row, history, cursor) || matched
+            }),
+            Expr::Followed {
+                first,
+                then,
+                within,
+            } => {
+                let slot = *cursor;
+                *cursor += 1;
+                let first = first.eval_at(hits, row, history, cursor);
+                let then = then.eval_at(hits, row, history, cursor);
+                let matched =
+                    then && history[slot].is_some_and(|last| row > last && row - last <= *within);
+                if first {
+                    history[slot] = Some(row);
+                }
+                matched
+            }
+        }
+    }
+
+    /// Count of `FOLLOWED BY` conditions in expression.
+    pub fn proximity_count(&self) -> usize {
+        match self {
+            Expr::Term(_) => 0,
+            Expr::Not(expr) => expr.proximity_count(),
+            Expr::And(exprs) | Expr::Or(exprs) => exprs.iter().map(Expr::proximity_count).sum(),
+            Expr::Followed { first, then, .. } => {
+                1 + first.proximity_count() + then.proximity_count()
+            }
+        }
+    }
+
+    /// Returns `true` if expression has to be evaluated for rows without any matching
+    /// term (for example `NOT A`). Otherwise only rows with matches can be checked.
+    pub fn requires_every_row(&self) -> bool {
+        self.on_empty_row() != Some(false) || self.tracks_empty_rows()
+    }
+
+    // Result of expression on a row without matching terms; `None` if it depends on
+    // previous rows.
+    fn on_empty_row(&self) -> Option<bool> {
+        match self {
+            Expr::Term(_) => Some(false),
+            Expr::Not(expr) => expr.on_empty_row().map(|matched| !matched),
+            Expr::And(exprs) => {
+                let results = exprs.iter().map(Expr::on_empty_row).collect::<Vec<_>>();
+                if results.contains(&Some(false)) {
+                    Some(false)
+                } else if results.iter().all(|r| r == &Some(true)) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            Expr::Or(exprs) => {
+                let results = exprs.iter().map(Expr::on_empty_row).collect::<Vec<_>>();
+                if results.contains(&Some(true)) {
+                    Some(true)
+                } else if results.iter().all(|r| r == &Some(false)) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Expr::Followed { then, .. } => match then.on_empty_row() {
+                Some(false) => Some(false),
+                _ => None,
+            },
+        }
+    }
+
+    // Whether some `FOLLOWED BY` has to remember rows without matching terms.
+    fn tracks_empty_rows(&self) -> bool {
+        match self {
+            Expr::Term(_) => false,
+            Expr::Not(expr) => expr.tracks_empty_rows(),
+            Expr::And(exprs) | Expr::Or(exprs) => exprs.iter().any(Expr::tracks_empty_rows),
+            Expr::Followed { first, then, .. } => {
+                first.on_empty_row() != Some(false)
+                    || first.tracks_empty_rows()
+                    || then.tracks_empty_rows()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Followed,
+    By,
+    Within,
+    Word(String),
+    Term(SearchFilter),
+    Columns(Vec<usize>),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Open => write!(f, "\"(\""),
+            Token::Close => write!(f, "\")\""),
+            Token::And => write!(f, "AND"),
+            Token::Or => write!(f, "OR"),
+            Token::Not => write!(f, "NOT"),
+            Token::Followed => write!(f, "FOLLOWED"),
+            Token::By => write!(f, "BY"),
+            Token::Within => write!(f, "WITHIN"),
+            Token::Word(word) => write!(f, "\"{word}\""),
+            Token::Term(filter) => write!(f, "\"{}\"", filter.value),
+            Token::Columns(columns) => write!(f, "columns {columns:?}"),
+        }
+    }
+}
+
+/// Parses the list of columns of the columns prefix (`1,2:`), which follows `#`. Returns
+/// columns and the length of the prefix including `:`.
+fn columns_prefix(input: &str) -> Option<(Vec<usize>, usize)> {
+    let end = input.find(|ch: char| !ch.is_ascii_digit() && ch != ',')?;
+    if !input[end..].starts_with(':') {
+        return None;
+    }
+    let columns = input[..end]
+        .split(',')
+        .map(|column| column.parse::<usize>().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    Some((columns, end + 1))
+}
+
+fn tokenize(query: &str) -> Result<Vec<(Token, usize)>, SearchError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some(&(pos, ch)) = chars.peek() {
+        if ch == '#'
+            && let Some((columns, len)) = columns_prefix(&query[pos + 1..])
+        {
+            // Skip '#', the list of columns and ':', which are all ASCII
+            for _ in 0..=len {
+                chars.next();
+            }
+            tokens.push((Token::Columns(columns), pos));
+            continue;
+        }
+        match ch {
+            _ if ch.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push((Token::Open, pos));
+            }
+            ')' => {
+                chars.next();
+                tokens.push((Token::Close, pos));
+            }
+            '"' | '/' => {
+                chars.next();
+                let value = delimited(&mut chars, ch, pos)?;
+                let mut filter = SearchFilter::plain(&value).regex(ch == '/');
+                while let Some(&(_, flag)) = chars.peek() {
+                    match flag {
+                        'i' => filter = filter.ignore_case(true),
+                        'w' => filter = filter.word(true),
+                        _ if flag.is_whitespace() || flag == '(' || flag == ')' => break,
+                        _ => {
+                            return Err(SearchError::Input(format!(
+                                "Unknown flag \"{flag}\" of term at position {pos}"
+                            )));
+                        }
+                    }
+                    chars.next();
+                }
+                tokens.push((Token::Term(filter), pos));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if ch.is_whitespace() || matches!(ch, '(' | ')' | '"') {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                let token = match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "FOLLOWED" => Token::Followed,
+                    "BY" => Token::By,
+                    "WITHIN" => Token::Within,
+                    _ => Token::Word(word),
+                };
+                tokens.push((token, pos));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn delimited(
+    chars: &mut Peekable<CharIndices<'_>>,
+    delimiter: char,
+    pos: usize,
+) -> Result<String, SearchError> {
+    let mut value = String::new();
+    while let Some((_, ch)) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                // Inside of regex only delimiter is unescaped, other sequences belong to regex
+                Some((_, next)) if next == delimiter || (delimiter == '"' && next == '\\') => {
+                    value.push(next);
+                }
+                Some((_, next)) => {
+                    value.push(ch);
+                    value.push(next);
+                }
+                None => break,
+            },
+            _ if ch == delimiter => return Ok(value),
+            _ => value.push(ch),
+        }
+    }
+    Err(SearchError::Input(format!(
+        "Term at position {pos} isn't closed with {delimiter}"
+    )))
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    filters: Vec<SearchFilter>,
+    spliter: Option<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), SearchError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(SearchError::Input(String::from("Unexpected end of query")))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), SearchError> {
+        let (token, pos) = self.next()?;
+        if token != expected {
+            return Err(SearchError::Input(format!(
+                "Expected {expected} at position {pos}, but found {token}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Expr, SearchError> {
+        let mut exprs = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, SearchError> {
+        let mut exprs = vec![self.not()?];
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            exprs.push(self.not()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::And(exprs)
+        })
+    }
+
+    fn not(&mut self) -> Result<Expr, SearchError> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else {
+            self.followed()
+        }
+    }
+
+    fn followed(&mut self) -> Result<Expr, SearchError> {
+        let mut expr = self.primary()?;
+        while self.peek() == Some(&Token::Followed) {
+            self.pos += 1;
+            self.expect(Token::By)?;
+            let then = self.primary()?;
+            self.expect(Token::Within)?;
+            let within = match self.next()? {
+                (Token::Word(word), pos) => word.parse::<u64>().map_err(|_| {
+                    SearchError::Input(format!(
+                        "Expected count of rows at position {pos}, but found \"{word}\""
+                    ))
+                })?,
+                (token, pos) => {
+                    return Err(SearchError::Input(format!(
+                        "Expected count of rows at position {pos}, but found {token}"
+                    )));
+                }
+            };
+            expr = Expr::Followed {
+                first: Box::new(expr),
+                then: Box::new(then),
+                within,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, SearchError> {
+        match self.next()? {
+            (Token::Open, _) => {
+                let expr = self.or()?;
+                self.expect(Token::Close)?;
+                Ok(expr)
+            }
+            (Token::Word(word), _) => self.term(SearchFilter::plain(&word)),
+            (Token::Term(filter), _) => self.term(filter),
+            (Token::Columns(columns), pos) => {
+                let Some(spliter) = self.spliter.clone() else {
+                    return Err(SearchError::Input(format!(
+                        "Columns at position {pos} cannot be used without columns separator"
+                    )));
+                };
+                let filter = match self.next()? {
+                    (Token::Word(word), _) => SearchFilter::plain(&word),
+                    (Token::Term(filter), _) => filter,
+                    (token, pos) => {
+                        return Err(SearchError::Input(format!(
+                            "Expected term at position {pos}, but found {token}"
+                        )));
+                    }
+                };
+                self.term(filter.columns(Some(ColumnsScope::new(spliter, columns))))
+            }
+            (token, pos) => Err(SearchError::Input(format!(
+                "Expected term at position {pos}, but found {token}"
+            ))),
+        }
+    }
+
+    fn term(&mut self, filter: SearchFilter) -> Result<Expr, SearchError> {
+        if let Some(index) = self.filters.iter().position(|f| f == &filter) {
+            return Ok(Expr::Term(index));
+        }
+        if self.filters.len() >= MAX_TERMS {
+            return Err(SearchError::Input(format!(
+                "Query can have at most {MAX_TERMS} different terms"
+            )));
+        }
+        self.filters.push(filter);
+        Ok(Expr::Term(self.filters.len() - 1))
+    }
+}
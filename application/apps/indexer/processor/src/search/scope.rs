@@ -0,0 +1,176 @@
+//! Includes definitions for restricting searches to some rows of the session: rows written
+//! by selected sources and rows within a time window.
+
+use crate::search::error::SearchError;
+use serde::{Deserialize, Serialize};
+use std::{ops::RangeInclusive, sync::Arc};
+
+/// Ranges of rows of the session along with the identifiers of sources, which have
+/// written them. Ranges are ordered by position.
+pub type SourcesMap = Arc<Vec<(RangeInclusive<u64>, u16)>>;
+
+/// Restricts search to some rows of the session.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SearchScope {
+    /// Identifiers of sources to search in; rows of all sources are searched if empty.
+    #[serde(default)]
+    pub sources: Vec<u16>,
+    /// Time window to search in; rows are searched regardless of time if not defined.
+    #[serde(default)]
+    pub time: Option<TimeWindow>,
+}
+
+impl SearchScope {
+    /// Returns `true` if scope doesn't restrict search.
+    pub fn is_unrestricted(&self) -> bool {
+        self.sources.is_empty() && self.time.is_none()
+    }
+}
+
+/// Time window of search. Rows are checked against the timestamps, which parsers have
+/// detected in messages (see [`TimestampsMap`]).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+    /// Beginning of the window (inclusive) in milliseconds since the epoch; the window
+    /// isn't limited from the left if not defined.
+    pub from: Option<i64>,
+    /// End of the window (inclusive) in milliseconds since the epoch; the window isn't
+    /// limited from the right if not defined.
+    pub to: Option<i64>,
+}
+
+impl TimeWindow {
+    fn contains(&self, timestamp: i64) -> bool {
+        self.from.is_none_or(|from| timestamp >= from) && self.to.is_none_or(|to| timestamp <= to)
+    }
+}
+
+/// Timestamps of rows of the session in milliseconds since the epoch, as detected by
+/// parsers. Only rows, where the timestamp changes, are stored: all other rows get the
+/// timestamp of the previous row anyway. Rows are kept in chunks as they are written,
+/// so the map can be shared with searchers and extended without copying rows.
+#[derive(Debug, Clone, Default)]
+pub struct TimestampsMap(Arc<Vec<Arc<[(u64, i64)]>>>);
+
+impl TimestampsMap {
+    /// Appends timestamps of written rows. Rows have to follow the rows of the map.
+    pub fn append(&mut self, timestamps: Vec<(u64, i64)>) {
+        let mut last = self
+            .0
+            .last()
+            .and_then(|chunk| chunk.last())
+            .map(|(_, timestamp)| *timestamp);
+        let changes: Vec<(u64, i64)> = timestamps
+            .into_iter()
+            .filter(|(_, timestamp)| {
+                let changed = last != Some(*timestamp);
+                last = Some(*timestamp);
+                changed
+            })
+            .collect();
+        if !changes.is_empty() {
+            Arc::make_mut(&mut self.0).push(changes.into());
+        }
+    }
+
+    /// Returns `true` if no row has a timestamp.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the timestamp of the row. Rows without a timestamp (e.g. continuation lines
+    /// of a message) get the timestamp of the nearest previous row with one.
+    pub fn get(&self, row: u64) -> Option<i64> {
+        let chunk = self.0.partition_point(|chunk| chunk[0].0 <= row);
+        let chunk = self.0.get(chunk.checked_sub(1)?)?;
+        let pos = chunk.partition_point(|(r, _)| *r <= row);
+        chunk
+            .get(pos.checked_sub(1)?)
+            .map(|(_, timestamp)| *timestamp)
+    }
+}
+
+/// Checks whether rows belong to the scope of search.
+#[derive(Debug)]
+pub struct ScopeMatcher {
+    sources: Vec<u16>,
+    time: Option<TimeWindow>,
+    map: SourcesMap,
+    timestamps: TimestampsMap,
+}
+
+impl ScopeMatcher {
+    /// Creates a new `ScopeMatcher` for the given scope.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Self))` - If scope restricts search.
+    /// * `Ok(None)` - If scope doesn't restrict search and there is nothing to check.
+    /// * `Err(SearchError)` - If the time window ends before it begins.
+    pub fn new(scope: &SearchScope) -> Result<Option<Self>, SearchError> {
+        if scope.is_unrestricted() {
+            return Ok(None);
+        }
+        if let Some(TimeWindow {
+            from: Some(from),
+            to: Some(to),
+        }) = scope.time
+            && from > to
+        {
+            return Err(SearchError::Input(String::from(
+                "Time window ends before it begins",
+            )));
+        }
+        Ok(Some(Self {
+            sources: scope.sources.clone(),
+            time: scope.time.clone(),
+            map: SourcesMap::default(),
+            timestamps: TimestampsMap::default(),
+        }))
+    }
+
+    /// Sets the map of sources, which is used to find the source of a row. Should be
+    /// updated as soon as new rows are written into the session.
+    pub fn set_sources_map(&mut self, map: SourcesMap) {
+        self.map = map;
+    }
+
+    /// Sets the map of timestamps, which is used to find the time of a row. Should be
+    /// updated as soon as new rows are written into the session.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::Input` if the scope has a time window, but no row has a
+    /// timestamp (e.g. the parser of the session doesn't detect timestamps), because
+    /// nothing could be found in the window.
+    pub fn set_timestamps_map(&mut self, timestamps: TimestampsMap) -> Result<(), SearchError> {
+        if self.time.is_some() && timestamps.is_empty() {
+            return Err(SearchError::Input(String::from(
+                "Time window cannot be used, because rows of the session have no timestamps",
+            )));
+        }
+        self.timestamps = timestamps;
+        Ok(())
+    }
+
+    /// Checks whether the row belongs to the scope of search. Rows without a known time
+    /// (e.g. rows of parsers, which don't detect timestamps) are out of a time window.
+    pub fn contains(&self, row: u64) -> bool {
+        if !self.sources.is_empty() {
+            let pos = self.map.partition_point(|(range, _)| *range.end() < row);
+            let in_sources = self
+                .map
+                .get(pos)
+                .filter(|(range, _)| range.contains(&row))
+                .is_some_and(|(_, source)| self.sources.contains(source));
+            if !in_sources {
+                return false;
+            }
+        }
+        self.time.as_ref().is_none_or(|window| {
+            self.timestamps
+                .get(row)
+                .is_some_and(|timestamp| window.contains(timestamp))
+        })
+    }
+}
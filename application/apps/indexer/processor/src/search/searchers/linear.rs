@@ -11,6 +11,8 @@ use std::str::FromStr;
 pub struct LineSearcher {
     /// A compiled regular expression used for matching lines.
     re: Regex,
+    /// Filter used to create the searcher.
+    filter: SearchFilter,
 }
 
 impl LineSearcher {
@@ -30,6 +32,7 @@ impl LineSearcher {
             re: Regex::from_str(&regex_as_str).map_err(|err| {
                 SearchError::Regex(format!("Failed to create regex for {regex_as_str}: {err}"))
             })?,
+            filter: filter.clone(),
         })
     }
 
@@ -44,6 +47,6 @@ impl LineSearcher {
     /// * `true` - If the line matches the regular expression.
     /// * `false` - Otherwise.
     pub fn is_match(&self, ln: &str) -> bool {
-        self.re.is_match(ln)
+        self.filter.is_match(&self.re, ln)
     }
 }
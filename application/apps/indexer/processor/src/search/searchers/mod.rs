@@ -1,11 +1,16 @@
-use crate::{grabber::GrabError, search::error::SearchError};
+use crate::{
+    grabber::GrabError,
+    search::{error::SearchError, ngrams::Segment},
+};
 use grep_regex::RegexMatcher;
 use grep_searcher::{Searcher, sinks::UTF8};
 use std::{
     fs::File,
-    io::{Read, Seek, SeekFrom},
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
     ops::Range,
     path::{Path, PathBuf},
+    thread,
 };
 use text_grep::buffer::CancellableBufReader;
 use tokio_util::sync::CancellationToken;
@@ -20,17 +25,27 @@ pub mod tests_regular;
 pub mod tests_values;
 pub mod values;
 
+/// Size of the chunks the searched part of the file is split into. Chunks are
+/// searched in parallel.
+const CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
 #[derive(Debug)]
 pub struct BaseSearcher<State: SearchState> {
     pub file_path: PathBuf,
     bytes_read: u64,
     lines_read: u64,
+    chunk_bytes: u64,
     search_state: State,
 }
 pub trait SearchState {
     type SearchResultType;
     fn new(path: &Path) -> Self;
     fn get_terms(&self) -> Vec<String>;
+    /// Returns parts of the given bytes range of the file, where matches can be found.
+    /// `None` means the whole range has to be searched.
+    fn get_segments(&self, _row: u64, _bytes: Range<u64>) -> Option<Vec<Segment>> {
+        None
+    }
 }
 
 impl<State: SearchState> BaseSearcher<State> {
@@ -40,6 +55,7 @@ impl<State: SearchState> BaseSearcher<State> {
             file_path: PathBuf::from(path),
             bytes_read: read_bytes,
             lines_read: rows_count,
+            chunk_bytes: CHUNK_BYTES,
             search_state,
         }
     }
@@ -84,43 +100,203 @@ impl<State: SearchState> BaseSearcher<State> {
                 )));
             }
         };
-        let in_file = File::open(&self.file_path).map_err(|_| {
-            GrabError::IoOperation(format!("Could not open file {:?}", self.file_path))
-        })?;
-        let mut in_file_reader = CancellableBufReader::new(in_file, cancel_token);
-        in_file_reader
-            .seek(SeekFrom::Start(self.bytes_read))
-            .map_err(|_| {
-                GrabError::IoOperation(format!(
-                    "Could not seek file {:?} to {}",
-                    self.file_path, self.bytes_read
-                ))
-            })?;
-        let mut reader_handler = in_file_reader.take(read_bytes - self.bytes_read);
-        // Take in account: we are counting on all levels (grabbing search, grabbing stream etc)
-        // from 0 line always. But grep gives results from 1. That's why here is a point of correct:
-        // lnum - 1
+        let segments = self
+            .search_state
+            .get_segments(self.lines_read, self.bytes_read..read_bytes)
+            .unwrap_or_else(|| {
+                vec![Segment {
+                    row: self.lines_read,
+                    bytes: self.bytes_read..read_bytes,
+                }]
+            });
+        let chunks = split(&self.file_path, segments, self.chunk_bytes)?;
+        let threads = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1);
         let lines_read = self.lines_read;
         let mut processed: usize = 0;
-        Searcher::new()
-            .search_reader(
-                &matcher,
-                &mut reader_handler,
-                UTF8(|row, line| {
-                    // self.matching(row + lines_read - 1, line);
-                    collect_fn(row + lines_read - 1, line, &mut self.search_state);
+        let mut row = self.lines_read;
+        // Chunks are searched in parallel by groups, but results are collected in
+        // the order of rows, because collecting of some searches depends on previous rows.
+        for group in chunks.chunks(threads) {
+            if cancel_token.is_cancelled() {
+                break;
+            }
+            let results: Vec<Result<Option<Found>, SearchError>> = if group.len() == 1 {
+                vec![search_chunk(
+                    &self.file_path,
+                    &matcher,
+                    &group[0],
+                    cancel_token.clone(),
+                )]
+            } else {
+                thread::scope(|scope| {
+                    let handles: Vec<_> = group
+                        .iter()
+                        .map(|chunk| {
+                            let matcher = matcher.clone();
+                            let cancel = cancel_token.clone();
+                            let path = &self.file_path;
+                            scope.spawn(move || search_chunk(path, &matcher, chunk, cancel))
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle.join().unwrap_or_else(|_| {
+                                Err(SearchError::IoOperation(String::from(
+                                    "Search thread panicked",
+                                )))
+                            })
+                        })
+                        .collect()
+                })
+            };
+            for (chunk, found) in group.iter().zip(results) {
+                // Rows of the following chunks are unknown, if a chunk was cancelled
+                let Some(found) = found? else {
+                    break;
+                };
+                let first = chunk.row.unwrap_or(row);
+                for (offset, line) in found.matches {
+                    collect_fn(first + offset, &line, &mut self.search_state);
                     processed += 1;
-                    Ok(true)
-                }),
-            )
-            .map_err(|e| {
-                SearchError::IoOperation(format!(
-                    "Could not search in file {:?}; error: {e}",
-                    self.file_path
-                ))
-            })?;
+                }
+                row = first + found.lines;
+            }
+        }
         self.lines_read = rows_count;
         self.bytes_read = read_bytes + 1;
         Ok(lines_read as usize..(lines_read as usize + processed))
     }
 }
+
+/// Line aligned part of the file, which is searched as a whole.
+#[derive(Debug)]
+struct Chunk {
+    bytes: Range<u64>,
+    /// Number of the first row of the chunk. It's known only for chunks, which are
+    /// starting a segment; otherwise it's defined by rows of previous chunks.
+    row: Option<u64>,
+}
+
+/// Findings in a chunk.
+struct Found {
+    /// Count of complete lines in the chunk.
+    lines: u64,
+    /// Matched lines with row numbers relative to the beginning of the chunk.
+    matches: Vec<(u64, String)>,
+}
+
+/// Counts lines of the content passing through the reader.
+struct LinesCounter<R> {
+    inner: R,
+    lines: u64,
+}
+
+impl<R: Read> Read for LinesCounter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.lines += bytecount::count(&buf[..len], b'\n') as u64;
+        Ok(len)
+    }
+}
+
+/// Splits the segments into chunks of (roughly) the given size. Chunks are ending
+/// right after a line break, so no line is split between two chunks.
+fn split(path: &Path, segments: Vec<Segment>, chunk_bytes: u64) -> Result<Vec<Chunk>, SearchError> {
+    let mut chunks = Vec::new();
+    let mut reader = if segments
+        .iter()
+        .any(|segment| segment.bytes.end - segment.bytes.start > chunk_bytes)
+    {
+        Some(BufReader::new(File::open(path).map_err(|_| {
+            GrabError::IoOperation(format!("Could not open file {path:?}"))
+        })?))
+    } else {
+        None
+    };
+    for segment in segments {
+        let mut start = segment.bytes.start;
+        let mut row = Some(segment.row);
+        while segment.bytes.end - start > chunk_bytes
+            && let Some(reader) = reader.as_mut()
+        {
+            let cut = start + chunk_bytes;
+            reader.seek(SeekFrom::Start(cut)).map_err(|_| {
+                GrabError::IoOperation(format!("Could not seek file {path:?} to {cut}"))
+            })?;
+            let skipped = reader.skip_until(b'\n').map_err(|e| {
+                SearchError::IoOperation(format!("Could not read file {path:?}; error: {e}"))
+            })?;
+            let end = cut + skipped as u64;
+            if skipped == 0 || end >= segment.bytes.end {
+                break;
+            }
+            chunks.push(Chunk {
+                bytes: start..end,
+                row: row.take(),
+            });
+            start = end;
+        }
+        chunks.push(Chunk {
+            bytes: start..segment.bytes.end,
+            row,
+        });
+    }
+    Ok(chunks)
+}
+
+/// Searches the chunk. Returns `None` if the search was cancelled, because the
+/// chunk might be searched only partially in this case.
+fn search_chunk(
+    path: &Path,
+    matcher: &RegexMatcher,
+    chunk: &Chunk,
+    cancel: CancellationToken,
+) -> Result<Option<Found>, SearchError> {
+    if cancel.is_cancelled() {
+        return Ok(None);
+    }
+    let in_file = File::open(path)
+        .map_err(|_| GrabError::IoOperation(format!("Could not open file {path:?}")))?;
+    let mut in_file_reader = CancellableBufReader::new(in_file, cancel.clone());
+    if let Err(err) = in_file_reader.seek(SeekFrom::Start(chunk.bytes.start)) {
+        // The reader refuses to seek, once the search is cancelled
+        if cancel.is_cancelled() {
+            return Ok(None);
+        }
+        return Err(GrabError::IoOperation(format!(
+            "Could not seek file {path:?} to {}; error: {err}",
+            chunk.bytes.start
+        ))
+        .into());
+    }
+    let mut reader_handler = LinesCounter {
+        inner: in_file_reader.take(chunk.bytes.end - chunk.bytes.start),
+        lines: 0,
+    };
+    let mut matches = Vec::new();
+    // Take in account: we are counting on all levels (grabbing search, grabbing stream etc)
+    // from 0 line always. But grep gives results from 1. That's why here is a point of correct:
+    // lnum - 1
+    Searcher::new()
+        .search_reader(
+            matcher,
+            &mut reader_handler,
+            UTF8(|row, line| {
+                matches.push((row - 1, line.to_owned()));
+                Ok(true)
+            }),
+        )
+        .map_err(|e| {
+            SearchError::IoOperation(format!("Could not search in file {path:?}; error: {e}"))
+        })?;
+    if cancel.is_cancelled() {
+        return Ok(None);
+    }
+    Ok(Some(Found {
+        lines: reader_handler.lines,
+        matches,
+    }))
+}
@@ -3,7 +3,14 @@
 
 use crate::{
     map::FiltersStats,
-    search::{error::SearchError, filter, filter::SearchFilter},
+    search::{
+        error::SearchError,
+        filter,
+        filter::SearchFilter,
+        ngrams::{Segment, SharedNgramIndex},
+        query::{Expr, Query},
+        scope::{ScopeMatcher, SearchScope, SourcesMap, TimestampsMap},
+    },
 };
 use regex::Regex;
 use std::{
@@ -40,13 +47,28 @@ pub struct RegularSearchState {
     filters: Vec<SearchFilter>,
     matchers: Vec<Regex>,
     aliases: HashMap<usize, String>,
+    expr: Expr,
+    /// Rows, where first parts of `FOLLOWED BY` conditions of `expr` were matched last time.
+    history: Vec<Option<u64>>,
+    /// Restricts search to some rows; all rows are searched if not defined.
+    scope: Option<ScopeMatcher>,
+    /// Index of the file, which is used to skip parts without matches.
+    index: Option<SharedNgramIndex>,
     results: Results,
 }
 
 pub type RegularSearchHolder = BaseSearcher<RegularSearchState>;
 
 impl RegularSearchHolder {
+    /// Setups search for rows matching at least one of filters.
     pub fn setup(&mut self, filters: Vec<SearchFilter>) -> Result<(), SearchError> {
+        self.setup_query(Query::any_of(filters))
+    }
+
+    /// Setups search for rows matching query expression. Filters of query (terms) are
+    /// reported in matches and statistics in the order they are defined in query.
+    pub fn setup_query(&mut self, query: Query) -> Result<(), SearchError> {
+        let (filters, expr) = query.into_parts();
         let invalid = filters
             .iter()
             .filter(|&f| !f.valid())
@@ -69,8 +91,39 @@ impl RegularSearchHolder {
         self.search_state.filters = filters;
         self.search_state.matchers = matchers;
         self.search_state.aliases = aliases;
+        self.search_state.history = vec![None; expr.proximity_count()];
+        self.search_state.expr = expr;
+        Ok(())
+    }
+
+    /// Setups scope of search. Rows out of scope are skipped as if they are absent, so they
+    /// don't affect `FOLLOWED BY` conditions.
+    pub fn setup_scope(&mut self, scope: &SearchScope) -> Result<(), SearchError> {
+        self.search_state.scope = ScopeMatcher::new(scope)?;
         Ok(())
     }
+
+    /// Updates the map of sources, which is used to restrict search to selected sources.
+    pub fn set_sources_map(&mut self, map: SourcesMap) {
+        if let Some(scope) = self.search_state.scope.as_mut() {
+            scope.set_sources_map(map);
+        }
+    }
+
+    /// Updates the map of timestamps, which is used to restrict search to a time window.
+    /// Fails if the search is restricted to a time window, but rows have no timestamps.
+    pub fn set_timestamps_map(&mut self, timestamps: TimestampsMap) -> Result<(), SearchError> {
+        match self.search_state.scope.as_mut() {
+            Some(scope) => scope.set_timestamps_map(timestamps),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the n-gram index of the file. With the index only parts of the file, where
+    /// matches can be found, are searched.
+    pub fn set_ngram_index(&mut self, index: Option<SharedNgramIndex>) {
+        self.search_state.index = index;
+    }
 }
 
 impl SearchState for RegularSearchState {
@@ -81,28 +134,59 @@ impl SearchState for RegularSearchState {
             filters: vec![],
             matchers: vec![],
             aliases: HashMap::new(),
+            expr: Expr::Or(vec![]),
+            history: vec![],
+            scope: None,
+            index: None,
             results: Results::new(),
         }
     }
     fn get_terms(&self) -> Vec<String> {
-        self.filters.iter().map(filter::as_regex).collect()
+        let mut terms: Vec<String> = self.filters.iter().map(filter::as_row_regex).collect();
+        if !terms.is_empty() && self.expr.requires_every_row() {
+            // Matches any row, so expression is checked for rows without matched filters too
+            terms.push(String::from("^"));
+        }
+        terms
+    }
+    fn get_segments(&self, row: u64, bytes: Range<u64>) -> Option<Vec<Segment>> {
+        if self.expr.requires_every_row() {
+            return None;
+        }
+        // Search isn't blocked while the index is being updated; the whole range is
+        // searched instead.
+        let index = self.index.as_ref()?.try_read().ok()?;
+        index.segments(&self.filters, row, bytes)
     }
 }
 
 fn collect(row: u64, line: &str, state: &mut RegularSearchState) {
-    let mut line_indexes = stypes::FilterMatch::new(row, vec![]);
-    let mut matched_rows = vec![];
-    for (index, re) in state.matchers.iter().enumerate() {
-        if re.is_match(line) {
-            matched_rows.push(index as u8);
-            line_indexes.filters.push(index as u8);
-            if let Some(alias) = state.aliases.get(&index)
-                && let Some(stats) = state.results.stats.as_mut()
-            {
-                stats.increment(alias, None);
-            }
+    if let Some(scope) = state.scope.as_ref()
+        && !scope.contains(row)
+    {
+        return;
+    }
+    let hits: Vec<bool> = state
+        .filters
+        .iter()
+        .zip(state.matchers.iter())
+        .map(|(filter, re)| filter.is_match(re, line))
+        .collect();
+    // Hits of filters are counted regardless of whether the whole expression matches.
+    for (index, _) in hits.iter().enumerate().filter(|(_, hit)| **hit) {
+        if let Some(alias) = state.aliases.get(&index)
+            && let Some(stats) = state.results.stats.as_mut()
+        {
+            stats.increment(alias, None);
         }
     }
+    if !state.expr.eval(&hits, row, &mut state.history) {
+        return;
+    }
+    let mut line_indexes = stypes::FilterMatch::new(row, vec![]);
+    for (index, _) in hits.iter().enumerate().filter(|(_, hit)| **hit) {
+        line_indexes.filters.push(index as u8);
+    }
     if let Some(indexes) = state.results.indexes.as_mut() {
         indexes.push(line_indexes);
     }
@@ -1,5 +1,6 @@
 use crate::search::{
-    filter::SearchFilter,
+    filter::{self, ColumnsScope, SearchFilter},
+    query::Query,
     searchers::{BaseSearcher, regular::RegularSearchState},
 };
 use std::io::{Error, Write};
@@ -74,3 +75,242 @@ fn test_ripgrep_case_sensitivity() -> Result<(), std::io::Error> {
     assert_eq!(3, matches[0].index);
     Ok(())
 }
+
+// create tmp file with content, apply search with query
+fn queried(
+    content: &str,
+    query: &str,
+) -> Result<(Vec<stypes::FilterMatch>, crate::map::FiltersStats), std::io::Error> {
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    let input_file = tmp_file.as_file_mut();
+    input_file.write_all(content.as_bytes())?;
+    let file_size = input_file.metadata()?.len();
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    let query = Query::parse(query, Some("\u{0004}"))
+        .map_err(|e| Error::other(format!("Fail to parse query: {e}")))?;
+    searcher
+        .setup_query(query)
+        .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+    let (_range, indexes, stats) =
+        regular::search(&mut searcher, 0, file_size, CancellationToken::new())
+            .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+    Ok((indexes, stats))
+}
+
+const ECU_LOGS: &[&str] = &[
+    "ECU1 ERROR heartbeat lost",
+    "ECU1 ERROR engine stopped",
+    "ECU2 ERROR engine stopped",
+    "ECU1 INFO request sent",
+    "ECU1 INFO idle",
+    "ECU1 WARN response timeout",
+    "ECU2 INFO request sent",
+    "ECU2 INFO idle",
+    "ECU2 INFO idle",
+    "ECU2 WARN response timeout",
+];
+
+#[test]
+fn test_query_boolean_operators() -> Result<(), std::io::Error> {
+    let (matches, stats) = queried(&ECU_LOGS.join("\n"), "ERROR AND ECU1 AND NOT heartbeat")?;
+    assert_eq!(1, matches.len());
+    assert_eq!(1, matches[0].index);
+    assert_eq!(vec![0, 1], matches[0].filters);
+    // Hits of each filter are counted, even on rows not matching the whole query
+    assert_eq!(Some(&3), stats.stats.get("ERROR:000"));
+    assert_eq!(Some(&5), stats.stats.get("ECU1:000"));
+    assert_eq!(Some(&1), stats.stats.get("heartbeat:000"));
+
+    let (matches, _) = queried(
+        &ECU_LOGS.join("\n"),
+        r#"("error"i OR /WARN|idle/) AND NOT (ECU1 OR "engine"w)"#,
+    )?;
+    assert_eq!(
+        vec![7, 8, 9],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_negation_only() -> Result<(), std::io::Error> {
+    let (matches, _) = queried(&ECU_LOGS.join("\n"), "not ECU2")?;
+    assert_eq!(
+        vec![0, 1, 3, 4, 5],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    assert!(matches.iter().all(|m| m.filters.is_empty()));
+    Ok(())
+}
+
+#[test]
+fn test_query_proximity() -> Result<(), std::io::Error> {
+    let (matches, _) = queried(
+        &ECU_LOGS.join("\n"),
+        r#""request sent" FOLLOWED BY "response timeout" WITHIN 2"#,
+    )?;
+    assert_eq!(1, matches.len());
+    assert_eq!(5, matches[0].index);
+    let (matches, _) = queried(
+        &ECU_LOGS.join("\n"),
+        r#""request sent" FOLLOWED BY "response timeout" WITHIN 3"#,
+    )?;
+    assert_eq!(
+        vec![5, 9],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_errors() {
+    for query in [
+        "",
+        "ERROR AND",
+        "(ERROR OR WARN",
+        "ERROR WARN",
+        "\"ERROR",
+        "/ERROR/x",
+        "A FOLLOWED BY B",
+        "A FOLLOWED BY B WITHIN many",
+        "/[/",
+    ] {
+        assert!(Query::parse(query, None).is_err(), "query: {query}");
+    }
+    assert!(Query::parse("#1:DIAG", None).is_err());
+    assert!(Query::parse("#1:(DIAG)", Some("\u{0004}")).is_err());
+}
+
+#[test]
+fn test_query_literal_hash() {
+    for query in ["#1,x:DIAG", "#define", "(#1) OR x:y", "#:DIAG"] {
+        let parsed = Query::parse(query, Some("\u{0004}")).expect("query is parsed");
+        assert!(
+            parsed.filters().iter().all(|f| f.get_columns().is_none()),
+            "query: {query}"
+        );
+    }
+    let parsed = Query::parse("#1,x:DIAG", None).expect("query is parsed");
+    assert_eq!("#1,x:DIAG", parsed.filters()[0].value);
+    let parsed = Query::parse("#0,2:ECU1", Some("\u{0004}")).expect("query is parsed");
+    assert!(parsed.filters()[0].get_columns().is_some());
+}
+
+#[test]
+fn test_anchors() {
+    assert!(filter::has_anchors("^DIAG"));
+    assert!(filter::has_anchors("DIAG$"));
+    assert!(filter::has_anchors("\\ADIAG\\z"));
+    assert!(!filter::has_anchors("\\^DIAG\\$"));
+    assert!(!filter::has_anchors("[^a$]b"));
+    assert!(!filter::has_anchors("\\\\A"));
+    assert!(filter::has_anchors("\\\\^A"));
+}
+
+#[test]
+fn test_columns_scoped_filters() -> Result<(), std::io::Error> {
+    let rows = [
+        ["ECU1", "DIAG", "timeout on ECU2"],
+        ["ECU2", "DIAG", "no timeout"],
+        ["ECU2", "APP", "ECU1 timeout"],
+        ["ECU1", "APP", "done"],
+    ]
+    .iter()
+    .map(|columns| columns.join("\u{0004}"))
+    .collect::<Vec<String>>();
+    let scope = |columns: Vec<usize>| Some(ColumnsScope::new("\u{0004}", columns));
+    let matches = filtered(
+        &rows.join("\n"),
+        vec![SearchFilter::plain("ECU1").columns(scope(vec![0]))],
+    )?;
+    assert_eq!(
+        vec![0, 3],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    let matches = filtered(
+        &rows.join("\n"),
+        vec![
+            SearchFilter::plain("^DIAG$")
+                .regex(true)
+                .columns(scope(vec![1])),
+            SearchFilter::plain("ECU2").columns(scope(vec![0, 2])),
+        ],
+    )?;
+    assert_eq!(
+        vec![0, 1, 2],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    assert_eq!(vec![0, 1], matches[0].filters);
+    assert_eq!(vec![1], matches[2].filters);
+    // Missing columns never match
+    let matches = filtered(
+        &rows.join("\n"),
+        vec![SearchFilter::plain("ECU").columns(scope(vec![5]))],
+    )?;
+    assert!(matches.is_empty());
+    assert!(!SearchFilter::plain("ECU").columns(scope(vec![])).valid());
+    // Anchored regex matches single column only
+    let matches = filtered(
+        &rows.join("\n"),
+        vec![
+            SearchFilter::plain("^DIAG$")
+                .regex(true)
+                .columns(scope(vec![1])),
+        ],
+    )?;
+    assert_eq!(
+        vec![0, 1],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    let (matches, _) = queried(
+        &rows.join("\n"),
+        "#1:/^DIAG$/ AND #2:timeout AND NOT #0:ECU2",
+    )?;
+    assert_eq!(
+        vec![0],
+        matches.iter().map(|m| m.index).collect::<Vec<u64>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_chunked_search() -> Result<(), std::io::Error> {
+    let content = ECU_LOGS.repeat(20).join("\n");
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    tmp_file.as_file_mut().write_all(content.as_bytes())?;
+    // Search is done in two steps to check incremental searching
+    let half = content[..content.len() / 2]
+        .rfind('\n')
+        .expect("content has line breaks") as u64;
+    let rows = content[..half as usize].matches('\n').count() as u64 + 1;
+    let search = |chunk_bytes: u64| -> Result<Vec<(u64, Vec<u8>)>, std::io::Error> {
+        let mut searcher: BaseSearcher<RegularSearchState> =
+            BaseSearcher::new(tmp_file.path(), 0, 0);
+        searcher.chunk_bytes = chunk_bytes;
+        let query = Query::parse(
+            r#"ERROR OR ("request sent" FOLLOWED BY "response timeout" WITHIN 2)"#,
+            None,
+        )
+        .map_err(|e| Error::other(format!("Fail to parse query: {e}")))?;
+        searcher
+            .setup_query(query)
+            .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+        let mut matches = Vec::new();
+        for (rows, bytes) in [
+            (rows, half),
+            (ECU_LOGS.len() as u64 * 20, content.len() as u64),
+        ] {
+            let (_range, indexes, _stats) =
+                regular::search(&mut searcher, rows, bytes, CancellationToken::new())
+                    .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+            matches.extend(indexes.into_iter().map(|m| (m.index, m.filters)));
+        }
+        Ok(matches)
+    };
+    let expected = search(u64::MAX)?;
+    assert_eq!(expected.len(), 80);
+    for chunk_bytes in [1, 16, 100, 1000] {
+        assert_eq!(search(chunk_bytes)?, expected);
+    }
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use crate::search::searchers::{
-    self, BaseSearcher,
-    values::{ValueSearchMatch, ValueSearchOutput, ValueSearchState},
+use crate::search::{
+    filter::ColumnsScope,
+    searchers::{
+        self, BaseSearcher,
+        values::{ValueSearchMatch, ValueSearchOutput, ValueSearchState},
+    },
 };
 use std::{
     collections::HashMap,
@@ -95,3 +98,36 @@ fn test_value_search2() -> Result<(), std::io::Error> {
     assert_eq!(control_1, sum_1);
     Ok(())
 }
+
+#[test]
+fn test_value_search_in_columns() -> Result<(), std::io::Error> {
+    let rows = (0..20)
+        .map(|n| format!("{n}\u{0004}CPU={n}\u{0004}payload CPU={}", n * 2))
+        .collect::<Vec<String>>();
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    let input_file = tmp_file.as_file_mut();
+    input_file.write_all(rows.join("\n").as_bytes())?;
+    let file_size = input_file.metadata()?.len();
+    let mut holder: BaseSearcher<ValueSearchState> = ValueSearchHolder::new(tmp_file.path(), 0, 0);
+    holder
+        .setup_in_columns(vec![
+            (
+                String::from("CPU=(\\d+)"),
+                Some(ColumnsScope::new("\u{0004}", vec![2])),
+            ),
+            (String::from("CPU=(\\d+)"), None),
+        ])
+        .expect("set_filters failed");
+    let ValueSearchOutput { values, .. } =
+        searchers::values::search(&mut holder, 0, file_size, CancellationToken::new())
+            .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+    assert_eq!(2, values.len());
+    for ValueSearchMatch { line, value } in values[&0].iter() {
+        assert_eq!(*line as f64 * 2.0, *value);
+    }
+    for ValueSearchMatch { line, value } in values[&1].iter() {
+        assert_eq!(*line as f64, *value);
+    }
+    assert_eq!(20, values[&0].len());
+    Ok(())
+}
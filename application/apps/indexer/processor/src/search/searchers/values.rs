@@ -1,6 +1,9 @@
 //! Search related to extract numeric values form logs for charts.
 
-use crate::search::error::SearchError;
+use crate::search::{
+    error::SearchError,
+    filter::{self, ColumnsScope},
+};
 use regex::Regex;
 use std::{
     collections::HashMap,
@@ -77,6 +80,8 @@ pub struct ValueSearchState {
     pub file_path: PathBuf,
     terms: Vec<String>,
     matchers: Vec<Regex>,
+    /// Columns to search in for each term; the whole row is used for `None`.
+    scopes: Vec<Option<ColumnsScope>>,
     results: SearchResults,
     errors: HashMap<u64, Vec<(u8, String)>>,
 }
@@ -85,9 +90,22 @@ pub type ValueSearchHolder = BaseSearcher<ValueSearchState>;
 
 impl ValueSearchHolder {
     pub fn setup(&mut self, terms: Vec<String>) -> Result<(), SearchError> {
+        self.setup_in_columns(terms.into_iter().map(|term| (term, None)).collect())
+    }
+
+    /// Setups search with terms, which can be restricted to some columns of rows.
+    pub fn setup_in_columns(
+        &mut self,
+        terms: Vec<(String, Option<ColumnsScope>)>,
+    ) -> Result<(), SearchError> {
+        let (terms, scopes): (Vec<String>, Vec<Option<ColumnsScope>>) = terms.into_iter().unzip();
         let invalid = terms
             .iter()
-            .filter(|f| !is_valid(f))
+            .zip(scopes.iter())
+            .filter(|(f, scope)| {
+                !is_valid(f) || scope.as_ref().is_some_and(|s| s.error().is_some())
+            })
+            .map(|(f, _)| f)
             .cloned()
             .collect::<Vec<String>>()
             .join("; ");
@@ -102,6 +120,7 @@ impl ValueSearchHolder {
         }
         self.search_state.terms = terms;
         self.search_state.matchers = matchers;
+        self.search_state.scopes = scopes;
         Ok(())
     }
 }
@@ -113,18 +132,34 @@ impl SearchState for ValueSearchState {
             file_path: PathBuf::from(path),
             terms: vec![],
             matchers: vec![],
+            scopes: vec![],
             results: SearchResults::new(),
             errors: HashMap::new(),
         }
     }
     fn get_terms(&self) -> Vec<String> {
-        self.terms.iter().map(|f| as_regex(f)).collect()
+        self.terms
+            .iter()
+            .zip(self.scopes.iter())
+            .map(|(f, scope)| {
+                if scope.is_some() && filter::has_anchors(f) {
+                    // Any row, columns are checked on collecting
+                    String::from("^")
+                } else {
+                    as_regex(f)
+                }
+            })
+            .collect()
     }
 }
 
 fn collect(row: u64, line: &str, state: &mut ValueSearchState) {
-    for (term_index, re) in state.matchers.iter().enumerate() {
-        if let Some(caps) = re.captures(line)
+    for (term_index, (re, scope)) in state.matchers.iter().zip(state.scopes.iter()).enumerate() {
+        let caps = match scope {
+            Some(scope) => scope.cells(line).find_map(|cell| re.captures(cell)),
+            None => re.captures(line),
+        };
+        if let Some(caps) = caps
             && let Some(value) = caps.get(1)
         {
             let value_str = value.as_str().to_owned();
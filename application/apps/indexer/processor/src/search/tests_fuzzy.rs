@@ -0,0 +1,161 @@
+use crate::search::{
+    extractor::MatchesExtractor,
+    filter::{SearchFilter, as_regex, get_filter_error},
+    fuzzy::{find_approximate, glob_as_regex, glob_literals, is_approximate_match, pieces},
+    highlight::Highlighter,
+    searchers::{BaseSearcher, regular, regular::RegularSearchState},
+};
+use std::io::Write;
+use tokio_util::sync::CancellationToken;
+
+const LOGS: &[&str] = &[
+    "[Info] ECU-1234: request sent",
+    "[Err] ECU_1234: request timed out",
+    "[Info] ECU-5678: request sent",
+    "[Err] ECU-1243: connection lost",
+    "[Info] ecu-1234: idle",
+];
+
+fn search(filter: SearchFilter) -> Vec<u64> {
+    let mut tmp_file = tempfile::NamedTempFile::new().expect("file is created");
+    let content = LOGS.join("\n");
+    tmp_file
+        .write_all(content.as_bytes())
+        .expect("content is written");
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    searcher.setup(vec![filter]).expect("search is setup");
+    let (_range, matches, _stats) = regular::search(
+        &mut searcher,
+        0,
+        content.len() as u64,
+        CancellationToken::new(),
+    )
+    .expect("search is done");
+    matches.into_iter().map(|m| m.index).collect()
+}
+
+#[test]
+fn test_glob() {
+    assert_eq!(glob_as_regex("ECU*.err"), r"ECU.*\.err");
+    assert_eq!(glob_as_regex("id-??[0-9][!a]"), r"id\-..[0-9][^a]");
+    assert_eq!(glob_as_regex("[]a]**"), r"[\]a].*");
+    assert_eq!(glob_as_regex("[abc"), r"\[abc");
+    assert_eq!(glob_as_regex(r"a\*b"), r"a\*b");
+    // Cases are pinned in `wasm-bindings` (`glob_as_processor`) as well
+    assert_eq!(glob_as_regex(r"a\**"), r"a\*.*");
+    assert_eq!(glob_as_regex("?*?**"), r"..*..*");
+    assert_eq!(glob_literals("ECU*.err?x"), vec!["ECU", ".err", "x"]);
+    assert!(glob_literals("*?").is_empty());
+    assert_eq!(
+        search(SearchFilter::plain("ECU-1??4:*sent").glob(true)),
+        vec![0]
+    );
+    assert_eq!(
+        search(SearchFilter::plain(r"\[Err] ECU[-_]12*").glob(true)),
+        vec![1, 3]
+    );
+    assert_eq!(
+        search(
+            SearchFilter::plain("ecu-[!5]*")
+                .glob(true)
+                .ignore_case(true)
+        ),
+        vec![0, 3, 4]
+    );
+}
+
+#[test]
+fn test_approximate() {
+    assert_eq!(pieces("abcdefg", 2), vec!["abc", "de", "fg"]);
+    assert!(pieces("ab", 2).is_empty());
+    assert!(is_approximate_match(
+        "timeout",
+        "request timed out",
+        2,
+        false
+    ));
+    assert!(!is_approximate_match(
+        "timeout",
+        "request timed out",
+        1,
+        false
+    ));
+    assert_eq!(
+        find_approximate(
+            "ECU-1234",
+            "id ECU_1234 and ECU-1243 and ecu-1234",
+            1,
+            false
+        ),
+        vec![3..11, 16..23]
+    );
+    assert_eq!(
+        find_approximate("ECU-1234", "id ECU_1234 and ECU-1243 and ecu-1234", 1, true),
+        vec![3..11, 16..23, 29..37]
+    );
+    assert_eq!(
+        search(SearchFilter::plain("ECU-1234").fuzzy(Some(1))),
+        vec![0, 1, 3]
+    );
+    assert_eq!(
+        search(
+            SearchFilter::plain("ECU-1234")
+                .fuzzy(Some(1))
+                .ignore_case(true)
+        ),
+        vec![0, 1, 3, 4]
+    );
+    assert_eq!(
+        search(SearchFilter::plain("ECU-1234").fuzzy(Some(0))),
+        vec![0]
+    );
+    // Rows preselected by a piece of the filter ("ECU-5678") aren't extracted
+    let mut tmp_file = tempfile::NamedTempFile::new().expect("file is created");
+    tmp_file
+        .write_all(LOGS.join("\n").as_bytes())
+        .expect("content is written");
+    let extracted = MatchesExtractor::new(
+        tmp_file.path().to_path_buf(),
+        vec![SearchFilter::plain("ECU-1234").fuzzy(Some(1))],
+    )
+    .extract_matches()
+    .expect("matches are extracted");
+    assert_eq!(
+        extracted
+            .iter()
+            .map(|value| value.index)
+            .collect::<Vec<u64>>(),
+        vec![0, 1, 3]
+    );
+    let highlighter = Highlighter::new(&[SearchFilter::plain("timeout").fuzzy(Some(2))])
+        .expect("highlighter is created");
+    assert_eq!(
+        highlighter
+            .spans(LOGS[1])
+            .into_iter()
+            .map(|span| (span.start, span.end))
+            .collect::<Vec<(u64, u64)>>(),
+        vec![(24, 33)]
+    );
+}
+
+#[test]
+fn test_modes_validation() {
+    assert!(SearchFilter::plain("a*[b-c]").glob(true).valid());
+    assert!(get_filter_error(&SearchFilter::plain("[z-a]").glob(true)).is_some());
+    assert!(get_filter_error(&SearchFilter::plain("a*").glob(true).regex(true)).is_some());
+    assert!(SearchFilter::plain("abc").fuzzy(Some(2)).valid());
+    assert!(!SearchFilter::plain("ab").fuzzy(Some(2)).valid());
+    assert!(
+        !SearchFilter::plain("abc")
+            .fuzzy(Some(1))
+            .regex(true)
+            .valid()
+    );
+    assert!(!SearchFilter::plain("abc").fuzzy(Some(1)).glob(true).valid());
+    assert!(!SearchFilter::plain("abc").fuzzy(Some(1)).word(true).valid());
+    assert_eq!(
+        as_regex(&SearchFilter::plain("a.bcd").fuzzy(Some(1))),
+        r"(?:a\.b|cd)"
+    );
+}
@@ -0,0 +1,60 @@
+use crate::search::{
+    filter::{ColumnsScope, SearchFilter},
+    highlight::Highlighter,
+};
+
+fn spans(filters: &[SearchFilter], line: &str) -> Vec<(u8, u64, u64)> {
+    Highlighter::new(filters)
+        .expect("highlighter is created")
+        .spans(line)
+        .into_iter()
+        .map(|span| (span.filter, span.start, span.end))
+        .collect()
+}
+
+#[test]
+fn test_spans_of_filters() {
+    let filters = [
+        SearchFilter::plain("warn").ignore_case(true),
+        SearchFilter::plain(r"\d+").regex(true),
+    ];
+    assert_eq!(
+        spans(&filters, "[WARN] retry 2 of 10, warning"),
+        vec![(0, 1, 5), (1, 13, 14), (1, 18, 20), (0, 22, 26)]
+    );
+    assert!(spans(&filters, "[Info] done").is_empty());
+}
+
+#[test]
+fn test_spans_are_in_chars() {
+    let filters = [SearchFilter::plain("Fehler")];
+    assert_eq!(
+        spans(&filters, "Größe: 5µs; Fehler; Fehler"),
+        vec![(0, 12, 18), (0, 20, 26)]
+    );
+}
+
+#[test]
+fn test_spans_of_overlapping_filters() {
+    let filters = [SearchFilter::plain("timeout"), SearchFilter::plain("time")];
+    assert_eq!(spans(&filters, "timeout"), vec![(0, 0, 7), (1, 0, 4)]);
+}
+
+#[test]
+fn test_empty_matches_are_skipped() {
+    let filters = [SearchFilter::plain("x*").regex(true)];
+    assert_eq!(spans(&filters, "abxxc"), vec![(0, 2, 4)]);
+}
+
+#[test]
+fn test_spans_in_columns() {
+    let filters = [SearchFilter::plain("ECU").columns(Some(ColumnsScope::new(";", vec![2])))];
+    assert_eq!(spans(&filters, "ECU;APP;ECU1;ECU"), vec![(0, 8, 11)]);
+    let filters = [SearchFilter::plain("^ECU$")
+        .regex(true)
+        .columns(Some(ColumnsScope::new("\u{0004}", vec![0, 3])))];
+    assert_eq!(
+        spans(&filters, "ECU\u{0004}ü\u{0004}ECU\u{0004}ECU"),
+        vec![(0, 0, 3), (0, 10, 13)]
+    );
+}
@@ -0,0 +1,182 @@
+use crate::search::{
+    filter::SearchFilter,
+    ngrams::{NgramIndex, Segment, required_literals},
+    query::Query,
+    searchers::{BaseSearcher, regular, regular::RegularSearchState},
+};
+use std::{
+    io::Write,
+    sync::{Arc, RwLock},
+};
+use tokio_util::sync::CancellationToken;
+
+fn literals(regex: &str) -> Vec<String> {
+    required_literals(&SearchFilter::plain(regex).regex(true))
+}
+
+fn content(rows: usize) -> String {
+    (0..rows)
+        .map(|n| match n {
+            300 => format!("[Err] row {n}: connection lost\n"),
+            900 => format!("[Warn] row {n}: Connection restored\n"),
+            _ => format!("[Info] row {n}: heartbeat\n"),
+        })
+        .collect()
+}
+
+fn index(path: &std::path::Path, len: u64) -> NgramIndex {
+    let mut index = NgramIndex::new();
+    while index.update(path, len, 1024).expect("index is updated") {}
+    index
+}
+
+fn search(
+    path: &std::path::Path,
+    len: u64,
+    query: Query,
+    index: Option<NgramIndex>,
+) -> Vec<(u64, Vec<u8>)> {
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(path, 0, 0);
+    searcher.setup_query(query).expect("search is setup");
+    searcher.set_ngram_index(index.map(|index| Arc::new(RwLock::new(index))));
+    let (_range, matches, _stats) =
+        regular::search(&mut searcher, 0, len, CancellationToken::new()).expect("search is done");
+    matches.into_iter().map(|m| (m.index, m.filters)).collect()
+}
+
+#[test]
+fn test_required_literals() {
+    assert_eq!(literals("connection lost"), vec!["connection lost"]);
+    assert_eq!(literals(r"\[Err\] row \d+"), vec!["[Err] row "]);
+    assert_eq!(literals("ab?cde*f"), vec!["a", "cd", "f"]);
+    assert_eq!(literals("ab+c{2}d"), vec!["ab", "d"]);
+    assert_eq!(literals("foo[a-z]+bar.baz"), vec!["foo", "bar", "baz"]);
+    assert_eq!(literals(r"[]\]x]abc"), vec!["abc"]);
+    assert_eq!(literals("(abc)def"), vec!["abc", "def"]);
+    assert!(literals("abc|def").is_empty());
+    assert!(literals("(abc)?def").is_empty());
+    assert!(literals("(?i)abc").is_empty());
+    assert!(literals(r"\x41bc").is_empty());
+    assert_eq!(
+        required_literals(&SearchFilter::plain("a|b (c)")),
+        vec!["a|b (c)"]
+    );
+}
+
+#[test]
+fn test_segments() {
+    let mut tmp_file = tempfile::NamedTempFile::new().expect("file is created");
+    let content = content(1000);
+    tmp_file
+        .write_all(content.as_bytes())
+        .expect("content is written");
+    let len = content.len() as u64;
+    let index = index(tmp_file.path(), len);
+    assert_eq!(index.rows(), 1000);
+    assert_eq!(index.bytes(), len);
+    // Each block has 256 rows
+    let segments = index
+        .segments(&[SearchFilter::plain("lost")], 0, 0..len)
+        .expect("index narrows search");
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].row, 256);
+    let segments = index
+        .segments(
+            &[
+                SearchFilter::plain("CONNECTION").ignore_case(true),
+                SearchFilter::plain("Warn"),
+            ],
+            0,
+            0..len,
+        )
+        .expect("index narrows search");
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].row, 256);
+    assert_eq!(segments[1].row, 768);
+    assert!(
+        index
+            .segments(&[SearchFilter::plain("absent")], 0, 0..len)
+            .expect("index narrows search")
+            .is_empty()
+    );
+    // Too short filter cannot be used
+    assert!(
+        index
+            .segments(&[SearchFilter::plain("ow")], 0, 0..len)
+            .is_none()
+    );
+    // Not indexed content is always included
+    let more = 10;
+    assert_eq!(
+        index.segments(&[SearchFilter::plain("absent")], 0, 0..len + more),
+        Some(vec![Segment {
+            row: 1000,
+            bytes: len..len + more,
+        }])
+    );
+}
+
+#[test]
+fn test_search_with_index() {
+    let mut tmp_file = tempfile::NamedTempFile::new().expect("file is created");
+    let content = content(1000);
+    tmp_file
+        .write_all(content.as_bytes())
+        .expect("content is written");
+    let len = content.len() as u64;
+    for query in [
+        Query::any_of(vec![SearchFilter::plain("connection").ignore_case(true)]),
+        Query::any_of(vec![
+            SearchFilter::plain(r"row \d+: connection").regex(true),
+            SearchFilter::plain("Warn"),
+        ]),
+        Query::any_of(vec![SearchFilter::plain("absent")]),
+        Query::parse("connection AND NOT lost", None).expect("query is parsed"),
+    ] {
+        let expected = search(tmp_file.path(), len, query.clone(), None);
+        let matches = search(
+            tmp_file.path(),
+            len,
+            query,
+            Some(index(tmp_file.path(), len)),
+        );
+        assert_eq!(matches, expected);
+    }
+}
+
+#[test]
+fn test_persistence() {
+    let mut tmp_file = tempfile::NamedTempFile::new().expect("file is created");
+    let content = content(1000);
+    tmp_file
+        .write_all(content.as_bytes())
+        .expect("content is written");
+    let len = content.len() as u64;
+    let dir = tempfile::tempdir().expect("directory is created");
+    let path = dir.path().join("index");
+    assert!(
+        NgramIndex::load(tmp_file.path(), &path)
+            .expect("index is loaded")
+            .is_none()
+    );
+    let index = index(tmp_file.path(), len);
+    index.save(tmp_file.path(), &path).expect("index is saved");
+    let loaded = NgramIndex::load(tmp_file.path(), &path)
+        .expect("index is loaded")
+        .expect("index exists");
+    assert_eq!(loaded.bytes(), len);
+    assert_eq!(
+        loaded.segments(&[SearchFilter::plain("lost")], 0, 0..len),
+        index.segments(&[SearchFilter::plain("lost")], 0, 0..len)
+    );
+    // File is rewritten
+    tmp_file
+        .as_file_mut()
+        .set_len(0)
+        .expect("file is truncated");
+    assert!(
+        NgramIndex::load(tmp_file.path(), &path)
+            .expect("index is loaded")
+            .is_none()
+    );
+}
@@ -0,0 +1,167 @@
+use crate::search::scope::{ScopeMatcher, SearchScope, SourcesMap, TimeWindow, TimestampsMap};
+use std::sync::Arc;
+
+// 2024-01-01 10:00:00 UTC
+const TEN_OCLOCK: i64 = 1_704_103_200_000;
+
+fn window(from: Option<i64>, to: Option<i64>) -> TimeWindow {
+    TimeWindow { from, to }
+}
+
+fn matcher(scope: SearchScope) -> ScopeMatcher {
+    ScopeMatcher::new(&scope)
+        .expect("scope matcher is created")
+        .expect("scope is restricted")
+}
+
+/// Rows 1..=4: 09:59:59, 10:00:00, (no timestamp), 10:01:00; rows 10..=11 (second chunk):
+/// 10:01:01, 10:00:30.
+fn timestamps() -> TimestampsMap {
+    let mut map = TimestampsMap::default();
+    map.append(vec![
+        (1, TEN_OCLOCK - 1_000),
+        (2, TEN_OCLOCK),
+        (4, TEN_OCLOCK + 60_000),
+    ]);
+    map.append(Vec::new());
+    map.append(vec![(10, TEN_OCLOCK + 61_000), (11, TEN_OCLOCK + 30_000)]);
+    map
+}
+
+#[test]
+fn test_unrestricted_scope() {
+    assert!(
+        ScopeMatcher::new(&SearchScope::default())
+            .expect("scope matcher is created")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_invalid_time_window() {
+    assert!(
+        ScopeMatcher::new(&SearchScope {
+            sources: Vec::new(),
+            time: Some(window(Some(TEN_OCLOCK), Some(TEN_OCLOCK - 1))),
+        })
+        .is_err()
+    );
+}
+
+#[test]
+fn test_timestamps_map() {
+    let map = timestamps();
+    assert_eq!(map.get(0), None);
+    assert_eq!(map.get(1), Some(TEN_OCLOCK - 1_000));
+    assert_eq!(map.get(2), Some(TEN_OCLOCK));
+    // Rows without a timestamp inherit the timestamp of the previous row
+    assert_eq!(map.get(3), Some(TEN_OCLOCK));
+    assert_eq!(map.get(4), Some(TEN_OCLOCK + 60_000));
+    assert_eq!(map.get(9), Some(TEN_OCLOCK + 60_000));
+    assert_eq!(map.get(10), Some(TEN_OCLOCK + 61_000));
+    assert_eq!(map.get(11), Some(TEN_OCLOCK + 30_000));
+    assert_eq!(map.get(100), Some(TEN_OCLOCK + 30_000));
+    assert_eq!(TimestampsMap::default().get(0), None);
+}
+
+#[test]
+fn test_timestamps_changes() {
+    let mut map = TimestampsMap::default();
+    assert!(map.is_empty());
+    map.append(vec![(0, TEN_OCLOCK), (1, TEN_OCLOCK), (3, TEN_OCLOCK + 1)]);
+    // Repeated timestamps aren't stored, also over chunks
+    map.append(vec![(4, TEN_OCLOCK + 1)]);
+    map.append(vec![(5, TEN_OCLOCK + 1), (6, TEN_OCLOCK)]);
+    assert!(!map.is_empty());
+    assert_eq!(map.get(1), Some(TEN_OCLOCK));
+    assert_eq!(map.get(2), Some(TEN_OCLOCK));
+    assert_eq!(map.get(4), Some(TEN_OCLOCK + 1));
+    assert_eq!(map.get(5), Some(TEN_OCLOCK + 1));
+    assert_eq!(map.get(6), Some(TEN_OCLOCK));
+}
+
+#[test]
+fn test_time_window_without_timestamps() {
+    let mut windowed = matcher(SearchScope {
+        sources: Vec::new(),
+        time: Some(window(Some(TEN_OCLOCK), None)),
+    });
+    assert!(
+        windowed
+            .set_timestamps_map(TimestampsMap::default())
+            .is_err()
+    );
+    // Without a time window timestamps aren't needed
+    let mut sourced = matcher(SearchScope {
+        sources: vec![1],
+        time: None,
+    });
+    assert!(sourced.set_timestamps_map(TimestampsMap::default()).is_ok());
+}
+
+#[test]
+fn test_sources() {
+    let mut matcher = matcher(SearchScope {
+        sources: vec![1],
+        time: None,
+    });
+    let map: SourcesMap = Arc::new(vec![(0..=9, 0), (10..=19, 1), (20..=29, 0)]);
+    matcher.set_sources_map(map);
+    assert!(!matcher.contains(0));
+    assert!(!matcher.contains(9));
+    assert!(matcher.contains(10));
+    assert!(matcher.contains(19));
+    assert!(!matcher.contains(20));
+    // Rows, which aren't in the map yet, are out of scope
+    assert!(!matcher.contains(30));
+}
+
+#[test]
+fn test_time_window() {
+    let mut matcher = matcher(SearchScope {
+        sources: Vec::new(),
+        time: Some(window(Some(TEN_OCLOCK), Some(TEN_OCLOCK + 60_000))),
+    });
+    matcher
+        .set_timestamps_map(timestamps())
+        .expect("timestamps are set");
+    // No timestamp is known before the first one
+    assert!(!matcher.contains(0));
+    assert!(!matcher.contains(1));
+    assert!(matcher.contains(2));
+    assert!(matcher.contains(3));
+    assert!(matcher.contains(4));
+    assert!(!matcher.contains(10));
+    assert!(matcher.contains(11));
+}
+
+#[test]
+fn test_open_time_window() {
+    let mut matcher = matcher(SearchScope {
+        sources: Vec::new(),
+        time: Some(window(Some(TEN_OCLOCK + 60_000), None)),
+    });
+    matcher
+        .set_timestamps_map(timestamps())
+        .expect("timestamps are set");
+    assert!(!matcher.contains(2));
+    assert!(matcher.contains(4));
+    assert!(matcher.contains(10));
+    assert!(!matcher.contains(11));
+}
+
+#[test]
+fn test_sources_and_time_window() {
+    let mut matcher = matcher(SearchScope {
+        sources: vec![1],
+        time: Some(window(Some(TEN_OCLOCK), None)),
+    });
+    matcher.set_sources_map(Arc::new(vec![(0..=2, 0), (3..=4, 1)]));
+    matcher
+        .set_timestamps_map(timestamps())
+        .expect("timestamps are set");
+    assert!(!matcher.contains(1));
+    assert!(!matcher.contains(2));
+    assert!(matcher.contains(3));
+    assert!(matcher.contains(4));
+}
@@ -6,6 +6,7 @@ use crate::grabber::{
 };
 use bufread::BufReader;
 use log::{debug, error};
+use sources::encoding::{count_lines, detect_encoding};
 use std::{
     fs,
     io::{Read, SeekFrom},
@@ -31,7 +32,8 @@ impl TextFileSource {
 }
 
 impl TextFileSource {
-    /// Check if the provided file is a text file containing text.
+    /// Check if the provided file is a text file containing text. Line breaks are
+    /// counted in the detected encoding, so e.g. UTF-16 files are accepted.
     pub fn contains_text(path: &Path) -> Result<bool, GrabError> {
         let chunk_size = 100 * 1024usize;
         let mut f = fs::File::open(path)
@@ -45,7 +47,7 @@ impl TextFileSource {
         if n < chunk_size {
             buffer.resize(n, 0);
         }
-        count += bytecount::count(&buffer, b'\n');
+        count += count_lines(&buffer, &detect_encoding(&buffer));
         Ok(count > 0)
     }
 }
@@ -1,28 +1,41 @@
 //! Module for handling exporting part or full content of files in raw format.
 
-use crate::{operations::OperationResult, state::SessionStateAPI};
+use crate::{
+    handlers::observing::{
+        can_dbc, dlt_v2_filter, doip_parser,
+        file::{journal_input_files, text_file_encoding},
+        json_parser, logcat_parser, multiline_text_parser, protobuf_parser, someip_parser,
+        text_pattern_parser,
+    },
+    operations::OperationResult,
+    state::SessionStateAPI,
+};
 use indexer_base::config::IndexSection;
 use log::debug;
 use parsers::{
     Parser,
+    can::{AscParser, BlfParser, CandumpParser, blf},
     dlt::{DltParser, fmt::FormatOptions},
-    someip::SomeipParser,
+    journal::JournalParser,
     text::StringTokenizer,
 };
 use plugins_host::PluginsParser;
 use processor::{
-    export::{ExportError, export_raw},
+    export::{ExportError, export_raw, export_text_raw},
     producer::MessageProducer,
 };
 use sources::{
     ByteSource,
     binary::{
+        journal::JournalByteSource,
         pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
         raw::BinaryByteSource,
     },
+    encoding::DecodeReader,
 };
 use std::{
-    fs::File,
+    fs::{File, OpenOptions},
+    io::Write,
     path::{Path, PathBuf},
 };
 use tokio_util::sync::CancellationToken;
@@ -35,6 +48,7 @@ pub async fn execute_export(
     ranges: Vec<std::ops::RangeInclusive<u64>>,
 ) -> OperationResult<bool> {
     debug!("RUST: ExportRaw operation is requested");
+    let ranges = state.get_view_ranges(ranges).await?;
     let observed = state.get_executed_holder().await?;
     if !observed.is_file_based_export_possible() {
         return Err(stypes::NativeError {
@@ -84,17 +98,40 @@ async fn assing_source(
     read_to_end: bool,
     cancel: &CancellationToken,
 ) -> Result<Option<usize>, stypes::NativeError> {
-    let reader = File::open(src).map_err(|e| stypes::NativeError {
-        severity: stypes::Severity::ERROR,
-        kind: stypes::NativeErrorKind::Io,
-        message: Some(format!("Fail open file {}: {}", src.to_string_lossy(), e)),
-    })?;
+    let reader = || {
+        File::open(src).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!("Fail open file {}: {}", src.to_string_lossy(), e)),
+        })
+    };
+    if let stypes::ParserType::Can(settings) = parser
+        && matches!(settings.format, stypes::CanTraceFormat::Blf)
+    {
+        write_blf_header(src, dest)?;
+    }
     match file_format {
-        stypes::FileFormat::Binary | stypes::FileFormat::Text => {
+        stypes::FileFormat::Binary => {
             export(
                 dest,
                 parser,
-                BinaryByteSource::new(reader),
+                BinaryByteSource::new(reader()?),
+                None,
+                sections,
+                read_to_end,
+                cancel,
+            )
+            .await
+        }
+        stypes::FileFormat::Text(encoding) => {
+            // Messages are parsed from decoded content, the original lines of the
+            // file are exported.
+            let encoding = text_file_encoding(src, encoding)?;
+            export(
+                dest,
+                parser,
+                BinaryByteSource::new(DecodeReader::new(reader()?, &encoding)),
+                Some((src.as_path(), &encoding)),
                 sections,
                 read_to_end,
                 cancel,
@@ -105,7 +142,8 @@ async fn assing_source(
             export(
                 dest,
                 parser,
-                PcapngByteSource::new(reader)?,
+                PcapngByteSource::new(reader()?)?,
+                None,
                 sections,
                 read_to_end,
                 cancel,
@@ -116,7 +154,20 @@ async fn assing_source(
             export(
                 dest,
                 parser,
-                PcapLegacyByteSource::new(reader)?,
+                PcapLegacyByteSource::new(reader()?)?,
+                None,
+                sections,
+                read_to_end,
+                cancel,
+            )
+            .await
+        }
+        stypes::FileFormat::Journal => {
+            export(
+                dest,
+                parser,
+                JournalByteSource::new(journal_input_files(src)?)?,
+                None,
                 sections,
                 read_to_end,
                 cancel,
@@ -126,14 +177,48 @@ async fn assing_source(
     }
 }
 
+/// Writes the file header of the BLF source, if nothing is exported yet. Exported
+/// objects of all sources follow the header of the first one.
+fn write_blf_header(src: &Path, dest: &Path) -> Result<(), stypes::NativeError> {
+    let io_err = |e: std::io::Error| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!(
+            "Fail to write BLF header of {} into {}: {e}",
+            src.to_string_lossy(),
+            dest.to_string_lossy()
+        )),
+    };
+    if dest.metadata().is_ok_and(|meta| meta.len() > 0) {
+        return Ok(());
+    }
+    let header = blf::read_file_header(&mut File::open(src).map_err(io_err)?).map_err(io_err)?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dest)
+        .and_then(|mut file| file.write_all(&header))
+        .map_err(io_err)
+}
+
+/// Exports the messages of the source. If `text_file` (path and encoding of the text
+/// file, which is the source) is defined, the original lines of the file are exported.
 async fn export<S: ByteSource>(
     dest: &Path,
     parser: &stypes::ParserType,
     source: S,
+    text_file: Option<(&Path, &stypes::TextEncoding)>,
     sections: &Vec<IndexSection>,
     read_to_end: bool,
     cancel: &CancellationToken,
 ) -> Result<Option<usize>, stypes::NativeError> {
+    let runner = Runner {
+        dest,
+        sections,
+        read_to_end,
+        text_file,
+        cancel,
+    };
     match parser {
         stypes::ParserType::Plugin(settings) => {
             let parser = PluginsParser::initialize(
@@ -143,51 +228,126 @@ async fn export<S: ByteSource>(
             )
             .await?;
             let producer = MessageProducer::new(parser, source);
-            export_runner(producer, dest, sections, read_to_end, false, cancel).await
+            runner.run(producer, false).await
         }
         stypes::ParserType::SomeIp(settings) => {
-            let parser = if let Some(files) = settings.fibex_file_paths.as_ref() {
-                SomeipParser::from_fibex_files(files.iter().map(PathBuf::from).collect())
-            } else {
-                SomeipParser::new()
-            };
+            let parser = someip_parser(settings)?;
             let producer = MessageProducer::new(parser, source);
-            export_runner(producer, dest, sections, read_to_end, false, cancel).await
+            runner.run(producer, false).await
         }
         stypes::ParserType::Dlt(settings) => {
             let fmt_options = Some(FormatOptions::from(settings.tz.as_ref()));
-            let parser = DltParser::new(
+            let mut parser = DltParser::new(
                 settings.filter_config.as_ref().map(|f| f.into()),
                 settings.fibex_metadata.as_ref(),
                 fmt_options.as_ref(),
                 None,
                 settings.with_storage_header,
             );
+            if let Some(filter) = settings.v2_filter_config.as_ref() {
+                parser = parser.with_v2_filter(dlt_v2_filter(filter));
+            }
             let producer = MessageProducer::new(parser, source);
-            export_runner(producer, dest, sections, read_to_end, false, cancel).await
+            runner.run(producer, false).await
         }
         stypes::ParserType::Text(()) => {
             let producer = MessageProducer::new(StringTokenizer {}, source);
-            export_runner(producer, dest, sections, read_to_end, true, cancel).await
+            runner.run(producer, true).await
+        }
+        stypes::ParserType::TextPattern(settings) => {
+            let producer = MessageProducer::new(text_pattern_parser(settings)?, source);
+            runner.run(producer, true).await
+        }
+        stypes::ParserType::TextMultiline(settings) => {
+            let producer = MessageProducer::new(multiline_text_parser(settings)?, source);
+            runner.run(producer, true).await
+        }
+        stypes::ParserType::Json(settings) => {
+            let producer = MessageProducer::new(json_parser(settings), source);
+            runner.run(producer, true).await
+        }
+        stypes::ParserType::Can(settings) => {
+            let dbc = can_dbc(settings)?;
+            match settings.format {
+                stypes::CanTraceFormat::Asc => {
+                    let producer = MessageProducer::new(AscParser::new(dbc), source);
+                    runner.run(producer, true).await
+                }
+                stypes::CanTraceFormat::Blf => {
+                    let producer = MessageProducer::new(BlfParser::new(dbc), source);
+                    runner.run(producer, false).await
+                }
+                stypes::CanTraceFormat::Candump => {
+                    let producer = MessageProducer::new(CandumpParser::new(dbc), source);
+                    runner.run(producer, true).await
+                }
+            }
+        }
+        stypes::ParserType::Logcat(settings) => {
+            let lines = matches!(settings.format, stypes::LogcatFormat::Threadtime);
+            let producer = MessageProducer::new(logcat_parser(settings)?, source);
+            runner.run(producer, lines).await
+        }
+        stypes::ParserType::Journal(()) => {
+            // Entries in the export format are terminated by an empty line already.
+            let producer = MessageProducer::new(JournalParser::new(), source);
+            runner.run(producer, false).await
+        }
+        stypes::ParserType::Doip(settings) => {
+            let producer = MessageProducer::new(doip_parser(settings)?, source);
+            runner.run(producer, false).await
+        }
+        stypes::ParserType::Protobuf(settings) => {
+            let producer = MessageProducer::new(protobuf_parser(settings)?, source);
+            runner.run(producer, false).await
         }
     }
 }
 
-async fn export_runner<P, D>(
-    producer: MessageProducer<P, D>,
-    dest: &Path,
-    sections: &Vec<IndexSection>,
+/// Arguments of exporting, which are the same for all parsers.
+struct Runner<'a> {
+    dest: &'a Path,
+    sections: &'a Vec<IndexSection>,
     read_to_end: bool,
-    text_file: bool,
-    cancel: &CancellationToken,
-) -> Result<Option<usize>, stypes::NativeError>
-where
-    P: Parser,
-    D: ByteSource,
-{
-    export_raw(producer, dest, sections, read_to_end, text_file, cancel)
-        .await
-        .map_or_else(
+    text_file: Option<(&'a Path, &'a stypes::TextEncoding)>,
+    cancel: &'a CancellationToken,
+}
+
+impl Runner<'_> {
+    /// Exports messages of the producer. `lines` should be `true` for messages of text
+    /// formats, which are written line by line.
+    async fn run<P, D>(
+        &self,
+        producer: MessageProducer<P, D>,
+        lines: bool,
+    ) -> Result<Option<usize>, stypes::NativeError>
+    where
+        P: Parser,
+        D: ByteSource,
+    {
+        let exported = if let Some((src, encoding)) = self.text_file {
+            export_text_raw(
+                producer,
+                src,
+                encoding,
+                self.dest,
+                self.sections,
+                self.read_to_end,
+                self.cancel,
+            )
+            .await
+        } else {
+            export_raw(
+                producer,
+                self.dest,
+                self.sections,
+                self.read_to_end,
+                lines,
+                self.cancel,
+            )
+            .await
+        };
+        exported.map_or_else(
             |err| match err {
                 ExportError::Cancelled => Ok(None),
                 _ => Err(stypes::NativeError {
@@ -198,4 +358,5 @@ where
             },
             |read| Ok(Some(read)),
         )
+    }
 }
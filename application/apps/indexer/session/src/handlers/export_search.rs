@@ -0,0 +1,66 @@
+//! Module for handling exporting of search results along with rows around matches (context).
+
+use crate::{
+    operations::OperationResult,
+    state::{SearchExportOptions, SessionStateAPI},
+};
+use log::debug;
+use processor::search::{
+    filter::SearchFilter,
+    searchers::{self, regular::RegularSearchHolder},
+};
+use std::path::PathBuf;
+use tokio::task;
+use tokio_util::sync::CancellationToken;
+
+/// Exports matches of search with context into a text file.
+///
+/// Matches of the main search (or of the named search `search`) are exported, if `filters` are
+/// empty. Otherwise, the session file is searched with `filters` without changing any search
+/// of session.
+pub async fn execute_export(
+    cancel: &CancellationToken,
+    state: SessionStateAPI,
+    out_path: PathBuf,
+    search: Option<String>,
+    filters: Vec<SearchFilter>,
+    options: SearchExportOptions,
+) -> OperationResult<bool> {
+    debug!("RUST: ExportSearch operation is requested");
+    let matches = if filters.is_empty() {
+        None
+    } else {
+        Some(search_matches(cancel, &state, filters).await?)
+    };
+    if cancel.is_cancelled() {
+        return Ok(Some(false));
+    }
+    state
+        .export_search(out_path, search, matches, options, cancel.clone())
+        .await
+        .map(Some)
+}
+
+async fn search_matches(
+    cancel: &CancellationToken,
+    state: &SessionStateAPI,
+    filters: Vec<SearchFilter>,
+) -> Result<Vec<stypes::FilterMatch>, stypes::NativeError> {
+    let (rows, read_bytes) = state.get_stream_len().await?;
+    let filename = state.get_session_file().await?;
+    let cancel = cancel.clone();
+    task::spawn_blocking(move || {
+        let mut holder = RegularSearchHolder::new(&filename, 0, 0);
+        holder.setup(filters)?;
+        searchers::regular::search(&mut holder, rows, read_bytes, cancel)
+            .map(|(_processed, matches, _stats)| matches)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|res| res.map_err(|e| e.to_string()))
+    .map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to search matches to export: {e}")),
+    })
+}
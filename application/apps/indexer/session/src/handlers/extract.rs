@@ -1,15 +1,36 @@
 //! Includes Extractor functions which are used in charts.
 
-use crate::operations::OperationResult;
+use crate::{operations::OperationResult, state::SessionStateAPI};
 
-use processor::search::{extractor::MatchesExtractor, filter::SearchFilter};
+use processor::search::{
+    extractor::MatchesExtractor,
+    filter::SearchFilter,
+    scope::{ScopeMatcher, SearchScope},
+};
 use std::path::PathBuf;
 
-pub fn handle(
+pub async fn handle(
     target_file_path: PathBuf,
     filters: Vec<SearchFilter>,
+    scope: SearchScope,
+    state: SessionStateAPI,
 ) -> OperationResult<Vec<stypes::ExtractedMatchValue>> {
-    let extractor = MatchesExtractor::new(target_file_path, filters);
+    let mut scope = ScopeMatcher::new(&scope).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to setup scope of extracting: {e}")),
+    })?;
+    if let Some(scope) = scope.as_mut() {
+        scope.set_sources_map(state.get_sources_map().await?);
+        scope
+            .set_timestamps_map(state.get_timestamps_map().await?)
+            .map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::OperationSearch,
+                message: Some(format!("Fail to setup scope of extracting: {e}")),
+            })?;
+    }
+    let extractor = MatchesExtractor::new(target_file_path, filters).scope(scope);
     extractor
         .extract_matches()
         .map(Some)
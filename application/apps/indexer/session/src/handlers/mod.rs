@@ -1,7 +1,8 @@
 pub mod export_raw;
+pub mod export_search;
 pub mod extract;
 pub mod observe;
-mod observing;
+pub(crate) mod observing;
 pub mod search;
 pub mod search_values;
 pub mod sleep;
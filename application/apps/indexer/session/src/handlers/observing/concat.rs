@@ -1,10 +1,15 @@
 use crate::{
+    handlers::observing::file::{journal_input_files, text_file_encoding},
     operations::{OperationAPI, OperationResult},
     state::SessionStateAPI,
 };
-use sources::binary::{
-    pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
-    raw::BinaryByteSource,
+use sources::{
+    binary::{
+        journal::JournalByteSource,
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
+    encoding::DecodeReader,
 };
 use std::{fs::File, path::PathBuf};
 
@@ -30,21 +35,23 @@ pub async fn concat_files(
                 uuid,
             )),
         })?;
-        let input_file = File::open(filename).map_err(|e| stypes::NativeError {
-            severity: stypes::Severity::ERROR,
-            kind: stypes::NativeErrorKind::Io,
-            message: Some(format!(
-                "Fail open file {}: {}",
-                filename.to_string_lossy(),
-                e
-            )),
-        })?;
+        let input_file = || {
+            File::open(filename).map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Io,
+                message: Some(format!(
+                    "Fail open file {}: {}",
+                    filename.to_string_lossy(),
+                    e
+                )),
+            })
+        };
         match file_type {
             stypes::FileFormat::Binary => {
                 super::run_source(
                     operation_api.clone(),
                     state.clone(),
-                    BinaryByteSource::new(input_file),
+                    BinaryByteSource::new(input_file()?),
                     source_id,
                     parser,
                     None,
@@ -56,7 +63,7 @@ pub async fn concat_files(
                 super::run_source(
                     operation_api.clone(),
                     state.clone(),
-                    PcapLegacyByteSource::new(input_file)?,
+                    PcapLegacyByteSource::new(input_file()?)?,
                     source_id,
                     parser,
                     None,
@@ -68,7 +75,20 @@ pub async fn concat_files(
                 super::run_source(
                     operation_api.clone(),
                     state.clone(),
-                    PcapngByteSource::new(input_file)?,
+                    PcapngByteSource::new(input_file()?)?,
+                    source_id,
+                    parser,
+                    None,
+                    None,
+                )
+                .await?
+            }
+            stypes::FileFormat::Journal => {
+                // Entries of all files of a journal directory are merged by their time.
+                super::run_source(
+                    operation_api.clone(),
+                    state.clone(),
+                    JournalByteSource::new(journal_input_files(filename)?)?,
                     source_id,
                     parser,
                     None,
@@ -76,11 +96,12 @@ pub async fn concat_files(
                 )
                 .await?
             }
-            stypes::FileFormat::Text => {
+            stypes::FileFormat::Text(encoding) => {
+                let encoding = text_file_encoding(filename, encoding)?;
                 super::run_source(
                     operation_api.clone(),
                     state.clone(),
-                    BinaryByteSource::new(input_file),
+                    BinaryByteSource::new(DecodeReader::new(input_file()?, &encoding)),
                     source_id,
                     parser,
                     None,
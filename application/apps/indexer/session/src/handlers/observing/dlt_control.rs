@@ -0,0 +1,113 @@
+use parsers::dlt::{
+    ControlListener,
+    control::{ControlRequest, ControlResponse},
+    fmt::DLT_ARGUMENT_SENTINAL,
+};
+use sources::sde::{SdeReceiver, SdeSender};
+use tokio::{
+    sync::{
+        mpsc::{UnboundedReceiver, unbounded_channel},
+        oneshot,
+    },
+    time::{Duration, timeout},
+};
+
+/// Time to wait for the response of the ECU to a sent control request.
+const RESPONSE_TIMEOUT_MS: u64 = 2000;
+
+/// Forwards the SDE requests of a DLT session to its source. DLT control requests
+/// are encoded as DLT messages and answered with the decoded response of the ECU.
+///
+/// Returns the receiver which has to be passed to the producer and the listener
+/// which has to be attached to the DLT parser.
+pub(crate) fn forward_sde(rx_sde: SdeReceiver) -> (SdeReceiver, ControlListener) {
+    let (tx_responses, rx_responses) = unbounded_channel();
+    let listener: ControlListener = Box::new(move |service_id, response| {
+        // Receiver is dropped as soon as the session doesn't accept requests anymore.
+        let _ = tx_responses.send((service_id, response));
+    });
+    let (tx_source, rx_source) = unbounded_channel();
+    tokio::spawn(forward(rx_sde, tx_source, rx_responses));
+    (rx_source, listener)
+}
+
+async fn forward(
+    mut rx_sde: SdeReceiver,
+    tx_source: SdeSender,
+    mut rx_responses: UnboundedReceiver<(u8, ControlResponse)>,
+) {
+    while let Some((request, tx_response)) = rx_sde.recv().await {
+        let result = match request {
+            stypes::SdeRequest::DltControl(request) => {
+                send_control(request, &tx_source, &mut rx_responses).await
+            }
+            request => send(request, &tx_source).await,
+        };
+        if tx_response.send(result).is_err() {
+            log::warn!("Fail to send back message from source");
+        }
+    }
+}
+
+async fn send(
+    request: stypes::SdeRequest,
+    tx_source: &SdeSender,
+) -> Result<stypes::SdeResponse, String> {
+    let (tx_response, rx_response) = oneshot::channel();
+    tx_source
+        .send((request, tx_response))
+        .map_err(|_| String::from("Source isn't available anymore"))?;
+    rx_response
+        .await
+        .map_err(|_| String::from("Source didn't respond"))?
+}
+
+async fn send_control(
+    request: stypes::DltControlRequest,
+    tx_source: &SdeSender,
+    rx_responses: &mut UnboundedReceiver<(u8, ControlResponse)>,
+) -> Result<stypes::SdeResponse, String> {
+    let control = control_request(request.command);
+    let service_id = control.service_id();
+    // Drop responses which weren't requested by us.
+    while rx_responses.try_recv().is_ok() {}
+    let mut response = send(
+        stypes::SdeRequest::WriteBytes(control.to_message(&request.ecu_id)),
+        tx_source,
+    )
+    .await?;
+    response.response = timeout(Duration::from_millis(RESPONSE_TIMEOUT_MS), async {
+        while let Some((id, response)) = rx_responses.recv().await {
+            if id == service_id {
+                return Some(response.to_string().replace(DLT_ARGUMENT_SENTINAL, "\n"));
+            }
+        }
+        None
+    })
+    .await
+    .ok()
+    .flatten();
+    Ok(response)
+}
+
+fn control_request(command: stypes::DltControlCommand) -> ControlRequest {
+    match command {
+        stypes::DltControlCommand::SetLogLevel {
+            app_id,
+            context_id,
+            log_level,
+        } => ControlRequest::SetLogLevel {
+            app_id,
+            context_id,
+            log_level,
+        },
+        stypes::DltControlCommand::SetDefaultTraceStatus(status) => {
+            ControlRequest::SetDefaultTraceStatus(status)
+        }
+        stypes::DltControlCommand::GetLogInfo { app_id, context_id } => {
+            ControlRequest::GetLogInfo { app_id, context_id }
+        }
+        stypes::DltControlCommand::StoreConfig => ControlRequest::StoreConfig,
+        stypes::DltControlCommand::ResetToFactoryDefault => ControlRequest::ResetToFactoryDefault,
+    }
+}
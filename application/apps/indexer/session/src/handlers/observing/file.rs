@@ -3,9 +3,13 @@ use crate::{
     state::SessionStateAPI,
     tail,
 };
-use sources::binary::{
-    pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
-    raw::BinaryByteSource,
+use sources::{
+    binary::{
+        journal::{JournalByteSource, journal_files},
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
+    encoding::{DecodeReader, detect_file_encoding},
 };
 use std::{fs::File, path::Path};
 use tokio::{join, select, sync::mpsc::channel};
@@ -21,6 +25,53 @@ pub async fn observe_file(
     let source_id = state.add_source(uuid).await?;
     let (tx_tail, mut rx_tail) = channel(1);
     match file_format {
+        // Plain UTF-8 text files are used as session file as they are, other
+        // text files are decoded and parsed like binary files.
+        stypes::FileFormat::Text(encoding)
+            if matches!(parser, stypes::ParserType::Text(()))
+                && matches!(
+                    text_file_encoding(filename, encoding)?,
+                    stypes::TextEncoding::Utf8
+                ) =>
+        {
+            state.set_session_file(Some(filename.to_path_buf())).await?;
+            // Grab main file content
+            state.update_session(source_id).await?;
+            operation_api.processing();
+            // Confirm: main file content has been read
+            state.file_read().await?;
+            // Switching to tail
+            let cancel = operation_api.cancellation_token();
+            let (result, tracker) = join!(
+                async {
+                    let result = select! {
+                        res = async move {
+                            while let Some(update) = rx_tail.recv().await {
+                                update.map_err(|err| stypes::NativeError {
+                                    severity: stypes::Severity::ERROR,
+                                    kind: stypes::NativeErrorKind::Interrupted,
+                                    message: Some(err.to_string()),
+                                })?;
+                                state.update_session(source_id).await?;
+                            }
+                            Ok(())
+                        } => res,
+                        _ = cancel.cancelled() => Ok(())
+                    };
+                    result
+                },
+                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+            );
+            result
+                .and_then(|_| {
+                    tracker.map_err(|e| stypes::NativeError {
+                        severity: stypes::Severity::ERROR,
+                        kind: stypes::NativeErrorKind::Interrupted,
+                        message: Some(format!("Tailing error: {e}")),
+                    })
+                })
+                .map(|_| None)
+        }
         stypes::FileFormat::Binary => {
             let source = BinaryByteSource::new(input_file(filename)?);
             let (_, listening) = join!(
@@ -37,6 +88,23 @@ pub async fn observe_file(
             );
             listening
         }
+        stypes::FileFormat::Text(encoding) => {
+            let encoding = text_file_encoding(filename, encoding)?;
+            let source = BinaryByteSource::new(DecodeReader::new(input_file(filename)?, &encoding));
+            let (_, listening) = join!(
+                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    None,
+                    Some(rx_tail)
+                )
+            );
+            listening
+        }
         stypes::FileFormat::PcapLegacy => {
             let source = PcapLegacyByteSource::new(input_file(filename)?)?;
             let (_, listening) = join!(
@@ -69,48 +137,41 @@ pub async fn observe_file(
             );
             listening
         }
-        stypes::FileFormat::Text => {
-            state.set_session_file(Some(filename.to_path_buf())).await?;
-            // Grab main file content
-            state.update_session(source_id).await?;
-            operation_api.processing();
-            // Confirm: main file content has been read
-            state.file_read().await?;
-            // Switching to tail
-            let cancel = operation_api.cancellation_token();
-            let (result, tracker) = join!(
-                async {
-                    let result = select! {
-                        res = async move {
-                            while let Some(update) = rx_tail.recv().await {
-                                update.map_err(|err| stypes::NativeError {
-                                    severity: stypes::Severity::ERROR,
-                                    kind: stypes::NativeErrorKind::Interrupted,
-                                    message: Some(err.to_string()),
-                                })?;
-                                state.update_session(source_id).await?;
-                            }
-                            Ok(())
-                        } => res,
-                        _ = cancel.cancelled() => Ok(())
-                    };
-                    result
-                },
-                tail::track(filename, tx_tail, operation_api.cancellation_token()),
-            );
-            result
-                .and_then(|_| {
-                    tracker.map_err(|e| stypes::NativeError {
-                        severity: stypes::Severity::ERROR,
-                        kind: stypes::NativeErrorKind::Interrupted,
-                        message: Some(format!("Tailing error: {e}")),
-                    })
-                })
-                .map(|_| None)
+        stypes::FileFormat::Journal => {
+            // Journal files are preallocated, so their size can't be used for tailing.
+            let source = JournalByteSource::new(journal_input_files(filename)?)?;
+            super::run_source(operation_api, state, source, source_id, parser, None, None).await
         }
     }
 }
 
+/// Returns the defined encoding of a text file or detects it.
+pub(crate) fn text_file_encoding(
+    filename: &Path,
+    encoding: &Option<stypes::TextEncoding>,
+) -> Result<stypes::TextEncoding, stypes::NativeError> {
+    match encoding {
+        Some(encoding) => Ok(encoding.clone()),
+        None => detect_file_encoding(filename).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to detect encoding of file {}: {}",
+                filename.to_string_lossy(),
+                e
+            )),
+        }),
+    }
+}
+
+/// Opens the journal file or all journal files of a journal directory.
+pub(crate) fn journal_input_files(path: &Path) -> Result<Vec<File>, stypes::NativeError> {
+    journal_files(path)?
+        .iter()
+        .map(|filename| input_file(filename))
+        .collect()
+}
+
 fn input_file(filename: &Path) -> Result<File, stypes::NativeError> {
     File::open(filename).map_err(|e| stypes::NativeError {
         severity: stypes::Severity::ERROR,
@@ -8,8 +8,9 @@ use crate::state::SessionStateAPI;
 /// This buffer is linked to a session file via the `SessionStateAPI`.
 /// It accumulates updates and sends them when `flush()` is called.
 ///
-/// It uses two internal stores to manage data:
+/// It uses three internal stores to manage data:
 /// - `buffer` accumulates textual log entries.
+/// - `timestamps` collects timestamps of buffered log entries.
 /// - `attachments` stores associated `Attachment` objects. These are sent
 ///   only after the text buffer is flushed to ensure synchronization.
 pub struct LogsWriter {
@@ -20,6 +21,12 @@ pub struct LogsWriter {
     /// the buffered data is stored as a `String`.
     text_buffer: String,
 
+    /// Count of rows in the text buffer.
+    rows: u64,
+
+    /// Timestamps detected by the parser, paired with rows of the text buffer.
+    timestamps: Vec<(u64, i64)>,
+
     /// Buffer for received attachments. These are queued and sent only after
     /// the buffered log messages have been flushed, preserving the logical
     /// order between messages and attachments.
@@ -36,6 +43,8 @@ impl LogsWriter {
             state,
             id,
             text_buffer: String::new(),
+            rows: 0,
+            timestamps: Vec::new(),
             attachments: Vec::new(),
         }
     }
@@ -52,7 +61,11 @@ impl LogsWriter {
             // iterations).
             let msgs = String::from(&self.text_buffer);
             self.text_buffer.clear();
-            self.state.write_session_file(self.id, msgs).await?;
+            self.rows = 0;
+            let timestamps = std::mem::take(&mut self.timestamps);
+            self.state
+                .write_session_file(self.id, msgs, timestamps)
+                .await?;
         }
         for attachment in self.attachments.drain(..) {
             // TODO: send all attachments with 1 call
@@ -60,21 +73,36 @@ impl LogsWriter {
         }
         Ok(())
     }
+
+    fn write_message<T: LogMessage>(&mut self, msg: &T) {
+        use std::fmt::Write;
+        // Only changes of the time are needed, following rows inherit the timestamp.
+        if let Some(timestamp) = msg.timestamp()
+            && self
+                .timestamps
+                .last()
+                .is_none_or(|(_, last)| *last != timestamp)
+        {
+            self.timestamps.push((self.rows, timestamp));
+        }
+        let start = self.text_buffer.len();
+        // Writing to string never fails.
+        _ = writeln!(&mut self.text_buffer, "{msg}");
+        self.rows += self.text_buffer[start..].matches('\n').count() as u64;
+    }
 }
 
 impl<T: LogMessage> LogRecordsCollector<T> for LogsWriter {
     fn append(&mut self, log_record: parsers::ParseYield<T>) {
-        use std::fmt::Write;
         match log_record {
             parsers::ParseYield::Message(msg) => {
-                // Writing to string never fails.
-                _ = writeln!(&mut self.text_buffer, "{msg}");
+                self.write_message(&msg);
             }
             parsers::ParseYield::Attachment(attachment) => {
                 self.attachments.push(attachment);
             }
             parsers::ParseYield::MessageAndAttachment((msg, attachment)) => {
-                _ = writeln!(&mut self.text_buffer, "{msg}");
+                self.write_message(&msg);
                 self.attachments.push(attachment);
             }
         }
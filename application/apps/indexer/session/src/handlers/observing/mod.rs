@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use crate::{
     handlers::observing::logs_writer::LogsWriter,
@@ -8,9 +8,19 @@ use crate::{
 };
 use parsers::{
     Parser,
-    dlt::{DltParser, fmt::FormatOptions},
-    someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser},
-    text::StringTokenizer,
+    can::{AscParser, BlfParser, CandumpParser, Dbc},
+    dlt::{DltParser, fmt::FormatOptions, v2},
+    doip::{DidNames, DoipParser},
+    journal::JournalParser,
+    json::{JsonConfig, JsonParser},
+    logcat::{LogcatFormat, LogcatParser},
+    protobuf::{Framing, ProtobufParser, Rendering},
+    someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser, e2e::E2EConfig},
+    text::{
+        StringTokenizer,
+        multiline::{ContinuationRule, MultilineTextParser},
+        pattern::{TextPatternConfig, TextPatternParser, TextPatternPreset},
+    },
 };
 use plugins_host::PluginsParser;
 use processor::producer::{MessageProducer, ProduceError, ProduceSummary};
@@ -25,6 +35,7 @@ use tokio::{
 };
 
 pub mod concat;
+mod dlt_control;
 pub mod file;
 mod logs_writer;
 pub mod stream;
@@ -100,12 +111,7 @@ async fn run_source_intern<S: ByteSource>(
             run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
         }
         stypes::ParserType::SomeIp(settings) => {
-            let someip_parser = match &settings.fibex_file_paths {
-                Some(paths) => {
-                    SomeipParser::from_fibex_files(paths.iter().map(PathBuf::from).collect())
-                }
-                None => SomeipParser::new(),
-            };
+            let someip_parser = someip_parser(settings)?;
             let producer = MessageProducer::new(someip_parser, source);
             run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
         }
@@ -113,6 +119,51 @@ async fn run_source_intern<S: ByteSource>(
             let producer = MessageProducer::new(StringTokenizer {}, source);
             run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
         }
+        stypes::ParserType::TextPattern(settings) => {
+            let producer = MessageProducer::new(text_pattern_parser(settings)?, source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::TextMultiline(settings) => {
+            let producer = MessageProducer::new(multiline_text_parser(settings)?, source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::Json(settings) => {
+            let producer = MessageProducer::new(json_parser(settings), source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::Can(settings) => {
+            let dbc = can_dbc(settings)?;
+            match settings.format {
+                stypes::CanTraceFormat::Asc => {
+                    let producer = MessageProducer::new(AscParser::new(dbc), source);
+                    run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+                }
+                stypes::CanTraceFormat::Blf => {
+                    let producer = MessageProducer::new(BlfParser::new(dbc), source);
+                    run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+                }
+                stypes::CanTraceFormat::Candump => {
+                    let producer = MessageProducer::new(CandumpParser::new(dbc), source);
+                    run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+                }
+            }
+        }
+        stypes::ParserType::Logcat(settings) => {
+            let producer = MessageProducer::new(logcat_parser(settings)?, source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::Journal(()) => {
+            let producer = MessageProducer::new(JournalParser::new(), source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::Doip(settings) => {
+            let producer = MessageProducer::new(doip_parser(settings)?, source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
+        stypes::ParserType::Protobuf(settings) => {
+            let producer = MessageProducer::new(protobuf_parser(settings)?, source);
+            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+        }
         stypes::ParserType::Dlt(settings) => {
             let fmt_options = Some(FormatOptions::from(settings.tz.as_ref()));
             let someip_metadata = settings.fibex_file_paths.as_ref().and_then(|paths| {
@@ -124,13 +175,175 @@ async fn run_source_intern<S: ByteSource>(
                 fmt_options.as_ref(),
                 someip_metadata.as_ref(),
                 settings.with_storage_header,
-            );
+            )
+            .with_registry(state.get_dlt_registry().await?);
+            let dlt_parser = match settings.v2_filter_config.as_ref() {
+                Some(filter) => dlt_parser.with_v2_filter(dlt_v2_filter(filter)),
+                None => dlt_parser,
+            };
+            let (dlt_parser, rx_sde) = match rx_sde {
+                Some(rx_sde) => {
+                    let (rx_sde, listener) = dlt_control::forward_sde(rx_sde);
+                    (dlt_parser.with_control_listener(listener), Some(rx_sde))
+                }
+                None => (dlt_parser, None),
+            };
             let producer = MessageProducer::new(dlt_parser, source);
             run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
         }
     }
 }
 
+/// Converts the error of parser's configuration (e.g. an invalid file) into a native error.
+fn configuration_error<E: std::fmt::Display>(err: E) -> stypes::NativeError {
+    stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Configuration,
+        message: Some(err.to_string()),
+    }
+}
+
+/// Creates the filter of DLT protocol version 2 messages for the given settings.
+pub(crate) fn dlt_v2_filter(settings: &stypes::DltV2FilterConfig) -> v2::FilterConfig {
+    v2::FilterConfig::new(settings.tags.clone(), settings.max_privacy_level)
+}
+
+/// Creates the SOME/IP parser for the given settings.
+pub(crate) fn someip_parser(
+    settings: &stypes::SomeIpParserSettings,
+) -> Result<SomeipParser, stypes::NativeError> {
+    let mut parser = match &settings.fibex_file_paths {
+        Some(paths) => SomeipParser::from_fibex_files(paths.iter().map(PathBuf::from).collect()),
+        None => SomeipParser::new(),
+    };
+    if let Some(path) = &settings.e2e_config_path {
+        let config = E2EConfig::from_file(&PathBuf::from(path)).map_err(configuration_error)?;
+        parser = parser.with_e2e_config(config);
+    }
+    Ok(parser)
+}
+
+/// Creates the text pattern parser for the given settings. Explicitly defined
+/// settings take precedence over the ones of the preset.
+pub(crate) fn text_pattern_parser(
+    settings: &stypes::TextPatternParserSettings,
+) -> Result<TextPatternParser, stypes::NativeError> {
+    let mut config = match &settings.preset {
+        Some(preset) => TextPatternConfig::preset(match preset {
+            stypes::TextPatternPreset::Log4j => TextPatternPreset::Log4j,
+            stypes::TextPatternPreset::LogcatThreadtime => TextPatternPreset::LogcatThreadtime,
+            stypes::TextPatternPreset::Nginx => TextPatternPreset::Nginx,
+            stypes::TextPatternPreset::PythonLogging => TextPatternPreset::PythonLogging,
+        }),
+        None => TextPatternConfig::default(),
+    };
+    if let Some(pattern) = &settings.pattern {
+        config.pattern = pattern.clone();
+    }
+    if settings.timestamp_group.is_some() {
+        config.timestamp_group = settings.timestamp_group.clone();
+    }
+    if settings.timestamp_format.is_some() {
+        config.timestamp_format = settings.timestamp_format.clone();
+    }
+    if settings.year.is_some() {
+        config.year = settings.year;
+    }
+    if settings.severity_group.is_some() {
+        config.severity_group = settings.severity_group.clone();
+    }
+    if settings.continuation_pattern.is_some() {
+        config.continuation_pattern = settings.continuation_pattern.clone();
+    }
+    if let Some(join_unmatched) = settings.join_unmatched {
+        config.join_unmatched = join_unmatched;
+    }
+    TextPatternParser::new(config).map_err(configuration_error)
+}
+
+/// Creates the parser folding continuation lines of text for the given settings.
+pub(crate) fn multiline_text_parser(
+    settings: &stypes::TextMultilineParserSettings,
+) -> Result<MultilineTextParser, stypes::NativeError> {
+    let rule = match &settings.rule {
+        stypes::TextContinuationRule::LeadingWhitespace => ContinuationRule::LeadingWhitespace,
+        stypes::TextContinuationRule::NoTimestamp => ContinuationRule::NoTimestamp,
+        stypes::TextContinuationRule::Pattern(pattern) => {
+            ContinuationRule::Pattern(pattern.clone())
+        }
+    };
+    MultilineTextParser::new(rule, settings.max_lines.map(|max| max as usize))
+        .map_err(configuration_error)
+}
+
+/// Creates the JSON Lines parser for the given settings.
+pub(crate) fn json_parser(settings: &stypes::JsonParserSettings) -> JsonParser {
+    JsonParser::new(JsonConfig {
+        fields: settings.fields.clone(),
+        timestamp_field: settings.timestamp_field.clone(),
+        timestamp_format: settings.timestamp_format.clone(),
+        level_field: settings.level_field.clone(),
+    })
+}
+
+/// Reads the DBC files of the given CAN settings.
+pub(crate) fn can_dbc(
+    settings: &stypes::CanParserSettings,
+) -> Result<Option<Arc<Dbc>>, stypes::NativeError> {
+    let Some(paths) = &settings.dbc_file_paths else {
+        return Ok(None);
+    };
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let dbc = Dbc::from_files(&paths).map_err(configuration_error)?;
+    Ok(Some(Arc::new(dbc)))
+}
+
+/// Creates the logcat parser for the given settings.
+pub(crate) fn logcat_parser(
+    settings: &stypes::LogcatParserSettings,
+) -> Result<LogcatParser, stypes::NativeError> {
+    let format = match settings.format {
+        stypes::LogcatFormat::Binary => LogcatFormat::Binary,
+        stypes::LogcatFormat::Threadtime => LogcatFormat::Threadtime,
+    };
+    LogcatParser::new(format, settings.tz.as_deref(), settings.year).map_err(configuration_error)
+}
+
+/// Creates the DoIP parser, reading the DID names of the given settings if any.
+pub(crate) fn doip_parser(
+    settings: &stypes::DoipParserSettings,
+) -> Result<DoipParser, stypes::NativeError> {
+    let Some(paths) = &settings.did_file_paths else {
+        return Ok(DoipParser::new(None));
+    };
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let dids = DidNames::from_files(&paths).map_err(configuration_error)?;
+    Ok(DoipParser::new(Some(dids)))
+}
+
+/// Creates the Protobuf parser of the `.proto` files of the given settings.
+pub(crate) fn protobuf_parser(
+    settings: &stypes::ProtobufParserSettings,
+) -> Result<ProtobufParser, stypes::NativeError> {
+    let framing = match settings.framing {
+        stypes::ProtobufFraming::Varint => Framing::Varint,
+        stypes::ProtobufFraming::Fixed32Be => Framing::Fixed32Be,
+        stypes::ProtobufFraming::Fixed32Le => Framing::Fixed32Le,
+        stypes::ProtobufFraming::Datagram => Framing::Datagram,
+    };
+    let rendering = match settings.rendering {
+        stypes::ProtobufRendering::Columns => Rendering::Columns,
+        stypes::ProtobufRendering::Json => Rendering::Json,
+    };
+    let paths: Vec<PathBuf> = settings
+        .proto_file_paths
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    ProtobufParser::new(&paths, &settings.message_type, framing, rendering)
+        .map_err(configuration_error)
+}
+
 async fn run_producer<P: Parser, S: ByteSource>(
     operation_api: OperationAPI,
     state: SessionStateAPI,
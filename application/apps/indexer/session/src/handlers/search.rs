@@ -10,6 +10,9 @@ use processor::{
     map::FiltersStats,
     search::{
         filter::SearchFilter,
+        highlight::Highlighter,
+        query::Query,
+        scope::SearchScope,
         searchers::{self, regular::RegularSearchHolder},
     },
 };
@@ -37,32 +40,175 @@ struct SearchOutput {
     holder: RegularSearchHolder,
 }
 
+/// Defines where the results of search are delivered.
+enum Target {
+    /// The main search of session, which drives indexes and search map.
+    Main,
+    /// The named search, which runs independently of the main search.
+    Named(String),
+}
+
+impl Target {
+    async fn drop_search(&self, state: &SessionStateAPI) -> Result<(), stypes::NativeError> {
+        match self {
+            Self::Main => state.drop_search().await.map(|_| ()),
+            Self::Named(id) => {
+                if state.drop_named_search(id.to_owned()).await? {
+                    Ok(())
+                } else {
+                    Err(stypes::NativeError {
+                        severity: stypes::Severity::ERROR,
+                        kind: stypes::NativeErrorKind::OperationSearch,
+                        message: Some(format!("Named search \"{id}\" is still in progress")),
+                    })
+                }
+            }
+        }
+    }
+
+    async fn get_holder(
+        &self,
+        operation_api: &OperationAPI,
+        state: &SessionStateAPI,
+    ) -> Result<RegularSearchHolder, stypes::NativeError> {
+        match self {
+            Self::Main => state.get_search_holder(operation_api.id()).await,
+            Self::Named(id) => state.get_named_search_holder(id.to_owned()).await,
+        }
+    }
+
+    async fn set_holder(
+        &self,
+        operation_api: &OperationAPI,
+        state: &SessionStateAPI,
+        holder: Option<RegularSearchHolder>,
+    ) -> Result<(), stypes::NativeError> {
+        match self {
+            Self::Main => state.set_search_holder(holder, operation_api.id()).await,
+            Self::Named(id) => state.set_named_search_holder(id.to_owned(), holder).await,
+        }
+    }
+
+    async fn set_highlighter(
+        &self,
+        state: &SessionStateAPI,
+        highlighter: Highlighter,
+    ) -> Result<(), stypes::NativeError> {
+        match self {
+            Self::Main => state.set_highlighter(None, highlighter).await,
+            Self::Named(id) => {
+                state
+                    .set_highlighter(Some(id.to_owned()), highlighter)
+                    .await
+            }
+        }
+    }
+
+    async fn set_matches(
+        &self,
+        state: &SessionStateAPI,
+        matches: Option<Vec<stypes::FilterMatch>>,
+        stats: Option<FiltersStats>,
+    ) -> Result<(), stypes::NativeError> {
+        match self {
+            Self::Main => state.set_matches(matches, stats).await,
+            Self::Named(id) => state.set_named_matches(id.to_owned(), matches, stats).await,
+        }
+    }
+}
+
 pub async fn execute_search(
     operation_api: &OperationAPI,
     filters: Vec<SearchFilter>,
+    scope: SearchScope,
     state: SessionStateAPI,
 ) -> OperationResult<u64> {
     debug!("RUST: Search operation is requested");
-    state.drop_search().await?;
+    search(
+        operation_api,
+        Query::any_of(filters),
+        scope,
+        Target::Main,
+        state,
+    )
+    .await
+}
+
+pub async fn execute_named_search(
+    operation_api: &OperationAPI,
+    id: String,
+    filters: Vec<SearchFilter>,
+    scope: SearchScope,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Named search \"{id}\" operation is requested");
+    search(
+        operation_api,
+        Query::any_of(filters),
+        scope,
+        Target::Named(id),
+        state,
+    )
+    .await
+}
+
+pub async fn execute_search_query(
+    operation_api: &OperationAPI,
+    query: String,
+    spliter: Option<String>,
+    scope: SearchScope,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Search with query operation is requested");
+    let query = Query::parse(&query, spliter.as_deref()).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to parse search query: {e}")),
+    })?;
+    search(operation_api, query, scope, Target::Main, state).await
+}
+
+async fn search(
+    operation_api: &OperationAPI,
+    query: Query,
+    scope: SearchScope,
+    target: Target,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    target.drop_search(&state).await?;
     let (rows, read_bytes) = state.get_stream_len().await?;
-    let mut holder = state.get_search_holder(operation_api.id()).await?;
-    if let Err(err) = holder
-        .setup(filters.clone())
+    let sources = state.get_sources_map().await?;
+    let timestamps = state.get_timestamps_map().await?;
+    let index = state.get_ngram_index().await?;
+    let mut holder = target.get_holder(operation_api, &state).await?;
+    let no_filters = query.filters().is_empty();
+    let highlighter = Highlighter::new(query.filters());
+    let setup = holder
+        .setup_query(query)
+        .and_then(|_| holder.setup_scope(&scope))
+        .and_then(|_| holder.set_timestamps_map(timestamps))
+        .and(highlighter)
         .map_err(|e| stypes::NativeError {
             severity: stypes::Severity::ERROR,
             kind: stypes::NativeErrorKind::OperationSearch,
             message: Some(format!("Fail to setup search terms: {e}")),
-        })
-    {
-        state
-            .set_search_holder(Some(holder), operation_api.id())
-            .await?;
-        return Err(err);
-    }
-    if filters.is_empty() {
+        });
+    let highlighter = match setup {
+        Ok(highlighter) => highlighter,
+        Err(err) => {
+            target
+                .set_holder(operation_api, &state, Some(holder))
+                .await?;
+            return Err(err);
+        }
+    };
+    target.set_highlighter(&state, highlighter).await?;
+    holder.set_sources_map(sources);
+    holder.set_ngram_index(index);
+    if no_filters {
         debug!("RUST: Search are dropped. Filters are empty");
-        state
-            .set_search_holder(Some(holder), operation_api.id())
+        target
+            .set_holder(operation_api, &state, Some(holder))
             .await?;
         Ok(Some(0))
     } else {
@@ -110,7 +256,7 @@ pub async fn execute_search(
                         }
                         Err(_) => {
                             if !cancel.is_cancelled() {
-                                state.set_matches(None, None).await.map_err(|err| (None, err))?;
+                                target.set_matches(&state, None, None).await.map_err(|err| (None, err))?;
                             }
                         },
                     };
@@ -129,30 +275,28 @@ pub async fn execute_search(
                         holder,
                     } = res;
                     let found = matches.len();
-                    state
-                        .set_search_holder(Some(holder), operation_api.id())
+                    target
+                        .set_holder(operation_api, &state, Some(holder))
                         .await?;
                     // stats - isn't big object, it's small hashmap and clone operation here will not decrease performance.
                     // even this happens just once per search
-                    state.set_matches(Some(matches), Some(stats)).await?;
+                    target
+                        .set_matches(&state, Some(matches), Some(stats))
+                        .await?;
                     Ok(Some(found as u64))
                 }
                 Err((holder, err)) => {
-                    if let Some(holder) = holder {
-                        state
-                            .set_search_holder(Some(*holder), operation_api.id())
-                            .await?;
-                    } else {
-                        state.set_search_holder(None, operation_api.id()).await?;
-                    }
-                    state.drop_search().await?;
+                    target
+                        .set_holder(operation_api, &state, holder.map(|holder| *holder))
+                        .await?;
+                    target.drop_search(&state).await?;
                     Err(err)
                 }
             }
         } else {
             // We should not recreate holder, but just drop into NotInited
-            state.set_search_holder(None, operation_api.id()).await?;
-            state.drop_search().await?;
+            target.set_holder(operation_api, &state, None).await?;
+            target.drop_search(&state).await?;
             Ok(Some(0))
         }
     }
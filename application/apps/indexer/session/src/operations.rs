@@ -1,10 +1,14 @@
 //! Operations definitions and their main functionalities, additionally it includes
 //! Management of listening to incoming sessions then invoking them.
 
-use crate::{handlers, state::SessionStateAPI, tracker::OperationTrackerAPI};
+use crate::{
+    handlers,
+    state::{SearchExportOptions, SessionStateAPI},
+    tracker::OperationTrackerAPI,
+};
 use log::{debug, error, warn};
 use merging::merger::FileMergeOptions;
-use processor::search::filter::SearchFilter;
+use processor::search::{filter::SearchFilter, scope::SearchScope};
 use serde::Serialize;
 use sources::sde::{SdeReceiver, SdeSender};
 use std::{
@@ -76,12 +80,36 @@ impl Operation {
 #[allow(clippy::large_enum_variant)]
 pub enum OperationKind {
     Observe(stypes::ObserveOptions),
+    /// Main search of session.
+    ///
+    /// * `scope` - restricts search to rows of selected sources and/or to a time window.
     Search {
         filters: Vec<SearchFilter>,
+        scope: SearchScope,
+    },
+    /// Search with query expression (see `processor::search::query`).
+    ///
+    /// * `spliter` - separator of columns in session file; required to restrict terms
+    ///   to columns.
+    /// * `scope` - restricts search to rows of selected sources and/or to a time window.
+    SearchQuery {
+        query: String,
+        spliter: Option<String>,
+        scope: SearchScope,
     },
     SearchValues {
         filters: Vec<String>,
     },
+    /// Search which runs alongside the main search and keeps own results.
+    ///
+    /// * `id` - identifier of named search; applying filters to an existing named search
+    ///   replaces its results.
+    /// * `scope` - restricts search to rows of selected sources and/or to a time window.
+    NamedSearch {
+        id: String,
+        filters: Vec<SearchFilter>,
+        scope: SearchScope,
+    },
     /// Export operation containing parameters for exporting data.
     ///
     /// # Fields
@@ -112,12 +140,29 @@ pub enum OperationKind {
         out_path: PathBuf,
         ranges: Vec<std::ops::RangeInclusive<u64>>,
     },
+    /// Exports matches of search along with rows around them (context).
+    ///
+    /// * `search` - identifier of named search; `None` for the main search.
+    /// * `filters` - filters to search matches with; results of search (`search`) are
+    ///   exported if empty.
+    ExportSearch {
+        out_path: PathBuf,
+        search: Option<String>,
+        filters: Vec<SearchFilter>,
+        options: SearchExportOptions,
+    },
     Extract {
         filters: Vec<SearchFilter>,
+        /// Restricts extracting to rows of selected sources and/or to a time window.
+        scope: SearchScope,
     },
     Map {
         dataset_len: u16,
         range: Option<(u64, u64)>,
+        /// Identifier of named search; `None` for the main search.
+        search: Option<String>,
+        /// Restricts map to matches on rows of selected sources and/or within a time window.
+        scope: SearchScope,
     },
     Values {
         dataset_len: u16,
@@ -129,7 +174,7 @@ pub enum OperationKind {
         append: bool,
         source_id: String,
     },
-    GetNearestPosition(u64),
+    GetNearestPosition(u64, Option<String>),
     Cancel {
         target: Uuid,
     },
@@ -145,16 +190,19 @@ impl std::fmt::Display for OperationKind {
             match self {
                 OperationKind::Observe(_) => "Observing",
                 OperationKind::Search { .. } => "Searching",
+                OperationKind::SearchQuery { .. } => "Searching with query",
                 OperationKind::SearchValues { .. } => "Searching values",
+                OperationKind::NamedSearch { .. } => "Named searching",
                 OperationKind::Export { .. } => "Exporting",
                 OperationKind::ExportRaw { .. } => "Exporting as Raw",
+                OperationKind::ExportSearch { .. } => "Exporting search results",
                 OperationKind::Extract { .. } => "Extracting",
                 OperationKind::Map { .. } => "Mapping",
                 OperationKind::Values { .. } => "Values",
                 OperationKind::Merge { .. } => "Merging",
                 OperationKind::Sleep(_, _) => "Sleeping",
                 OperationKind::Cancel { .. } => "Canceling",
-                OperationKind::GetNearestPosition(_) => "Getting nearest position",
+                OperationKind::GetNearestPosition(_, _) => "Getting nearest position",
                 OperationKind::End => "End",
             }
         )
@@ -317,9 +365,31 @@ impl OperationAPI {
                     )
                     .await;
                 }
-                OperationKind::Search { filters } => {
+                OperationKind::Search { filters, scope } => {
                     api.finish(
-                        handlers::search::execute_search(&api, filters, state)
+                        handlers::search::execute_search(&api, filters, scope, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::SearchQuery {
+                    query,
+                    spliter,
+                    scope,
+                } => {
+                    api.finish(
+                        handlers::search::execute_search_query(&api, query, spliter, scope, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::NamedSearch { id, filters, scope } => {
+                    api.finish(
+                        handlers::search::execute_named_search(&api, id, filters, scope, state)
                             .await
                             .map(|v| v.map(stypes::ResultU64)),
                         operation_str,
@@ -371,7 +441,28 @@ impl OperationAPI {
                     )
                     .await;
                 }
-                OperationKind::Extract { filters } => {
+                OperationKind::ExportSearch {
+                    out_path,
+                    search,
+                    filters,
+                    options,
+                } => {
+                    api.finish(
+                        handlers::export_search::execute_export(
+                            &api.cancellation_token(),
+                            state,
+                            out_path,
+                            search,
+                            filters,
+                            options,
+                        )
+                        .await
+                        .map(|v| v.map(stypes::ResultBool)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::Extract { filters, scope } => {
                     let session_file = if let Some(session_file) = session_file {
                         session_file
                     } else {
@@ -379,27 +470,34 @@ impl OperationAPI {
                         return;
                     };
                     api.finish(
-                        handlers::extract::handle(session_file, filters)
+                        handlers::extract::handle(session_file, filters, scope, state)
+                            .await
                             .map(|v| v.map(stypes::ResultExtractedMatchValues)),
                         operation_str,
                     )
                     .await;
                 }
-                OperationKind::Map { dataset_len, range } => {
-                    match state.get_scaled_map(dataset_len, range).await {
-                        Ok(map) => {
-                            api.finish(
-                                Ok(Some(stypes::ResultScaledDistribution(map))),
-                                operation_str,
-                            )
+                OperationKind::Map {
+                    dataset_len,
+                    range,
+                    search,
+                    scope,
+                } => match state
+                    .get_scaled_map(dataset_len, range, search, scope)
+                    .await
+                {
+                    Ok(map) => {
+                        api.finish(
+                            Ok(Some(stypes::ResultScaledDistribution(map))),
+                            operation_str,
+                        )
+                        .await;
+                    }
+                    Err(err) => {
+                        api.finish::<OperationResult<()>>(Err(err), operation_str)
                             .await;
-                        }
-                        Err(err) => {
-                            api.finish::<OperationResult<()>>(Err(err), operation_str)
-                                .await;
-                        }
                     }
-                }
+                },
                 OperationKind::Values { dataset_len, range } => {
                     match state.get_search_values(range, dataset_len).await {
                         Ok(map) => {
@@ -469,8 +567,8 @@ impl OperationAPI {
                         .await;
                     }
                 },
-                OperationKind::GetNearestPosition(position) => {
-                    match state.get_nearest_position(position).await {
+                OperationKind::GetNearestPosition(position, search) => {
+                    match state.get_nearest_position(position, search).await {
                         Ok(nearest) => {
                             api.finish(Ok(Some(nearest)), operation_str).await;
                         }
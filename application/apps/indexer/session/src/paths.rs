@@ -1,11 +1,13 @@
 //! Contains the paths for chipmunk home directory and it's sub-directories.
 
 use dirs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CHIPMUNK_HOME: &str = ".chipmunk";
 const CHIPMUNK_TMP: &str = "tmp";
 const CHIPMUNK_DOWNLOADS: &str = "downloads";
+const CHIPMUNK_NGRAMS: &str = "ngrams";
+const NGRAMS_FILE_EXTENSION: &str = "ngrams";
 
 pub fn get_home_dir() -> Result<PathBuf, stypes::NativeError> {
     if let Some(home) = dirs::home_dir() {
@@ -43,3 +45,54 @@ pub fn get_chipmunk_downloads_dir() -> Result<PathBuf, stypes::NativeError> {
 
     Ok(downloads)
 }
+
+/// Gets n-gram indexes directory path within Chipmunk home directory.
+pub fn get_ngrams_dir() -> Result<PathBuf, stypes::NativeError> {
+    let ngrams = get_home_dir()?.join(CHIPMUNK_NGRAMS);
+
+    Ok(ngrams)
+}
+
+/// Gets path of n-gram index file of the given file. The index is persisted next to the
+/// file; if the folder of the file isn't writable (e.g. read-only mounts), the index is
+/// kept in n-gram indexes directory within Chipmunk home directory, which is created if
+/// it doesn't exist.
+pub fn get_ngrams_file(filename: &Path) -> Result<PathBuf, stypes::NativeError> {
+    let mut sibling = filename.as_os_str().to_owned();
+    sibling.push(format!(".{NGRAMS_FILE_EXTENSION}"));
+    let sibling = PathBuf::from(sibling);
+    if is_writable(&sibling) {
+        return Ok(sibling);
+    }
+    let ngrams = get_ngrams_dir()?;
+    if !ngrams.exists() {
+        std::fs::create_dir_all(&ngrams).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to create n-gram indexes folder {}: {}",
+                ngrams.to_string_lossy(),
+                e
+            )),
+        })?;
+    }
+    let filename = filename
+        .canonicalize()
+        .unwrap_or_else(|_| filename.to_path_buf());
+    let hash = blake3::hash(filename.as_os_str().as_encoded_bytes());
+    Ok(ngrams.join(format!("{}.{NGRAMS_FILE_EXTENSION}", hash.to_hex())))
+}
+
+/// Checks whether the index file can be written. Index is saved into a temporary file
+/// next to it first, so creating of this file is probed.
+fn is_writable(index_file: &Path) -> bool {
+    let mut probe = index_file.as_os_str().to_owned();
+    probe.push(".tmp");
+    let probe = PathBuf::from(probe);
+    if std::fs::File::create(&probe).is_err() {
+        return false;
+    }
+    let _ = std::fs::remove_file(&probe);
+    !index_file.exists()
+        || std::fs::metadata(index_file).is_ok_and(|meta| !meta.permissions().readonly())
+}
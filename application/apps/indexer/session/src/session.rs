@@ -5,13 +5,16 @@ use crate::{
     operations,
     operations::Operation,
     state,
-    state::{IndexesMode, SessionStateAPI},
+    state::{IndexesMode, SearchExportOptions, SessionStateAPI},
     tracker,
     tracker::OperationTrackerAPI,
 };
 use futures::Future;
 use log::{debug, error, warn};
-use processor::{grabber::LineRange, search::filter::SearchFilter};
+use processor::{
+    grabber::LineRange,
+    search::{filter::SearchFilter, scope::SearchScope},
+};
 use std::{ops::RangeInclusive, path::PathBuf};
 use tokio::{
     join,
@@ -187,6 +190,43 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Enables or disables n-gram index of the session file. The index is built in the
+    /// background while the session is being written; searches use it to skip parts of the
+    /// session file without matches. For linked files the index is persisted next to the
+    /// file (or in Chipmunk home directory, if the folder of the file isn't writable) and
+    /// reused when the file is opened again.
+    pub async fn set_ngram_index(&self, enabled: bool) -> Result<(), stypes::ComputationError> {
+        self.state
+            .set_ngram_index(enabled)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Hides rows matched by the named search `search` from the main view. With applied
+    /// exclusion, ranges given to `grab` and export are positions in the reduced view.
+    /// `None` makes the whole stream visible again.
+    pub async fn set_exclusion(
+        &self,
+        search: Option<String>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.state
+            .set_exclusion(search)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Returns the position of the row (position in the stream) in the main view; `None`
+    /// if the row is hidden by exclusion.
+    pub async fn get_filtered_position(
+        &self,
+        row: u64,
+    ) -> Result<Option<u64>, stypes::ComputationError> {
+        self.state
+            .get_filtered_position(row)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn get_indexed_len(&self) -> Result<usize, stypes::ComputationError> {
         self.state
             .get_indexed_len()
@@ -273,12 +313,15 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Grabs lines from results of the main search (if `search` is `None`) or from
+    /// results of named search.
     pub async fn grab_search(
         &self,
         range: LineRange,
+        search: Option<String>,
     ) -> Result<stypes::GrabbedElementList, stypes::ComputationError> {
         self.state
-            .grab_search(range)
+            .grab_search(range, search)
             .await
             .map(|els| els.into())
             .map_err(stypes::ComputationError::NativeError)
@@ -439,6 +482,37 @@ impl Session {
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
 
+    /// Exports matches of search along with rows around them (context) into a text file.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation_id` - A `Uuid` of the export operation, used for tracking.
+    /// * `out_path` - A `PathBuf` representing the path to the output file.
+    /// * `search` - Identifier of named search; `None` for the main search.
+    /// * `filters` - Filters to find matches with. If empty, results of search (`search`) are
+    ///   exported; otherwise the session file is searched without changing any search.
+    /// * `options` - Size of context and the format of exported rows.
+    pub fn export_search(
+        &self,
+        operation_id: Uuid,
+        out_path: PathBuf,
+        search: Option<String>,
+        filters: Vec<SearchFilter>,
+        options: SearchExportOptions,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::ExportSearch {
+                    out_path,
+                    search,
+                    filters,
+                    options,
+                },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
     pub fn export_raw(
         &self,
         operation_id: Uuid,
@@ -464,11 +538,31 @@ impl Session {
         &self,
         operation_id: Uuid,
         filters: Vec<SearchFilter>,
+        scope: SearchScope,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::Search { filters },
+                operations::OperationKind::Search { filters, scope },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    pub fn apply_search_query(
+        &self,
+        operation_id: Uuid,
+        query: String,
+        spliter: Option<String>,
+        scope: SearchScope,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::SearchQuery {
+                    query,
+                    spliter,
+                    scope,
+                },
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -493,15 +587,42 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Starts (or restarts) named search. Named searches are running independently of the
+    /// main search and of each other; results are reported with
+    /// `CallbackEvent::NamedSearchUpdated`.
+    pub fn apply_named_search(
+        &self,
+        operation_id: Uuid,
+        id: String,
+        filters: Vec<SearchFilter>,
+        scope: SearchScope,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::NamedSearch { id, filters, scope },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Drops named search with its results. Returns `false` if the search is in progress.
+    pub async fn drop_named_search(&self, id: String) -> Result<bool, stypes::ComputationError> {
+        self.state
+            .drop_named_search(id)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub fn extract_matches(
         &self,
         operation_id: Uuid,
         filters: Vec<SearchFilter>,
+        scope: SearchScope,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::Extract { filters },
+                operations::OperationKind::Extract { filters, scope },
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -511,11 +632,18 @@ impl Session {
         operation_id: Uuid,
         dataset_len: u16,
         range: Option<(u64, u64)>,
+        search: Option<String>,
+        scope: SearchScope,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::Map { dataset_len, range },
+                operations::OperationKind::Map {
+                    dataset_len,
+                    range,
+                    search,
+                    scope,
+                },
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -538,11 +666,12 @@ impl Session {
         &self,
         operation_id: Uuid,
         position_in_stream: u64,
+        search: Option<String>,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::GetNearestPosition(position_in_stream),
+                operations::OperationKind::GetNearestPosition(position_in_stream, search),
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -557,6 +686,16 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    pub async fn get_dlt_contexts(
+        &self,
+    ) -> Result<stypes::DltContextInfoList, stypes::ComputationError> {
+        self.state
+            .get_dlt_contexts()
+            .await
+            .map(|v| v.into())
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn get_indexed_ranges(&self) -> Result<stypes::Ranges, stypes::ComputationError> {
         self.state
             .get_indexed_ranges()
@@ -1,18 +1,21 @@
 use super::values::graph::CandlePoint;
 use crate::{
     state::{
-        indexes::controller::Mode as IndexesMode, observed::Observed,
+        export::SearchExportOptions, indexes::controller::Mode as IndexesMode, observed::Observed,
         session_file::SessionFileOrigin, values::ValuesError,
     },
     tracker::OperationTrackerAPI,
 };
 use log::error;
-use parsers;
+use parsers::{self, dlt::registry::SharedDltRegistry};
 use processor::{
     grabber::LineRange,
     map::{FiltersStats, ScaledDistribution},
     search::{
         filter::SearchFilter,
+        highlight::Highlighter,
+        ngrams::SharedNgramIndex,
+        scope::{SearchScope, SourcesMap, TimestampsMap},
         searchers::{
             regular::RegularSearchHolder,
             values::{ValueSearchHolder, ValueSearchMatch},
@@ -40,6 +43,7 @@ pub enum Api {
         (
             u16,
             String,
+            Vec<(u64, i64)>,
             oneshot::Sender<Result<(), stypes::NativeError>>,
         ),
     ),
@@ -85,6 +89,22 @@ pub enum Api {
         /// Used to send operation status result
         tx_response: oneshot::Sender<Result<bool, stypes::NativeError>>,
     },
+    /// Exports rows matched by search along with rows around them (context). Ranges of
+    /// rows are built with the search map, overlapping ranges are merged.
+    ExportSearch {
+        /// The output path where the exported data will be written.
+        out_path: PathBuf,
+        /// Identifier of named search; `None` for the main search.
+        search: Option<String>,
+        /// Matches to export instead of the results of search (`search` is ignored).
+        matches: Option<Vec<stypes::FilterMatch>>,
+        /// Size of context and the format of exported rows.
+        options: SearchExportOptions,
+        /// Used to stop export operation
+        cancel: CancellationToken,
+        /// Used to send operation status result
+        tx_response: oneshot::Sender<Result<bool, stypes::NativeError>>,
+    },
     FileRead(oneshot::Sender<()>),
     Grab(
         (
@@ -124,6 +144,7 @@ pub enum Api {
     GrabSearch(
         (
             LineRange,
+            Option<String>,
             oneshot::Sender<Result<Vec<GrabbedElement>, stypes::NativeError>>,
         ),
     ),
@@ -144,6 +165,24 @@ pub enum Api {
         ),
     ),
     GetStreamLen(oneshot::Sender<(u64, u64)>),
+    GetSourcesMap(oneshot::Sender<SourcesMap>),
+    GetTimestampsMap(oneshot::Sender<TimestampsMap>),
+    SetNgramIndex((bool, oneshot::Sender<()>)),
+    GetNgramIndex(oneshot::Sender<Option<SharedNgramIndex>>),
+    SetExclusion(
+        (
+            Option<String>,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
+    GetFilteredPosition((u64, oneshot::Sender<Option<u64>>)),
+    #[allow(clippy::type_complexity)]
+    GetViewRanges(
+        (
+            Vec<RangeInclusive<u64>>,
+            oneshot::Sender<Result<Vec<RangeInclusive<u64>>, stypes::NativeError>>,
+        ),
+    ),
     GetSearchResultLen(oneshot::Sender<usize>),
     GetSearchHolder(
         (
@@ -159,8 +198,24 @@ pub enum Api {
         ),
     ),
     DropSearch(oneshot::Sender<bool>),
-    GetNearestPosition((u64, oneshot::Sender<stypes::ResultNearestPosition>)),
-    GetScaledMap((u16, Option<(u64, u64)>, oneshot::Sender<ScaledDistribution>)),
+    #[allow(clippy::type_complexity)]
+    GetNearestPosition(
+        (
+            u64,
+            Option<String>,
+            oneshot::Sender<Result<stypes::ResultNearestPosition, stypes::NativeError>>,
+        ),
+    ),
+    #[allow(clippy::type_complexity)]
+    GetScaledMap(
+        (
+            u16,
+            Option<(u64, u64)>,
+            Option<String>,
+            SearchScope,
+            oneshot::Sender<Result<ScaledDistribution, stypes::NativeError>>,
+        ),
+    ),
     SetMatches(
         (
             Option<Vec<stypes::FilterMatch>>,
@@ -182,6 +237,29 @@ pub enum Api {
         ),
     ),
     SetSearchValues(HashMap<u8, Vec<ValueSearchMatch>>, oneshot::Sender<()>),
+    GetNamedSearchHolder(
+        (
+            String,
+            oneshot::Sender<Result<RegularSearchHolder, stypes::NativeError>>,
+        ),
+    ),
+    SetNamedSearchHolder(
+        (
+            String,
+            Option<RegularSearchHolder>,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
+    DropNamedSearch((String, oneshot::Sender<bool>)),
+    SetHighlighter((Option<String>, Highlighter, oneshot::Sender<()>)),
+    SetNamedMatches(
+        (
+            String,
+            Option<Vec<stypes::FilterMatch>>,
+            Option<FiltersStats>,
+            oneshot::Sender<()>,
+        ),
+    ),
     #[allow(clippy::type_complexity)]
     GetSearchValues(
         (
@@ -198,6 +276,8 @@ pub enum Api {
     NotifyCanceledOperation(Uuid),
     AddAttachment(parsers::Attachment),
     GetAttachments(oneshot::Sender<Vec<stypes::AttachmentInfo>>),
+    GetDltRegistry(oneshot::Sender<SharedDltRegistry>),
+    GetDltContexts(oneshot::Sender<Vec<stypes::DltContextInfo>>),
     // Used for tests of error handeling
     ShutdownWithError,
     Shutdown,
@@ -222,9 +302,17 @@ impl Display for Api {
                 Self::GetExecutedHolder(_) => "GetExecutedHolder",
                 Self::IsRawExportAvailable(_) => "IsRawExportAvailable",
                 Self::ExportSession { .. } => "ExportSession",
+                Self::ExportSearch { .. } => "ExportSearch",
                 Self::FileRead(_) => "FileRead",
                 Self::Grab(_) => "Grab",
                 Self::GetStreamLen(_) => "GetStreamLen",
+                Self::GetSourcesMap(_) => "GetSourcesMap",
+                Self::GetTimestampsMap(_) => "GetTimestampsMap",
+                Self::SetNgramIndex(_) => "SetNgramIndex",
+                Self::GetNgramIndex(_) => "GetNgramIndex",
+                Self::SetExclusion(_) => "SetExclusion",
+                Self::GetFilteredPosition(_) => "GetFilteredPosition",
+                Self::GetViewRanges(_) => "GetViewRanges",
                 Self::GetSearchResultLen(_) => "GetSearchResultLen",
                 Self::GetSearchHolder(_) => "GetSearchHolder",
                 Self::SetSearchHolder(_) => "SetSearchHolder",
@@ -246,6 +334,11 @@ impl Display for Api {
                 Self::GetSearchValuesHolder(_) => "GetSearchValuesHolder",
                 Self::SetSearchValuesHolder(_) => "SetSearchValuesHolder",
                 Self::SetSearchValues(_, _) => "SetSearchValues",
+                Self::GetNamedSearchHolder(_) => "GetNamedSearchHolder",
+                Self::SetNamedSearchHolder(_) => "SetNamedSearchHolder",
+                Self::DropNamedSearch(_) => "DropNamedSearch",
+                Self::SetNamedMatches(_) => "SetNamedMatches",
+                Self::SetHighlighter(_) => "SetHighlighter",
                 Self::GetSearchValues(_) => "GetSearchValues",
                 Self::DropSearchValues(_) => "DropSearchValues",
                 Self::GetIndexedRanges(_) => "GetIndexedRanges",
@@ -255,6 +348,8 @@ impl Display for Api {
                 Self::NotifyCanceledOperation(_) => "NotifyCanceledOperation",
                 Self::AddAttachment(_) => "AddAttachment",
                 Self::GetAttachments(_) => "GetAttachments",
+                Self::GetDltRegistry(_) => "GetDltRegistry",
+                Self::GetDltContexts(_) => "GetDltContexts",
                 Self::Shutdown => "Shutdown",
                 Self::ShutdownWithError => "ShutdownWithError",
             }
@@ -368,12 +463,16 @@ impl SessionStateAPI {
         .await?
     }
 
+    /// Grabs lines from search results.
+    ///
+    /// * `search` - identifier of named search; `None` to grab from the main search.
     pub async fn grab_search(
         &self,
         range: LineRange,
+        search: Option<String>,
     ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::GrabSearch((range, tx)), rx)
+        self.exec_operation(Api::GrabSearch((range, search, tx)), rx)
             .await?
     }
 
@@ -402,6 +501,61 @@ impl SessionStateAPI {
         self.exec_operation(Api::GetStreamLen(tx), rx).await
     }
 
+    /// Returns ranges of rows written by each source of the session.
+    pub async fn get_sources_map(&self) -> Result<SourcesMap, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetSourcesMap(tx), rx).await
+    }
+
+    /// Returns timestamps of rows, which parsers have detected in messages.
+    pub async fn get_timestamps_map(&self) -> Result<TimestampsMap, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimestampsMap(tx), rx).await
+    }
+
+    /// Enables or disables building of n-gram index of the session file, which is used to
+    /// speed up searches.
+    pub async fn set_ngram_index(&self, enabled: bool) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetNgramIndex((enabled, tx)), rx)
+            .await
+    }
+
+    /// Returns n-gram index of the session file; `None` if indexing isn't enabled.
+    pub async fn get_ngram_index(&self) -> Result<Option<SharedNgramIndex>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetNgramIndex(tx), rx).await
+    }
+
+    /// Hides rows matched by the named search `search` from the main view; `None` makes
+    /// the whole stream visible again.
+    pub async fn set_exclusion(&self, search: Option<String>) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetExclusion((search, tx)), rx)
+            .await?
+    }
+
+    /// Returns the position of the row in the main view. If the filtered view isn't applied,
+    /// the position is the same as in the stream; `None` if the row is hidden.
+    pub async fn get_filtered_position(
+        &self,
+        row: u64,
+    ) -> Result<Option<u64>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetFilteredPosition((row, tx)), rx)
+            .await
+    }
+
+    /// Converts ranges of the main view into ranges of the stream.
+    pub async fn get_view_ranges(
+        &self,
+        ranges: Vec<RangeInclusive<u64>>,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetViewRanges((ranges, tx)), rx)
+            .await?
+    }
+
     pub async fn get_search_result_len(&self) -> Result<usize, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
         self.exec_operation(Api::GetSearchResultLen(tx), rx).await
@@ -410,20 +564,26 @@ impl SessionStateAPI {
     pub async fn get_nearest_position(
         &self,
         position: u64,
+        search: Option<String>,
     ) -> Result<stypes::ResultNearestPosition, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::GetNearestPosition((position, tx)), rx)
-            .await
+        self.exec_operation(Api::GetNearestPosition((position, search, tx)), rx)
+            .await?
     }
 
     pub async fn get_scaled_map(
         &self,
         dataset_len: u16,
         range: Option<(u64, u64)>,
+        search: Option<String>,
+        scope: SearchScope,
     ) -> Result<ScaledDistribution, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::GetScaledMap((dataset_len, range, tx)), rx)
-            .await
+        self.exec_operation(
+            Api::GetScaledMap((dataset_len, range, search, scope, tx)),
+            rx,
+        )
+        .await?
     }
 
     pub async fn set_session_file(
@@ -440,13 +600,16 @@ impl SessionStateAPI {
         self.exec_operation(Api::GetSessionFile(tx), rx).await?
     }
 
+    /// Writes messages into the session file. `timestamps` are timestamps of written rows
+    /// with rows counted from the beginning of `msg`.
     pub async fn write_session_file(
         &self,
         source_id: u16,
         msg: String,
+        timestamps: Vec<(u64, i64)>,
     ) -> Result<(), stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::WriteSessionFile((source_id, msg, tx)), rx)
+        self.exec_operation(Api::WriteSessionFile((source_id, msg, timestamps, tx)), rx)
             .await?
     }
 
@@ -552,6 +715,42 @@ impl SessionStateAPI {
         .await?
     }
 
+    /// Exports rows matched by search along with rows around them (context).
+    ///
+    /// # Arguments
+    ///
+    /// * `out_path` - The path to the output file.
+    /// * `search` - Identifier of named search; `None` for the main search.
+    /// * `matches` - Matches to export instead of the results of search.
+    /// * `options` - Size of context and the format of exported rows.
+    /// * `cancel` - A `CancellationToken` used to stop export operation
+    ///
+    /// # Returns
+    ///
+    /// `Ok(false)` if the export was stopped with `cancel`.
+    pub async fn export_search(
+        &self,
+        out_path: PathBuf,
+        search: Option<String>,
+        matches: Option<Vec<stypes::FilterMatch>>,
+        options: SearchExportOptions,
+        cancel: CancellationToken,
+    ) -> Result<bool, stypes::NativeError> {
+        let (tx_response, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::ExportSearch {
+                out_path,
+                search,
+                matches,
+                options,
+                cancel,
+                tx_response,
+            },
+            rx,
+        )
+        .await?
+    }
+
     pub async fn file_read(&self) -> Result<(), stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
         self.exec_operation(Api::FileRead(tx), rx).await
@@ -591,6 +790,56 @@ impl SessionStateAPI {
             .await
     }
 
+    pub async fn get_named_search_holder(
+        &self,
+        id: String,
+    ) -> Result<RegularSearchHolder, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetNamedSearchHolder((id, tx)), rx)
+            .await?
+    }
+
+    pub async fn set_named_search_holder(
+        &self,
+        id: String,
+        holder: Option<RegularSearchHolder>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetNamedSearchHolder((id, holder, tx)), rx)
+            .await?
+    }
+
+    /// Drops named search with all its results. Returns `false` if the search is still
+    /// in progress and cannot be dropped.
+    pub async fn drop_named_search(&self, id: String) -> Result<bool, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::DropNamedSearch((id, tx)), rx)
+            .await
+    }
+
+    pub async fn set_named_matches(
+        &self,
+        id: String,
+        matches: Option<Vec<stypes::FilterMatch>>,
+        stats: Option<FiltersStats>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetNamedMatches((id, matches, stats, tx)), rx)
+            .await
+    }
+
+    /// Sets filters of the main search (if `search` isn't defined) or of the named search,
+    /// which are used to locate matches in grabbed rows.
+    pub async fn set_highlighter(
+        &self,
+        search: Option<String>,
+        highlighter: Highlighter,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetHighlighter((search, highlighter, tx)), rx)
+            .await
+    }
+
     pub async fn canceling_operation(&self, uuid: Uuid) -> Result<(), stypes::NativeError> {
         self.tx_api
             .send(Api::NotifyCancelingOperation(uuid))
@@ -706,6 +955,19 @@ impl SessionStateAPI {
         self.exec_operation(Api::GetAttachments(tx), rx).await
     }
 
+    /// Returns the registry of DLT contexts, which is filled by the DLT parsers of the session.
+    pub async fn get_dlt_registry(&self) -> Result<SharedDltRegistry, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetDltRegistry(tx), rx).await
+    }
+
+    pub async fn get_dlt_contexts(
+        &self,
+    ) -> Result<Vec<stypes::DltContextInfo>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetDltContexts(tx), rx).await
+    }
+
     pub fn is_closing(&self) -> bool {
         self.closing_token.is_cancelled()
     }
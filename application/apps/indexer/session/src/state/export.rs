@@ -0,0 +1,28 @@
+//! Includes definitions of exporting session content into text files.
+
+/// Defines the format of exported rows.
+///
+/// Exporting with considering selected columns (`columns`) will be done only if `spliter` and
+/// `delimiter` are defined. In all other cases full log records are exported.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFormat {
+    /// The indices of the columns to include in the export.
+    pub columns: Vec<usize>,
+    /// String used as the record separator in session file to split log message to columns.
+    pub spliter: Option<String>,
+    /// String used as the field delimiter within each record in output file.
+    pub delimiter: Option<String>,
+    /// Line written between exported ranges of rows (for example "--"). Ranges are
+    /// separated with a line break only, if it isn't defined.
+    pub separator: Option<String>,
+}
+
+/// Defines exporting of search results along with rows around each match (context).
+#[derive(Debug, Clone, Default)]
+pub struct SearchExportOptions {
+    /// Count of rows before each match to export.
+    pub before: u64,
+    /// Count of rows after each match to export.
+    pub after: u64,
+    pub format: ExportFormat,
+}
@@ -0,0 +1,238 @@
+//! Includes the definitions and implementation of reduced ("filtered") view of the session
+//! stream. Rows matched by exclusion filters are hidden and the main view is backed by the
+//! index of remaining rows.
+
+use processor::map::SearchMap;
+use std::ops::RangeInclusive;
+use stypes::{FilterMatch, GrabbedElement, NearestPosition};
+
+/// Reduced view of the session stream.
+///
+/// Rows to hide are taken from the results of a named search. The view keeps only sorted
+/// positions of hidden rows; positions of visible rows are calculated from them, so the view
+/// doesn't grow with the stream.
+#[derive(Debug)]
+pub struct Filtered {
+    /// Identifier of the named search, which provides rows to hide.
+    search: String,
+    /// Sorted positions (in the stream) of hidden rows.
+    hidden: Vec<u64>,
+    /// Length of the session stream.
+    stream_len: u64,
+}
+
+impl Filtered {
+    pub(crate) fn new(search: String, stream_len: u64) -> Self {
+        Self {
+            search,
+            hidden: Vec::new(),
+            stream_len,
+        }
+    }
+
+    /// Identifier of the named search, which provides rows to hide.
+    pub(crate) fn search(&self) -> &str {
+        &self.search
+    }
+
+    /// Replaces hidden rows.
+    pub(crate) fn set_hidden(&mut self, rows: impl Iterator<Item = u64>) {
+        self.hidden = rows.collect();
+        self.hidden.sort_unstable();
+        self.hidden.dedup();
+    }
+
+    /// Adds hidden rows. Search results are delivered in order of the stream, so in most
+    /// cases rows are just appended.
+    pub(crate) fn append_hidden(&mut self, rows: impl Iterator<Item = u64>) {
+        let from = self.hidden.len().saturating_sub(1);
+        self.hidden.extend(rows);
+        if !self.hidden[from..].is_sorted_by(|a, b| a < b) {
+            self.hidden.sort_unstable();
+            self.hidden.dedup();
+        }
+    }
+
+    pub(crate) fn set_stream_len(&mut self, len: u64) {
+        self.stream_len = len;
+    }
+
+    /// Number of visible rows.
+    pub(crate) fn len(&self) -> u64 {
+        self.stream_len.saturating_sub(self.hidden_len())
+    }
+
+    /// Returns `true` if the row (position in the stream) is hidden.
+    pub(crate) fn is_hidden(&self, row: u64) -> bool {
+        self.hidden.binary_search(&row).is_ok()
+    }
+
+    /// Converts the position in the view into the position in the stream.
+    pub(crate) fn stream_row(&self, index: u64) -> Option<u64> {
+        if index >= self.len() {
+            return None;
+        }
+        // For the i-th hidden row there are `hidden[i] - i` visible rows before it. The row
+        // is shifted by the number of hidden rows, which have not more visible rows before
+        // them than `index`.
+        let (mut left, mut right) = (0, self.hidden.len());
+        while left < right {
+            let middle = (left + right) / 2;
+            if self.hidden[middle] - middle as u64 <= index {
+                left = middle + 1;
+            } else {
+                right = middle;
+            }
+        }
+        Some(index + left as u64)
+    }
+
+    /// Converts the position in the stream into the position in the view. Returns `None` if
+    /// the row is hidden.
+    pub(crate) fn view_index(&self, row: u64) -> Option<u64> {
+        match self.hidden.binary_search(&row) {
+            Ok(_) => None,
+            Err(hidden_before) => Some(row - hidden_before as u64),
+        }
+    }
+
+    /// Converts the position in the stream into the position in the view. A hidden row is
+    /// converted into the position of the next visible row (or the last one), so matches
+    /// and breadcrumbs on hidden rows still point to the place, where they would be.
+    pub(crate) fn view_position(&self, row: u64) -> u64 {
+        self.visible_before(row).min(self.len().saturating_sub(1))
+    }
+
+    /// Converts the position in the view into the position in the stream. Used for positions
+    /// coming from the view (bookmarks, nearest lookups).
+    pub(crate) fn to_stream_row(&self, index: u64) -> Result<u64, stypes::NativeError> {
+        self.stream_row(index)
+            .ok_or_else(|| Self::out_of_range(&(index..=index), self.len()))
+    }
+
+    /// Sets positions of grabbed rows in the view.
+    pub(crate) fn to_view(&self, elements: &mut [GrabbedElement]) {
+        elements.iter_mut().for_each(|el| {
+            el.pos = self.view_position(el.pos as u64) as usize;
+        });
+    }
+
+    /// Returns matches on visible rows. Positions stay in the stream.
+    pub(crate) fn visible_matches(&self, matches: &[FilterMatch]) -> Vec<FilterMatch> {
+        matches
+            .iter()
+            .filter(|m| !self.is_hidden(m.index))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the map of search with matches on visible rows only and positions in the view.
+    pub(crate) fn view_map(&self, map: &SearchMap) -> SearchMap {
+        let mut view = SearchMap::new();
+        view.set_stream_len(self.len());
+        view.set(
+            Some(
+                map.matches()
+                    .iter()
+                    .filter_map(|m| {
+                        self.view_index(m.index).map(|index| FilterMatch {
+                            index,
+                            filters: m.filters.clone(),
+                        })
+                    })
+                    .collect(),
+            ),
+            None,
+        );
+        view
+    }
+
+    /// Looks for the match nearest to the position in the view. Matches on hidden rows are
+    /// ignored; the position of found match is returned in the view.
+    pub(crate) fn nearest_to(
+        &self,
+        map: &SearchMap,
+        position: u64,
+    ) -> Result<Option<NearestPosition>, stypes::NativeError> {
+        let row = self.to_stream_row(position)?;
+        Ok(map
+            .nearest_to_visible(row, |row| !self.is_hidden(row))
+            .map(|nearest| NearestPosition {
+                index: nearest.index,
+                position: self.view_position(nearest.position),
+            }))
+    }
+
+    /// Converts ranges of rows in the stream into ranges in the view. Ranges, which are
+    /// adjacent in the view, are joined.
+    pub(crate) fn view_ranges(&self, ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclusive<u64>> {
+        let mut converted: Vec<RangeInclusive<u64>> = Vec::new();
+        for range in ranges {
+            let start = self.visible_before(*range.start());
+            let end = self.visible_before(range.end() + 1);
+            if end <= start {
+                // All rows of range are hidden
+                continue;
+            }
+            let end = end - 1;
+            match converted.last_mut() {
+                Some(last) if *last.end() + 1 >= start => {
+                    *last = *last.start()..=end.max(*last.end());
+                }
+                _ => converted.push(start..=end),
+            }
+        }
+        converted
+    }
+
+    /// Converts the range of the view into the ranges of visible rows in the stream.
+    pub(crate) fn ranges(
+        &self,
+        range: &RangeInclusive<u64>,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        if *range.end() >= self.len() || range.start() > range.end() {
+            return Err(Self::out_of_range(range, self.len()));
+        }
+        let Some(mut start) = self.stream_row(*range.start()) else {
+            return Err(Self::out_of_range(range, self.len()));
+        };
+        let mut left = range.end() - range.start() + 1;
+        let mut ranges = Vec::new();
+        let mut next_hidden = self.hidden.partition_point(|row| *row < start);
+        while left > 0 {
+            let end = match self.hidden.get(next_hidden) {
+                Some(hidden) => (hidden - 1).min(start + left - 1),
+                None => start + left - 1,
+            };
+            left -= end - start + 1;
+            ranges.push(start..=end);
+            // Skip the block of hidden rows after the range.
+            start = end + 1;
+            while self.hidden.get(next_hidden) == Some(&start) {
+                next_hidden += 1;
+                start += 1;
+            }
+        }
+        Ok(ranges)
+    }
+
+    /// Number of visible rows before the row (position in the stream).
+    fn visible_before(&self, row: u64) -> u64 {
+        row - self.hidden.partition_point(|hidden| *hidden < row) as u64
+    }
+
+    fn hidden_len(&self) -> u64 {
+        // Hidden rows could be delivered before the stream length is updated.
+        self.hidden.partition_point(|row| *row < self.stream_len) as u64
+    }
+
+    fn out_of_range(range: &RangeInclusive<u64>, len: u64) -> stypes::NativeError {
+        stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Grabber,
+            message: Some(format!(
+                "Filtered view has: {len} rows. Requested: {range:?}"
+            )),
+        }
+    }
+}
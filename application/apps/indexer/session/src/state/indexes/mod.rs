@@ -1,4 +1,5 @@
 pub mod controller;
+pub mod filtered;
 pub mod frame;
 pub mod keys;
 pub mod map;
@@ -7,6 +8,8 @@ pub mod nature;
 #[cfg(test)]
 pub mod tests_controller;
 #[cfg(test)]
+pub mod tests_filtered;
+#[cfg(test)]
 pub mod tests_map_cases;
 #[cfg(test)]
 pub mod tests_map_performance;
@@ -0,0 +1,171 @@
+use super::filtered::Filtered;
+use processor::map::SearchMap;
+use stypes::{FilterMatch, GrabbedElement};
+
+fn filtered(stream_len: u64, hidden: &[u64]) -> Filtered {
+    let mut filtered = Filtered::new(String::from("noise"), stream_len);
+    filtered.set_hidden(hidden.iter().copied());
+    filtered
+}
+
+fn matches(rows: &[u64]) -> Vec<FilterMatch> {
+    rows.iter()
+        .map(|row| FilterMatch {
+            index: *row,
+            filters: vec![0],
+        })
+        .collect()
+}
+
+fn search_map(stream_len: u64, rows: &[u64]) -> SearchMap {
+    let mut map = SearchMap::new();
+    map.set_stream_len(stream_len);
+    map.set(Some(matches(rows)), None);
+    map
+}
+
+#[test]
+fn test_filtered_len() {
+    assert_eq!(filtered(10, &[]).len(), 10);
+    assert_eq!(filtered(10, &[0, 5, 9]).len(), 7);
+    // Hidden rows out of stream are ignored until stream is updated
+    let mut view = filtered(10, &[2, 10, 11]);
+    assert_eq!(view.len(), 9);
+    view.set_stream_len(12);
+    assert_eq!(view.len(), 9);
+    view.set_stream_len(13);
+    assert_eq!(view.len(), 10);
+}
+
+#[test]
+fn test_filtered_positions() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    let visible = [1, 4, 5, 8, 9];
+    for (index, row) in visible.iter().enumerate() {
+        assert_eq!(view.stream_row(index as u64), Some(*row));
+        assert_eq!(view.view_index(*row), Some(index as u64));
+    }
+    assert_eq!(view.stream_row(5), None);
+    for row in [0, 2, 3, 6, 7] {
+        assert!(view.is_hidden(row));
+        assert_eq!(view.view_index(row), None);
+    }
+}
+
+#[test]
+fn test_filtered_ranges() {
+    // Visible rows: 1, 4, 5, 8, 9, 10
+    let view = filtered(11, &[0, 2, 3, 6, 7]);
+    assert_eq!(view.ranges(&(0..=5)).unwrap(), vec![1..=1, 4..=5, 8..=10]);
+    assert_eq!(view.ranges(&(1..=2)).unwrap(), vec![4..=5]);
+    assert_eq!(view.ranges(&(2..=3)).unwrap(), vec![5..=5, 8..=8]);
+    assert_eq!(view.ranges(&(5..=5)).unwrap(), vec![10..=10]);
+    assert!(view.ranges(&(5..=6)).is_err());
+    // Without hidden rows view matches the stream
+    let view = filtered(11, &[]);
+    assert_eq!(view.ranges(&(3..=7)).unwrap(), vec![3..=7]);
+}
+
+#[test]
+fn test_filtered_append() {
+    let mut view = filtered(20, &[1, 2]);
+    view.append_hidden([5, 6].into_iter());
+    assert_eq!(view.len(), 16);
+    assert_eq!(view.ranges(&(0..=3)).unwrap(), vec![0..=0, 3..=4, 7..=7]);
+    // Out of order and duplicated rows
+    view.append_hidden([4, 2].into_iter());
+    assert_eq!(view.len(), 15);
+    assert_eq!(view.ranges(&(0..=2)).unwrap(), vec![0..=0, 3..=3, 7..=7]);
+}
+
+#[test]
+fn test_filtered_grabbed_positions() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    let mut elements = [1, 3, 8, 9]
+        .iter()
+        .map(|row| GrabbedElement {
+            source_id: 0,
+            content: String::new(),
+            pos: *row,
+            nature: 0,
+            spans: Vec::new(),
+        })
+        .collect::<Vec<GrabbedElement>>();
+    view.to_view(&mut elements);
+    // Hidden row 3 takes the position of the next visible row
+    assert_eq!(
+        elements.iter().map(|el| el.pos).collect::<Vec<usize>>(),
+        vec![0, 1, 3, 4]
+    );
+    // Hidden rows after the last visible row stay on it
+    let view = filtered(10, &[8, 9]);
+    assert_eq!(view.view_position(9), 7);
+}
+
+#[test]
+fn test_filtered_search_map() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    let map = search_map(10, &[0, 3, 4, 7, 9]);
+    assert_eq!(
+        view.visible_matches(map.matches())
+            .iter()
+            .map(|m| m.index)
+            .collect::<Vec<u64>>(),
+        vec![4, 9]
+    );
+    let in_view = view.view_map(&map);
+    assert_eq!(
+        in_view
+            .matches()
+            .iter()
+            .map(|m| m.index)
+            .collect::<Vec<u64>>(),
+        vec![1, 4]
+    );
+    assert_eq!(
+        in_view.scaled(5, None),
+        vec![vec![], vec![(0, 1)], vec![], vec![], vec![(0, 1)]]
+    );
+}
+
+fn nearest(view: &Filtered, map: &SearchMap, position: u64) -> Option<(u64, u64)> {
+    view.nearest_to(map, position)
+        .expect("position is in view")
+        .map(|nearest| (nearest.index, nearest.position))
+}
+
+#[test]
+fn test_filtered_nearest() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    let map = search_map(10, &[0, 3, 5, 7]);
+    // Position 3 of the view is row 8; match on hidden row 7 is ignored
+    assert_eq!(nearest(&view, &map, 3), Some((2, 2)));
+    assert_eq!(nearest(&view, &map, 0), Some((2, 2)));
+    assert!(view.nearest_to(&map, 5).is_err());
+    let map = search_map(10, &[0, 3]);
+    assert_eq!(nearest(&view, &map, 1), None);
+}
+
+#[test]
+fn test_filtered_bookmarks() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    assert_eq!(view.to_stream_row(0).unwrap(), 1);
+    assert_eq!(view.to_stream_row(3).unwrap(), 8);
+    assert!(view.to_stream_row(5).is_err());
+}
+
+#[test]
+fn test_filtered_indexed_ranges() {
+    // Visible rows: 1, 4, 5, 8, 9
+    let view = filtered(10, &[0, 2, 3, 6, 7]);
+    assert_eq!(
+        view.view_ranges(vec![0..=1, 2..=3, 4..=4, 6..=9]),
+        vec![0..=1, 3..=4]
+    );
+    assert_eq!(view.view_ranges(vec![2..=3, 6..=7]), Vec::new());
+}
@@ -1,10 +1,17 @@
-use log::{debug, error};
-use parsers;
+use crate::paths;
+use log::{debug, error, warn};
+use parsers::{
+    self,
+    dlt::registry::{DltRegistry, SharedDltRegistry},
+};
 use processor::{
     grabber::LineRange,
-    map::SearchMap,
+    map::{ScaledDistribution, SearchMap},
     search::{
         filter::SearchFilter,
+        highlight::Highlighter,
+        ngrams::SharedNgramIndex,
+        scope::{ScopeMatcher, SearchScope},
         searchers::{
             linear::LineSearcher,
             regular::RegularSearchHolder,
@@ -13,6 +20,7 @@ use processor::{
     },
 };
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fs::File,
     io::{BufWriter, Write},
@@ -28,7 +36,9 @@ use uuid::Uuid;
 
 mod api;
 pub(crate) mod attachments;
+mod export;
 mod indexes;
+mod ngrams;
 mod observed;
 mod searchers;
 mod session_file;
@@ -37,12 +47,15 @@ pub(crate) mod values;
 
 pub use api::{Api, SessionStateAPI};
 pub use attachments::{Attachments, AttachmentsError};
+pub use export::{ExportFormat, SearchExportOptions};
 pub use indexes::{
     controller::{Controller as Indexes, Mode as IndexesMode},
+    filtered::Filtered,
     frame::Frame,
     map::Map,
     nature::Nature,
 };
+use ngrams::NgramIndexer;
 use observed::Observed;
 use searchers::{SearchRequest, SearchResponse};
 pub use session_file::{SessionFile, SessionFileOrigin, SessionFileState};
@@ -65,12 +78,29 @@ pub struct SessionState {
     /// Collection of executed observe operations.
     pub observed: Observed,
     pub search_map: SearchMap,
+    /// Results of named searches, which are running independently of the main search.
+    /// Unlike the main search, they do not affect indexes of session.
+    pub named_searches: HashMap<String, SearchMap>,
+    /// Locates matches of the main search filters in grabbed rows.
+    pub highlighter: Highlighter,
+    /// Locates matches of named searches filters in rows grabbed from their results.
+    pub named_highlighters: HashMap<String, Highlighter>,
     pub indexes: Indexes,
+    /// Reduced view of the stream; if defined, rows matched by the named search it's
+    /// backed by are hidden from the main view.
+    pub filtered: Option<Filtered>,
     pub values: Values,
     pub attachments: Attachments,
+    /// ECUs, applications and contexts seen in DLT sources of the session.
+    pub dlt_registry: SharedDltRegistry,
     pub cancelling_operations: HashMap<Uuid, bool>,
     pub status: Status,
     searcher_tx: mpsc::Sender<SearchRequest>,
+    /// Whether n-gram index of the session file is enabled.
+    ngram_index: bool,
+    /// Builds n-gram index of the session file; started as soon as the session file is
+    /// created, if the index is enabled.
+    ngram_indexer: Option<NgramIndexer>,
     pub debug: bool,
 }
 
@@ -83,25 +113,276 @@ impl SessionState {
             session_file: SessionFile::new(),
             observed: Observed::new(),
             search_map: SearchMap::new(),
+            named_searches: HashMap::new(),
+            highlighter: Highlighter::default(),
+            named_highlighters: HashMap::new(),
             attachments: Attachments::new(),
+            dlt_registry: DltRegistry::shared(),
             indexes: Indexes::new(Some(tx_callback_events.clone())),
+            filtered: None,
             values: Values::new(Some(tx_callback_events)),
             status: Status::Open,
             cancelling_operations: HashMap::new(),
             searcher_tx,
+            ngram_index: false,
+            ngram_indexer: None,
             debug: false,
         }
     }
 
+    fn handle_set_ngram_index(&mut self, enabled: bool) {
+        self.ngram_index = enabled;
+        if enabled {
+            self.update_ngram_indexer();
+        } else {
+            self.ngram_indexer = None;
+        }
+    }
+
+    fn update_ngram_indexer(&mut self) {
+        if !self.ngram_index {
+            return;
+        }
+        if self.ngram_indexer.is_none()
+            && let Some(origin) = self.session_file.filename.as_ref()
+        {
+            // Index is persisted only for linked files (next to them); generated session files
+            // are removed with the session, so their index can never be reused.
+            let filename = origin.filename();
+            let index_file = if origin.is_linked() {
+                paths::get_ngrams_file(&filename)
+                    .map_err(|err| {
+                        warn!("N-gram index of {filename:?} won't be persisted: {err:?}")
+                    })
+                    .ok()
+            } else {
+                None
+            };
+            self.ngram_indexer = Some(NgramIndexer::spawn(filename, index_file));
+        }
+        if self.session_file.len() == 0 {
+            return;
+        }
+        let bytes = self.session_file.read_bytes();
+        if let Some(indexer) = self.ngram_indexer.as_ref() {
+            // Grabber reports the offset of the last processed byte
+            indexer.update(bytes + 1);
+        }
+    }
+
+    fn get_ngram_index(&self) -> Option<SharedNgramIndex> {
+        self.ngram_indexer.as_ref().map(|indexer| indexer.index())
+    }
+
+    fn handle_get_dlt_contexts(&self) -> Vec<stypes::DltContextInfo> {
+        let Ok(registry) = self.dlt_registry.lock() else {
+            error!("Fail to access DLT registry");
+            return Vec::new();
+        };
+        registry
+            .entries()
+            .into_iter()
+            .map(|entry| stypes::DltContextInfo {
+                ecu_id: entry.ecu_id,
+                app_id: entry.app_id,
+                app_description: entry.app_description,
+                context_id: entry.context_id,
+                context_description: entry.context_description,
+                log_level: entry.log_level,
+                trace_status: entry.trace_status,
+                messages: entry.messages,
+            })
+            .collect()
+    }
+
     fn handle_grab(
         &mut self,
         range: &LineRange,
     ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
-        let mut elements = self.session_file.grab(range)?;
+        let mut elements = if let Some(filtered) = self.filtered.as_ref() {
+            let mut elements = Vec::new();
+            for range in filtered.ranges(&range.range)? {
+                elements.append(&mut self.session_file.grab(&LineRange::from(range))?);
+            }
+            elements
+        } else {
+            self.session_file.grab(range)?
+        };
         self.indexes.naturalize(&mut elements);
+        self.highlight(&mut elements, None);
+        self.to_view(&mut elements);
         Ok(elements)
     }
 
+    /// Converts positions of grabbed rows into positions in the main view. Positions are kept
+    /// as they are if the filtered view isn't applied.
+    fn to_view(&self, elements: &mut [GrabbedElement]) {
+        if let Some(filtered) = self.filtered.as_ref() {
+            filtered.to_view(elements);
+        }
+    }
+
+    /// Converts the position in the main view into the position in the stream.
+    fn to_stream_row(&self, row: u64) -> Result<u64, stypes::NativeError> {
+        match self.filtered.as_ref() {
+            Some(filtered) => filtered.to_stream_row(row),
+            None => Ok(row),
+        }
+    }
+
+    /// Converts the position in the stream into the position in the main view.
+    fn to_view_position(&self, row: u64) -> u64 {
+        match self.filtered.as_ref() {
+            Some(filtered) => filtered.view_position(row),
+            None => row,
+        }
+    }
+
+    /// Returns matches on rows, which aren't hidden from the main view.
+    fn visible_matches<'a>(&self, matches: &'a [FilterMatch]) -> Cow<'a, [FilterMatch]> {
+        match self.filtered.as_ref() {
+            Some(filtered) => Cow::Owned(filtered.visible_matches(matches)),
+            None => Cow::Borrowed(matches),
+        }
+    }
+
+    /// Sets parts of rows matched by filters of the main search (if `search` isn't defined)
+    /// or of the named search.
+    fn highlight(&self, elements: &mut [GrabbedElement], search: Option<&str>) {
+        let highlighter = match search {
+            None => &self.highlighter,
+            Some(id) => match self.named_highlighters.get(id) {
+                Some(highlighter) => highlighter,
+                None => return,
+            },
+        };
+        if highlighter.is_empty() {
+            return;
+        }
+        for element in elements.iter_mut() {
+            element.spans = highlighter.spans(&element.content);
+        }
+    }
+
+    /// Converts ranges of the main view into ranges of the stream. Ranges are returned as
+    /// they are if the filtered view isn't applied.
+    fn get_view_ranges(
+        &self,
+        ranges: Vec<RangeInclusive<u64>>,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let Some(filtered) = self.filtered.as_ref() else {
+            return Ok(ranges);
+        };
+        let mut converted = Vec::new();
+        for range in ranges.iter() {
+            converted.append(&mut filtered.ranges(range)?);
+        }
+        Ok(converted)
+    }
+
+    /// Applies (or drops if `search` is `None`) the filtered view, which hides rows matched
+    /// by the named search. The view is bound to the identifier of search: the search can
+    /// be started later, restarted or dropped without losing the view.
+    fn handle_set_exclusion(
+        &mut self,
+        search: Option<String>,
+        tx_callback_events: &UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        self.filtered = if let Some(search) = search {
+            let mut filtered = Filtered::new(search, self.session_file.len());
+            if let Some(map) = self.named_searches.get(filtered.search()) {
+                filtered.set_hidden(map.matches().iter().map(|m| m.index));
+            }
+            Some(filtered)
+        } else {
+            None
+        };
+        self.update_filtered(tx_callback_events)
+    }
+
+    /// Returns the filtered view if it's backed by the given named search.
+    fn get_filtered_by(&mut self, search: &str) -> Option<&mut Filtered> {
+        self.filtered
+            .as_mut()
+            .filter(|filtered| filtered.search() == search)
+    }
+
+    /// Should be called after hidden rows have been changed: matches on hidden rows are
+    /// removed from indexes (and restored if rows became visible).
+    fn update_filtered(
+        &mut self,
+        tx_callback_events: &UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        if !self.search_map.is_empty() {
+            let matches = self.visible_matches(self.search_map.matches());
+            self.indexes.set_search_results(&matches)?;
+        }
+        self.notify_filtered(tx_callback_events)
+    }
+
+    fn notify_filtered(
+        &self,
+        tx_callback_events: &UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        tx_callback_events.send(stypes::CallbackEvent::FilteredViewUpdated(
+            self.filtered.as_ref().map(|filtered| filtered.len()),
+        ))?;
+        Ok(())
+    }
+
+    fn handle_add_bookmark(&mut self, row: u64) -> Result<(), stypes::NativeError> {
+        let row = self.to_stream_row(row)?;
+        self.indexes.add_bookmark(row)
+    }
+
+    fn handle_remove_bookmark(&mut self, row: u64) -> Result<(), stypes::NativeError> {
+        let row = self.to_stream_row(row)?;
+        self.indexes.remove_bookmark(row)
+    }
+
+    fn handle_set_bookmarks(&mut self, rows: Vec<u64>) -> Result<(), stypes::NativeError> {
+        let rows = rows
+            .into_iter()
+            .map(|row| self.to_stream_row(row))
+            .collect::<Result<Vec<u64>, stypes::NativeError>>()?;
+        self.indexes.set_bookmarks(rows)
+    }
+
+    fn handle_get_around_indexes(
+        &mut self,
+        position: u64,
+    ) -> Result<(Option<u64>, Option<u64>), stypes::NativeError> {
+        let position = self.to_stream_row(position)?;
+        let (before, after) = self.indexes.get_around_indexes(&position)?;
+        Ok((
+            before.map(|row| self.to_view_position(row)),
+            after.map(|row| self.to_view_position(row)),
+        ))
+    }
+
+    fn handle_get_indexed_ranges(&self) -> Vec<RangeInclusive<u64>> {
+        let ranges = self.indexes.get_all_as_ranges();
+        match self.filtered.as_ref() {
+            Some(filtered) => filtered.view_ranges(ranges),
+            None => ranges,
+        }
+    }
+
+    fn handle_get_nearest_position(
+        &self,
+        position: u64,
+        search: Option<&str>,
+    ) -> Result<stypes::ResultNearestPosition, stypes::NativeError> {
+        let map = self.get_search_map(search)?;
+        Ok(stypes::ResultNearestPosition(
+            if let Some(filtered) = self.filtered.as_ref() {
+                filtered.nearest_to(map, position)?
+            } else {
+                map.nearest_to(position)
+            },
+        ))
+    }
+
     fn handle_grab_indexed(
         &mut self,
         mut range: RangeInclusive<u64>,
@@ -113,6 +394,8 @@ impl SessionState {
             elements.append(&mut session_elements);
         }
         frame.set_elements_nature(&mut elements)?;
+        self.highlight(&mut elements, None);
+        self.to_view(&mut elements);
         Ok(elements)
     }
 
@@ -149,12 +432,78 @@ impl SessionState {
         ranges
     }
 
+    /// Returns the map of the main search if `search` isn't defined, or the map of
+    /// named search with the given identifier.
+    fn get_search_map(&self, search: Option<&str>) -> Result<&SearchMap, stypes::NativeError> {
+        match search {
+            None => Ok(&self.search_map),
+            Some(id) => self.named_searches.get(id).ok_or(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::OperationSearch,
+                message: Some(format!("Named search \"{id}\" doesn't exist")),
+            }),
+        }
+    }
+
+    /// Returns scaled map of search results. If `scope` restricts search, only matches in
+    /// scope are considered.
+    fn handle_get_scaled_map(
+        &self,
+        len: u16,
+        range: Option<(u64, u64)>,
+        search: Option<&str>,
+        scope: &SearchScope,
+    ) -> Result<ScaledDistribution, stypes::NativeError> {
+        let map = self.get_search_map(search)?;
+        let Some(mut scope) = ScopeMatcher::new(scope).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::OperationSearch,
+            message: Some(format!("Fail to setup scope of map: {e}")),
+        })?
+        else {
+            return Ok(self.scaled_in_view(map, len, range));
+        };
+        scope.set_sources_map(self.session_file.sources.get_map());
+        scope
+            .set_timestamps_map(self.session_file.timestamps.clone())
+            .map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::OperationSearch,
+                message: Some(format!("Fail to setup scope of map: {e}")),
+            })?;
+        let matches = map
+            .matches()
+            .iter()
+            .filter(|m| scope.contains(m.index))
+            .cloned()
+            .collect::<Vec<FilterMatch>>();
+        let mut scoped = SearchMap::new();
+        scoped.set_stream_len(self.session_file.len());
+        scoped.set(Some(matches), None);
+        Ok(self.scaled_in_view(&scoped, len, range))
+    }
+
+    /// Returns scaled map of search results in the main view: with the filtered view applied
+    /// matches on hidden rows are dropped and the map is scaled to the length of the view.
+    fn scaled_in_view(
+        &self,
+        map: &SearchMap,
+        len: u16,
+        range: Option<(u64, u64)>,
+    ) -> ScaledDistribution {
+        match self.filtered.as_ref() {
+            Some(filtered) => filtered.view_map(map).scaled(len, range),
+            None => map.scaled(len, range),
+        }
+    }
+
     fn handle_grab_search(
         &mut self,
         range: LineRange,
+        search: Option<String>,
     ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
         let indexes = self
-            .search_map
+            .get_search_map(search.as_deref())?
             .indexes(&range.range)
             .map_err(|e| stypes::NativeError {
                 severity: stypes::Severity::ERROR,
@@ -167,6 +516,8 @@ impl SessionState {
             elements.append(&mut session_elements);
         }
         self.indexes.naturalize(&mut elements);
+        self.highlight(&mut elements, search.as_deref());
+        self.to_view(&mut elements);
         Ok(elements)
     }
 
@@ -249,7 +600,7 @@ impl SessionState {
                         )),
                     });
                 };
-                return Ok(Some((ln.pos as u64, srch_pos)));
+                return Ok(Some((self.to_view_position(ln.pos as u64), srch_pos)));
             };
         }
         Ok(None)
@@ -260,11 +611,13 @@ impl SessionState {
         ranges: Vec<RangeInclusive<u64>>,
     ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
         let mut elements: Vec<GrabbedElement> = vec![];
-        for range in ranges.iter() {
+        for range in self.get_view_ranges(ranges)?.iter() {
             let mut session_elements = self.session_file.grab(&LineRange::from(range.clone()))?;
             elements.append(&mut session_elements);
         }
         self.indexes.naturalize(&mut elements);
+        self.highlight(&mut elements, None);
+        self.to_view(&mut elements);
         Ok(elements)
     }
 
@@ -274,10 +627,15 @@ impl SessionState {
         state_cancellation_token: CancellationToken,
         tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
         msg: String,
+        timestamps: Vec<(u64, i64)>,
     ) -> Result<(), stypes::NativeError> {
         if matches!(
-            self.session_file
-                .write(source_id, state_cancellation_token.clone(), msg)?,
+            self.session_file.write(
+                source_id,
+                state_cancellation_token.clone(),
+                msg,
+                timestamps
+            )?,
             SessionFileState::Changed
         ) {
             self.update_searchers(state_cancellation_token, tx_callback_events)
@@ -329,12 +687,23 @@ impl SessionState {
         let rows = self.session_file.len();
         let bytes = self.session_file.read_bytes();
         self.search_map.set_stream_len(rows);
+        self.named_searches
+            .values_mut()
+            .for_each(|map| map.set_stream_len(rows));
         self.indexes.set_stream_len(rows)?;
         tx_callback_events.send(stypes::CallbackEvent::StreamUpdated(rows))?;
+        if let Some(filtered) = self.filtered.as_mut() {
+            filtered.set_stream_len(rows);
+            self.notify_filtered(&tx_callback_events)?;
+        }
+        self.update_ngram_indexer();
         self.searcher_tx
             .send(SearchRequest::SearchRegular {
                 rows,
                 bytes,
+                sources: self.session_file.sources.get_map(),
+                timestamps: self.session_file.timestamps.clone(),
+                index: self.get_ngram_index(),
                 cancel: state_cancellation_token.clone(),
             })
             .await
@@ -382,6 +751,70 @@ impl SessionState {
         delimiter: Option<String>,
         cancel: CancellationToken,
     ) -> Result<bool, stypes::NativeError> {
+        let ranges = self.get_view_ranges(ranges)?;
+        let format = ExportFormat {
+            columns,
+            spliter,
+            delimiter,
+            separator: None,
+        };
+        self.write_ranges(out_path, ranges, &format, cancel)
+    }
+
+    /// Exports rows matched by search along with rows around them into a text file.
+    ///
+    /// # Arguments
+    ///
+    /// * `out_path` - A `PathBuf` representing the path to the output file.
+    /// * `search` - Identifier of named search; `None` for the main search.
+    /// * `matches` - Matches to export instead of the results of search.
+    /// * `options` - Size of context and the format of exported rows.
+    /// * `cancel` - A `CancellationToken` used to cancel exporting operation
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, stypes::NativeError>`:
+    ///     - `Ok(true)` if the export is successful.
+    ///     - `Ok(false)` if the export was stopped with `cancel`.
+    ///     - `Err(stypes::NativeError)` if an error occurs during the export process.
+    ///
+    fn handle_export_search(
+        &mut self,
+        out_path: PathBuf,
+        search: Option<String>,
+        matches: Option<Vec<FilterMatch>>,
+        options: SearchExportOptions,
+        cancel: CancellationToken,
+    ) -> Result<bool, stypes::NativeError> {
+        let ranges = if let Some(matches) = matches {
+            let mut map = SearchMap::new();
+            map.set_stream_len(self.session_file.len());
+            map.set(Some(matches), None);
+            map.context_ranges(options.before, options.after)
+        } else {
+            self.get_search_map(search.as_deref())?
+                .context_ranges(options.before, options.after)
+        };
+        self.write_ranges(out_path, ranges, &options.format, cancel)
+    }
+
+    /// Writes ranges (positions in stream) of rows into a text file.
+    fn write_ranges(
+        &mut self,
+        out_path: PathBuf,
+        ranges: Vec<RangeInclusive<u64>>,
+        format: &ExportFormat,
+        cancel: CancellationToken,
+    ) -> Result<bool, stypes::NativeError> {
+        let io_error = |e: std::io::Error| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to write to file {}: {}",
+                out_path.to_string_lossy(),
+                e
+            )),
+        };
         let mut writer =
             BufWriter::new(File::create(&out_path).map_err(|e| stypes::NativeError {
                 severity: stypes::Severity::ERROR,
@@ -393,34 +826,31 @@ impl SessionState {
                 )),
             })?);
         for (i, range) in ranges.iter().enumerate() {
-            let modifier =
-                if let (Some(spliter), Some(delimiter)) = (spliter.as_ref(), delimiter.as_ref()) {
-                    Some(|s: String| {
-                        s.split(spliter.as_str())
-                            .enumerate()
-                            .filter(|(n, _)| columns.contains(n))
-                            .map(|(_, s)| s)
-                            .collect::<Vec<&str>>()
-                            .join(delimiter.as_str())
-                    })
-                } else {
-                    None
-                };
+            let modifier = if let (Some(spliter), Some(delimiter)) =
+                (format.spliter.as_ref(), format.delimiter.as_ref())
+            {
+                Some(|s: String| {
+                    s.split(spliter.as_str())
+                        .enumerate()
+                        .filter(|(n, _)| format.columns.contains(n))
+                        .map(|(_, s)| s)
+                        .collect::<Vec<&str>>()
+                        .join(delimiter.as_str())
+                })
+            } else {
+                None
+            };
             self.session_file.copy_content(
                 &mut writer,
                 &LineRange::from(range.clone()),
                 modifier,
             )?;
             if i != ranges.len() - 1 {
-                writer.write(b"\n").map_err(|e| stypes::NativeError {
-                    severity: stypes::Severity::ERROR,
-                    kind: stypes::NativeErrorKind::Io,
-                    message: Some(format!(
-                        "Fail to write to file {}: {}",
-                        out_path.to_string_lossy(),
-                        e
-                    )),
-                })?;
+                writer.write_all(b"\n").map_err(io_error)?;
+                if let Some(separator) = format.separator.as_ref() {
+                    writer.write_all(separator.as_bytes()).map_err(io_error)?;
+                    writer.write_all(b"\n").map_err(io_error)?;
+                }
             }
             if cancel.is_cancelled() {
                 return Ok(false);
@@ -457,6 +887,31 @@ impl SessionState {
         })?
     }
 
+    async fn handle_get_named_search_holder(
+        &mut self,
+        id: String,
+    ) -> Result<RegularSearchHolder, stypes::NativeError> {
+        let filename = self.session_file.filename()?;
+        let (holder_rx, holder_tx) = oneshot::channel();
+        self.searcher_tx
+            .send(SearchRequest::GetNamedSearchHolder {
+                id,
+                filename,
+                sender: holder_rx,
+            })
+            .await
+            .map_err(|_| {
+                stypes::NativeError::channel(
+                    "Failed to send get named search holder request to searchers",
+                )
+            })?;
+        holder_tx.await.map_err(|err| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::ChannelError,
+            message: Some(err.to_string()),
+        })?
+    }
+
     async fn handle_get_search_values_holder(
         &mut self,
     ) -> Result<ValueSearchHolder, stypes::NativeError> {
@@ -567,7 +1022,7 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::GetSessionFile")
                 })?;
         }
-        Api::WriteSessionFile((source_id, msg, tx_response)) => {
+        Api::WriteSessionFile((source_id, msg, timestamps, tx_response)) => {
             tx_response
                 .send(
                     state
@@ -576,6 +1031,7 @@ async fn handle_api_msg(
                             state_cancellation_token.clone(),
                             tx_callback_events.clone(),
                             msg,
+                            timestamps,
                         )
                         .await,
                 )
@@ -664,6 +1120,19 @@ async fn handle_api_msg(
                 stypes::NativeError::channel("Failed to respond to Api::ExportSession")
             })?;
         }
+        Api::ExportSearch {
+            out_path,
+            search,
+            matches,
+            options,
+            cancel,
+            tx_response,
+        } => {
+            let res = state.handle_export_search(out_path, search, matches, options, cancel);
+            tx_response.send(res).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::ExportSearch")
+            })?;
+        }
         Api::Grab((range, tx_response)) => {
             tx_response
                 .send(state.handle_grab(&range))
@@ -690,28 +1159,28 @@ async fn handle_api_msg(
         }
         Api::GetDistancesAroundIndex((position, tx_response)) => {
             tx_response
-                .send(state.indexes.get_around_indexes(&position))
+                .send(state.handle_get_around_indexes(position))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::GetIndexedMapLen")
                 })?;
         }
         Api::AddBookmark((row, tx_response)) => {
             tx_response
-                .send(state.indexes.add_bookmark(row))
+                .send(state.handle_add_bookmark(row))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::AddBookmark")
                 })?;
         }
         Api::SetBookmarks((rows, tx_response)) => {
             tx_response
-                .send(state.indexes.set_bookmarks(rows))
+                .send(state.handle_set_bookmarks(rows))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::SetBookmarks")
                 })?;
         }
         Api::RemoveBookmark((row, tx_response)) => {
             tx_response
-                .send(state.indexes.remove_bookmark(row))
+                .send(state.handle_remove_bookmark(row))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::RemoveBookmark")
                 })?;
@@ -728,9 +1197,9 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::ExpandBreadcrumbs")
                 })?;
         }
-        Api::GrabSearch((range, tx_response)) => {
+        Api::GrabSearch((range, search, tx_response)) => {
             tx_response
-                .send(state.handle_grab_search(range))
+                .send(state.handle_grab_search(range, search))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::GrabSearch")
                 })?;
@@ -749,18 +1218,16 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::GrabSearch")
                 })?;
         }
-        Api::GetNearestPosition((position, tx_response)) => {
+        Api::GetNearestPosition((position, search, tx_response)) => {
             tx_response
-                .send(stypes::ResultNearestPosition(
-                    state.search_map.nearest_to(position),
-                ))
+                .send(state.handle_get_nearest_position(position, search.as_deref()))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::GetNearestPosition")
                 })?;
         }
-        Api::GetScaledMap((len, range, tx_response)) => {
+        Api::GetScaledMap((len, range, search, scope, tx_response)) => {
             tx_response
-                .send(state.search_map.scaled(len, range))
+                .send(state.handle_get_scaled_map(len, range, search.as_deref(), &scope))
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::GetScaledMap")
                 })?;
@@ -778,6 +1245,55 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::GetStreamLen")
                 })?;
         }
+        Api::GetSourcesMap(tx_response) => {
+            tx_response
+                .send(state.session_file.sources.get_map())
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetSourcesMap")
+                })?;
+        }
+        Api::GetTimestampsMap(tx_response) => {
+            tx_response
+                .send(state.session_file.timestamps.clone())
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetTimestampsMap")
+                })?;
+        }
+        Api::SetNgramIndex((enabled, tx_response)) => {
+            state.handle_set_ngram_index(enabled);
+            tx_response.send(()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetNgramIndex")
+            })?;
+        }
+        Api::GetNgramIndex(tx_response) => {
+            tx_response.send(state.get_ngram_index()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetNgramIndex")
+            })?;
+        }
+        Api::SetExclusion((search, tx_response)) => {
+            tx_response
+                .send(state.handle_set_exclusion(search, tx_callback_events))
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::SetExclusion")
+                })?;
+        }
+        Api::GetFilteredPosition((row, tx_response)) => {
+            tx_response
+                .send(match state.filtered.as_ref() {
+                    Some(filtered) => filtered.view_index(row),
+                    None => Some(row),
+                })
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetFilteredPosition")
+                })?;
+        }
+        Api::GetViewRanges((ranges, tx_response)) => {
+            tx_response
+                .send(state.get_view_ranges(ranges))
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetViewRanges")
+                })?;
+        }
         Api::GetSearchResultLen(tx_response) => {
             tx_response.send(state.search_map.len()).map_err(|_| {
                 stypes::NativeError::channel("Failed to respond to Api::GetSearchResultLen")
@@ -817,6 +1333,7 @@ async fn handle_api_msg(
 
             if result {
                 state.search_map.set(None, None);
+                state.highlighter = Highlighter::default();
                 state.indexes.drop_search()?;
             }
             tx_callback_events.send(stypes::CallbackEvent::no_search_results())?;
@@ -829,7 +1346,8 @@ async fn handle_api_msg(
             let update: Option<stypes::FilterMatchList> =
                 matches.as_ref().map(|matches| matches.into());
             if let Some(matches) = matches.as_ref() {
-                state.indexes.set_search_results(matches)?;
+                let visible = state.visible_matches(matches);
+                state.indexes.set_search_results(&visible)?;
             }
             state.search_map.set(matches, stats);
             tx_callback_events.send(stypes::CallbackEvent::SearchMapUpdated(update))?;
@@ -841,6 +1359,97 @@ async fn handle_api_msg(
                 stypes::NativeError::channel("Failed to respond to Api::SetMatches")
             })?;
         }
+        Api::GetNamedSearchHolder((id, tx_response)) => {
+            tx_response
+                .send(state.handle_get_named_search_holder(id).await)
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetNamedSearchHolder")
+                })?;
+        }
+        Api::SetNamedSearchHolder((id, holder, tx_response)) => {
+            state
+                .searcher_tx
+                .send(SearchRequest::SetNamedSearchHolder {
+                    id,
+                    holder,
+                    tx_response,
+                })
+                .await
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to send set to set named search holder")
+                })?;
+        }
+        Api::DropNamedSearch((id, tx_response)) => {
+            let (tx_result, rx_result) = oneshot::channel();
+            state
+                .searcher_tx
+                .send(SearchRequest::DropNamedSearch {
+                    id: id.clone(),
+                    tx_result,
+                })
+                .await
+                .map_err(|_| {
+                    stypes::NativeError::channel(
+                        "Failed to send drop named search request to searchers",
+                    )
+                })?;
+            let result = rx_result.await.map_err(|_| {
+                stypes::NativeError::channel("Failed to receive drop response from searchers")
+            })?;
+            if result {
+                state.named_highlighters.remove(&id);
+            }
+            if result && state.named_searches.remove(&id).is_some() {
+                if let Some(filtered) = state.get_filtered_by(&id) {
+                    filtered.set_hidden(std::iter::empty());
+                    state.update_filtered(tx_callback_events)?;
+                }
+                tx_callback_events.send(stypes::CallbackEvent::named_search_results(
+                    id,
+                    0,
+                    HashMap::new(),
+                ))?;
+            }
+            tx_response.send(result).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::DropNamedSearch")
+            })?;
+        }
+        Api::SetNamedMatches((id, matches, stats, tx_response)) => {
+            let rows = state.session_file.len();
+            let map = state.named_searches.entry(id.clone()).or_insert_with(|| {
+                let mut map = SearchMap::new();
+                map.set_stream_len(rows);
+                map
+            });
+            map.set(matches, stats);
+            tx_callback_events.send(stypes::CallbackEvent::named_search_results(
+                id.clone(),
+                map.len() as u64,
+                map.get_stats(),
+            ))?;
+            if let Some(filtered) = state
+                .filtered
+                .as_mut()
+                .filter(|filtered| filtered.search() == id)
+            {
+                filtered.set_hidden(map.matches().iter().map(|m| m.index));
+                state.update_filtered(tx_callback_events)?;
+            }
+            tx_response.send(()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetNamedMatches")
+            })?;
+        }
+        Api::SetHighlighter((search, highlighter, tx_response)) => {
+            match search {
+                None => state.highlighter = highlighter,
+                Some(id) => {
+                    state.named_highlighters.insert(id, highlighter);
+                }
+            }
+            tx_response.send(()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetHighlighter")
+            })?;
+        }
         Api::GetSearchValuesHolder((_uuid, tx_response)) => {
             tx_response
                 .send(state.handle_get_search_values_holder().await)
@@ -888,7 +1497,7 @@ async fn handle_api_msg(
         }
         Api::GetIndexedRanges(tx_response) => {
             tx_response
-                .send(state.indexes.get_all_as_ranges())
+                .send(state.handle_get_indexed_ranges())
                 .map_err(|_| {
                     stypes::NativeError::channel("Failed to respond to Api::GetIndexedRanges")
                 })?;
@@ -929,6 +1538,18 @@ async fn handle_api_msg(
                 stypes::NativeError::channel("Failed to respond to Api::GetAttachments")
             })?;
         }
+        Api::GetDltRegistry(tx_response) => {
+            tx_response.send(state.dlt_registry.clone()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetDltRegistry")
+            })?;
+        }
+        Api::GetDltContexts(tx_response) => {
+            tx_response
+                .send(state.handle_get_dlt_contexts())
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetDltContexts")
+                })?;
+        }
         Api::Shutdown => {
             state_cancellation_token.cancel();
             debug!("shutdown has been requested");
@@ -956,7 +1577,8 @@ async fn handle_searchers(
         SearchResponse::SearchRegularResult(res) => {
             match res {
                 Ok((_processed, mut matches, stats)) => {
-                    state.indexes.append_search_results(&matches)?;
+                    let visible = state.visible_matches(&matches);
+                    state.indexes.append_search_results(&visible)?;
                     state.search_map.append_stats(stats);
 
                     let updates: stypes::FilterMatchList = (&matches).into();
@@ -971,6 +1593,24 @@ async fn handle_searchers(
                 Err(err) => error!("Fail to append search: {err}"),
             };
         }
+        SearchResponse::SearchNamedResult(id, res) => match res {
+            Ok((_processed, mut matches, stats)) => {
+                if let Some(filtered) = state.get_filtered_by(&id) {
+                    filtered.append_hidden(matches.iter().map(|m| m.index));
+                    state.update_filtered(tx_callback_events)?;
+                }
+                if let Some(map) = state.named_searches.get_mut(&id) {
+                    map.append_stats(stats);
+                    let found = map.append(&mut matches) as u64;
+                    tx_callback_events.send(stypes::CallbackEvent::named_search_results(
+                        id,
+                        found,
+                        map.get_stats(),
+                    ))?;
+                }
+            }
+            Err(err) => error!("Fail to append named search \"{id}\": {err}"),
+        },
         SearchResponse::SearchValueResult(value_search_output) => match value_search_output {
             Ok(ValueSearchOutput { values, .. }) => {
                 state.values.append_values(values);
@@ -0,0 +1,169 @@
+//! Includes the background indexer, which builds n-gram index of the session file while
+//! the session is being written. Searches use the index to skip parts of the session file
+//! without possible matches.
+
+use log::{debug, warn};
+use processor::search::ngrams::{NgramIndex, SharedNgramIndex};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tokio::{select, sync::watch, task};
+use tokio_util::sync::CancellationToken;
+
+/// Maximum count of bytes indexed at once. Searches are running without the index while
+/// it's being updated, so the index is released between steps.
+const STEP_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Minimal interval between savings of the index.
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub struct NgramIndexer {
+    index: SharedNgramIndex,
+    tx_bytes: watch::Sender<u64>,
+    cancel: CancellationToken,
+}
+
+impl NgramIndexer {
+    /// Starts indexing of the session file. Persisted index is used if it still matches
+    /// the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Path to the session file.
+    /// * `index_file` - Path to the file to persist the index in; `None` if the index
+    ///   isn't persisted. It makes sense only for files, which are kept after the session
+    ///   is closed.
+    pub fn spawn(filename: PathBuf, index_file: Option<PathBuf>) -> Self {
+        let index = Arc::new(RwLock::new(NgramIndex::new()));
+        let (tx_bytes, rx_bytes) = watch::channel(0);
+        let cancel = CancellationToken::new();
+        tokio::spawn(run(
+            filename,
+            index_file,
+            index.clone(),
+            rx_bytes,
+            cancel.clone(),
+        ));
+        Self {
+            index,
+            tx_bytes,
+            cancel,
+        }
+    }
+
+    pub fn index(&self) -> SharedNgramIndex {
+        self.index.clone()
+    }
+
+    /// Notifies the indexer about new rows in the session file.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Length of the processed content of the session file.
+    pub fn update(&self, bytes: u64) {
+        self.tx_bytes.send_replace(bytes);
+    }
+}
+
+impl Drop for NgramIndexer {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+async fn run(
+    filename: PathBuf,
+    index_file: Option<PathBuf>,
+    index: SharedNgramIndex,
+    mut rx_bytes: watch::Receiver<u64>,
+    cancel: CancellationToken,
+) {
+    if let Some(index_file) = index_file.clone() {
+        let path = filename.clone();
+        match task::spawn_blocking(move || NgramIndex::load(&path, &index_file)).await {
+            Ok(Ok(Some(loaded))) => {
+                debug!("Persisted n-gram index of {filename:?} is used");
+                if let Ok(mut index) = index.write() {
+                    *index = loaded;
+                }
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(err)) => warn!("Fail to load n-gram index of {filename:?}: {err}"),
+            Err(err) => warn!("Fail to load n-gram index of {filename:?}: {err}"),
+        }
+    }
+    let mut saved = Instant::now();
+    let mut changed = false;
+    loop {
+        select! {
+            _ = cancel.cancelled() => break,
+            res = rx_bytes.changed() => if res.is_err() {
+                break;
+            }
+        }
+        let to = *rx_bytes.borrow_and_update();
+        while !cancel.is_cancelled() {
+            let index = index.clone();
+            let path = filename.clone();
+            let step = task::spawn_blocking(move || {
+                let mut index = index.write().map_err(|e| e.to_string())?;
+                let before = index.bytes();
+                let more = index
+                    .update(&path, to, STEP_BYTES)
+                    .map_err(|e| e.to_string())?;
+                Ok::<(bool, bool), String>((more, index.bytes() != before))
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|res| res);
+            match step {
+                Ok((more, updated)) => {
+                    changed |= updated;
+                    if !more {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    warn!("Fail to update n-gram index of {filename:?}: {err}");
+                    return;
+                }
+            }
+        }
+        if let Some(index_file) = index_file.as_ref()
+            && changed
+            && saved.elapsed() >= SAVE_INTERVAL
+        {
+            save(&filename, index_file, &index).await;
+            saved = Instant::now();
+            changed = false;
+        }
+    }
+    if let Some(index_file) = index_file.as_ref()
+        && changed
+    {
+        save(&filename, index_file, &index).await;
+    }
+}
+
+async fn save(filename: &Path, index_file: &Path, index: &SharedNgramIndex) {
+    let path = filename.to_path_buf();
+    let index_file = index_file.to_path_buf();
+    let index = index.clone();
+    // Index is saved under read lock, so searches keep using it while it's being saved
+    let res = task::spawn_blocking(move || {
+        index
+            .read()
+            .map_err(|e| e.to_string())?
+            .save(&path, &index_file)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|res| res);
+    if let Err(err) = res {
+        warn!("Fail to save n-gram index of {filename:?}: {err}");
+    }
+}
@@ -1,13 +1,17 @@
 //! Includes Definitions for searchers in sessions with their current state.
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use tokio::sync::mpsc::{self};
 use tokio_util::sync::CancellationToken;
 
-use processor::search::searchers::{
-    self, BaseSearcher, SearchState,
-    regular::{self, RegularSearchHolder, RegularSearchState},
-    values::{OperationResults, ValueSearchHolder, ValueSearchState},
+use processor::search::{
+    ngrams::SharedNgramIndex,
+    scope::{SourcesMap, TimestampsMap},
+    searchers::{
+        self, BaseSearcher, SearchState,
+        regular::{self, RegularSearchHolder, RegularSearchState},
+        values::{OperationResults, ValueSearchHolder, ValueSearchState},
+    },
 };
 
 mod definitions;
@@ -38,17 +42,40 @@ async fn run(
     let mut searchers = Searchers {
         regular: SearcherState::NotInited,
         values: SearcherState::NotInited,
+        named: HashMap::new(),
     };
     while let Some(request) = request_rx.recv().await {
         match request {
             SearchRequest::SearchRegular {
                 rows,
                 bytes,
+                sources,
+                timestamps,
+                index,
                 cancel,
             } => {
-                let Some(res) =
-                    tokio::task::block_in_place(|| searchers.regular.search(rows, bytes, cancel))
-                else {
+                let named = tokio::task::block_in_place(|| {
+                    searchers
+                        .named
+                        .iter_mut()
+                        .filter_map(|(id, searcher)| {
+                            searcher
+                                .search(rows, bytes, &sources, &timestamps, &index, cancel.clone())
+                                .map(|res| (id.to_owned(), res))
+                        })
+                        .collect::<Vec<_>>()
+                });
+                for (id, res) in named {
+                    let res = response_tx
+                        .send(SearchResponse::SearchNamedResult(id, res))
+                        .await;
+                    log_if_err(res);
+                }
+                let Some(res) = tokio::task::block_in_place(|| {
+                    searchers
+                        .regular
+                        .search(rows, bytes, &sources, &timestamps, &index, cancel)
+                }) else {
                     continue;
                 };
                 let res = response_tx
@@ -192,6 +219,60 @@ async fn run(
                 let res = tx_result.send(result);
                 log_if_err(res);
             }
+            SearchRequest::GetNamedSearchHolder {
+                id,
+                filename,
+                sender,
+            } => {
+                let searcher = searchers
+                    .named
+                    .entry(id)
+                    .or_insert(SearcherState::NotInited);
+                let holder = match std::mem::replace(searcher, SearcherState::InUse) {
+                    SearcherState::Available(holder) => Ok(holder),
+                    SearcherState::InUse => Err(stypes::NativeError::channel(
+                        "Named search holder is in use",
+                    )),
+                    SearcherState::NotInited => Ok(RegularSearchHolder::new(&filename, 0, 0)),
+                };
+                let res = sender.send(holder);
+                log_if_err(res);
+            }
+            SearchRequest::SetNamedSearchHolder {
+                id,
+                holder,
+                tx_response,
+            } => {
+                let result = match searchers.named.get_mut(&id) {
+                    Some(searcher) if searcher.is_in_use() => {
+                        if let Some(holder) = holder {
+                            searcher.set_searcher(holder);
+                        } else {
+                            searchers.named.remove(&id);
+                        }
+                        Ok(())
+                    }
+                    _ => Err(stypes::NativeError::channel(
+                        "Cannot set named search holder - it wasn't in use",
+                    )),
+                };
+                let res = tx_response.send(result);
+                log_if_err(res);
+            }
+            SearchRequest::DropNamedSearch { id, tx_result } => {
+                let result = if searchers
+                    .named
+                    .get(&id)
+                    .is_some_and(|searcher| searcher.is_in_use())
+                {
+                    false
+                } else {
+                    searchers.named.remove(&id);
+                    true
+                };
+                let res = tx_result.send(result);
+                log_if_err(res);
+            }
         }
     }
 }
@@ -242,15 +323,25 @@ impl SearcherState<RegularSearchState> {
         &mut self,
         rows_count: u64,
         read_bytes: u64,
+        sources: &SourcesMap,
+        timestamps: &TimestampsMap,
+        index: &Option<SharedNgramIndex>,
         cancel_token: CancellationToken,
     ) -> Option<regular::SearchResults> {
         match self {
-            Self::Available(h) => Some(searchers::regular::search(
-                h,
-                rows_count,
-                read_bytes,
-                cancel_token,
-            )),
+            Self::Available(h) => {
+                h.set_sources_map(sources.clone());
+                if let Err(err) = h.set_timestamps_map(timestamps.clone()) {
+                    return Some(Err(err));
+                }
+                h.set_ngram_index(index.clone());
+                Some(searchers::regular::search(
+                    h,
+                    rows_count,
+                    read_bytes,
+                    cancel_token,
+                ))
+            }
             _ => None,
         }
     }
@@ -260,4 +351,6 @@ impl SearcherState<RegularSearchState> {
 pub struct Searchers {
     pub regular: SearcherState<RegularSearchState>,
     pub values: SearcherState<ValueSearchState>,
+    /// Searchers of named searches, which are running independently of the main search.
+    pub named: HashMap<String, SearcherState<RegularSearchState>>,
 }
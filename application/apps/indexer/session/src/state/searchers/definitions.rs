@@ -3,9 +3,13 @@ use std::path::PathBuf;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 
-use processor::search::searchers::{
-    regular::{self, RegularSearchHolder},
-    values::{OperationResults, ValueSearchHolder},
+use processor::search::{
+    ngrams::SharedNgramIndex,
+    scope::{SourcesMap, TimestampsMap},
+    searchers::{
+        regular::{self, RegularSearchHolder},
+        values::{OperationResults, ValueSearchHolder},
+    },
 };
 
 #[derive(Debug)]
@@ -13,6 +17,12 @@ pub enum SearchRequest {
     SearchRegular {
         rows: u64,
         bytes: u64,
+        /// Ranges of rows written by sources, to restrict searches to selected sources.
+        sources: SourcesMap,
+        /// Timestamps of rows, to restrict searches to a time window.
+        timestamps: TimestampsMap,
+        /// N-gram index of the session file, if it's enabled.
+        index: Option<SharedNgramIndex>,
         cancel: CancellationToken,
     },
     SearchValue {
@@ -42,10 +52,25 @@ pub enum SearchRequest {
     DropSearchValue {
         tx_result: oneshot::Sender<bool>,
     },
+    GetNamedSearchHolder {
+        id: String,
+        filename: PathBuf,
+        sender: oneshot::Sender<Result<RegularSearchHolder, stypes::NativeError>>,
+    },
+    SetNamedSearchHolder {
+        id: String,
+        holder: Option<RegularSearchHolder>,
+        tx_response: oneshot::Sender<Result<(), stypes::NativeError>>,
+    },
+    DropNamedSearch {
+        id: String,
+        tx_result: oneshot::Sender<bool>,
+    },
 }
 
 #[derive(Debug)]
 pub enum SearchResponse {
     SearchRegularResult(regular::SearchResults),
     SearchValueResult(OperationResults),
+    SearchNamedResult(String, regular::SearchResults),
 }
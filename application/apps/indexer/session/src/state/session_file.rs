@@ -7,6 +7,7 @@ use crate::paths;
 use log::debug;
 use processor::{
     grabber::{Grabber, LineRange},
+    search::scope::TimestampsMap,
     text_source::TextFileSource,
 };
 use std::{
@@ -56,6 +57,11 @@ pub struct SessionFile {
     pub writer: Option<BufWriter<File>>,
     pub last_message_timestamp: Instant,
     pub sources: SourceIDs,
+    /// Count of rows written into the generated session file.
+    pub written_rows: u64,
+    /// Timestamps of written rows. The map is shared with searchers, which are restricted
+    /// to a time window.
+    pub timestamps: TimestampsMap,
 }
 
 impl SessionFile {
@@ -66,6 +72,8 @@ impl SessionFile {
             writer: None,
             last_message_timestamp: Instant::now(),
             sources: SourceIDs::new(),
+            written_rows: 0,
+            timestamps: TimestampsMap::default(),
         }
     }
 
@@ -123,6 +131,7 @@ impl SessionFile {
         source_id: u16,
         state_cancellation_token: CancellationToken,
         msg: String,
+        timestamps: Vec<(u64, i64)>,
     ) -> Result<SessionFileState, stypes::NativeError> {
         if !self.sources.is_source_same(source_id) {
             self.flush(state_cancellation_token.clone(), false)?;
@@ -135,6 +144,14 @@ impl SessionFile {
             )),
         })?;
         writer.write_all(msg.as_bytes())?;
+        let first = self.written_rows;
+        self.written_rows += msg.bytes().filter(|byte| *byte == b'\n').count() as u64;
+        self.timestamps.append(
+            timestamps
+                .into_iter()
+                .map(|(row, timestamp)| (first + row, timestamp))
+                .collect(),
+        );
 
         self.sources.source_update(source_id);
         if self.last_message_timestamp.elapsed().as_millis() > FLUSH_DATA_IN_MS {
@@ -216,6 +233,7 @@ impl SessionFile {
                     content,
                     pos,
                     nature: 0,
+                    spans: Vec::new(),
                 }
             })
             .collect())
@@ -1,7 +1,8 @@
 //! Module to handle Source IDs which are their indexes when they are combined
 //! in the same session.
 
-use std::{collections::HashMap, ops::RangeInclusive};
+use processor::search::scope::SourcesMap;
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 pub struct MappedRanges<'a> {
     ranges: Vec<&'a (RangeInclusive<u64>, u16)>,
@@ -26,7 +27,9 @@ impl<'a> MappedRanges<'a> {
 #[derive(Debug)]
 pub struct SourceIDs {
     pub sources: HashMap<u16, String>,
-    pub map: Vec<(RangeInclusive<u64>, u16)>,
+    /// Ranges of rows written by sources. The map is shared with searchers, which are
+    /// restricted to some sources; it's copied on writing only if it's still in use.
+    pub map: SourcesMap,
     pub recent: Option<u16>,
 }
 
@@ -34,7 +37,7 @@ impl SourceIDs {
     pub fn new() -> Self {
         Self {
             sources: HashMap::new(),
-            map: vec![],
+            map: Arc::default(),
             recent: None,
         }
     }
@@ -89,7 +92,21 @@ impl SourceIDs {
     }
 
     pub fn add_range(&mut self, range: RangeInclusive<u64>, source_id: u16) {
-        self.map.push((range, source_id));
+        let map = Arc::make_mut(&mut self.map);
+        // Rows of the same source are written in series, so ranges can be merged in most cases.
+        if let Some((last, id)) = map.last_mut()
+            && *id == source_id
+            && *last.end() + 1 == *range.start()
+        {
+            *last = *last.start()..=*range.end();
+        } else {
+            map.push((range, source_id));
+        }
+    }
+
+    /// Returns ranges of rows written by sources.
+    pub fn get_map(&self) -> SourcesMap {
+        self.map.clone()
     }
 
     pub fn get_mapped_ranges(&self, requested: &RangeInclusive<u64>) -> MappedRanges<'_> {
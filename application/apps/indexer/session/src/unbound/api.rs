@@ -117,6 +117,34 @@ impl UnboundSessionAPI {
             .await
     }
 
+    pub async fn detect_file_format(
+        &self,
+        id: u64,
+        file_path: String,
+    ) -> Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(
+            id,
+            rx_results,
+            Command::DetectFileFormat(file_path, tx_results),
+        )
+        .await
+    }
+
+    pub async fn get_parser_render_options(
+        &self,
+        id: u64,
+        parser: stypes::ParserType,
+    ) -> Result<stypes::CommandOutcome<stypes::ParserRenderOptions>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(
+            id,
+            rx_results,
+            Command::GetParserRenderOptions(parser, tx_results),
+        )
+        .await
+    }
+
     pub async fn get_file_checksum(
         &self,
         id: u64,
@@ -7,14 +7,15 @@ use std::{
     time::{Duration, SystemTime},
 };
 
-use crate::paths::{get_chipmunk_downloads_dir, get_streams_dir};
+use crate::paths::{get_chipmunk_downloads_dir, get_ngrams_dir, get_streams_dir};
 
 /// Cleanup temporary files that is generated by Chipmunk on previous sessions.
 pub fn cleanup_temp_files() -> Result<(), Vec<stypes::NativeError>> {
     let res_tmp = cleanup_temp_dir();
     let res_download = cleanup_download_dir();
+    let res_ngrams = cleanup_ngrams_dir();
 
-    let errs: Vec<_> = [res_tmp, res_download]
+    let errs: Vec<_> = [res_tmp, res_download, res_ngrams]
         .into_iter()
         .filter_map(|res| res.err())
         .collect();
@@ -67,6 +68,30 @@ fn cleanup_download_dir() -> Result<(), stypes::NativeError> {
     Ok(())
 }
 
+/// Iterates through n-gram indexes directory and remove the indexes, which weren't updated
+/// for one month.
+fn cleanup_ngrams_dir() -> Result<(), stypes::NativeError> {
+    let ngrams_dir = get_ngrams_dir()?;
+    if !ngrams_dir.exists() {
+        return Ok(());
+    }
+
+    const ONE_MONTHS_SECONDS: u64 = 30 * 60 * 24 * 60;
+    let modified_limit = SystemTime::now()
+        .checked_sub(Duration::from_secs(ONE_MONTHS_SECONDS))
+        .ok_or_else(|| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(String::from(
+                "Error while calculating modification time limit",
+            )),
+        })?;
+
+    cleanup_dir(&ngrams_dir, modified_limit)?;
+
+    Ok(())
+}
+
 // Clean files and directory within the given path that have a modified time older than
 // the given modified date limit
 fn cleanup_dir(path: &Path, modified_date_limit: SystemTime) -> io::Result<()> {
@@ -0,0 +1,224 @@
+//! Detectors of binary formats recognized by their signatures and record structure.
+
+use super::{candidate, scaled};
+use std::path::Path;
+use stypes::{
+    CanParserSettings, CanTraceFormat, DltParserSettings, FileCompression, FileFormat,
+    FormatCandidate, LogcatFormat, LogcatParserSettings, ParserType,
+};
+
+/// Minimal number of consecutive records to recognize a format without signature.
+const MIN_RECORDS: usize = 3;
+/// Length of the DLT storage header.
+const DLT_STORAGE_HEADER_LEN: usize = 16;
+/// Length of the header type, message counter and length of DLT v2 messages.
+const DLT_V2_BASE_HEADER_LEN: usize = 7;
+/// Header length of `logger_entry` v1, which has no header size field.
+const LOGCAT_V1_HEADER_LEN: usize = 20;
+/// Maximal payload length of `logger_entry` v4.
+const LOGCAT_MAX_PAYLOAD_LEN: usize = 5 * 1024;
+
+/// Recognizes compressed files by their signatures.
+pub fn compression(sample: &[u8]) -> Option<FileCompression> {
+    if sample.starts_with(&[0x1F, 0x8B]) {
+        Some(FileCompression::Gzip)
+    } else if sample.starts_with(b"BZh") {
+        Some(FileCompression::Bzip2)
+    } else if sample.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+        Some(FileCompression::Xz)
+    } else if sample.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        Some(FileCompression::Zstd)
+    } else if sample.starts_with(b"PK\x03\x04") {
+        Some(FileCompression::Zip)
+    } else {
+        None
+    }
+}
+
+pub fn detect(path: &Path, sample: &[u8]) -> Vec<FormatCandidate> {
+    let mut candidates = Vec::new();
+    if sample.starts_with(b"LPKSHHRH") {
+        candidates.push(candidate(
+            path,
+            FileFormat::Journal,
+            ParserType::Journal(()),
+            100,
+            vec![String::from("systemd journal file signature")],
+        ));
+    }
+    if sample.starts_with(b"LOGG") {
+        candidates.push(candidate(
+            path,
+            FileFormat::Binary,
+            ParserType::Can(CanParserSettings {
+                format: CanTraceFormat::Blf,
+                dbc_file_paths: None,
+            }),
+            95,
+            vec![String::from("Vector BLF file signature")],
+        ));
+    }
+    if sample.starts_with(b"DLT\x01") {
+        let messages = dlt_messages(sample, DLT_STORAGE_HEADER_LEN);
+        let mut evidence = vec![String::from("DLT storage header at the start of the file")];
+        if messages > 1 {
+            evidence.push(format!("{messages} consecutive DLT messages"));
+        }
+        candidates.push(candidate(
+            path,
+            FileFormat::Binary,
+            dlt_parser(true),
+            if messages > 1 { 98 } else { 85 },
+            evidence,
+        ));
+    }
+    // Text which starts with digits passes the checks of the DLT header. Binary
+    // records contain zero bytes nearly always.
+    if !sample.contains(&0) {
+        return candidates;
+    }
+    let messages = dlt_messages(sample, 0);
+    if messages >= MIN_RECORDS {
+        candidates.push(candidate(
+            path,
+            FileFormat::Binary,
+            dlt_parser(false),
+            60 + scaled(messages, 20, 25),
+            vec![format!(
+                "{messages} consecutive DLT messages without storage header"
+            )],
+        ));
+    }
+    let entries = logcat_entries(sample);
+    if entries >= MIN_RECORDS {
+        candidates.push(candidate(
+            path,
+            FileFormat::Binary,
+            ParserType::Logcat(LogcatParserSettings {
+                format: LogcatFormat::Binary,
+                tz: None,
+                year: None,
+            }),
+            60 + scaled(entries, 20, 25),
+            vec![format!("{entries} consecutive logcat logger entries")],
+        ));
+    }
+    candidates
+}
+
+fn dlt_parser(with_storage_header: bool) -> ParserType {
+    ParserType::Dlt(DltParserSettings {
+        with_storage_header,
+        ..DltParserSettings::default()
+    })
+}
+
+/// Minimal length of a DLT message with the given header type.
+fn dlt_header_len(htyp: u8) -> usize {
+    let mut len = 4;
+    // With ECU id, session id and timestamp.
+    for (flag, field_len) in [(0x04, 4), (0x08, 4), (0x10, 4)] {
+        if htyp & flag != 0 {
+            len += field_len;
+        }
+    }
+    // Extended header.
+    if htyp & 0x01 != 0 {
+        len += 10;
+    }
+    len
+}
+
+/// Counts consecutive DLT messages at the start of the sample, each preceded by
+/// `prefix_len` bytes of storage header.
+fn dlt_messages(sample: &[u8], prefix_len: usize) -> usize {
+    let mut offset = 0;
+    let mut count = 0;
+    while let Some(header) = sample.get(offset + prefix_len..offset + prefix_len + 4) {
+        if prefix_len > 0 && !sample[offset..].starts_with(b"DLT\x01") {
+            break;
+        }
+        let htyp = header[0];
+        let (len, min_len) = match (htyp >> 5) & 0b111 {
+            1 => (
+                u16::from_be_bytes([header[2], header[3]]) as usize,
+                dlt_header_len(htyp),
+            ),
+            // Version 2 has a 32 bit header type followed by the message counter.
+            2 => match sample.get(offset + prefix_len + 5..offset + prefix_len + 7) {
+                Some(len) => (
+                    u16::from_be_bytes([len[0], len[1]]) as usize,
+                    DLT_V2_BASE_HEADER_LEN,
+                ),
+                None => break,
+            },
+            _ => break,
+        };
+        if len < min_len {
+            break;
+        }
+        count += 1;
+        offset += prefix_len + len;
+    }
+    count
+}
+
+/// Counts consecutive `logger_entry` records at the start of the sample.
+fn logcat_entries(sample: &[u8]) -> usize {
+    let mut offset = 0;
+    let mut count = 0;
+    while let Some(header) = sample.get(offset..offset + LOGCAT_V1_HEADER_LEN) {
+        let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+        let header_len = match u16::from_le_bytes([header[2], header[3]]) {
+            0 => LOGCAT_V1_HEADER_LEN,
+            size @ (20 | 24 | 28) => size as usize,
+            _ => break,
+        };
+        if len == 0 || len > LOGCAT_MAX_PAYLOAD_LEN {
+            break;
+        }
+        count += 1;
+        offset += header_len + len;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dlt_and_logcat_records() {
+        // Standard header with ECU id and extended header, 4 bytes payload.
+        let message = [
+            0x25, 0x00, 0x00, 0x16, b'E', b'C', b'U', b'1', 0x41, 0x01, b'A', b'P', b'P', b'1',
+            b'C', b'T', b'X', b'1', 0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut stored = Vec::new();
+        for _ in 0..3 {
+            stored.extend_from_slice(b"DLT\x01\0\0\0\0\0\0\0\0ECU1");
+            stored.extend_from_slice(&message);
+        }
+        assert_eq!(dlt_messages(&stored, DLT_STORAGE_HEADER_LEN), 3);
+        assert_eq!(dlt_messages(&message.repeat(4), 0), 4);
+        assert!(detect(Path::new("log.txt"), b"2024-03-17 16:13:38 started\n").is_empty());
+
+        // logger_entry v4 with 24 bytes header and 8 bytes payload.
+        let mut entry = vec![0x08, 0x00, 0x18, 0x00];
+        entry.extend_from_slice(&[0; 20]);
+        entry.extend_from_slice(b"\x04tag\0msg");
+        let candidates = detect(Path::new("log.bin"), &entry.repeat(5));
+        assert_eq!(candidates.len(), 1);
+        assert!(matches!(
+            candidates[0].options.parser,
+            ParserType::Logcat(LogcatParserSettings {
+                format: LogcatFormat::Binary,
+                ..
+            })
+        ));
+        assert!(matches!(
+            compression(&[0x1F, 0x8B, 0x08]),
+            Some(FileCompression::Gzip)
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+//! Detection of the format of files.
+//!
+//! Inspects the beginning of a file and suggests the options to open it with,
+//! ranked by the confidence in them. Each detector contributes candidates
+//! together with the findings they are based on. Parser plugins can contribute
+//! candidates by describing the files they handle in their metadata.
+
+mod binary;
+mod network;
+mod text;
+
+use crate::unbound::signal::Signal;
+use plugins_host::plugins_manager::PluginsManager;
+use sources::binary::journal::journal_files;
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use stypes::{
+    CommandOutcome, ComputationError, FileFormat, FormatCandidate, FormatDetection, ObserveOptions,
+    ParserType, PluginEntity, PluginParserGeneralSettings, PluginParserSettings, PluginType,
+};
+use tokio::sync::RwLock;
+
+/// Number of bytes at the beginning of a file which are inspected.
+const SAMPLE_LEN: usize = 64 * 1024;
+
+fn candidate(
+    path: &Path,
+    format: FileFormat,
+    parser: ParserType,
+    confidence: u8,
+    evidence: Vec<String>,
+) -> FormatCandidate {
+    FormatCandidate {
+        options: ObserveOptions::file(path.to_owned(), format, parser),
+        confidence: confidence.min(100),
+        evidence,
+    }
+}
+
+/// Returns the share of `part` in `total` scaled to `range`.
+fn scaled(part: usize, total: usize, range: u8) -> u8 {
+    if total == 0 {
+        return 0;
+    }
+    (part.min(total) * range as usize / total) as u8
+}
+
+fn read_sample(path: &Path) -> Result<Vec<u8>, ComputationError> {
+    let mut sample = Vec::with_capacity(SAMPLE_LEN);
+    File::open(path)
+        .and_then(|file| file.take(SAMPLE_LEN as u64).read_to_end(&mut sample))
+        .map_err(|e| ComputationError::IoOperation(e.to_string()))?;
+    Ok(sample)
+}
+
+/// Detects the format of the given file or journal directory. The candidates
+/// are ordered by their confidence, starting with the most likely one.
+pub async fn detect_file_format(
+    file_path: String,
+    plugins_manager: &RwLock<PluginsManager>,
+    signal: Signal,
+) -> Result<CommandOutcome<FormatDetection>, ComputationError> {
+    let path = PathBuf::from(file_path);
+    if path.is_dir() {
+        let files =
+            journal_files(&path).map_err(|e| ComputationError::IoOperation(e.to_string()))?;
+        let candidates = if files.is_empty() {
+            Vec::new()
+        } else {
+            vec![candidate(
+                &path,
+                FileFormat::Journal,
+                ParserType::Journal(()),
+                90,
+                vec![format!("directory contains {} journal files", files.len())],
+            )]
+        };
+        return Ok(CommandOutcome::Finished(FormatDetection {
+            compression: None,
+            candidates,
+        }));
+    }
+    let sample = read_sample(&path)?;
+    if let Some(compression) = binary::compression(&sample) {
+        return Ok(CommandOutcome::Finished(FormatDetection {
+            compression: Some(compression),
+            candidates: Vec::new(),
+        }));
+    }
+    let mut candidates = binary::detect(&path, &sample);
+    candidates.extend(network::detect(&path, &sample));
+    if signal.is_cancelled() {
+        return Ok(CommandOutcome::Cancelled);
+    }
+    candidates.extend(text::detect(&path, &sample, sample.len() == SAMPLE_LEN));
+    let manager = plugins_manager.read().await;
+    for plugin in manager.installed_plugins() {
+        candidates.extend(plugin_candidate(&path, &sample, plugin));
+    }
+    // The sort is stable, so candidates with the same confidence keep the
+    // order of the detectors.
+    candidates.sort_by(|a, b| b.confidence.cmp(&a.confidence));
+    Ok(CommandOutcome::Finished(FormatDetection {
+        compression: None,
+        candidates,
+    }))
+}
+
+/// Matches the file against the detector of a parser plugin.
+fn plugin_candidate(path: &Path, sample: &[u8], plugin: &PluginEntity) -> Option<FormatCandidate> {
+    if plugin.plugin_type != PluginType::Parser {
+        return None;
+    }
+    let detector = plugin.metadata.detector.as_ref()?;
+    let title = &plugin.metadata.title;
+    let mut evidence = Vec::new();
+    let mut confidence = 0;
+    if let Some(signature) = detector
+        .signatures
+        .iter()
+        .find(|signature| decode_hex(signature).is_some_and(|bytes| sample.starts_with(&bytes)))
+    {
+        evidence.push(format!(
+            "file starts with signature {signature} of plugin {title}"
+        ));
+        confidence = 90;
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    if let Some(extension) = extension
+        && detector
+            .extensions
+            .iter()
+            .any(|ext| ext.trim_start_matches('.').to_lowercase() == extension)
+    {
+        evidence.push(format!(
+            "extension .{extension} is handled by plugin {title}"
+        ));
+        confidence = confidence.max(50);
+    }
+    if evidence.is_empty() {
+        return None;
+    }
+    let format = if sample.contains(&0) {
+        FileFormat::Binary
+    } else {
+        FileFormat::Text(None)
+    };
+    let parser = ParserType::Plugin(PluginParserSettings {
+        plugin_path: plugin.dir_path.clone(),
+        general_settings: PluginParserGeneralSettings::default(),
+        plugin_configs: Vec::new(),
+    });
+    Some(candidate(path, format, parser, confidence, evidence))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stypes::{ObserveOrigin, PluginFormatDetector, PluginInfo, PluginMetadata, RenderOptions};
+
+    fn format_and_parser(candidate: &FormatCandidate) -> (&FileFormat, &ParserType) {
+        match &candidate.options.origin {
+            ObserveOrigin::File(_, format, _) => (format, &candidate.options.parser),
+            _ => panic!("candidate isn't a file"),
+        }
+    }
+
+    #[test]
+    fn plugin_detector() {
+        let plugin = PluginEntity {
+            dir_path: PathBuf::from("plugins/parsers/dlt"),
+            plugin_type: PluginType::Parser,
+            info: PluginInfo {
+                wasm_file_path: PathBuf::from("plugins/parsers/dlt/dlt.wasm"),
+                api_version: stypes::SemanticVersion::new(0, 1, 0),
+                plugin_version: stypes::SemanticVersion::new(0, 1, 0),
+                config_schemas: Vec::new(),
+                render_options: RenderOptions::ByteSource,
+            },
+            metadata: PluginMetadata {
+                title: String::from("DLT"),
+                description: None,
+                detector: Some(PluginFormatDetector {
+                    extensions: vec![String::from("dlt")],
+                    signatures: vec![String::from("444c5401")],
+                }),
+            },
+            readme_path: None,
+        };
+        let found = plugin_candidate(Path::new("trace.DLT"), b"DLT\x01\0\0", &plugin).unwrap();
+        assert_eq!(found.confidence, 90);
+        assert_eq!(found.evidence.len(), 2);
+        assert!(matches!(
+            format_and_parser(&found),
+            (FileFormat::Binary, ParserType::Plugin(_))
+        ));
+        let found = plugin_candidate(Path::new("trace.dlt"), b"text", &plugin).unwrap();
+        assert_eq!(found.confidence, 50);
+        assert!(plugin_candidate(Path::new("trace.txt"), b"text", &plugin).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! Detector of PCAP and PCAPNG captures and the protocols they carry.
+
+use super::{SAMPLE_LEN, candidate, scaled};
+use etherparse::{SlicedPacket, TransportSlice};
+use pcap_parser::{Block, PcapBlockOwned, create_reader};
+use std::path::Path;
+use stypes::{
+    DltParserSettings, DoipParserSettings, FileFormat, FormatCandidate, ParserType,
+    SomeIpParserSettings,
+};
+
+/// Maximal number of packets inspected.
+const MAX_PACKETS: usize = 500;
+/// Port of DoIP.
+const DOIP_PORT: u16 = 13400;
+/// Length of the SOME/IP header.
+const SOMEIP_HEADER_LEN: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Dlt,
+    SomeIp,
+    Doip,
+}
+
+impl Protocol {
+    fn name(&self) -> &'static str {
+        match self {
+            Protocol::Dlt => "DLT",
+            Protocol::SomeIp => "SOME/IP",
+            Protocol::Doip => "DoIP",
+        }
+    }
+
+    fn parser(&self) -> ParserType {
+        match self {
+            Protocol::Dlt => ParserType::Dlt(DltParserSettings {
+                with_storage_header: false,
+                ..DltParserSettings::default()
+            }),
+            Protocol::SomeIp => ParserType::SomeIp(SomeIpParserSettings {
+                fibex_file_paths: None,
+                e2e_config_path: None,
+            }),
+            Protocol::Doip => ParserType::Doip(DoipParserSettings {
+                did_file_paths: None,
+            }),
+        }
+    }
+}
+
+fn capture_format(sample: &[u8]) -> Option<(FileFormat, &'static str)> {
+    match sample.get(..4)? {
+        [0x0A, 0x0D, 0x0D, 0x0A] => Some((FileFormat::PcapNG, "pcapng section header")),
+        [0xD4, 0xC3, 0xB2, 0xA1]
+        | [0xA1, 0xB2, 0xC3, 0xD4]
+        | [0x4D, 0x3C, 0xB2, 0xA1]
+        | [0xA1, 0xB2, 0x3C, 0x4D] => Some((FileFormat::PcapLegacy, "pcap file header")),
+        _ => None,
+    }
+}
+
+pub fn detect(path: &Path, sample: &[u8]) -> Vec<FormatCandidate> {
+    let Some((format, header)) = capture_format(sample) else {
+        return Vec::new();
+    };
+    let (packets, found) = protocols(sample);
+    let mut candidates = Vec::new();
+    for protocol in [Protocol::Dlt, Protocol::SomeIp, Protocol::Doip] {
+        let count = found.iter().filter(|p| **p == protocol).count();
+        if count == 0 {
+            continue;
+        }
+        candidates.push(candidate(
+            path,
+            format.clone(),
+            protocol.parser(),
+            60 + scaled(count, found.len(), 35),
+            vec![
+                String::from(header),
+                format!(
+                    "{count} of {packets} UDP/TCP packets contain {}",
+                    protocol.name()
+                ),
+            ],
+        ));
+    }
+    if candidates.is_empty() {
+        // The protocols might be sent later in the capture.
+        for protocol in [Protocol::Dlt, Protocol::SomeIp] {
+            candidates.push(candidate(
+                path,
+                format.clone(),
+                protocol.parser(),
+                20,
+                vec![
+                    String::from(header),
+                    format!("no DLT, SOME/IP or DoIP in the first {packets} UDP/TCP packets"),
+                ],
+            ));
+        }
+    }
+    candidates
+}
+
+/// Returns the number of inspected UDP/TCP packets and the recognized
+/// protocols of their payloads.
+fn protocols(sample: &[u8]) -> (usize, Vec<Protocol>) {
+    let mut packets = 0;
+    let mut found = Vec::new();
+    let Ok(mut reader) = create_reader(SAMPLE_LEN, sample) else {
+        return (packets, found);
+    };
+    while packets < MAX_PACKETS {
+        let consumed = match reader.next() {
+            Ok((consumed, block)) => {
+                let data = match &block {
+                    PcapBlockOwned::Legacy(packet) => packet.data.get(..packet.caplen as usize),
+                    PcapBlockOwned::NG(Block::EnhancedPacket(packet)) => {
+                        packet.data.get(..packet.caplen as usize)
+                    }
+                    PcapBlockOwned::NG(Block::SimplePacket(packet)) => Some(packet.data),
+                    _ => None,
+                };
+                if let Some(payload) = data.and_then(transport_payload) {
+                    packets += 1;
+                    found.extend(classify(payload));
+                }
+                consumed
+            }
+            // The buffer holds the whole sample, so incomplete blocks are truncated.
+            Err(_) => break,
+        };
+        reader.consume(consumed);
+    }
+    (packets, found)
+}
+
+/// Returns the ports and the payload of UDP and TCP packets.
+fn transport_payload(frame: &[u8]) -> Option<(u16, u16, &[u8])> {
+    let packet = SlicedPacket::from_ethernet(frame).ok()?;
+    match packet.transport? {
+        TransportSlice::Udp(udp) => {
+            Some((udp.source_port(), udp.destination_port(), udp.payload()))
+        }
+        TransportSlice::Tcp(tcp) => {
+            Some((tcp.source_port(), tcp.destination_port(), tcp.payload()))
+        }
+        _ => None,
+    }
+}
+
+fn classify((source_port, destination_port, payload): (u16, u16, &[u8])) -> Option<Protocol> {
+    if payload.len() < 8 {
+        return None;
+    }
+    let doip_port = source_port == DOIP_PORT || destination_port == DOIP_PORT;
+    // Protocol version followed by its inverse.
+    if doip_port && payload[0] ^ payload[1] == 0xFF {
+        return Some(Protocol::Doip);
+    }
+    // Protocol version 1 and a length field matching the payload.
+    if payload.len() >= SOMEIP_HEADER_LEN && payload[12] == 0x01 {
+        let len = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]) as usize;
+        if len >= 8 && len <= payload.len() - 8 {
+            return Some(Protocol::SomeIp);
+        }
+    }
+    // Version 1 of the DLT standard header and a length field matching the payload.
+    let len = u16::from_be_bytes([payload[2], payload[3]]) as usize;
+    if (payload[0] >> 5) & 0b111 == 1 && len >= 4 && len <= payload.len() {
+        return Some(Protocol::Dlt);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_protocols() {
+        let doip = [0x02, 0xFD, 0x80, 0x01, 0x00, 0x00, 0x00, 0x06, 0x0E, 0x80];
+        assert_eq!(classify((50000, DOIP_PORT, &doip)), Some(Protocol::Doip));
+        let someip = [
+            0x12, 0x34, 0x00, 0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01,
+            0x02, 0x00, 0xAB, 0xCD,
+        ];
+        assert_eq!(classify((30501, 30501, &someip)), Some(Protocol::SomeIp));
+        let dlt = [
+            0x21, 0x00, 0x00, 0x0E, 0x41, 0x01, b'A', b'P', b'P', b'1', b'C', b'T', b'X', b'1',
+        ];
+        assert_eq!(classify((3490, 3490, &dlt)), Some(Protocol::Dlt));
+        assert_eq!(classify((80, 443, b"GET / HTTP/1.1\r\n")), None);
+        assert!(matches!(
+            capture_format(&[0x0A, 0x0D, 0x0D, 0x0A, 0x1C, 0x00]),
+            Some((FileFormat::PcapNG, _))
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+//! Detectors of text formats recognized by the lines at the start of a file.
+
+use super::{candidate, scaled};
+use lazy_static::lazy_static;
+use parsers::text::pattern::{TextPatternConfig, TextPatternPreset as Preset};
+use regex::Regex;
+use sources::encoding::{decode, detect_encoding};
+use std::path::Path;
+use stypes::{
+    CanParserSettings, CanTraceFormat, FileFormat, FormatCandidate, JsonParserSettings,
+    LogcatFormat, LogcatParserSettings, ParserType, TextContinuationRule, TextEncoding,
+    TextMultilineParserSettings, TextPatternParserSettings, TextPatternPreset,
+};
+
+/// Maximal number of lines inspected.
+const MAX_LINES: usize = 200;
+
+/// RFC 3164 and RFC 5424 syslog messages.
+const SYSLOG_PATTERN: &str = r"^(?:<(?P<priority>\d{1,3})>(?:1 )?)?(?P<timestamp>[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}|\d{4}-\d{2}-\d{2}T\S+) (?P<host>\S+) (?P<process>[^:\[\s]+)(?:\[(?P<pid>\d+)\])?:? (?P<message>.*)$";
+
+lazy_static! {
+    static ref LOGCAT_THREADTIME: Regex = Regex::new(
+        r"^(?:\d{4}-)?\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3,9}\s+(?:\S+\s+)??\d+\s+\d+\s+[VDIWEFAS]\s"
+    )
+    .unwrap();
+    static ref SYSLOG: Regex = Regex::new(SYSLOG_PATTERN).unwrap();
+    static ref CANDUMP: Regex =
+        Regex::new(r"^\s*\(\d+\.\d+\)\s+\S+\s+[0-9A-Fa-f]+(?:#|\s+\[\d+\])").unwrap();
+    static ref PRESETS: Vec<(TextPatternPreset, &'static str, Regex)> = [
+        (TextPatternPreset::Log4j, Preset::Log4j, "log4j"),
+        (
+            TextPatternPreset::PythonLogging,
+            Preset::PythonLogging,
+            "Python logging"
+        ),
+        (TextPatternPreset::Nginx, Preset::Nginx, "nginx access log"),
+    ]
+    .into_iter()
+    .map(|(preset, config, name)| {
+        let regex = Regex::new(&TextPatternConfig::preset(config).pattern).unwrap();
+        (preset, name, regex)
+    })
+    .collect();
+}
+
+fn encoding_name(encoding: &TextEncoding) -> &'static str {
+    match encoding {
+        TextEncoding::Utf8 => "UTF-8",
+        TextEncoding::Utf16Le => "UTF-16LE",
+        TextEncoding::Utf16Be => "UTF-16BE",
+        TextEncoding::Latin1 => "Latin-1",
+        TextEncoding::Windows1251 => "Windows-1251",
+        TextEncoding::ShiftJis => "Shift JIS",
+    }
+}
+
+/// Detects text formats. `truncated` is set if the sample doesn't contain the
+/// whole file, so its last line is incomplete.
+pub fn detect(path: &Path, sample: &[u8], truncated: bool) -> Vec<FormatCandidate> {
+    let encoding = detect_encoding(sample);
+    // Besides UTF-16 text contains no zero bytes.
+    if sample.is_empty()
+        || (sample.contains(&0)
+            && !matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be))
+    {
+        return Vec::new();
+    }
+    let content = decode(sample, &encoding);
+    let mut lines: Vec<&str> = content.lines().collect();
+    if truncated && lines.len() > 1 {
+        lines.pop();
+    }
+    let lines: Vec<&str> = lines
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .take(MAX_LINES)
+        .collect();
+    let total = lines.len();
+    let format = FileFormat::Text(Some(encoding.clone()));
+    let mut candidates = Vec::new();
+    let mut push = |parser: ParserType, confidence: u8, evidence: String| {
+        candidates.push(candidate(
+            path,
+            format.clone(),
+            parser,
+            confidence,
+            vec![evidence],
+        ));
+    };
+    let matching =
+        |matches: &dyn Fn(&str) -> bool| lines.iter().filter(|line| matches(line)).count();
+    // At least half of the lines have to match, e.g. stack traces don't.
+    let recognized = |count: usize| total > 0 && count * 2 >= total;
+    let evidence = |count: usize, name: &str| format!("{count} of {total} lines are {name}");
+
+    if lines
+        .first()
+        .is_some_and(|line| line.starts_with("__CURSOR="))
+    {
+        push(
+            ParserType::Journal(()),
+            95,
+            String::from("journal export format, starting with __CURSOR"),
+        );
+    }
+    if lines.first().is_some_and(|line| line.starts_with("date "))
+        && lines.iter().take(5).any(|line| line.starts_with("base "))
+    {
+        push(
+            ParserType::Can(CanParserSettings {
+                format: CanTraceFormat::Asc,
+                dbc_file_paths: None,
+            }),
+            90,
+            String::from("Vector ASC header with date and base"),
+        );
+    }
+    let count = matching(&|line| {
+        line.starts_with('{')
+            && serde_json::from_str::<serde_json::Value>(line).is_ok_and(|value| value.is_object())
+    });
+    if recognized(count) {
+        push(
+            ParserType::Json(JsonParserSettings {
+                fields: Vec::new(),
+                timestamp_field: None,
+                timestamp_format: None,
+                level_field: None,
+            }),
+            50 + scaled(count, total, 45),
+            evidence(count, "JSON objects"),
+        );
+    }
+    let count = matching(&|line| LOGCAT_THREADTIME.is_match(line));
+    if recognized(count) {
+        push(
+            ParserType::Logcat(LogcatParserSettings {
+                format: LogcatFormat::Threadtime,
+                tz: None,
+                year: None,
+            }),
+            50 + scaled(count, total, 45),
+            evidence(count, "logcat threadtime records"),
+        );
+    }
+    let count = matching(&|line| CANDUMP.is_match(line));
+    if recognized(count) {
+        push(
+            ParserType::Can(CanParserSettings {
+                format: CanTraceFormat::Candump,
+                dbc_file_paths: None,
+            }),
+            50 + scaled(count, total, 45),
+            evidence(count, "candump frames"),
+        );
+    }
+    let count = matching(&|line| SYSLOG.is_match(line));
+    if recognized(count) {
+        push(
+            ParserType::TextPattern(TextPatternParserSettings {
+                preset: None,
+                pattern: Some(SYSLOG_PATTERN.to_owned()),
+                timestamp_group: Some(String::from("timestamp")),
+                timestamp_format: None,
+                year: None,
+                severity_group: None,
+                continuation_pattern: None,
+                join_unmatched: None,
+            }),
+            45 + scaled(count, total, 45),
+            evidence(count, "syslog messages"),
+        );
+    }
+    for (preset, name, regex) in PRESETS.iter() {
+        let count = matching(&|line| regex.is_match(line));
+        if recognized(count) {
+            push(
+                ParserType::TextPattern(TextPatternParserSettings {
+                    preset: Some(preset.clone()),
+                    pattern: None,
+                    timestamp_group: None,
+                    timestamp_format: None,
+                    year: None,
+                    severity_group: None,
+                    continuation_pattern: None,
+                    join_unmatched: None,
+                }),
+                50 + scaled(count, total, 45),
+                evidence(count, name),
+            );
+        }
+    }
+    let count = matching(&|line| line.starts_with([' ', '\t']) || line.starts_with("Caused by:"));
+    if count > 0 && count * 10 >= total {
+        push(
+            ParserType::TextMultiline(TextMultilineParserSettings {
+                rule: TextContinuationRule::LeadingWhitespace,
+                max_lines: None,
+            }),
+            35,
+            evidence(count, "indented continuation lines"),
+        );
+    }
+    push(
+        ParserType::Text(()),
+        30,
+        format!("{} text", encoding_name(&encoding)),
+    );
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsers(sample: &[u8]) -> Vec<ParserType> {
+        detect(Path::new("log.txt"), sample, false)
+            .into_iter()
+            .map(|candidate| candidate.options.parser)
+            .collect()
+    }
+
+    #[test]
+    fn text_formats() {
+        let found = parsers(b"{\"level\":\"info\",\"msg\":\"started\"}\n{\"level\":\"warn\"}\n");
+        assert!(matches!(found[0], ParserType::Json(_)));
+        let found = parsers(
+            b"03-17 16:13:38.811  1702  2395 E WindowManager: Exception thrown\n\
+            03-17 16:13:38.812  1702  2395 I WindowManager: Recovered\n",
+        );
+        assert!(matches!(found[0], ParserType::Logcat(_)));
+        let found = parsers(
+            b"Mar 17 16:13:38 host sshd[1024]: Accepted publickey\n\
+            <34>1 2024-03-17T16:13:38.811Z host app 12 - - Started\n",
+        );
+        assert!(matches!(
+            &found[0],
+            ParserType::TextPattern(TextPatternParserSettings {
+                pattern: Some(_),
+                ..
+            })
+        ));
+        let found = parsers(b"2024-03-17 16:13:38,811 [main] ERROR com.example.App - failed\n");
+        assert!(matches!(
+            &found[0],
+            ParserType::TextPattern(TextPatternParserSettings {
+                preset: Some(TextPatternPreset::Log4j),
+                ..
+            })
+        ));
+        let found = parsers(b"(1436509052.249713) can0 123#DEADBEEF\n");
+        assert!(matches!(found[0], ParserType::Can(_)));
+        let found = parsers(b"started\nnothing special\n");
+        assert_eq!(found.len(), 1);
+        assert!(matches!(found[0], ParserType::Text(())));
+        assert!(parsers(b"\x00\x01\x02binary").is_empty());
+    }
+}
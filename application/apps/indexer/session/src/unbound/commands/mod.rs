@@ -2,11 +2,13 @@
 
 mod cancel_test;
 mod checksum;
+mod detect;
 mod dlt;
 mod file;
 mod folder;
 pub mod plugins;
 mod regex;
+mod render;
 mod serial;
 mod shells;
 mod sleep;
@@ -69,6 +71,20 @@ pub enum Command {
         String,
         oneshot::Sender<Result<stypes::CommandOutcome<bool>, stypes::ComputationError>>,
     ),
+    /// Detects the format of a file and suggests options to open it with.
+    DetectFileFormat(
+        String,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError>,
+        >,
+    ),
+    /// Gets the render options (e.g. columns) of the log view for the parser.
+    GetParserRenderOptions(
+        stypes::ParserType,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::ParserRenderOptions>, stypes::ComputationError>,
+        >,
+    ),
     CancelTest(
         i64,
         i64,
@@ -153,6 +169,8 @@ impl std::fmt::Display for Command {
                 Command::GetSomeipStatistic(_, _) => "Getting someip statistic",
                 Command::GetRegexError(_, _) => "Checking regex",
                 Command::IsFileBinary(_, _) => "Checking if file is binary",
+                Command::DetectFileFormat(_, _) => "Detecting file format",
+                Command::GetParserRenderOptions(_, _) => "Getting parser render options",
                 Command::InstalledPluginsList(..) => "Getting installed plugins",
                 Command::InvalidPluginsList(..) => "Getting invalid plugins",
                 Command::InstalledPluginsPaths(..) => "Getting installed plugins paths",
@@ -194,6 +212,12 @@ pub async fn process(command: Command, signal: Signal, plugins_manager: &RwLock<
         Command::GetShellProfiles(tx) => tx.send(shells::get_available_shells(signal)).is_err(),
         Command::SerialPortsList(tx) => tx.send(serial::available_ports(signal)).is_err(),
         Command::IsFileBinary(file_path, tx) => tx.send(file::is_file_binary(file_path)).is_err(),
+        Command::DetectFileFormat(file_path, tx) => tx
+            .send(detect::detect_file_format(file_path, plugins_manager, signal).await)
+            .is_err(),
+        Command::GetParserRenderOptions(parser, tx) => tx
+            .send(render::get_parser_render_options(parser, signal))
+            .is_err(),
         Command::CancelTest(a, b, tx) => tx
             .send(cancel_test::cancel_test(a, b, signal).await)
             .is_err(),
@@ -244,6 +268,8 @@ pub fn err(command: Command, err: stypes::ComputationError) {
         Command::GetShellProfiles(tx) => tx.send(Err(err)).is_err(),
         Command::SerialPortsList(tx) => tx.send(Err(err)).is_err(),
         Command::IsFileBinary(_filepath, tx) => tx.send(Err(err)).is_err(),
+        Command::DetectFileFormat(_filepath, tx) => tx.send(Err(err)).is_err(),
+        Command::GetParserRenderOptions(_parser, tx) => tx.send(Err(err)).is_err(),
         Command::CancelTest(_a, _b, tx) => tx.send(Err(err)).is_err(),
         Command::InstalledPluginsList(tx) => tx.send(Err(err)).is_err(),
         Command::InvalidPluginsList(tx) => tx.send(Err(err)).is_err(),
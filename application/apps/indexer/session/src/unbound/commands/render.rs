@@ -0,0 +1,66 @@
+use crate::{
+    handlers::observing::{json_parser, protobuf_parser, text_pattern_parser},
+    unbound::signal::Signal,
+};
+
+/// Default width of a column in the log view.
+const COLUMN_WIDTH: i16 = 150;
+/// Minimum width of a column in the log view.
+const MIN_COLUMN_WIDTH: u16 = 30;
+/// Maximum width of a column in the log view.
+const MAX_COLUMN_WIDTH: u16 = 600;
+
+/// Returns the render options of the log view for messages of the parser with the given
+/// settings. Parsers with columns defined by their settings provide the captions of the
+/// columns, all other parsers don't provide columns options.
+pub fn get_parser_render_options(
+    parser: stypes::ParserType,
+    _signal: Signal,
+) -> Result<stypes::CommandOutcome<stypes::ParserRenderOptions>, stypes::ComputationError> {
+    let captions: Option<Vec<String>> = match &parser {
+        stypes::ParserType::TextPattern(settings) => Some(
+            text_pattern_parser(settings)
+                .map_err(stypes::ComputationError::NativeError)?
+                .columns()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        ),
+        stypes::ParserType::Json(settings) => Some(
+            json_parser(settings)
+                .columns()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        ),
+        stypes::ParserType::Protobuf(settings) => Some(
+            protobuf_parser(settings)
+                .map_err(stypes::ComputationError::NativeError)?
+                .columns(),
+        ),
+        _ => None,
+    };
+    Ok(stypes::CommandOutcome::Finished(
+        stypes::ParserRenderOptions {
+            columns_options: captions.map(columns_options),
+        },
+    ))
+}
+
+fn columns_options(captions: Vec<String>) -> stypes::ColumnsRenderOptions {
+    let last = captions.len().saturating_sub(1);
+    stypes::ColumnsRenderOptions {
+        columns: captions
+            .into_iter()
+            .enumerate()
+            .map(|(idx, caption)| stypes::ColumnInfo {
+                caption,
+                description: String::new(),
+                // The last column takes the remaining space.
+                width: if idx == last { -1 } else { COLUMN_WIDTH },
+            })
+            .collect(),
+        min_width: MIN_COLUMN_WIDTH,
+        max_width: MAX_COLUMN_WIDTH,
+    }
+}
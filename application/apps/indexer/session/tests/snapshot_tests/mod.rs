@@ -91,6 +91,7 @@ async fn observe_someip_bcapng_session() {
 
     let parser_settings = stypes::SomeIpParserSettings {
         fibex_file_paths: Some(vec![String::from(fibex_file)]),
+        e2e_config_path: None,
     };
 
     let session_main_file = run_observe_session(
@@ -126,6 +127,7 @@ async fn observe_someip_legacy_session() {
 
     let parser_settings = stypes::SomeIpParserSettings {
         fibex_file_paths: Some(vec![String::from(fibex_file)]),
+        e2e_config_path: None,
     };
 
     let session_main_file = run_observe_session(
@@ -0,0 +1,496 @@
+//! # systemd journal files
+//!
+//! Reads the entries of binary journal files (`*.journal`) without `journalctl`
+//! and provides them in the journal export format, one block of `FIELD=value`
+//! lines per entry, separated by an empty line. Several files (e.g. of a
+//! `/var/log/journal` directory) are merged by the realtime of their entries.
+//!
+//! Entry objects are read in the order they have been appended to a file, new
+//! objects of files which are still written are picked up on the next load.
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+const SIGNATURE: &[u8; 8] = b"LPKSHHRH";
+/// Length of the header fields used by the reader.
+const HEADER_LEN: usize = 144;
+const OBJECT_HEADER_LEN: usize = 16;
+/// Maximal size of a decompressed data object, the same as `DATA_SIZE_MAX` of systemd.
+/// Sizes of compressed objects are taken from the (possibly corrupted) file, so
+/// decompressing stops at this size.
+const MAX_DECOMPRESSED_LEN: usize = 768 * 1024 * 1024;
+
+const INCOMPATIBLE_COMPRESSED_XZ: u32 = 1 << 0;
+const INCOMPATIBLE_COMPRESSED_LZ4: u32 = 1 << 1;
+const INCOMPATIBLE_KEYED_HASH: u32 = 1 << 2;
+const INCOMPATIBLE_COMPRESSED_ZSTD: u32 = 1 << 3;
+const INCOMPATIBLE_COMPACT: u32 = 1 << 4;
+const INCOMPATIBLE_SUPPORTED: u32 = INCOMPATIBLE_COMPRESSED_XZ
+    | INCOMPATIBLE_COMPRESSED_LZ4
+    | INCOMPATIBLE_KEYED_HASH
+    | INCOMPATIBLE_COMPRESSED_ZSTD
+    | INCOMPATIBLE_COMPACT;
+
+const OBJECT_DATA: u8 = 1;
+const OBJECT_ENTRY: u8 = 3;
+
+const OBJECT_COMPRESSED_XZ: u8 = 1 << 0;
+const OBJECT_COMPRESSED_LZ4: u8 = 1 << 1;
+const OBJECT_COMPRESSED_ZSTD: u8 = 1 << 2;
+
+/// Returns the journal files of `path`, which is either a single file or a
+/// journal directory. Like `/var/log/journal`, directories may contain one
+/// subdirectory per machine id.
+pub fn journal_files(path: &Path) -> Result<Vec<PathBuf>, SourceError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    collect_journal_files(path, 1, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_journal_files(
+    dir: &Path,
+    depth: usize,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), SourceError> {
+    for entry in fs::read_dir(dir).map_err(SourceError::Io)? {
+        let path = entry.map_err(SourceError::Io)?.path();
+        if path.is_dir() {
+            if depth > 0 {
+                collect_journal_files(&path, depth - 1, files)?;
+            }
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(".journal") || name.ends_with(".journal~"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+struct Entry {
+    realtime: u64,
+    monotonic: u64,
+    boot_id: [u8; 16],
+    /// The `FIELD=value` payloads of the entry.
+    fields: Vec<Vec<u8>>,
+}
+
+impl Entry {
+    /// Writes the entry in the journal export format. Values containing line
+    /// breaks are written as length prefixed binary values.
+    fn export(&self, out: &mut Vec<u8>) {
+        let boot_id: String = self.boot_id.iter().map(|b| format!("{b:02x}")).collect();
+        // Writing into a vector can't fail.
+        let _ = write!(
+            out,
+            "__REALTIME_TIMESTAMP={}\n__MONOTONIC_TIMESTAMP={}\n_BOOT_ID={boot_id}\n",
+            self.realtime, self.monotonic
+        );
+        for field in self.fields.iter() {
+            let Some(pos) = field.iter().position(|b| *b == b'=') else {
+                continue;
+            };
+            let (name, value) = (&field[..pos], &field[pos + 1..]);
+            if value.contains(&b'\n') {
+                out.extend_from_slice(name);
+                out.push(b'\n');
+                out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                out.extend_from_slice(value);
+            } else {
+                out.extend_from_slice(field);
+            }
+            out.push(b'\n');
+        }
+        out.push(b'\n');
+    }
+}
+
+struct JournalFile<R: Read + Seek> {
+    reader: R,
+    compact: bool,
+    /// Offset of the next object to read.
+    next_object: u64,
+    /// Offset of the last object written to the file.
+    tail_object: u64,
+    /// Length of the file when the header was read. Objects can't exceed it.
+    len: u64,
+    peeked: Option<Entry>,
+}
+
+impl<R: Read + Seek> JournalFile<R> {
+    fn new(mut reader: R) -> Result<Self, SourceError> {
+        let header = read_header(&mut reader)?;
+        if &header[..8] != SIGNATURE {
+            return Err(SourceError::Setup("Not a journal file".to_owned()));
+        }
+        let incompatible = u32_at(&header, 12);
+        if incompatible & !INCOMPATIBLE_SUPPORTED != 0 {
+            return Err(SourceError::Setup(format!(
+                "Unsupported journal features: {incompatible:#x}"
+            )));
+        }
+        let len = file_len(&mut reader)?;
+        Ok(Self {
+            reader,
+            compact: incompatible & INCOMPATIBLE_COMPACT != 0,
+            next_object: u64_at(&header, 88),
+            tail_object: u64_at(&header, 136),
+            len,
+            peeked: None,
+        })
+    }
+
+    /// Returns the next entry of the file without consuming it.
+    fn peek(&mut self) -> Result<Option<&Entry>, SourceError> {
+        if self.peeked.is_none() {
+            self.peeked = self.next_entry()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next_entry(&mut self) -> Result<Option<Entry>, SourceError> {
+        loop {
+            if self.next_object > self.tail_object {
+                // The file might still be written.
+                let header = read_header(&mut self.reader)?;
+                self.tail_object = u64_at(&header, 136);
+                self.len = file_len(&mut self.reader)?;
+                if self.next_object > self.tail_object {
+                    return Ok(None);
+                }
+            }
+            let offset = self.next_object;
+            let (kind, _, size) = self.object_header(offset)?;
+            self.next_object = offset + size.next_multiple_of(8);
+            if kind == OBJECT_ENTRY {
+                return self.entry(offset, size).map(Some);
+            }
+        }
+    }
+
+    fn object_header(&mut self, offset: u64) -> Result<(u8, u8, u64), SourceError> {
+        let mut header = [0u8; OBJECT_HEADER_LEN];
+        self.read_at(offset, &mut header)?;
+        let size = u64_at(&header, 8);
+        // Objects are read at once, so the size is checked before it's allocated.
+        if size < OBJECT_HEADER_LEN as u64
+            || offset.checked_add(size).is_none_or(|end| end > self.len)
+        {
+            return Err(SourceError::Unrecoverable(format!(
+                "Invalid journal object size {size} at {offset}"
+            )));
+        }
+        Ok((header[0], header[1], size))
+    }
+
+    fn object(&mut self, offset: u64, size: u64) -> Result<Vec<u8>, SourceError> {
+        let mut object = vec![0u8; size as usize];
+        self.read_at(offset, &mut object)?;
+        Ok(object)
+    }
+
+    fn entry(&mut self, offset: u64, size: u64) -> Result<Entry, SourceError> {
+        let object = self.object(offset, size)?;
+        if object.len() < 64 {
+            return Err(SourceError::Unrecoverable(format!(
+                "Invalid journal entry at {offset}"
+            )));
+        }
+        let mut boot_id = [0u8; 16];
+        boot_id.copy_from_slice(&object[40..56]);
+        let item_len = if self.compact { 4 } else { 16 };
+        let mut fields = Vec::new();
+        for item in object[64..].chunks_exact(item_len) {
+            let data_offset = if self.compact {
+                u32_at(item, 0) as u64
+            } else {
+                u64_at(item, 0)
+            };
+            if let Some(field) = self.data(data_offset)? {
+                fields.push(field);
+            }
+        }
+        Ok(Entry {
+            realtime: u64_at(&object, 24),
+            monotonic: u64_at(&object, 32),
+            boot_id,
+            fields,
+        })
+    }
+
+    /// Reads the payload of a data object.
+    fn data(&mut self, offset: u64) -> Result<Option<Vec<u8>>, SourceError> {
+        let (kind, flags, size) = self.object_header(offset)?;
+        let start = if self.compact { 72 } else { 64 };
+        if kind != OBJECT_DATA || size < start {
+            warn!("Skipping invalid journal data object at {offset}");
+            return Ok(None);
+        }
+        let object = self.object(offset, size)?;
+        let payload = &object[start as usize..];
+        let decompressed = if flags & OBJECT_COMPRESSED_ZSTD != 0 {
+            decompress_zstd(payload)
+        } else if flags & OBJECT_COMPRESSED_LZ4 != 0 {
+            decompress_lz4(payload)
+        } else if flags & OBJECT_COMPRESSED_XZ != 0 {
+            decompress_xz(payload)
+        } else {
+            return Ok(Some(payload.to_vec()));
+        };
+        match decompressed {
+            Ok(payload) => Ok(Some(payload)),
+            Err(err) => {
+                warn!("Fail to decompress journal data object at {offset}: {err}");
+                Ok(None)
+            }
+        }
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), SourceError> {
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| self.reader.read_exact(buf))
+            .map_err(|e| {
+                SourceError::Unrecoverable(format!("Fail to read journal at {offset}: {e}"))
+            })
+    }
+}
+
+fn read_header<R: Read + Seek>(reader: &mut R) -> Result<[u8; HEADER_LEN], SourceError> {
+    let mut header = [0u8; HEADER_LEN];
+    reader
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| reader.read_exact(&mut header))
+        .map_err(|e| SourceError::Setup(format!("Fail to read journal header: {e}")))?;
+    Ok(header)
+}
+
+fn file_len<R: Read + Seek>(reader: &mut R) -> Result<u64, SourceError> {
+    reader
+        .seek(SeekFrom::End(0))
+        .map_err(|e| SourceError::Setup(format!("Fail to get length of journal: {e}")))
+}
+
+/// Output of decompression, which fails if [`MAX_DECOMPRESSED_LEN`] is exceeded.
+#[derive(Default)]
+struct Decompressed(Vec<u8>);
+
+impl Write for Decompressed {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.0.len() + buf.len() > MAX_DECOMPRESSED_LEN {
+            return Err(std::io::Error::other("decompressed size exceeds the limit"));
+        }
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn decompress_zstd(payload: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder =
+        ruzstd::decoding::StreamingDecoder::new(payload).map_err(|e| e.to_string())?;
+    let mut out = Decompressed::default();
+    std::io::copy(&mut decoder, &mut out).map_err(|e| e.to_string())?;
+    Ok(out.0)
+}
+
+/// LZ4 payloads start with the decompressed size.
+fn decompress_lz4(payload: &[u8]) -> Result<Vec<u8>, String> {
+    if payload.len() < 8 {
+        return Err("missing size".to_owned());
+    }
+    let size = u64_at(payload, 0);
+    if size > MAX_DECOMPRESSED_LEN as u64 {
+        return Err(format!("decompressed size {size} exceeds the limit"));
+    }
+    lz4_flex::block::decompress(&payload[8..], size as usize).map_err(|e| e.to_string())
+}
+
+fn decompress_xz(payload: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Decompressed::default();
+    lzma_rs::xz_decompress(&mut &payload[..], &mut out).map_err(|e| e.to_string())?;
+    Ok(out.0)
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut value = [0u8; 4];
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(value)
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(value)
+}
+
+/// Provides the entries of one or more journal files in the journal export
+/// format, ordered by their realtime.
+pub struct JournalByteSource<R: Read + Seek> {
+    files: Vec<JournalFile<R>>,
+    buffer: Vec<u8>,
+}
+
+impl<R: Read + Seek> JournalByteSource<R> {
+    pub fn new(readers: Vec<R>) -> Result<Self, SourceError> {
+        Ok(Self {
+            files: readers
+                .into_iter()
+                .map(JournalFile::new)
+                .collect::<Result<Vec<_>, _>>()?,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Takes the entry with the lowest realtime of all files.
+    fn next_entry(&mut self) -> Result<Option<Entry>, SourceError> {
+        let mut next: Option<(usize, u64)> = None;
+        for (idx, file) in self.files.iter_mut().enumerate() {
+            if let Some(entry) = file.peek()?
+                && next.is_none_or(|(_, realtime)| entry.realtime < realtime)
+            {
+                next = Some((idx, entry.realtime));
+            }
+        }
+        Ok(next.and_then(|(idx, _)| self.files[idx].peeked.take()))
+    }
+}
+
+impl<R: Read + Seek + Send + Sync> ByteSource for JournalByteSource<R> {
+    async fn load(&mut self, _: Option<&SourceFilter>) -> Result<Option<ReloadInfo>, SourceError> {
+        let Some(entry) = self.next_entry()? else {
+            trace!("no more journal entries");
+            return Ok(None);
+        };
+        let before = self.buffer.len();
+        entry.export(&mut self.buffer);
+        Ok(Some(ReloadInfo::new(
+            self.buffer.len() - before,
+            self.buffer.len(),
+            0,
+            Some(entry.realtime / 1000),
+        )))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.drain(..offset.min(self.buffer.len()));
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Builds a journal file with one data object per field and one entry
+    /// object per entry.
+    fn journal(entries: &[(u64, &[&str])]) -> Vec<u8> {
+        const HEADER_SIZE: usize = 256;
+        let mut file = vec![0u8; HEADER_SIZE];
+        file[..8].copy_from_slice(SIGNATURE);
+        file[88..96].copy_from_slice(&(HEADER_SIZE as u64).to_le_bytes());
+        let mut tail = 0;
+        let mut object = |file: &mut Vec<u8>, kind: u8, body: &[u8]| {
+            let offset = file.len();
+            let size = (OBJECT_HEADER_LEN + body.len()) as u64;
+            file.push(kind);
+            file.extend_from_slice(&[0u8; 7]);
+            file.extend_from_slice(&size.to_le_bytes());
+            file.extend_from_slice(body);
+            file.resize(file.len().next_multiple_of(8), 0);
+            tail = offset as u64;
+            offset as u64
+        };
+        for (realtime, fields) in entries {
+            let offsets: Vec<u64> = fields
+                .iter()
+                .map(|field| {
+                    let mut body = vec![0u8; 48];
+                    body.extend_from_slice(field.as_bytes());
+                    object(&mut file, OBJECT_DATA, &body)
+                })
+                .collect();
+            let mut body = vec![0u8; 48];
+            body[8..16].copy_from_slice(&realtime.to_le_bytes());
+            body[16..24].copy_from_slice(&42u64.to_le_bytes());
+            for offset in offsets {
+                body.extend_from_slice(&offset.to_le_bytes());
+                body.extend_from_slice(&[0u8; 8]);
+            }
+            object(&mut file, OBJECT_ENTRY, &body);
+        }
+        file[136..144].copy_from_slice(&tail.to_le_bytes());
+        file
+    }
+
+    #[tokio::test]
+    async fn merges_entries_of_files() {
+        let first = journal(&[
+            (1_000_000, &["MESSAGE=first", "_PID=1"]),
+            (3_000_000, &["MESSAGE=multi\nline"]),
+        ]);
+        let second = journal(&[(2_000_000, &["MESSAGE=second"])]);
+        let mut source =
+            JournalByteSource::new(vec![Cursor::new(first), Cursor::new(second)]).unwrap();
+        let mut realtimes = Vec::new();
+        while let Some(info) = source.load(None).await.unwrap() {
+            realtimes.push(info.last_known_ts.unwrap());
+        }
+        assert_eq!(realtimes, vec![1_000, 2_000, 3_000]);
+        let export = source.current_slice();
+        let zeros = "0".repeat(32);
+        let mut expected = format!(
+            "__REALTIME_TIMESTAMP=1000000\n__MONOTONIC_TIMESTAMP=42\n_BOOT_ID={zeros}\n\
+             MESSAGE=first\n_PID=1\n\n\
+             __REALTIME_TIMESTAMP=2000000\n__MONOTONIC_TIMESTAMP=42\n_BOOT_ID={zeros}\n\
+             MESSAGE=second\n\n\
+             __REALTIME_TIMESTAMP=3000000\n__MONOTONIC_TIMESTAMP=42\n_BOOT_ID={zeros}\n\
+             MESSAGE\n"
+        )
+        .into_bytes();
+        expected.extend_from_slice(&10u64.to_le_bytes());
+        expected.extend_from_slice(b"multi\nline\n\n");
+        assert_eq!(export, &expected[..]);
+        source.consume(export.len());
+        assert!(source.is_empty());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(JournalByteSource::new(vec![Cursor::new(vec![0u8; 512])]).is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_objects() {
+        let mut file = journal(&[(1_000_000, &["MESSAGE=first"])]);
+        // Size of the entry object (the tail object)
+        let tail = u64_at(&file, 136) as usize;
+        file[tail + 8..tail + 16].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut source = JournalByteSource::new(vec![Cursor::new(file)]).unwrap();
+        assert!(source.load(None).await.is_err());
+
+        let mut payload = (MAX_DECOMPRESSED_LEN as u64 + 1).to_le_bytes().to_vec();
+        payload.extend_from_slice(&[0u8; 8]);
+        assert!(decompress_lz4(&payload).is_err());
+    }
+}
@@ -1,2 +1,3 @@
+pub mod journal;
 pub mod pcap;
 pub mod raw;
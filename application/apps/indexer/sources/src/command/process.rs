@@ -193,9 +193,13 @@ impl ByteSource for ProcessSource {
         let bytes = match request {
             stypes::SdeRequest::WriteText(ref str) => str.as_bytes(),
             stypes::SdeRequest::WriteBytes(ref bytes) => bytes,
+            stypes::SdeRequest::DltControl(_) => return Err(SourceError::NotSupported),
         };
         self.stdin.write_all(bytes).await.map_err(SourceError::Io)?;
-        Ok(stypes::SdeResponse { bytes: bytes.len() })
+        Ok(stypes::SdeResponse {
+            bytes: bytes.len(),
+            response: None,
+        })
     }
 }
 
@@ -0,0 +1,323 @@
+//! Character encodings of text files.
+//!
+//! Sources and parsers work with UTF-8. Text files in other encodings are
+//! transcoded on the fly with [`DecodeReader`] while they are read. To export
+//! content in raw format, [`copy_lines`] copies the original lines of a file
+//! by positions in its decoded content.
+use encoding_rs::{
+    Decoder, Encoding, SHIFT_JIS, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1251, WINDOWS_1252,
+};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    ops::Range,
+    path::Path,
+};
+use stypes::TextEncoding;
+
+/// Number of bytes at the beginning of a file used to detect its encoding.
+pub const DETECTION_SAMPLE_LEN: usize = 64 * 1024;
+/// Size of the buffer for undecoded content.
+const DECODE_BUFFER_LEN: usize = 64 * 1024;
+
+fn encoding(text_encoding: &TextEncoding) -> &'static Encoding {
+    match text_encoding {
+        TextEncoding::Utf8 => UTF_8,
+        TextEncoding::Utf16Le => UTF_16LE,
+        TextEncoding::Utf16Be => UTF_16BE,
+        // WHATWG decodes ISO 8859-1 as its superset Windows-1252.
+        TextEncoding::Latin1 => WINDOWS_1252,
+        TextEncoding::Windows1251 => WINDOWS_1251,
+        TextEncoding::ShiftJis => SHIFT_JIS,
+    }
+}
+
+/// Detects the encoding of text by its byte order mark. Without it UTF-16 is
+/// recognized by the zero bytes of ASCII characters, all other content which
+/// isn't valid UTF-8 is taken as Latin-1.
+pub fn detect_encoding(sample: &[u8]) -> TextEncoding {
+    match Encoding::for_bom(sample) {
+        Some((bom, _)) if bom == UTF_16LE => return TextEncoding::Utf16Le,
+        Some((bom, _)) if bom == UTF_16BE => return TextEncoding::Utf16Be,
+        Some(_) => return TextEncoding::Utf8,
+        None => {}
+    }
+    let units = sample.len() / 2;
+    if units > 0 {
+        let (mut even, mut odd) = (0usize, 0usize);
+        for pair in sample.chunks_exact(2) {
+            even += usize::from(pair[0] == 0);
+            odd += usize::from(pair[1] == 0);
+        }
+        // Mostly ASCII text has a zero byte in almost every code unit.
+        if odd * 3 > units && even * 10 <= units {
+            return TextEncoding::Utf16Le;
+        }
+        if even * 3 > units && odd * 10 <= units {
+            return TextEncoding::Utf16Be;
+        }
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => TextEncoding::Utf8,
+        // The sample may end within a character.
+        Err(err) if err.error_len().is_none() => TextEncoding::Utf8,
+        Err(_) => TextEncoding::Latin1,
+    }
+}
+
+/// Detects the encoding of a text file by its beginning.
+pub fn detect_file_encoding(path: &Path) -> io::Result<TextEncoding> {
+    let mut sample = Vec::with_capacity(DETECTION_SAMPLE_LEN);
+    File::open(path)?
+        .take(DETECTION_SAMPLE_LEN as u64)
+        .read_to_end(&mut sample)?;
+    Ok(detect_encoding(&sample))
+}
+
+/// Decodes text into UTF-8, invalid sequences are replaced.
+pub fn decode(content: &[u8], text_encoding: &TextEncoding) -> String {
+    encoding(text_encoding)
+        .decode_with_bom_removal(content)
+        .0
+        .into_owned()
+}
+
+/// Reader transcoding text of the given encoding into UTF-8. A byte order mark
+/// is removed.
+///
+/// Reaching the end of the inner reader doesn't finish decoding, so content
+/// appended to a file later (e.g. while tailing) is decoded correctly even if
+/// a character was split.
+pub struct DecodeReader<R: Read> {
+    inner: R,
+    decoder: Decoder,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> DecodeReader<R> {
+    pub fn new(inner: R, text_encoding: &TextEncoding) -> Self {
+        Self {
+            inner,
+            decoder: encoding(text_encoding).new_decoder_with_bom_removal(),
+            input: vec![0; DECODE_BUFFER_LEN],
+            output: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.output.len() {
+            let read = self.inner.read(&mut self.input)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let capacity = self
+                .decoder
+                .max_utf8_buffer_length(read)
+                .ok_or_else(|| io::Error::other("Decoded content is too large"))?;
+            self.output.resize(capacity, 0);
+            let (_, _, written, _) =
+                self.decoder
+                    .decode_to_utf8(&self.input[..read], &mut self.output, false);
+            self.output.truncate(written);
+            self.pos = 0;
+        }
+        let len = buf.len().min(self.output.len() - self.pos);
+        buf[..len].copy_from_slice(&self.output[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// Line break as it's encoded in the given encoding.
+fn line_break(text_encoding: &TextEncoding) -> &'static [u8] {
+    match text_encoding {
+        TextEncoding::Utf16Le => &[b'\n', 0],
+        TextEncoding::Utf16Be => &[0, b'\n'],
+        _ => b"\n",
+    }
+}
+
+/// Counts line breaks in text of the given encoding without decoding it.
+pub fn count_lines(content: &[u8], text_encoding: &TextEncoding) -> usize {
+    match line_break(text_encoding) {
+        [byte] => content.iter().filter(|b| *b == byte).count(),
+        unit => content
+            .chunks_exact(unit.len())
+            .filter(|chunk| *chunk == unit)
+            .count(),
+    }
+}
+
+/// Reads the next line (including its line break) of text in the given encoding.
+fn read_line<R: BufRead>(
+    reader: &mut R,
+    text_encoding: &TextEncoding,
+    line: &mut Vec<u8>,
+) -> io::Result<usize> {
+    line.clear();
+    match line_break(text_encoding) {
+        [byte] => reader.read_until(*byte, line),
+        unit => loop {
+            // UTF-16: the line break is searched in code units only.
+            let start = line.len();
+            reader.read_until(unit[unit.len() - 1], line)?;
+            if line.len() == start
+                || (line.len() % unit.len() == 0 && line.ends_with(unit))
+                || !line.ends_with(&unit[unit.len() - 1..])
+            {
+                return Ok(line.len());
+            }
+        },
+    }
+}
+
+/// Copies lines of text in the given encoding, which contain any part of `ranges`, to
+/// `dest`. Lines are copied as they are in the source: with the original encoding, line
+/// breaks, byte order mark and invalid sequences.
+///
+/// `ranges` are sorted and not overlapping positions in the decoded (UTF-8) content of the
+/// source, as it's delivered by [`DecodeReader`].
+pub fn copy_lines<R: Read, W: Write>(
+    src: R,
+    text_encoding: &TextEncoding,
+    ranges: &[Range<u64>],
+    dest: &mut W,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(src);
+    let mut decoder = encoding(text_encoding).new_decoder_with_bom_removal();
+    let mut line = Vec::new();
+    let mut decoded = Vec::new();
+    let mut pos = 0u64;
+    let mut ranges = ranges.iter().filter(|range| !range.is_empty()).peekable();
+    while ranges.peek().is_some() && read_line(&mut reader, text_encoding, &mut line)? > 0 {
+        let capacity = decoder
+            .max_utf8_buffer_length(line.len())
+            .ok_or_else(|| io::Error::other("Decoded content is too large"))?;
+        decoded.resize(capacity, 0);
+        let (_, _, written, _) = decoder.decode_to_utf8(&line, &mut decoded, false);
+        let end = pos + written as u64;
+        while ranges.next_if(|range| range.end <= pos).is_some() {}
+        if ranges.peek().is_some_and(|range| range.start < end) {
+            dest.write_all(&line)?;
+        }
+        pos = end;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    /// Reader returning one byte per read, which splits all characters.
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((byte, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *byte;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn detect() {
+        let mut bom = vec![0xFF, 0xFE];
+        bom.extend(utf16le("ä\n"));
+        assert!(matches!(detect_encoding(&bom), TextEncoding::Utf16Le));
+        assert!(matches!(
+            detect_encoding(&utf16le("12:00:01 started\r\n")),
+            TextEncoding::Utf16Le
+        ));
+        assert!(matches!(
+            detect_encoding(&[0, b'a', 0, b'\n']),
+            TextEncoding::Utf16Be
+        ));
+        assert!(matches!(
+            detect_encoding("Grüße\n".as_bytes()),
+            TextEncoding::Utf8
+        ));
+        assert!(matches!(
+            detect_encoding(&"Grüße".as_bytes()[..3]),
+            TextEncoding::Utf8
+        ));
+        assert!(matches!(
+            detect_encoding(b"Gr\xFC\xDFe\n"),
+            TextEncoding::Latin1
+        ));
+    }
+
+    #[test]
+    fn transcode() {
+        let mut content = vec![0xFF, 0xFE];
+        content.extend(utf16le("Grüße 😀\nzweite Zeile\n"));
+        let mut decoded = Vec::new();
+        DecodeReader::new(ByteReader(&content), &TextEncoding::Utf16Le)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(decoded.clone()).unwrap(),
+            "Grüße 😀\nzweite Zeile\n"
+        );
+
+        let mut latin1 = Vec::new();
+        DecodeReader::new(&b"Gr\xFC\xDFe"[..], &TextEncoding::Latin1)
+            .read_to_end(&mut latin1)
+            .unwrap();
+        assert_eq!(latin1, "Grüße".as_bytes());
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(count_lines(&utf16le("a\nb\n"), &TextEncoding::Utf16Le), 2);
+        // 0x0A as the high byte of a code unit isn't a line break
+        assert_eq!(
+            count_lines(&utf16le("\u{0A00}\n"), &TextEncoding::Utf16Le),
+            1
+        );
+        assert_eq!(count_lines(b"a\r\nb", &TextEncoding::Utf8), 1);
+    }
+
+    fn copy(content: &[u8], text_encoding: &TextEncoding, ranges: &[Range<u64>]) -> Vec<u8> {
+        let mut copied = Vec::new();
+        copy_lines(content, text_encoding, ranges, &mut copied).unwrap();
+        copied
+    }
+
+    #[test]
+    fn copy_original_lines() {
+        // Decoded: "Grüße\r\n" (0..9), "zweite\n" (9..16), "dritte" (16..22)
+        let mut content = vec![0xFF, 0xFE];
+        content.extend(utf16le("Grüße\r\nzweite\ndritte"));
+        let utf16 = TextEncoding::Utf16Le;
+        assert_eq!(copy(&content, &utf16, &[0..9]), content[..16]);
+        assert_eq!(copy(&content, &utf16, &[9..16]), utf16le("zweite\n"));
+        assert_eq!(copy(&content, &utf16, &[3..10, 20..21]), content[..]);
+        assert_eq!(copy(&content, &utf16, &[16..22]), utf16le("dritte"));
+        assert!(copy(&content, &utf16, &[22..30]).is_empty());
+        // Invalid sequences are kept
+        let content = b"ok\n\xFF\xFEbad\r\nlast\n";
+        // Decoded: "ok\n" (0..3), "\u{FFFD}\u{FFFD}bad\r\n" (3..14), "last\n" (14..19)
+        assert_eq!(
+            copy(content, &TextEncoding::Utf8, &[3..14]),
+            b"\xFF\xFEbad\r\n"
+        );
+        let latin1 = b"Gr\xFC\xDFe\nzwei\n";
+        assert_eq!(
+            copy(latin1, &TextEncoding::Latin1, &[0..1]),
+            b"Gr\xFC\xDFe\n"
+        );
+    }
+}
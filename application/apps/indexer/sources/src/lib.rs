@@ -11,6 +11,7 @@ mod tests;
 
 pub mod binary;
 pub mod command;
+pub mod encoding;
 pub mod sde;
 pub mod serial;
 pub mod socket;
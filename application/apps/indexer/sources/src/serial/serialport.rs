@@ -192,7 +192,10 @@ impl ByteSource for SerialSource {
                         sleep(Duration::from_millis(self.send_data_delay as u64)).await;
                     }
                 }
-                stypes::SdeResponse { bytes: len }
+                stypes::SdeResponse {
+                    bytes: len,
+                    response: None,
+                }
             }
             stypes::SdeRequest::WriteBytes(mut bytes) => {
                 let len = bytes.len();
@@ -210,8 +213,12 @@ impl ByteSource for SerialSource {
                         sleep(Duration::from_millis(self.send_data_delay as u64)).await;
                     }
                 }
-                stypes::SdeResponse { bytes: len }
+                stypes::SdeResponse {
+                    bytes: len,
+                    response: None,
+                }
             }
+            stypes::SdeRequest::DltControl(_) => return Err(SourceError::NotSupported),
         })
     }
 }
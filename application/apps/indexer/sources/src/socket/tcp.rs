@@ -4,7 +4,7 @@ use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
 use bufread::DeqBuffer;
 use reconnect::{ReconnectInfo, ReconnectResult, TcpReconnecter};
 use socket2::{SockRef, TcpKeepalive};
-use tokio::net::TcpStream;
+use tokio::{io::AsyncWriteExt, net::TcpStream};
 
 use super::{BuffCapacityState, MAX_BUFF_SIZE, MAX_DATAGRAM_SIZE, handle_buff_capacity};
 
@@ -166,6 +166,25 @@ impl ByteSource for TcpSource {
     fn len(&self) -> usize {
         self.buffer.read_available()
     }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        let bytes = match request {
+            stypes::SdeRequest::WriteText(ref str) => str.as_bytes(),
+            stypes::SdeRequest::WriteBytes(ref bytes) => bytes,
+            stypes::SdeRequest::DltControl(_) => return Err(SourceError::NotSupported),
+        };
+        self.socket
+            .write_all(bytes)
+            .await
+            .map_err(SourceError::Io)?;
+        Ok(stypes::SdeResponse {
+            bytes: bytes.len(),
+            response: None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -176,7 +195,7 @@ mod tests {
     use reconnect::ReconnectStateMsg;
     use std::time::Duration;
     use tokio::{
-        io::AsyncWriteExt,
+        io::{AsyncReadExt, AsyncWriteExt},
         net::TcpListener,
         task::yield_now,
         time::{sleep, timeout},
@@ -559,4 +578,33 @@ mod tests {
 
         assert!(rec_res.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_income() {
+        static SERVER: &str = "127.0.0.1:4008";
+        let listener = TcpListener::bind(&SERVER).await.unwrap();
+        let receive_handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = [0u8; 7];
+            stream.read_exact(&mut received).await.unwrap();
+            received
+        });
+        let mut tcp_source = TcpSource::new(SERVER, None, None).await.unwrap();
+
+        let response = tcp_source
+            .income(stypes::SdeRequest::WriteText(String::from("abc")))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 3);
+        let response = tcp_source
+            .income(stypes::SdeRequest::WriteBytes(vec![1, 2, 3, 4]))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 4);
+
+        assert_eq!(
+            receive_handle.await.unwrap(),
+            [b'a', b'b', b'c', 1, 2, 3, 4]
+        );
+    }
 }
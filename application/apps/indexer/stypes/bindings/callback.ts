@@ -21,7 +21,19 @@ stat: Map<string, number>, } } | { "IndexedMapUpdated": {
 /**
  * The number of log entries from search results available for reading.
  */
-len: number, } } | { "SearchMapUpdated": FilterMatchList | null } | { "SearchValuesUpdated": Map<number, [number, number]> } | { "AttachmentsUpdated": { 
+len: number, } } | { "SearchMapUpdated": FilterMatchList | null } | { "SearchValuesUpdated": Map<number, [number, number]> } | { "NamedSearchUpdated": { 
+/**
+ * The identifier of the named search.
+ */
+id: string, 
+/**
+ * The number of logs with matches.
+ */
+found: number, 
+/**
+ * A map of search conditions and their global match counts within the named search.
+ */
+stat: Map<string, number>, } } | { "FilteredViewUpdated": number | null } | { "AttachmentsUpdated": { 
 /**
  * The size of the attachment in bytes.
  */
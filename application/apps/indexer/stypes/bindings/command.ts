@@ -1,4 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ObserveOptions } from "./observe";
+import type { ParserRenderOptions } from "./plugins";
 
 /**
  * Represents the result of a command execution.
@@ -21,6 +23,13 @@ export type CommandOutcomeDltStatisticInfoResult = { "Finished": DltStatisticInf
  */
 export type CommandOutcomeFoldersScanningResult = { "Finished": FoldersScanningResult } | "Cancelled";
 
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeFormatDetection = { "Finished": FormatDetection } | "Cancelled";
+
 /**
  * Represents the result of a command execution.
  * At the core level, this type is used for all commands invoked within an `UnboundSession`.
@@ -28,6 +37,13 @@ export type CommandOutcomeFoldersScanningResult = { "Finished": FoldersScanningR
  */
 export type CommandOutcomeOptionalString = { "Finished": string | null } | "Cancelled";
 
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeParserRenderOptions = { "Finished": ParserRenderOptions } | "Cancelled";
+
 /**
  * Represents the result of a command execution.
  * At the core level, this type is used for all commands invoked within an `UnboundSession`.
@@ -67,6 +83,11 @@ export type DltLevelDistribution = { non_log: number, log_fatal: number, log_err
 
 export type DltStatisticInfo = { app_ids: Array<[string, DltLevelDistribution]>, context_ids: Array<[string, DltLevelDistribution]>, ecu_ids: Array<[string, DltLevelDistribution]>, contained_non_verbose: boolean, };
 
+/**
+ * Compression formats recognized by the format detection.
+ */
+export type FileCompression = "Gzip" | "Bzip2" | "Xz" | "Zstd" | "Zip";
+
 /**
  * Represents a folder entity in the file system.
  */
@@ -131,6 +152,37 @@ list: Array<FolderEntity>,
  */
 max_len_reached: boolean, };
 
+/**
+ * A configuration to open a file with, suggested by the format detection.
+ */
+export type FormatCandidate = { 
+/**
+ * The options to observe the file with.
+ */
+options: ObserveOptions, 
+/**
+ * The confidence in the candidate in percent.
+ */
+confidence: number, 
+/**
+ * The findings the candidate is based on, e.g. `pcapng section header`.
+ */
+evidence: Array<string>, };
+
+/**
+ * The result of the format detection of a file.
+ */
+export type FormatDetection = { 
+/**
+ * The compression of the file. Compressed files have to be extracted
+ * before they can be opened, no candidates are suggested for them.
+ */
+compression: FileCompression | null, 
+/**
+ * The candidates, starting with the most likely one.
+ */
+candidates: Array<FormatCandidate>, };
+
 /**
  * Represents a list of serial ports.
  *
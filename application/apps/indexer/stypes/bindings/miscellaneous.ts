@@ -6,6 +6,66 @@
  */
 export type AroundIndexes = [number | undefined | null, number | undefined | null];
 
+/**
+ * Describes a DLT context as seen in a session.
+ */
+export type DltContextInfo = { 
+/**
+ * The ECU the context belongs to.
+ */
+ecu_id: string, 
+/**
+ * The application id (APID).
+ */
+app_id: string, 
+/**
+ * The description of the application, if reported by the ECU.
+ */
+app_description: string | null, 
+/**
+ * The context id (CTID).
+ */
+context_id: string, 
+/**
+ * The description of the context, if reported by the ECU.
+ */
+context_description: string | null, 
+/**
+ * The log level of the context, if reported by the ECU.
+ */
+log_level: number | null, 
+/**
+ * The trace status of the context, if reported by the ECU.
+ */
+trace_status: number | null, 
+/**
+ * The number of messages received for the context.
+ */
+messages: number, };
+
+/**
+ * A list of DLT contexts.
+ */
+export type DltContextInfoList = Array<DltContextInfo>;
+
+/**
+ * The supported DLT control commands.
+ */
+export type DltControlCommand = { "SetLogLevel": { app_id: string, context_id: string, log_level: number, } } | { "SetDefaultTraceStatus": number } | { "GetLogInfo": { app_id: string, context_id: string, } } | "StoreConfig" | "ResetToFactoryDefault";
+
+/**
+ * A DLT control message addressed to an ECU.
+ */
+export type DltControlRequest = { 
+/**
+ * The id of the ECU the message is sent to.
+ */
+ecu_id: string, 
+/**
+ * The control command to send.
+ */
+command: DltControlCommand, };
+
 /**
  * Describes a match for a search condition.
  */
@@ -49,7 +109,11 @@ pos: number,
  * - `BREADCRUMB`: Nature = Nature(1 << 6)
  * - `BREADCRUMB_SEPARATOR`: Nature = Nature(1 << 7)
  */
-nature: number, };
+nature: number, 
+/**
+ * Parts of the content matched by active search filters, ordered by position.
+ */
+spans: Array<MatchSpan>, };
 
 /**
  * A list of log entries.
@@ -58,6 +122,23 @@ export type GrabbedElementList = Array<GrabbedElement>;
 
 export type MapKeyValue = { [key in string]?: string };
 
+/**
+ * Describes a part of the log entry matched by a search condition.
+ */
+export type MatchSpan = { 
+/**
+ * The identifier of the filter (search condition), the same as in `FilterMatch`.
+ */
+filter: number, 
+/**
+ * The position of the first character of the match (in characters, not bytes).
+ */
+start: number, 
+/**
+ * The position after the last character of the match (in characters, not bytes).
+ */
+end: number, };
+
 /**
  * Representation of ranges. We cannot use std ranges as soon as no way
  * to derive Serialize, Deserialize
@@ -73,7 +154,7 @@ export type Ranges = Array<Range>;
  * A request to a stream that supports feedback, such as a terminal command
  * that accepts input through `stdin`.
  */
-export type SdeRequest = { "WriteText": string } | { "WriteBytes": Array<number> };
+export type SdeRequest = { "WriteText": string } | { "WriteBytes": Array<number> } | { "DltControl": DltControlRequest };
 
 /**
  * The response from a source to a sent `SdeRequest`. Note that sending data
@@ -84,7 +165,12 @@ export type SdeResponse = {
 /**
  * The number of bytes received.
  */
-bytes: number, };
+bytes: number, 
+/**
+ * The decoded answer of the source, if the request expects one
+ * (e.g. the response to a DLT control message).
+ */
+response: string | null, };
 
 /**
  * Describes a data source.
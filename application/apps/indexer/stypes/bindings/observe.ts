@@ -9,6 +9,27 @@ import type { PluginParserSettings } from './plugins';
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ShellProfile } from './command';
 
+/**
+ * Settings for the CAN parser.
+ */
+export type CanParserSettings = {
+    /**
+     * Format of the trace.
+     */
+    format: CanTraceFormat;
+    /**
+     * Paths to DBC files for decoding frames into messages and signals.
+     * Signals are shown as `Name=value unit`, so their values can be charted
+     * with terms like `EngineSpeed=(-?[\d.]+)`.
+     */
+    dbc_file_paths: Array<string> | null;
+};
+
+/**
+ * Formats of CAN traces.
+ */
+export type CanTraceFormat = 'Asc' | 'Blf' | 'Candump';
+
 /**
  * Settings for the DLT parser.
  */
@@ -29,12 +50,96 @@ export type DltParserSettings = {
      * Timezone for timestamp adjustment. If specified, timestamps are converted to this timezone.
      */
     tz: string | null;
+    /**
+     * Configuration for filtering DLT protocol version 2 messages by the fields, which
+     * aren't covered by `filter_config`.
+     */
+    v2_filter_config: DltV2FilterConfig | null;
+};
+
+/**
+ * Filters of the fields of DLT protocol version 2 messages. Version 1 messages
+ * aren't affected by these filters.
+ */
+export type DltV2FilterConfig = {
+    /**
+     * Only messages with at least one of these tags are selected.
+     */
+    tags: Array<string> | null;
+    /**
+     * Messages with a higher privacy level are filtered out.
+     */
+    max_privacy_level: number | null;
+};
+
+/**
+ * Settings for the DoIP parser.
+ */
+export type DoipParserSettings = {
+    /**
+     * Paths to CSV (`<did>;<name>`) or ODX files naming the data identifiers
+     * of UDS services.
+     */
+    did_file_paths: Array<string> | null;
 };
 
 /**
  * Supported file formats for observation.
  */
-export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary';
+export type FileFormat =
+    | 'PcapNG'
+    | 'PcapLegacy'
+    | { Text: TextEncoding | null }
+    | 'Binary'
+    | 'Journal';
+
+/**
+ * Settings for the JSON Lines parser.
+ */
+export type JsonParserSettings = {
+    /**
+     * Dotted paths (e.g. `span.name`) of the fields shown as columns. All other
+     * fields are shown in an additional last column.
+     */
+    fields: Array<string>;
+    /**
+     * Dotted path of the timestamp field. Common names are checked if not defined.
+     */
+    timestamp_field: string | null;
+    /**
+     * `chrono` format string of timestamps which are neither RFC 3339 nor epoch numbers.
+     */
+    timestamp_format: string | null;
+    /**
+     * Dotted path of the level field. Common names are checked if not defined.
+     */
+    level_field: string | null;
+};
+
+/**
+ * Formats of Android logcat logs.
+ */
+export type LogcatFormat = 'Binary' | 'Threadtime';
+
+/**
+ * Settings for the Android logcat parser.
+ */
+export type LogcatParserSettings = {
+    /**
+     * Format of the log.
+     */
+    format: LogcatFormat;
+    /**
+     * Time zone of the device (e.g. `Europe/Berlin`). Text records are written
+     * in local time and read as UTC if not defined.
+     */
+    tz: string | null;
+    /**
+     * Year of text records, which don't contain it. The current year is used
+     * if not defined.
+     */
+    year: number | null;
+};
 
 /**
  * Multicast configuration information.
@@ -73,6 +178,14 @@ export type ParserType =
     | { Dlt: DltParserSettings }
     | { SomeIp: SomeIpParserSettings }
     | { Text: null }
+    | { TextPattern: TextPatternParserSettings }
+    | { TextMultiline: TextMultilineParserSettings }
+    | { Json: JsonParserSettings }
+    | { Can: CanParserSettings }
+    | { Logcat: LogcatParserSettings }
+    | { Journal: null }
+    | { Doip: DoipParserSettings }
+    | { Protobuf: ProtobufParserSettings }
     | { Plugin: PluginParserSettings };
 
 /**
@@ -93,6 +206,39 @@ export type ProcessTransportConfig = {
     shell: ShellProfile | null;
 };
 
+/**
+ * Framing of Protobuf messages in a stream.
+ */
+export type ProtobufFraming = 'Varint' | 'Fixed32Be' | 'Fixed32Le' | 'Datagram';
+
+/**
+ * Settings for the Protobuf parser.
+ */
+export type ProtobufParserSettings = {
+    /**
+     * Paths to `.proto` files or descriptor sets (`protoc --descriptor_set_out`).
+     */
+    proto_file_paths: Array<string>;
+    /**
+     * Name of the message type, e.g. `my_package.Request`. The package can be
+     * omitted if the name is unique.
+     */
+    message_type: string;
+    /**
+     * Framing of the messages.
+     */
+    framing: ProtobufFraming;
+    /**
+     * Presentation of the messages.
+     */
+    rendering: ProtobufRendering;
+};
+
+/**
+ * Presentation of decoded Protobuf messages.
+ */
+export type ProtobufRendering = 'Columns' | 'Json';
+
 /**
  * Configuration for serial port connections.
  */
@@ -139,6 +285,12 @@ export type SomeIpParserSettings = {
      * Paths to FIBEX files for additional interpretation of `payload` content.
      */
     fibex_file_paths: Array<string> | null;
+    /**
+     * Path to a JSON or ARXML file with the E2E protections of the traced
+     * messages. If set, the CRC and counter of protected messages are verified.
+     * E2E settings of FIBEX files aren't supported.
+     */
+    e2e_config_path: string | null;
 };
 
 /**
@@ -151,6 +303,75 @@ export type TCPTransportConfig = {
     bind_addr: string;
 };
 
+/**
+ * Rules to detect lines continuing the previous message of a text source.
+ */
+export type TextContinuationRule = 'LeadingWhitespace' | 'NoTimestamp' | { Pattern: string };
+
+/**
+ * Character encodings of text files.
+ */
+export type TextEncoding = 'Utf8' | 'Utf16Le' | 'Utf16Be' | 'Latin1' | 'Windows1251' | 'ShiftJis';
+
+/**
+ * Settings for the multi-line text parser.
+ */
+export type TextMultilineParserSettings = {
+    /**
+     * Rule to detect lines which are folded into the previous message.
+     */
+    rule: TextContinuationRule;
+    /**
+     * Maximal number of lines of a message. Defaults to 1000.
+     */
+    max_lines: number | null;
+};
+
+/**
+ * Settings for the text pattern parser.
+ */
+export type TextPatternParserSettings = {
+    /**
+     * Ready-made configuration. Fields defined below take precedence over it.
+     */
+    preset: TextPatternPreset | null;
+    /**
+     * Regular expression matching a log line. Each named group becomes a column.
+     */
+    pattern: string | null;
+    /**
+     * Name of the group containing the timestamp.
+     */
+    timestamp_group: string | null;
+    /**
+     * `chrono` format string of the timestamp, e.g. `%Y-%m-%d %H:%M:%S%.3f`.
+     */
+    timestamp_format: string | null;
+    /**
+     * Year of timestamps, which don't contain it. The current year is used
+     * if not defined.
+     */
+    year: number | null;
+    /**
+     * Name of the group containing the severity. Its column shows the normalized severity.
+     */
+    severity_group: string | null;
+    /**
+     * Regular expression matching lines which continue the previous message.
+     */
+    continuation_pattern: string | null;
+    /**
+     * Appends lines which don't match `pattern` to the previous message (e.g. stack traces).
+     * The preset decides if not defined; without preset such lines aren't joined.
+     */
+    join_unmatched: boolean | null;
+};
+
+/**
+ * Ready-made configurations of the text pattern parser for common log formats.
+ */
+export type TextPatternPreset = 'Log4j' | 'LogcatThreadtime' | 'Nginx' | 'PythonLogging';
+
 /**
  * Describes the transport source for a session.
  */
@@ -150,6 +150,20 @@ export type PluginEntity = {
     readme_path: string | null;
 };
 
+/**
+ * Describes the files a parser plugin can handle.
+ */
+export type PluginFormatDetector = {
+    /**
+     * File extensions without the leading dot, e.g. `dlt`.
+     */
+    extensions: Array<string>;
+    /**
+     * Byte sequences as hex strings which the files start with, e.g. `444C5401`.
+     */
+    signatures: Array<string>;
+};
+
 /**
  * Contains the infos and options for a valid plugin.
  */
@@ -187,7 +201,15 @@ export type PluginLogMessage = {
 /**
  * Represents the plugins metadata like title, description...
  */
-export type PluginMetadata = { title: string; description: string | null };
+export type PluginMetadata = {
+    title: string;
+    description: string | null;
+    /**
+     * Describes the files a parser plugin can handle, so it's suggested when
+     * the format of a file is detected.
+     */
+    detector: PluginFormatDetector | null;
+};
 
 /**
  * General settings for all parsers as plugins
@@ -23,4 +23,14 @@ impl CallbackEvent {
     pub fn search_results(found: u64, stat: HashMap<String, u64>) -> Self {
         CallbackEvent::SearchUpdated { found, stat }
     }
+
+    /// Creates a `CallbackEvent::NamedSearchUpdated` with the given results of named search.
+    ///
+    /// # Parameters
+    /// - `id`: The identifier of the named search.
+    /// - `found`: The number of matches found during the search.
+    /// - `stat`: A map containing search conditions as keys and the match counts as values.
+    pub fn named_search_results(id: String, found: u64, stat: HashMap<String, u64>) -> Self {
+        CallbackEvent::NamedSearchUpdated { id, found, stat }
+    }
 }
@@ -13,6 +13,8 @@ impl std::fmt::Display for CallbackEvent {
     /// - `IndexedMapUpdated(len)` - Displays the number of indexed map entries.
     /// - `SearchMapUpdated` - Indicates that the search map has been updated.
     /// - `SearchValuesUpdated` - Indicates that search values have been updated.
+    /// - `NamedSearchUpdated({id}: {found})` - Shows the number of results of a named search.
+    /// - `FilteredViewUpdated(len)` - Displays the number of visible rows in filtered view.
     /// - `AttachmentsUpdated: {len}` - Displays the size of the updated attachment.
     /// - `Progress` - Indicates progress for an operation.
     /// - `SessionError: {err:?}` - Displays details of a session error.
@@ -29,6 +31,10 @@ impl std::fmt::Display for CallbackEvent {
             Self::IndexedMapUpdated { len } => write!(f, "IndexedMapUpdated({len})"),
             Self::SearchMapUpdated(_) => write!(f, "SearchMapUpdated"),
             Self::SearchValuesUpdated(_) => write!(f, "SearchValuesUpdated"),
+            Self::NamedSearchUpdated { id, found, stat: _ } => {
+                write!(f, "NamedSearchUpdated({id}: {found})")
+            }
+            Self::FilteredViewUpdated(len) => write!(f, "FilteredViewUpdated({len:?})"),
             Self::AttachmentsUpdated { len, attachment: _ } => {
                 write!(f, "AttachmentsUpdated: {len}")
             }
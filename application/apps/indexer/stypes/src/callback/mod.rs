@@ -79,6 +79,26 @@ pub enum CallbackEvent {
     )]
     SearchValuesUpdated(Option<HashMap<u8, (f64, f64)>>),
 
+    /// Triggered when results of a named search are updated. Named searches are running
+    /// alongside the main search and do not affect indexes of session.
+    NamedSearchUpdated {
+        /// The identifier of the named search.
+        id: String,
+        /// The number of logs with matches.
+        #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+        found: u64,
+        /// A map of search conditions and their global match counts within the named search.
+        #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "Map<string, number>"))]
+        stat: HashMap<String, u64>,
+    },
+
+    /// Triggered when the reduced (filtered) view of session is changed: exclusion is
+    /// applied or dropped, or the stream or hidden rows are updated.
+    /// - `Option<u64>`: The number of visible rows; `None` if the view is dropped and
+    ///   the whole stream is visible.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    FilteredViewUpdated(Option<u64>),
+
     /// Triggered whenever a new attachment is detected in the logs.
     AttachmentsUpdated {
         /// The size of the attachment in bytes.
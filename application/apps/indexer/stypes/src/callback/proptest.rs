@@ -31,6 +31,8 @@ impl Arbitrary for CallbackEvent {
     /// - `IndexedMapUpdated` with a random `u64` length.
     /// - `SearchMapUpdated` with an optional `FilterMatchList`.
     /// - `SearchValuesUpdated` with a map of random values, converting `f32` to `f64`.
+    /// - `NamedSearchUpdated` with a random identifier, `found` value and map of search conditions.
+    /// - `FilteredViewUpdated` with an optional random `u64` length.
     /// - `AttachmentsUpdated` with random attachment information.
     /// - `Progress` with a random `Uuid` and `Progress` instance.
     /// - `SessionError` with a random `NativeError`.
@@ -61,6 +63,15 @@ impl Arbitrary for CallbackEvent {
                         .collect()
                 }))
             }),
+            (any::<String>(), any::<u32>(), any::<HashMap<String, u32>>(),).prop_map(
+                |(id, found, stat)| CallbackEvent::NamedSearchUpdated {
+                    id,
+                    found: found as u64,
+                    stat: stat.into_iter().map(|(k, v)| (k, v as u64)).collect(),
+                }
+            ),
+            any::<Option<u32>>()
+                .prop_map(|len| CallbackEvent::FilteredViewUpdated(len.map(|len| len as u64))),
             (any::<u32>(), any::<AttachmentInfo>(),).prop_map(|(len, attachment)| {
                 CallbackEvent::AttachmentsUpdated {
                     len: len as u64,
@@ -0,0 +1,55 @@
+#[cfg(feature = "nodejs")]
+mod nodejs;
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// Compression formats recognized by the format detection.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum FileCompression {
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+    Zip,
+}
+
+/// A configuration to open a file with, suggested by the format detection.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FormatCandidate {
+    /// The options to observe the file with.
+    pub options: ObserveOptions,
+    /// The confidence in the candidate in percent.
+    pub confidence: u8,
+    /// The findings the candidate is based on, e.g. `pcapng section header`.
+    pub evidence: Vec<String>,
+}
+
+/// The result of the format detection of a file.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FormatDetection {
+    /// The compression of the file. Compressed files have to be extracted
+    /// before they can be opened, no candidates are suggested for them.
+    pub compression: Option<FileCompression>,
+    /// The candidates, starting with the most likely one.
+    pub candidates: Vec<FormatCandidate>,
+}
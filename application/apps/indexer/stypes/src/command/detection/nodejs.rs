@@ -0,0 +1,5 @@
+use crate::*;
+
+try_into_js!(FileCompression);
+try_into_js!(FormatCandidate);
+try_into_js!(FormatDetection);
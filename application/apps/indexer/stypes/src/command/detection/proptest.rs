@@ -0,0 +1,57 @@
+use crate::*;
+
+impl Arbitrary for FileCompression {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(FileCompression::Gzip),
+            Just(FileCompression::Bzip2),
+            Just(FileCompression::Xz),
+            Just(FileCompression::Zstd),
+            Just(FileCompression::Zip),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for FormatCandidate {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<ObserveOptions>(),
+            any::<u8>(),
+            prop::collection::vec(any::<String>(), 0..5),
+        )
+            .prop_map(|(options, confidence, evidence)| FormatCandidate {
+                options,
+                confidence,
+                evidence,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FormatDetection {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::option::of(any::<FileCompression>()),
+            prop::collection::vec(any::<FormatCandidate>(), 0..5),
+        )
+            .prop_map(|(compression, candidates)| FormatDetection {
+                compression,
+                candidates,
+            })
+            .boxed()
+    }
+}
+
+test_msg!(FileCompression, TESTS_USECASE_COUNT);
+test_msg!(FormatCandidate, TESTS_USECASE_COUNT);
+test_msg!(FormatDetection, TESTS_USECASE_COUNT);
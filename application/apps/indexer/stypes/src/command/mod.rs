@@ -7,11 +7,13 @@ mod proptest;
 #[cfg(test)]
 mod ts;
 
+mod detection;
 mod dltstat;
 mod folders;
 mod profiles;
 mod serial;
 
+pub use detection::*;
 pub use dltstat::*;
 pub use folders::*;
 pub use profiles::*;
@@ -95,6 +95,43 @@ impl Arbitrary for CommandOutcome<DltStatisticInfo> {
         .boxed()
     }
 }
+
+impl Arbitrary for CommandOutcome<FormatDetection> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<FormatDetection>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `FormatDetection`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<FormatDetection>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
+impl Arbitrary for CommandOutcome<ParserRenderOptions> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<ParserRenderOptions>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `ParserRenderOptions`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<ParserRenderOptions>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
 impl Arbitrary for CommandOutcome<()> {
     /// Implements the `Arbitrary` trait for `CommandOutcome<()>` to generate random instances.
     ///
@@ -192,4 +229,6 @@ test_msg!(CommandOutcome<SerialPortsList>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<String>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<FoldersScanningResult>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<DltStatisticInfo>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<FormatDetection>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<ParserRenderOptions>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<ProfileList>, TESTS_USECASE_COUNT);
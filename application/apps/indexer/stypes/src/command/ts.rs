@@ -16,6 +16,38 @@ pub enum CommandOutcomeDltStatisticInfoResult {
     Cancelled,
 }
 
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeFormatDetection {
+    /// Indicates that the command was successfully completed.
+    Finished(FormatDetection),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeParserRenderOptions {
+    /// Indicates that the command was successfully completed.
+    Finished(ParserRenderOptions),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
 /// Represents the result of a command execution.
 /// At the core level, this type is used for all commands invoked within an `UnboundSession`.
 /// It is only used to indicate the successful completion or interruption of a command.
@@ -33,6 +33,19 @@ impl From<Vec<SourceDefinition>> for Sources {
     }
 }
 
+impl From<Vec<DltContextInfo>> for DltContextInfoList {
+    /// Converts a `Vec<DltContextInfo>` into a `DltContextInfoList`.
+    ///
+    /// # Parameters
+    /// - `els`: A vector of `DltContextInfo` instances.
+    ///
+    /// # Returns
+    /// - A `DltContextInfoList` containing the elements from the input vector.
+    fn from(els: Vec<DltContextInfo>) -> Self {
+        Self(els)
+    }
+}
+
 impl From<(Option<u64>, Option<u64>)> for AroundIndexes {
     /// Converts a tuple `(Option<u64>, Option<u64>)` into an `AroundIndexes`.
     ///
@@ -83,6 +83,49 @@ pub enum SdeRequest {
     WriteText(String),
     /// Sends raw bytes.
     WriteBytes(Vec<u8>),
+    /// Sends a DLT control message. Supported by DLT sessions only.
+    DltControl(DltControlRequest),
+}
+
+/// A DLT control message addressed to an ECU.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct DltControlRequest {
+    /// The id of the ECU the message is sent to.
+    pub ecu_id: String,
+    /// The control command to send.
+    pub command: DltControlCommand,
+}
+
+/// The supported DLT control commands.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub enum DltControlCommand {
+    /// Sets the log level of a context (`set_log_level`).
+    SetLogLevel {
+        app_id: String,
+        context_id: String,
+        log_level: i8,
+    },
+    /// Sets the default trace status of the ECU (`set_default_trace_status`).
+    SetDefaultTraceStatus(i8),
+    /// Requests the registered applications and contexts (`get_log_info`).
+    /// Empty ids request all applications or contexts.
+    GetLogInfo { app_id: String, context_id: String },
+    /// Stores the current configuration on the ECU (`store_config`).
+    StoreConfig,
+    /// Resets the ECU to its factory defaults (`reset_to_factory_default`).
+    ResetToFactoryDefault,
 }
 
 /// The response from a source to a sent `SdeRequest`. Note that sending data
@@ -99,6 +142,9 @@ pub struct SdeResponse {
     /// The number of bytes received.
     #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
     pub bytes: usize,
+    /// The decoded answer of the source, if the request expects one
+    /// (e.g. the response to a DLT control message).
+    pub response: Option<String>,
 }
 
 /// Information about a log entry.
@@ -124,6 +170,27 @@ pub struct GrabbedElement {
     /// - `BREADCRUMB`: Nature = Nature(1 << 6)
     /// - `BREADCRUMB_SEPARATOR`: Nature = Nature(1 << 7)
     pub nature: u8,
+    /// Parts of the content matched by active search filters, ordered by position.
+    pub spans: Vec<MatchSpan>,
+}
+
+/// Describes a part of the log entry matched by a search condition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct MatchSpan {
+    /// The identifier of the filter (search condition), the same as in `FilterMatch`.
+    pub filter: u8,
+    /// The position of the first character of the match (in characters, not bytes).
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub start: u64,
+    /// The position after the last character of the match (in characters, not bytes).
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub end: u64,
 }
 
 /// A list of log entries.
@@ -177,3 +244,41 @@ pub struct FilterMatch {
     ts(export, export_to = "miscellaneous.ts")
 )]
 pub struct FilterMatchList(pub Vec<FilterMatch>);
+
+/// Describes a DLT context as seen in a session.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct DltContextInfo {
+    /// The ECU the context belongs to.
+    pub ecu_id: String,
+    /// The application id (APID).
+    pub app_id: String,
+    /// The description of the application, if reported by the ECU.
+    pub app_description: Option<String>,
+    /// The context id (CTID).
+    pub context_id: String,
+    /// The description of the context, if reported by the ECU.
+    pub context_description: Option<String>,
+    /// The log level of the context, if reported by the ECU.
+    pub log_level: Option<i8>,
+    /// The trace status of the context, if reported by the ECU.
+    pub trace_status: Option<i8>,
+    /// The number of messages received for the context.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub messages: u64,
+}
+
+/// A list of DLT contexts.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct DltContextInfoList(pub Vec<DltContextInfo>);
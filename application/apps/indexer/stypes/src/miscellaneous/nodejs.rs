@@ -7,6 +7,10 @@ try_into_js!(SourceDefinition);
 try_into_js!(Sources);
 try_into_js!(SdeRequest);
 try_into_js!(SdeResponse);
+try_into_js!(DltControlRequest);
+try_into_js!(DltControlCommand);
 try_into_js!(AroundIndexes);
 try_into_js!(FilterMatch);
 try_into_js!(FilterMatchList);
+try_into_js!(DltContextInfo);
+try_into_js!(DltContextInfoList);
@@ -72,6 +72,7 @@ impl Arbitrary for SdeRequest {
     /// - Generates either:
     ///   - `WriteText` with a random `String`.
     ///   - `WriteBytes` with a random vector of `u8` values (up to 100 bytes).
+    ///   - `DltControl` with a random `DltControlRequest`.
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
 
@@ -79,6 +80,50 @@ impl Arbitrary for SdeRequest {
         prop_oneof![
             any::<String>().prop_map(SdeRequest::WriteText),
             prop::collection::vec(any::<u8>(), 0..100).prop_map(SdeRequest::WriteBytes),
+            any::<DltControlRequest>().prop_map(SdeRequest::DltControl),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for DltControlRequest {
+    /// Implements the `Arbitrary` trait for `DltControlRequest` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates a random `ecu_id` (`String`) and a random `DltControlCommand`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<DltControlCommand>())
+            .prop_map(|(ecu_id, command)| DltControlRequest { ecu_id, command })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DltControlCommand {
+    /// Implements the `Arbitrary` trait for `DltControlCommand` to generate random instances.
+    ///
+    /// # Details
+    /// - Randomly selects one of the commands with random ids and levels.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            (any::<String>(), any::<String>(), any::<i8>()).prop_map(
+                |(app_id, context_id, log_level)| DltControlCommand::SetLogLevel {
+                    app_id,
+                    context_id,
+                    log_level,
+                }
+            ),
+            any::<i8>().prop_map(DltControlCommand::SetDefaultTraceStatus),
+            (any::<String>(), any::<String>()).prop_map(|(app_id, context_id)| {
+                DltControlCommand::GetLogInfo { app_id, context_id }
+            }),
+            Just(DltControlCommand::StoreConfig),
+            Just(DltControlCommand::ResetToFactoryDefault),
         ]
         .boxed()
     }
@@ -89,13 +134,16 @@ impl Arbitrary for SdeResponse {
     ///
     /// # Details
     /// - Generates a random `u32` for the `bytes` field.
+    /// - Generates a random optional `response` (`String`).
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        any::<u32>()
-            .prop_map(|n| n as usize)
-            .prop_map(|bytes| SdeResponse { bytes })
+        (any::<u32>(), any::<Option<String>>())
+            .prop_map(|(bytes, response)| SdeResponse {
+                bytes: bytes as usize,
+                response,
+            })
             .boxed()
     }
 }
@@ -109,16 +157,45 @@ impl Arbitrary for GrabbedElement {
     ///   - A random `content` (`String`).
     ///   - A random `pos` (`usize`).
     ///   - A random `nature` (`u8`).
+    ///   - A vector of up to 5 random `MatchSpan` instances.
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        (any::<u16>(), any::<String>(), any::<u32>(), any::<u8>())
-            .prop_map(|(source_id, content, pos, nature)| GrabbedElement {
+        (
+            any::<u16>(),
+            any::<String>(),
+            any::<u32>(),
+            any::<u8>(),
+            prop::collection::vec(any::<MatchSpan>(), 0..5),
+        )
+            .prop_map(|(source_id, content, pos, nature, spans)| GrabbedElement {
                 source_id,
                 content,
                 pos: pos as usize,
                 nature,
+                spans,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for MatchSpan {
+    /// Implements the `Arbitrary` trait for `MatchSpan` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates:
+    ///   - A random `filter` (`u8`).
+    ///   - Random `start` and `end` (`u32`), mapped to `u64`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u8>(), any::<u32>(), any::<u32>())
+            .prop_map(|(filter, start, end)| MatchSpan {
+                filter,
+                start: start as u64,
+                end: end as u64,
             })
             .boxed()
     }
@@ -191,12 +268,76 @@ impl Arbitrary for FilterMatchList {
     }
 }
 
+impl Arbitrary for DltContextInfo {
+    /// Implements the `Arbitrary` trait for `DltContextInfo` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates random ids, optional descriptions, levels and a `u32` message count.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            any::<Option<String>>(),
+            any::<String>(),
+            any::<Option<String>>(),
+            any::<Option<i8>>(),
+            any::<Option<i8>>(),
+            any::<u32>(),
+        )
+            .prop_map(
+                |(
+                    ecu_id,
+                    app_id,
+                    app_description,
+                    context_id,
+                    context_description,
+                    log_level,
+                    trace_status,
+                    messages,
+                )| DltContextInfo {
+                    ecu_id,
+                    app_id,
+                    app_description,
+                    context_id,
+                    context_description,
+                    log_level,
+                    trace_status,
+                    messages: messages as u64,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for DltContextInfoList {
+    /// Implements the `Arbitrary` trait for `DltContextInfoList` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates a vector of up to 10 random `DltContextInfo` instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(any::<DltContextInfo>(), 0..10)
+            .prop_map(DltContextInfoList)
+            .boxed()
+    }
+}
+
 test_msg!(SourceDefinition, TESTS_USECASE_COUNT);
 test_msg!(Sources, TESTS_USECASE_COUNT);
 test_msg!(SdeRequest, TESTS_USECASE_COUNT);
 test_msg!(SdeResponse, TESTS_USECASE_COUNT);
+test_msg!(DltControlRequest, TESTS_USECASE_COUNT);
+test_msg!(DltControlCommand, TESTS_USECASE_COUNT);
 test_msg!(GrabbedElement, TESTS_USECASE_COUNT);
+test_msg!(MatchSpan, TESTS_USECASE_COUNT);
 test_msg!(GrabbedElementList, TESTS_USECASE_COUNT);
 test_msg!(AroundIndexes, TESTS_USECASE_COUNT);
 test_msg!(FilterMatch, TESTS_USECASE_COUNT);
 test_msg!(FilterMatchList, TESTS_USECASE_COUNT);
+test_msg!(DltContextInfo, TESTS_USECASE_COUNT);
+test_msg!(DltContextInfoList, TESTS_USECASE_COUNT);
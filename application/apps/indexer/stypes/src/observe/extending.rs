@@ -29,6 +29,7 @@ impl Default for DltParserSettings {
     /// - `fibex_file_paths`: `None`
     /// - `with_storage_header`: `true`
     /// - `tz`: `None`
+    /// - `v2_filter_config`: `None`
     /// - `fibex_metadata`: `None`
     fn default() -> Self {
         Self {
@@ -36,6 +37,7 @@ impl Default for DltParserSettings {
             fibex_file_paths: None,
             with_storage_header: true,
             tz: None,
+            v2_filter_config: None,
             fibex_metadata: None,
         }
     }
@@ -59,6 +61,7 @@ impl DltParserSettings {
             fibex_file_paths,
             with_storage_header: true,
             tz: None,
+            v2_filter_config: None,
             fibex_metadata: None,
         }
     }
@@ -56,6 +56,22 @@ pub enum ParserType {
     SomeIp(SomeIpParserSettings),
     /// A pseudo-parser for reading plain text data without processing.
     Text(()),
+    /// Parser splitting plain text lines into columns with a regular expression.
+    TextPattern(TextPatternParserSettings),
+    /// Parser folding continuation lines of plain text (e.g. stack traces) into one message.
+    TextMultiline(TextMultilineParserSettings),
+    /// Parser for JSON Lines logs.
+    Json(JsonParserSettings),
+    /// Parser for CAN and CAN FD traces.
+    Can(CanParserSettings),
+    /// Parser for Android logcat logs.
+    Logcat(LogcatParserSettings),
+    /// Parser for systemd journal entries in the journal export format.
+    Journal(()),
+    /// Parser for DoIP messages and the UDS services they carry, e.g. of PCAP files.
+    Doip(DoipParserSettings),
+    /// Parser for length delimited Protobuf messages described by `.proto` files.
+    Protobuf(ProtobufParserSettings),
     /// Parser using plugins system.
     Plugin(PluginParserSettings),
 }
@@ -78,11 +94,30 @@ pub struct DltParserSettings {
     pub with_storage_header: bool,
     /// Timezone for timestamp adjustment. If specified, timestamps are converted to this timezone.
     pub tz: Option<String>,
+    /// Configuration for filtering DLT protocol version 2 messages by the fields, which
+    /// aren't covered by `filter_config`.
+    pub v2_filter_config: Option<DltV2FilterConfig>,
     /// Internal field that stores FIBEX schema metadata. Not exposed to the client.
     #[serde(skip)]
     pub fibex_metadata: Option<dlt_core::fibex::FibexMetadata>,
 }
 
+/// Filters of the fields of DLT protocol version 2 messages. Version 1 messages
+/// aren't affected by these filters.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct DltV2FilterConfig {
+    /// Only messages with at least one of these tags are selected.
+    pub tags: Option<Vec<String>>,
+    /// Messages with a higher privacy level are filtered out.
+    pub max_privacy_level: Option<u8>,
+}
+
 /// Settings for the SomeIp parser.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[extend::encode_decode]
@@ -94,6 +129,246 @@ pub struct DltParserSettings {
 pub struct SomeIpParserSettings {
     /// Paths to FIBEX files for additional interpretation of `payload` content.
     pub fibex_file_paths: Option<Vec<String>>,
+    /// Path to a JSON or ARXML file with the E2E protections of the traced
+    /// messages. If set, the CRC and counter of protected messages are verified.
+    /// E2E settings of FIBEX files aren't supported.
+    pub e2e_config_path: Option<String>,
+}
+
+/// Settings for the DoIP parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct DoipParserSettings {
+    /// Paths to CSV (`<did>;<name>`) or ODX files naming the data identifiers
+    /// of UDS services.
+    pub did_file_paths: Option<Vec<String>>,
+}
+
+/// Framing of Protobuf messages in a stream.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum ProtobufFraming {
+    /// Messages are prefixed with their length as varint (e.g. `writeDelimitedTo`).
+    Varint,
+    /// Messages are prefixed with their length as 32 bit big endian integer.
+    Fixed32Be,
+    /// Messages are prefixed with their length as 32 bit little endian integer.
+    Fixed32Le,
+    /// Messages have no prefix, each datagram is one message (UDP sources only).
+    Datagram,
+}
+
+/// Presentation of decoded Protobuf messages.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum ProtobufRendering {
+    /// Each field of the message is a column.
+    Columns,
+    /// The message is shown as JSON.
+    Json,
+}
+
+/// Settings for the Protobuf parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct ProtobufParserSettings {
+    /// Paths to `.proto` files or descriptor sets (`protoc --descriptor_set_out`).
+    pub proto_file_paths: Vec<String>,
+    /// Name of the message type, e.g. `my_package.Request`. The package can be
+    /// omitted if the name is unique.
+    pub message_type: String,
+    /// Framing of the messages.
+    pub framing: ProtobufFraming,
+    /// Presentation of the messages.
+    pub rendering: ProtobufRendering,
+}
+
+/// Formats of CAN traces.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum CanTraceFormat {
+    /// Vector ASCII logging format.
+    Asc,
+    /// Vector binary logging format.
+    Blf,
+    /// Log format of Linux `candump`.
+    Candump,
+}
+
+/// Settings for the CAN parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct CanParserSettings {
+    /// Format of the trace.
+    pub format: CanTraceFormat,
+    /// Paths to DBC files for decoding frames into messages and signals.
+    /// Signals are shown as `Name=value unit`, so their values can be charted
+    /// with terms like `EngineSpeed=(-?[\d.]+)`.
+    pub dbc_file_paths: Option<Vec<String>>,
+}
+
+/// Formats of Android logcat logs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum LogcatFormat {
+    /// Binary `logger_entry` records written by `logcat -B`.
+    Binary,
+    /// `threadtime` text format, e.g. as part of bugreports.
+    Threadtime,
+}
+
+/// Settings for the Android logcat parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct LogcatParserSettings {
+    /// Format of the log.
+    pub format: LogcatFormat,
+    /// Time zone of the device (e.g. `Europe/Berlin`). Text records are written
+    /// in local time and read as UTC if not defined.
+    pub tz: Option<String>,
+    /// Year of text records, which don't contain it. The current year is used
+    /// if not defined.
+    pub year: Option<i32>,
+}
+
+/// Settings for the JSON Lines parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct JsonParserSettings {
+    /// Dotted paths (e.g. `span.name`) of the fields shown as columns. All other
+    /// fields are shown in an additional last column.
+    pub fields: Vec<String>,
+    /// Dotted path of the timestamp field. Common names are checked if not defined.
+    pub timestamp_field: Option<String>,
+    /// `chrono` format string of timestamps which are neither RFC 3339 nor epoch numbers.
+    pub timestamp_format: Option<String>,
+    /// Dotted path of the level field. Common names are checked if not defined.
+    pub level_field: Option<String>,
+}
+
+/// Ready-made configurations of the text pattern parser for common log formats.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum TextPatternPreset {
+    /// log4j with the pattern `%d{ISO8601} [%t] %-5p %c - %m%n`.
+    Log4j,
+    /// Android logcat with the `threadtime` format.
+    LogcatThreadtime,
+    /// nginx access log with the `combined` format.
+    Nginx,
+    /// Python logging with the format `%(asctime)s - %(name)s - %(levelname)s - %(message)s`.
+    PythonLogging,
+}
+
+/// Settings for the text pattern parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct TextPatternParserSettings {
+    /// Ready-made configuration. Fields defined below take precedence over it.
+    pub preset: Option<TextPatternPreset>,
+    /// Regular expression matching a log line. Each named group becomes a column.
+    pub pattern: Option<String>,
+    /// Name of the group containing the timestamp.
+    pub timestamp_group: Option<String>,
+    /// `chrono` format string of the timestamp, e.g. `%Y-%m-%d %H:%M:%S%.3f`.
+    pub timestamp_format: Option<String>,
+    /// Year of timestamps, which don't contain it. The current year is used
+    /// if not defined.
+    pub year: Option<i32>,
+    /// Name of the group containing the severity. Its column shows the normalized severity.
+    pub severity_group: Option<String>,
+    /// Regular expression matching lines which continue the previous message.
+    pub continuation_pattern: Option<String>,
+    /// Appends lines which don't match `pattern` to the previous message (e.g. stack traces).
+    /// The preset decides if not defined; without preset such lines aren't joined.
+    pub join_unmatched: Option<bool>,
+}
+
+/// Rules to detect lines continuing the previous message of a text source.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum TextContinuationRule {
+    /// Indented lines and `Caused by:` lines of Java exceptions.
+    LeadingWhitespace,
+    /// Lines which don't start with a timestamp.
+    NoTimestamp,
+    /// Lines matching the given regular expression.
+    Pattern(String),
+}
+
+/// Settings for the multi-line text parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct TextMultilineParserSettings {
+    /// Rule to detect lines which are folded into the previous message.
+    pub rule: TextContinuationRule,
+    /// Maximal number of lines of a message. Defaults to 1000.
+    pub max_lines: Option<u32>,
 }
 
 /// Describes the transport source for a session.
@@ -198,8 +473,29 @@ pub struct UDPTransportConfig {
 pub enum FileFormat {
     PcapNG,
     PcapLegacy,
-    Text,
+    /// Text file. The encoding is detected if it isn't defined.
+    Text(Option<TextEncoding>),
     Binary,
+    /// systemd journal file or directory of journal files.
+    Journal,
+}
+
+/// Character encodings of text files.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO 8859-1, decoded as its superset Windows-1252.
+    Latin1,
+    Windows1251,
+    ShiftJis,
 }
 
 /// Describes the source of data for observation.
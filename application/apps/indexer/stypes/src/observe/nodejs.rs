@@ -3,12 +3,27 @@ use crate::*;
 try_into_js!(MulticastInfo);
 try_into_js!(ParserType);
 try_into_js!(DltParserSettings);
+try_into_js!(DltV2FilterConfig);
 try_into_js!(SomeIpParserSettings);
+try_into_js!(TextPatternParserSettings);
+try_into_js!(JsonParserSettings);
+try_into_js!(CanParserSettings);
+try_into_js!(CanTraceFormat);
+try_into_js!(LogcatFormat);
+try_into_js!(LogcatParserSettings);
+try_into_js!(DoipParserSettings);
+try_into_js!(ProtobufFraming);
+try_into_js!(ProtobufRendering);
+try_into_js!(ProtobufParserSettings);
+try_into_js!(TextPatternPreset);
+try_into_js!(TextContinuationRule);
+try_into_js!(TextMultilineParserSettings);
 try_into_js!(Transport);
 try_into_js!(ProcessTransportConfig);
 try_into_js!(SerialTransportConfig);
 try_into_js!(TCPTransportConfig);
 try_into_js!(UDPTransportConfig);
 try_into_js!(FileFormat);
+try_into_js!(TextEncoding);
 try_into_js!(ObserveOrigin);
 try_into_js!(ObserveOptions);
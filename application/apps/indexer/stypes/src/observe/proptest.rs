@@ -35,6 +35,14 @@ impl Arbitrary for ParserType {
             any::<DltParserSettings>().prop_map(ParserType::Dlt),
             any::<SomeIpParserSettings>().prop_map(ParserType::SomeIp),
             Just(ParserType::Text(())),
+            any::<TextPatternParserSettings>().prop_map(ParserType::TextPattern),
+            any::<TextMultilineParserSettings>().prop_map(ParserType::TextMultiline),
+            any::<JsonParserSettings>().prop_map(ParserType::Json),
+            any::<CanParserSettings>().prop_map(ParserType::Can),
+            any::<LogcatParserSettings>().prop_map(ParserType::Logcat),
+            Just(ParserType::Journal(())),
+            any::<DoipParserSettings>().prop_map(ParserType::Doip),
+            any::<ProtobufParserSettings>().prop_map(ParserType::Protobuf),
             any::<PluginParserSettings>().prop_map(ParserType::Plugin)
         ]
         .boxed()
@@ -83,15 +91,24 @@ impl Arbitrary for DltParserSettings {
             any::<Option<Vec<String>>>(),
             any::<bool>(),
             any::<Option<String>>(),
+            any::<Option<DltV2FilterConfig>>(),
             Just(None), // fibex_metadata is skipped
         )
             .prop_map(
-                |(filter_config, fibex_file_paths, with_storage_header, tz, fibex_metadata)| {
+                |(
+                    filter_config,
+                    fibex_file_paths,
+                    with_storage_header,
+                    tz,
+                    v2_filter_config,
+                    fibex_metadata,
+                )| {
                     DltParserSettings {
                         filter_config,
                         fibex_file_paths,
                         with_storage_header,
                         tz,
+                        v2_filter_config,
                         fibex_metadata,
                     }
                 },
@@ -100,13 +117,247 @@ impl Arbitrary for DltParserSettings {
     }
 }
 
+impl Arbitrary for DltV2FilterConfig {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Option<Vec<String>>>(), any::<Option<u8>>())
+            .prop_map(|(tags, max_privacy_level)| DltV2FilterConfig {
+                tags,
+                max_privacy_level,
+            })
+            .boxed()
+    }
+}
+
 impl Arbitrary for SomeIpParserSettings {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
 
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Option<Vec<String>>>(), any::<Option<String>>())
+            .prop_map(|(fibex_file_paths, e2e_config_path)| SomeIpParserSettings {
+                fibex_file_paths,
+                e2e_config_path,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DoipParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
         any::<Option<Vec<String>>>()
-            .prop_map(|fibex_file_paths| SomeIpParserSettings { fibex_file_paths })
+            .prop_map(|did_file_paths| DoipParserSettings { did_file_paths })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ProtobufFraming {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(ProtobufFraming::Varint),
+            Just(ProtobufFraming::Fixed32Be),
+            Just(ProtobufFraming::Fixed32Le),
+            Just(ProtobufFraming::Datagram),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ProtobufRendering {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(ProtobufRendering::Columns),
+            Just(ProtobufRendering::Json),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ProtobufParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Vec<String>>(),
+            any::<String>(),
+            any::<ProtobufFraming>(),
+            any::<ProtobufRendering>(),
+        )
+            .prop_map(|(proto_file_paths, message_type, framing, rendering)| {
+                ProtobufParserSettings {
+                    proto_file_paths,
+                    message_type,
+                    framing,
+                    rendering,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CanTraceFormat {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(CanTraceFormat::Asc),
+            Just(CanTraceFormat::Blf),
+            Just(CanTraceFormat::Candump),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CanParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<CanTraceFormat>(), any::<Option<Vec<String>>>())
+            .prop_map(|(format, dbc_file_paths)| CanParserSettings {
+                format,
+                dbc_file_paths,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LogcatFormat {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(LogcatFormat::Binary), Just(LogcatFormat::Threadtime)].boxed()
+    }
+}
+
+impl Arbitrary for LogcatParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<LogcatFormat>(),
+            any::<Option<String>>(),
+            any::<Option<i32>>(),
+        )
+            .prop_map(|(format, tz, year)| LogcatParserSettings { format, tz, year })
+            .boxed()
+    }
+}
+
+impl Arbitrary for JsonParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<String>(), 0..5),
+            any::<Option<String>>(),
+            any::<Option<String>>(),
+            any::<Option<String>>(),
+        )
+            .prop_map(|(fields, timestamp_field, timestamp_format, level_field)| {
+                JsonParserSettings {
+                    fields,
+                    timestamp_field,
+                    timestamp_format,
+                    level_field,
+                }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TextPatternPreset {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TextPatternPreset::Log4j),
+            Just(TextPatternPreset::LogcatThreadtime),
+            Just(TextPatternPreset::Nginx),
+            Just(TextPatternPreset::PythonLogging),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TextPatternParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Option<TextPatternPreset>>(),
+            any::<Option<String>>(),
+            any::<Option<String>>(),
+            any::<Option<String>>(),
+            any::<Option<i32>>(),
+            any::<Option<String>>(),
+            any::<Option<String>>(),
+            any::<Option<bool>>(),
+        )
+            .prop_map(
+                |(
+                    preset,
+                    pattern,
+                    timestamp_group,
+                    timestamp_format,
+                    year,
+                    severity_group,
+                    continuation_pattern,
+                    join_unmatched,
+                )| TextPatternParserSettings {
+                    preset,
+                    pattern,
+                    timestamp_group,
+                    timestamp_format,
+                    year,
+                    severity_group,
+                    continuation_pattern,
+                    join_unmatched,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for TextContinuationRule {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TextContinuationRule::LeadingWhitespace),
+            Just(TextContinuationRule::NoTimestamp),
+            any::<String>().prop_map(TextContinuationRule::Pattern),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TextMultilineParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<TextContinuationRule>(), any::<Option<u32>>())
+            .prop_map(|(rule, max_lines)| TextMultilineParserSettings { rule, max_lines })
             .boxed()
     }
 }
@@ -221,18 +472,37 @@ impl Arbitrary for FileFormat {
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
         // Reminder to extend tests here when new items are added.
-        match FileFormat::Text {
+        match FileFormat::Binary {
             FileFormat::PcapNG => {}
             FileFormat::PcapLegacy => {}
-            FileFormat::Text => {}
+            FileFormat::Text(_) => {}
             FileFormat::Binary => {}
+            FileFormat::Journal => {}
         };
 
         prop_oneof![
             Just(FileFormat::PcapNG),
             Just(FileFormat::PcapLegacy),
-            Just(FileFormat::Text),
+            any::<Option<TextEncoding>>().prop_map(FileFormat::Text),
             Just(FileFormat::Binary),
+            Just(FileFormat::Journal),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TextEncoding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TextEncoding::Utf8),
+            Just(TextEncoding::Utf16Le),
+            Just(TextEncoding::Utf16Be),
+            Just(TextEncoding::Latin1),
+            Just(TextEncoding::Windows1251),
+            Just(TextEncoding::ShiftJis),
         ]
         .boxed()
     }
@@ -272,13 +542,28 @@ impl Arbitrary for ObserveOptions {
 test_msg!(ObserveOptions, TESTS_USECASE_COUNT);
 test_msg!(ObserveOrigin, TESTS_USECASE_COUNT);
 test_msg!(FileFormat, TESTS_USECASE_COUNT);
+test_msg!(TextEncoding, TESTS_USECASE_COUNT);
 test_msg!(UDPTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(TCPTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(SerialTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(ProcessTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(Transport, TESTS_USECASE_COUNT);
 test_msg!(SomeIpParserSettings, TESTS_USECASE_COUNT);
+test_msg!(TextPatternParserSettings, TESTS_USECASE_COUNT);
+test_msg!(JsonParserSettings, TESTS_USECASE_COUNT);
+test_msg!(CanParserSettings, TESTS_USECASE_COUNT);
+test_msg!(CanTraceFormat, TESTS_USECASE_COUNT);
+test_msg!(LogcatFormat, TESTS_USECASE_COUNT);
+test_msg!(LogcatParserSettings, TESTS_USECASE_COUNT);
+test_msg!(DoipParserSettings, TESTS_USECASE_COUNT);
+test_msg!(ProtobufFraming, TESTS_USECASE_COUNT);
+test_msg!(ProtobufRendering, TESTS_USECASE_COUNT);
+test_msg!(ProtobufParserSettings, TESTS_USECASE_COUNT);
+test_msg!(TextPatternPreset, TESTS_USECASE_COUNT);
+test_msg!(TextContinuationRule, TESTS_USECASE_COUNT);
+test_msg!(TextMultilineParserSettings, TESTS_USECASE_COUNT);
 test_msg!(DltParserSettings, TESTS_USECASE_COUNT);
+test_msg!(DltV2FilterConfig, TESTS_USECASE_COUNT);
 test_msg!(ParserType, TESTS_USECASE_COUNT);
 test_msg!(UdpConnectionInfo, TESTS_USECASE_COUNT);
 test_msg!(MulticastInfo, TESTS_USECASE_COUNT);
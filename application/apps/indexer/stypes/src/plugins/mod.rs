@@ -241,6 +241,26 @@ pub struct InvalidPluginEntity {
 pub struct PluginMetadata {
     pub title: String,
     pub description: Option<String>,
+    /// Describes the files a parser plugin can handle, so it's suggested when
+    /// the format of a file is detected.
+    pub detector: Option<PluginFormatDetector>,
+}
+
+/// Describes the files a parser plugin can handle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "plugins.ts")
+)]
+pub struct PluginFormatDetector {
+    /// File extensions without the leading dot, e.g. `dlt`.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Byte sequences as hex strings which the files start with, e.g. `444C5401`.
+    #[serde(default)]
+    pub signatures: Vec<String>,
 }
 
 /// Represents plugins main types
@@ -10,6 +10,7 @@ try_into_js!(PluginConfigSchemaType);
 try_into_js!(PluginConfigSchemaItem);
 try_into_js!(PluginEntity);
 try_into_js!(PluginMetadata);
+try_into_js!(PluginFormatDetector);
 try_into_js!(PluginType);
 try_into_js!(PluginInfo);
 try_into_js!(InvalidPluginEntity);
@@ -191,8 +191,33 @@ impl Arbitrary for PluginMetadata {
     type Strategy = BoxedStrategy<Self>;
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        (any::<String>(), prop::option::of(any::<String>()))
-            .prop_map(|(title, description)| Self { title, description })
+        (
+            any::<String>(),
+            prop::option::of(any::<String>()),
+            prop::option::of(any::<PluginFormatDetector>()),
+        )
+            .prop_map(|(title, description, detector)| Self {
+                title,
+                description,
+                detector,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for PluginFormatDetector {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<String>(), 0..5),
+            prop::collection::vec(any::<String>(), 0..5),
+        )
+            .prop_map(|(extensions, signatures)| Self {
+                extensions,
+                signatures,
+            })
             .boxed()
     }
 }
@@ -418,6 +443,7 @@ test_msg!(PluginConfigSchemaType, TESTS_USECASE_COUNT);
 test_msg!(PluginConfigSchemaItem, TESTS_USECASE_COUNT);
 test_msg!(PluginEntity, TESTS_USECASE_COUNT);
 test_msg!(PluginMetadata, TESTS_USECASE_COUNT);
+test_msg!(PluginFormatDetector, TESTS_USECASE_COUNT);
 test_msg!(PluginType, TESTS_USECASE_COUNT);
 test_msg!(PluginInfo, TESTS_USECASE_COUNT);
 test_msg!(InvalidPluginEntity, TESTS_USECASE_COUNT);
@@ -117,14 +117,31 @@ gen_encode_decode_fns!(UdpConnectionInfo);
 gen_encode_decode_fns!(ParserType);
 gen_encode_decode_fns!(DltParserSettings);
 gen_encode_decode_fns!(SomeIpParserSettings);
+gen_encode_decode_fns!(TextPatternParserSettings);
+gen_encode_decode_fns!(JsonParserSettings);
+gen_encode_decode_fns!(CanParserSettings);
+gen_encode_decode_fns!(CanTraceFormat);
+gen_encode_decode_fns!(LogcatFormat);
+gen_encode_decode_fns!(LogcatParserSettings);
+gen_encode_decode_fns!(DoipParserSettings);
+gen_encode_decode_fns!(ProtobufFraming);
+gen_encode_decode_fns!(ProtobufRendering);
+gen_encode_decode_fns!(ProtobufParserSettings);
+gen_encode_decode_fns!(TextPatternPreset);
+gen_encode_decode_fns!(TextContinuationRule);
+gen_encode_decode_fns!(TextMultilineParserSettings);
 gen_encode_decode_fns!(Transport);
 gen_encode_decode_fns!(ProcessTransportConfig);
 gen_encode_decode_fns!(SerialTransportConfig);
 gen_encode_decode_fns!(TCPTransportConfig);
 gen_encode_decode_fns!(UDPTransportConfig);
 gen_encode_decode_fns!(FileFormat);
+gen_encode_decode_fns!(TextEncoding);
 gen_encode_decode_fns!(ObserveOrigin);
 gen_encode_decode_fns!(FoldersScanningResult);
+gen_encode_decode_fns!(FileCompression);
+gen_encode_decode_fns!(FormatCandidate);
+gen_encode_decode_fns!(FormatDetection);
 gen_encode_decode_fns!(DltStatisticInfo);
 gen_encode_decode_fns!(ShellType);
 gen_encode_decode_fns!(ShellProfile);
@@ -142,6 +159,7 @@ gen_encode_decode_fns!(PluginLogLevel);
 gen_encode_decode_fns!(PluginLogMessage);
 gen_encode_decode_fns!(PluginRunData);
 gen_encode_decode_fns!(PluginMetadata);
+gen_encode_decode_fns!(PluginFormatDetector);
 gen_encode_decode_fns!(PluginType);
 gen_encode_decode_fns!(PluginInfo);
 gen_encode_decode_fns!(InvalidPluginEntity);
@@ -163,6 +181,8 @@ gen_encode_decode_fns!(CommandOutcome<FoldersScanningResult>);
 gen_encode_decode_fns!(CommandOutcome<SerialPortsList>);
 gen_encode_decode_fns!(CommandOutcome<ProfileList>);
 gen_encode_decode_fns!(CommandOutcome<DltStatisticInfo>);
+gen_encode_decode_fns!(CommandOutcome<FormatDetection>);
+gen_encode_decode_fns!(CommandOutcome<ParserRenderOptions>);
 gen_encode_decode_fns!(CommandOutcome<MapKeyValue>);
 gen_encode_decode_fns!(CommandOutcome<()>);
 gen_encode_decode_fns!(CommandOutcome<i64>);
@@ -186,11 +206,16 @@ gen_encode_decode_fns!(SourceDefinition);
 gen_encode_decode_fns!(Sources);
 gen_encode_decode_fns!(SdeRequest);
 gen_encode_decode_fns!(SdeResponse);
+gen_encode_decode_fns!(DltControlRequest);
+gen_encode_decode_fns!(DltControlCommand);
 gen_encode_decode_fns!(GrabbedElement);
 gen_encode_decode_fns!(GrabbedElementList);
+gen_encode_decode_fns!(MatchSpan);
 gen_encode_decode_fns!(AroundIndexes);
 gen_encode_decode_fns!(FilterMatch);
 gen_encode_decode_fns!(FilterMatchList);
+gen_encode_decode_fns!(DltContextInfo);
+gen_encode_decode_fns!(DltContextInfoList);
 gen_encode_decode_fns!(FolderEntity);
 gen_encode_decode_fns!(FolderEntityDetails);
 gen_encode_decode_fns!(FolderEntityType);
@@ -0,0 +1,74 @@
+use node_bindgen::{
+    core::{
+        JSValue, NjError,
+        val::{JsEnv, JsObject},
+    },
+    sys::napi_value,
+};
+use session::state::{ExportFormat, SearchExportOptions};
+
+#[derive(Debug, Clone)]
+pub struct WrappedSearchExportOptions(SearchExportOptions);
+
+impl WrappedSearchExportOptions {
+    pub fn as_options(&self) -> SearchExportOptions {
+        self.0.clone()
+    }
+}
+
+fn get_count(js_obj: &JsObject, name: &str) -> Result<u64, NjError> {
+    match js_obj.get_property(name) {
+        Ok(Some(value)) => {
+            let count: i64 = value.as_value()?;
+            u64::try_from(count).map_err(|_| NjError::Other(format!("Invalid [{name}]: {count}")))
+        }
+        Ok(None) => Err(NjError::Other(format!("[{name}] property is not found"))),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_optional_string(js_obj: &JsObject, name: &str) -> Result<Option<String>, NjError> {
+    match js_obj.get_property(name) {
+        Ok(Some(value)) => Ok(Some(value.as_value()?)),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+impl JSValue<'_> for WrappedSearchExportOptions {
+    fn convert_to_rust(env: &JsEnv, n_value: napi_value) -> Result<Self, NjError> {
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(n_value) {
+            let before = get_count(&js_obj, "before")?;
+            let after = get_count(&js_obj, "after")?;
+            // Optional: number[]
+            let columns: Vec<usize> = match js_obj.get_property("columns") {
+                Ok(Some(value)) => {
+                    let columns: Vec<i64> = value.as_value()?;
+                    columns
+                        .into_iter()
+                        .map(|c| {
+                            usize::try_from(c)
+                                .map_err(|_| NjError::Other(format!("Invalid column index: {c}")))
+                        })
+                        .collect::<Result<Vec<usize>, NjError>>()?
+                }
+                Ok(None) => Vec::new(),
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            Ok(WrappedSearchExportOptions(SearchExportOptions {
+                before,
+                after,
+                format: ExportFormat {
+                    columns,
+                    spliter: get_optional_string(&js_obj, "spliter")?,
+                    delimiter: get_optional_string(&js_obj, "delimiter")?,
+                    separator: get_optional_string(&js_obj, "separator")?,
+                },
+            }))
+        } else {
+            Err(NjError::Other("not valid format".to_owned()))
+        }
+    }
+}
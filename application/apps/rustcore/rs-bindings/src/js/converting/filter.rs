@@ -5,7 +5,10 @@ use node_bindgen::{
     },
     sys::napi_value,
 };
-use processor::search::filter::SearchFilter;
+use processor::search::{
+    filter::{ColumnsScope, SearchFilter},
+    scope::{SearchScope, TimeWindow},
+};
 use serde::Serialize;
 #[derive(Serialize, Debug, Clone)]
 pub struct WrappedSearchFilter(SearchFilter);
@@ -59,12 +62,137 @@ impl JSValue<'_> for WrappedSearchFilter {
                     return Err(e);
                 }
             };
-            Ok(WrappedSearchFilter(SearchFilter::new(
-                value,
-                is_regex,
-                ignore_case,
-                is_word,
-            )))
+            // Optional: { spliter: string, columns: number[] }
+            let columns: Option<ColumnsScope> = match js_obj.get_property("columns") {
+                Ok(Some(value)) => {
+                    let scope: JsObject = value.as_value()?;
+                    let spliter: String = match scope.get_property("spliter") {
+                        Ok(Some(value)) => value.as_value()?,
+                        Ok(None) => {
+                            return Err(NjError::Other(
+                                "[columns.spliter] property is not found".to_owned(),
+                            ));
+                        }
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    };
+                    let columns: Vec<i64> = match scope.get_property("columns") {
+                        Ok(Some(value)) => value.as_value()?,
+                        Ok(None) => {
+                            return Err(NjError::Other(
+                                "[columns.columns] property is not found".to_owned(),
+                            ));
+                        }
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    };
+                    Some(ColumnsScope::new(
+                        spliter,
+                        columns
+                            .into_iter()
+                            .map(|c| {
+                                usize::try_from(c).map_err(|_| {
+                                    NjError::Other(format!("Invalid column index: {c}"))
+                                })
+                            })
+                            .collect::<Result<Vec<usize>, NjError>>()?,
+                    ))
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            // Optional: boolean
+            let is_glob: bool = match js_obj.get_property("is_glob") {
+                Ok(Some(value)) => value.as_value()?,
+                Ok(None) => false,
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            // Optional: number
+            let distance: Option<u8> = match js_obj.get_property("distance") {
+                Ok(Some(value)) => {
+                    let distance: i64 = value.as_value()?;
+                    Some(u8::try_from(distance).map_err(|_| {
+                        NjError::Other(format!("Invalid edit distance: {distance}"))
+                    })?)
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            Ok(WrappedSearchFilter(
+                SearchFilter::new(value, is_regex, ignore_case, is_word)
+                    .columns(columns)
+                    .glob(is_glob)
+                    .fuzzy(distance),
+            ))
+        } else {
+            Err(NjError::Other("not valid format".to_owned()))
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct WrappedSearchScope(SearchScope);
+
+impl WrappedSearchScope {
+    pub fn as_scope(&self) -> SearchScope {
+        self.0.clone()
+    }
+}
+
+impl JSValue<'_> for WrappedSearchScope {
+    fn convert_to_rust(env: &JsEnv, n_value: napi_value) -> Result<Self, NjError> {
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(n_value) {
+            // Optional: number[]
+            let sources: Vec<u16> = match js_obj.get_property("sources") {
+                Ok(Some(value)) => {
+                    let sources: Vec<i64> = value.as_value()?;
+                    sources
+                        .into_iter()
+                        .map(|s| {
+                            u16::try_from(s)
+                                .map_err(|_| NjError::Other(format!("Invalid source id: {s}")))
+                        })
+                        .collect::<Result<Vec<u16>, NjError>>()?
+                }
+                Ok(None) => Vec::new(),
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            // Optional: { from?: number, to?: number }
+            let time: Option<TimeWindow> = match js_obj.get_property("time") {
+                Ok(Some(value)) => {
+                    let window: JsObject = value.as_value()?;
+                    let from: Option<i64> = match window.get_property("from") {
+                        Ok(Some(value)) => Some(value.as_value()?),
+                        Ok(None) => None,
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    };
+                    let to: Option<i64> = match window.get_property("to") {
+                        Ok(Some(value)) => Some(value.as_value()?),
+                        Ok(None) => None,
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    };
+                    Some(TimeWindow { from, to })
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    return Err(e);
+                }
+            };
+            Ok(WrappedSearchScope(SearchScope { sources, time }))
         } else {
             Err(NjError::Other("not valid format".to_owned()))
         }
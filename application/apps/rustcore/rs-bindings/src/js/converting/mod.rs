@@ -1,2 +1,3 @@
 // pub mod concat;
+pub mod export;
 pub mod filter;
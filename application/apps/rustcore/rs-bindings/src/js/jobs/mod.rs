@@ -1,6 +1,6 @@
 use crate::js::converting::filter::WrappedSearchFilter;
 use log::{debug, error};
-use node_bindgen::derive::node_bindgen;
+use node_bindgen::{core::buffer::JSArrayBuffer, derive::node_bindgen};
 
 use session::unbound::{UnboundSession, api::UnboundSessionAPI};
 use std::{convert::TryFrom, thread};
@@ -118,6 +118,34 @@ impl UnboundJobs {
             .await
     }
 
+    #[node_bindgen]
+    async fn detect_file_format(
+        &self,
+        id: i64,
+        file_path: String,
+    ) -> Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError> {
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .detect_file_format(u64_from_i64(id)?, file_path)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_parser_render_options(
+        &self,
+        id: i64,
+        parser: JSArrayBuffer,
+    ) -> Result<stypes::CommandOutcome<stypes::ParserRenderOptions>, stypes::ComputationError> {
+        let parser =
+            stypes::ParserType::decode(&parser).map_err(stypes::ComputationError::Decoding)?;
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_parser_render_options(u64_from_i64(id)?, parser)
+            .await
+    }
+
     #[node_bindgen]
     async fn get_file_checksum(
         &self,
@@ -1,6 +1,12 @@
 pub mod progress_tracker;
 
-use crate::{js::converting::filter::WrappedSearchFilter, logging::targets};
+use crate::{
+    js::converting::{
+        export::WrappedSearchExportOptions,
+        filter::{WrappedSearchFilter, WrappedSearchScope},
+    },
+    logging::targets,
+};
 use log::{debug, error, info, warn};
 use node_bindgen::{core::buffer::JSArrayBuffer, derive::node_bindgen};
 use processor::grabber::LineRange;
@@ -207,6 +213,36 @@ impl RustSession {
             )
     }
 
+    /// Exports matches of search along with rows around them (context) into a text file.
+    ///
+    /// # Arguments
+    ///
+    /// * `out_path` - The path to the output file.
+    /// * `search` - Identifier of named search; `None` for the main search.
+    /// * `filters` - Filters to find matches with; results of search are exported if empty.
+    /// * `options` - Size of context and the format of exported rows.
+    /// * `operation_id` - The unique identifier for the export operation.
+    #[node_bindgen]
+    async fn export_search(
+        &self,
+        out_path: String,
+        search: Option<String>,
+        filters: Vec<WrappedSearchFilter>,
+        options: WrappedSearchExportOptions,
+        operation_id: String,
+    ) -> Result<(), stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .export_search(
+                operations::uuid_from_str(&operation_id)?,
+                PathBuf::from(out_path),
+                search,
+                filters.iter().map(|f| f.as_filter()).collect(),
+                options.as_options(),
+            )
+    }
+
     #[node_bindgen]
     async fn export_raw(
         &self,
@@ -280,6 +316,38 @@ impl RustSession {
             .await
     }
 
+    #[node_bindgen]
+    async fn set_exclusion(&self, search: Option<String>) -> Result<(), stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .set_exclusion(search)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn set_ngram_index(&self, enabled: bool) -> Result<(), stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .set_ngram_index(enabled)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_filtered_position(
+        &self,
+        row: i64,
+    ) -> Result<Option<i64>, stypes::ComputationError> {
+        let row = u64::try_from(row).map_err(|_| stypes::ComputationError::InvalidData)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_filtered_position(row)
+            .await
+            .map(|pos| pos.map(|pos| pos as i64))
+    }
+
     #[node_bindgen]
     async fn get_indexed_len(&self) -> Result<i64, stypes::ComputationError> {
         self.session
@@ -357,6 +425,7 @@ impl RustSession {
         &self,
         start_line_index: i64,
         number_of_lines: i64,
+        search: Option<String>,
     ) -> Result<GrabbedElementList, stypes::ComputationError> {
         let start =
             u64::try_from(start_line_index).map_err(|_| stypes::ComputationError::InvalidData)?;
@@ -365,7 +434,7 @@ impl RustSession {
         self.session
             .as_ref()
             .ok_or(stypes::ComputationError::SessionUnavailable)?
-            .grab_search(LineRange::from(start..=end))
+            .grab_search(LineRange::from(start..=end), search)
             .await
     }
 
@@ -459,6 +528,7 @@ impl RustSession {
         &self,
         filters: Vec<WrappedSearchFilter>,
         operation_id: String,
+        scope: Option<WrappedSearchScope>,
     ) -> Result<(), stypes::ComputationError> {
         let session = self
             .session
@@ -466,14 +536,72 @@ impl RustSession {
             .ok_or(stypes::ComputationError::SessionUnavailable)?;
         info!(
             target: targets::SESSION,
-            "Search (operation: {operation_id}) will be done withing next filters: {filters:?}"
+            "Search (operation: {operation_id}) will be done withing next filters: {filters:?}; scope: {scope:?}"
         );
         session.apply_search_filters(
             operations::uuid_from_str(&operation_id)?,
             filters.iter().map(|f| f.as_filter()).collect(),
+            scope.map(|s| s.as_scope()).unwrap_or_default(),
         )
     }
 
+    #[node_bindgen]
+    async fn apply_search_query(
+        &self,
+        query: String,
+        spliter: Option<String>,
+        operation_id: String,
+        scope: Option<WrappedSearchScope>,
+    ) -> Result<(), stypes::ComputationError> {
+        let session = self
+            .session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?;
+        info!(
+            target: targets::SESSION,
+            "Search (operation: {operation_id}) will be done with query: {query}; scope: {scope:?}"
+        );
+        session.apply_search_query(
+            operations::uuid_from_str(&operation_id)?,
+            query,
+            spliter,
+            scope.map(|s| s.as_scope()).unwrap_or_default(),
+        )
+    }
+
+    #[node_bindgen]
+    async fn apply_named_search(
+        &self,
+        id: String,
+        filters: Vec<WrappedSearchFilter>,
+        operation_id: String,
+        scope: Option<WrappedSearchScope>,
+    ) -> Result<(), stypes::ComputationError> {
+        let session = self
+            .session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?;
+        info!(
+            target: targets::SESSION,
+            "Named search \"{id}\" (operation: {operation_id}) will be done withing next filters: {filters:?}"
+        );
+        session.apply_named_search(
+            operations::uuid_from_str(&operation_id)?,
+            id,
+            filters.iter().map(|f| f.as_filter()).collect(),
+            scope.map(|s| s.as_scope()).unwrap_or_default(),
+        )
+    }
+
+    #[node_bindgen]
+    async fn drop_named_search(&self, id: String) -> Result<bool, stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .drop_named_search(id)
+            .await
+    }
+
     #[node_bindgen]
     async fn apply_search_values_filters(
         &self,
@@ -514,6 +642,7 @@ impl RustSession {
         &self,
         filters: Vec<WrappedSearchFilter>,
         operation_id: String,
+        scope: Option<WrappedSearchScope>,
     ) -> Result<(), stypes::ComputationError> {
         let session = self
             .session
@@ -526,6 +655,7 @@ impl RustSession {
         session.extract_matches(
             operations::uuid_from_str(&operation_id)?,
             filters.iter().map(|f| f.as_filter()).collect(),
+            scope.map(|s| s.as_scope()).unwrap_or_default(),
         )
     }
 
@@ -536,6 +666,8 @@ impl RustSession {
         dataset_len: i32,
         from: Option<i64>,
         to: Option<i64>,
+        search: Option<String>,
+        scope: Option<WrappedSearchScope>,
     ) -> Result<(), stypes::ComputationError> {
         let session = self
             .session
@@ -558,12 +690,14 @@ impl RustSession {
         }
         info!(
             target: targets::SESSION,
-            "Map requested (operation: {operation_id}). Range: {range:?}"
+            "Map requested (operation: {operation_id}). Range: {range:?}; search: {search:?}; scope: {scope:?}"
         );
         session.get_map(
             operations::uuid_from_str(&operation_id)?,
             dataset_len as u16,
             range,
+            search,
+            scope.map(|s| s.as_scope()).unwrap_or_default(),
         )
     }
 
@@ -605,6 +739,7 @@ impl RustSession {
         &self,
         operation_id: String,
         position_in_stream: i64,
+        search: Option<String>,
     ) -> Result<(), stypes::ComputationError> {
         self.session
             .as_ref()
@@ -612,6 +747,7 @@ impl RustSession {
             .get_nearest_to(
                 operations::uuid_from_str(&operation_id)?,
                 position_in_stream as u64,
+                search,
             )
     }
 
@@ -651,6 +787,17 @@ impl RustSession {
         Ok(stypes::AttachmentList(attachments))
     }
 
+    #[node_bindgen]
+    async fn get_dlt_contexts(
+        &self,
+    ) -> Result<stypes::DltContextInfoList, stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_dlt_contexts()
+            .await
+    }
+
     #[node_bindgen]
     async fn get_indexed_ranges(&self) -> Result<stypes::Ranges, stypes::ComputationError> {
         let session = self
@@ -736,6 +883,7 @@ impl RustSession {
                 nature: 0,
                 content: format!("{pos}Test line content:{}", " test ".repeat(pos + 1)),
                 pos,
+                spans: Vec::new(),
             })
         }
         serde_json::to_string(&els).map_err(|_| stypes::ComputationError::InvalidData)
@@ -750,6 +898,7 @@ impl RustSession {
                 nature: 0,
                 content: format!("{pos}Test line content:{}", " test ".repeat(pos + 1)),
                 pos,
+                spans: Vec::new(),
             })
         }
         Ok(stypes::GrabbedElementList(els))
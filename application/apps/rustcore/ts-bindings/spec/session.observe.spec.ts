@@ -626,7 +626,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            'SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:123 METH:32773 LENG:16 CLID:0 SEID:58252 IVER:1 MSTP:2 RETC:0 [00, 00, 01, 88, 01, C3, C4, 1D]',
+                            '',
+                            '',
+                            '',
+                            '',
+                            'SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:123\u0005METH:32773\u0005LENG:16\u0005CLID:0\u0005SEID:58252\u0005IVER:1\u0005MSTP:2\u0005RETC:0\u0005[00, 00, 01, 88, 01, C3, C4, 1D]',
                         ]);
                         expect(result[5].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -639,7 +643,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            "SOME/IP 'Incomplete, not enough data for a message' [00, 7B, 80, 05, 00, 00, 00, 11, 00, 00, E3, 8C, 01, 01, 02, 00, 00, 00, 01, 88, 01, C3, C4, 1D]",
+                            '',
+                            '',
+                            '',
+                            '',
+                            "SOME/IP 0.0.0.0:0 >> INST:1\u0005'Incomplete, not enough data for a message' [00, 7B, 80, 05, 00, 00, 00, 11, 00, 00, E3, 8C, 01, 01, 02, 00, 00, 00, 01, 88, 01, C3, C4, 1D]",
                         ]);
                         logger.debug('result of grab was: ' + JSON.stringify(result));
                         finish(comps.session, done);
@@ -709,7 +717,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            'SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:123 METH:32773 LENG:16 CLID:0 SEID:58252 IVER:1 MSTP:2 RETC:0 TestService::timeEvent {\u0006\ttimestamp (INT64) : 1683656786973,\u0006}',
+                            '',
+                            '',
+                            '',
+                            '',
+                            'SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:123\u0005METH:32773\u0005LENG:16\u0005CLID:0\u0005SEID:58252\u0005IVER:1\u0005MSTP:2\u0005RETC:0\u0005TestService::timeEvent {\u0006\ttimestamp (INT64) : 1683656786973,\u0006}',
                         ]);
                         expect(result[1].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -722,7 +734,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            'SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:124 METH:32773 LENG:16 CLID:0 SEID:58252 IVER:1 MSTP:2 RETC:0 UnknownService [00, 00, 01, 88, 01, C3, C4, 1D]',
+                            '',
+                            '',
+                            '',
+                            '',
+                            'SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:124\u0005METH:32773\u0005LENG:16\u0005CLID:0\u0005SEID:58252\u0005IVER:1\u0005MSTP:2\u0005RETC:0\u0005UnknownService [00, 00, 01, 88, 01, C3, C4, 1D]',
                         ]);
                         expect(result[2].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -735,7 +751,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            'SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:123 METH:32773 LENG:16 CLID:0 SEID:58252 IVER:3 MSTP:2 RETC:0 TestService<1?>::timeEvent {\u0006\ttimestamp (INT64) : 1683656786973,\u0006}',
+                            '',
+                            '',
+                            '',
+                            '',
+                            'SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:123\u0005METH:32773\u0005LENG:16\u0005CLID:0\u0005SEID:58252\u0005IVER:3\u0005MSTP:2\u0005RETC:0\u0005TestService<1?>::timeEvent {\u0006\ttimestamp (INT64) : 1683656786973,\u0006}',
                         ]);
                         expect(result[3].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -748,7 +768,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            'SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:123 METH:32774 LENG:16 CLID:0 SEID:58252 IVER:1 MSTP:2 RETC:0 TestService::UnknownMethod [00, 00, 01, 88, 01, C3, C4, 1D]',
+                            '',
+                            '',
+                            '',
+                            '',
+                            'SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:123\u0005METH:32774\u0005LENG:16\u0005CLID:0\u0005SEID:58252\u0005IVER:1\u0005MSTP:2\u0005RETC:0\u0005TestService::UnknownMethod [00, 00, 01, 88, 01, C3, C4, 1D]',
                         ]);
                         expect(result[4].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -761,7 +785,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            "SOME/IP 0.0.0.0:0 >> INST:1 RPC SERV:123 METH:32773 LENG:15 CLID:0 SEID:58252 IVER:1 MSTP:2 RETC:0 TestService::timeEvent 'SOME/IP Error: Parser exhausted at offset 0 for Object size 8' [00, 00, 01, 88, 01, C3, C4]",
+                            '',
+                            '',
+                            '',
+                            '',
+                            "SOME/IP 0.0.0.0:0 >> INST:1\u0005RPC\u0005SERV:123\u0005METH:32773\u0005LENG:15\u0005CLID:0\u0005SEID:58252\u0005IVER:1\u0005MSTP:2\u0005RETC:0\u0005TestService::timeEvent 'SOME/IP Error: Parser exhausted at offset 0 for Object size 8' [00, 00, 01, 88, 01, C3, C4]",
                         ]);
                         expect(result[5].content.split('\u0004')).toEqual([
                             '2024-02-20T13:17:26.713537000Z',
@@ -774,7 +802,11 @@ describe('Observe', function () {
                             'APP1',
                             'C1',
                             'IPC',
-                            "SOME/IP 'Incomplete, not enough data for a message' [00, 7B, 80, 05, 00, 00, 00, 11, 00, 00, E3, 8C, 01, 01, 02, 00, 00, 00, 01, 88, 01, C3, C4, 1D]",
+                            '',
+                            '',
+                            '',
+                            '',
+                            "SOME/IP 0.0.0.0:0 >> INST:1\u0005'Incomplete, not enough data for a message' [00, 7B, 80, 05, 00, 00, 00, 11, 00, 00, E3, 8C, 01, 01, 02, 00, 00, 00, 01, 88, 01, C3, C4, 1D]",
                         ]);
                         logger.debug('result of grab was: ' + JSON.stringify(result));
                         finish(comps.session, done);
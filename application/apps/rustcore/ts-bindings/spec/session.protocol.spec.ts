@@ -53,9 +53,13 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     AttachmentInfo: protocol.decodeAttachmentInfo,
     AttachmentList: protocol.decodeAttachmentList,
     CallbackEvent: protocol.decodeCallbackEvent,
+    CanParserSettings: protocol.decodeCanParserSettings,
+    CanTraceFormat: protocol.decodeCanTraceFormat,
     CommandOutcome_bool: protocol.decodeCommandOutcomeWithbool,
     CommandOutcome_FoldersScanningResult: protocol.decodeCommandOutcomeWithFoldersScanningResult,
     CommandOutcome_DltStatisticInfo: protocol.decodeCommandOutcomeWithDltStatisticInfo,
+    CommandOutcome_FormatDetection: protocol.decodeCommandOutcomeWithFormatDetection,
+    CommandOutcome_ParserRenderOptions: protocol.decodeCommandOutcomeWithParserRenderOptions,
     CommandOutcome_ProfileList: protocol.decodeCommandOutcomeWithProfileList,
     CommandOutcome_MapKeyValue: protocol.decodeCommandOutcomeWithMapKeyValue,
     CommandOutcome_i64: protocol.decodeCommandOutcomeWithi64,
@@ -64,7 +68,13 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     CommandOutcome_String: protocol.decodeCommandOutcomeWithString,
     CommandOutcome_Void: protocol.decodeCommandOutcomeWithVoid,
     ComputationError: protocol.decodeComputationError,
+    DltContextInfo: protocol.decodeDltContextInfo,
+    DltContextInfoList: protocol.decodeDltContextInfoList,
+    DltControlCommand: protocol.decodeDltControlCommand,
+    DltControlRequest: protocol.decodeDltControlRequest,
     DltParserSettings: protocol.decodeDltParserSettings,
+    DoipParserSettings: protocol.decodeDoipParserSettings,
+    FileCompression: protocol.decodeFileCompression,
     FileFormat: protocol.decodeFileFormat,
     FilterMatch: protocol.decodeFilterMatch,
     FilterMatchList: protocol.decodeFilterMatchList,
@@ -72,9 +82,15 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     FolderEntityDetails: protocol.decodeFolderEntityDetails,
     FolderEntityType: protocol.decodeFolderEntityType,
     FoldersScanningResult: protocol.decodeFoldersScanningResult,
+    FormatCandidate: protocol.decodeFormatCandidate,
+    FormatDetection: protocol.decodeFormatDetection,
     GrabbedElement: protocol.decodeGrabbedElement,
     GrabbedElementList: protocol.decodeGrabbedElementList,
+    JsonParserSettings: protocol.decodeJsonParserSettings,
     LifecycleTransition: protocol.decodeLifecycleTransition,
+    LogcatFormat: protocol.decodeLogcatFormat,
+    LogcatParserSettings: protocol.decodeLogcatParserSettings,
+    MatchSpan: protocol.decodeMatchSpan,
     MulticastInfo: protocol.decodeMulticastInfo,
     NativeError: protocol.decodeNativeError,
     NativeErrorKind: protocol.decodeNativeErrorKind,
@@ -84,6 +100,9 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     ParserType: protocol.decodeParserType,
     ProcessTransportConfig: protocol.decodeProcessTransportConfig,
     Progress: protocol.decodeProgress,
+    ProtobufFraming: protocol.decodeProtobufFraming,
+    ProtobufParserSettings: protocol.decodeProtobufParserSettings,
+    ProtobufRendering: protocol.decodeProtobufRendering,
     Ranges: protocol.decodeRanges,
     SdeRequest: protocol.decodeSdeRequest,
     SdeResponse: protocol.decodeSdeResponse,
@@ -94,6 +113,11 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     SourceDefinition: protocol.decodeSourceDefinition,
     Sources: protocol.decodeSources,
     TCPTransportConfig: protocol.decodeTCPTransportConfig,
+    TextContinuationRule: protocol.decodeTextContinuationRule,
+    TextEncoding: protocol.decodeTextEncoding,
+    TextMultilineParserSettings: protocol.decodeTextMultilineParserSettings,
+    TextPatternParserSettings: protocol.decodeTextPatternParserSettings,
+    TextPatternPreset: protocol.decodeTextPatternPreset,
     Transport: protocol.decodeTransport,
     UdpConnectionInfo: protocol.decodeUdpConnectionInfo,
     UDPTransportConfig: protocol.decodeUDPTransportConfig,
@@ -122,6 +146,7 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     PluginConfigSchemaItem: protocol.decodePluginConfigSchemaItem,
     PluginEntity: protocol.decodePluginEntity,
     PluginMetadata: protocol.decodePluginMetadata,
+    PluginFormatDetector: protocol.decodePluginFormatDetector,
     PluginType: protocol.decodePluginType,
     PluginInfo: protocol.decodePluginInfo,
     PluginLogMessage: protocol.decodePluginLogMessage,
@@ -0,0 +1,53 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { IFilter } from 'platform/types/filter';
+import { SearchExportOptions } from 'platform/types/exporting';
+
+import * as protocol from 'protocol';
+
+export interface Options {
+    dest: string;
+    options: SearchExportOptions;
+    filters: IFilter[];
+    search: string | undefined;
+}
+
+export const executor: TExecutor<boolean, Options> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    opt: Options,
+): CancelablePromise<boolean> => {
+    return AsyncResultsExecutor<boolean, Options>(
+        session,
+        provider,
+        logger,
+        opt,
+        function (session: RustSession, opt: Options, operationUuid: string): Promise<void> {
+            return session.exportSearch(
+                opt.dest,
+                opt.options,
+                opt.filters,
+                opt.search,
+                operationUuid,
+            );
+        },
+        function (
+            data: Uint8Array,
+            resolve: (done: boolean) => void,
+            reject: (err: Error) => void,
+        ) {
+            const result: boolean = protocol.decodeResultBool(data);
+            if (typeof result !== 'boolean') {
+                return reject(
+                    new Error(
+                        `Fail to parse export results. Invalid format. Expecting valid { boolean }; gotten: ${typeof data}`,
+                    ),
+                );
+            }
+            resolve(result);
+        },
+        'exporting search',
+    );
+};
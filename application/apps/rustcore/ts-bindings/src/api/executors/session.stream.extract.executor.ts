@@ -8,23 +8,37 @@ import {
     TExtractedValues,
     IExtractedMatch,
     IExtractedValue,
+    ISearchScope,
 } from 'platform/types/filter';
 
 import * as protocol from 'protocol';
 
-export const executor: TExecutor<TExtractedValues, IFilter[]> = (
+export interface IExecuteExtractOptions {
+    filters: IFilter[];
+    /**
+     * Restricts extracting to some sources and/or time window
+     */
+    scope?: ISearchScope;
+}
+
+export const executor: TExecutor<TExtractedValues, IExecuteExtractOptions> = (
     session: RustSession,
     provider: EventProvider,
     logger: Logger,
-    filters: IFilter[],
+    options: IExecuteExtractOptions,
 ): CancelablePromise<TExtractedValues> => {
-    return AsyncResultsExecutor<TExtractedValues, IFilter[]>(
+    const filters = options.filters;
+    return AsyncResultsExecutor<TExtractedValues, IExecuteExtractOptions>(
         session,
         provider,
         logger,
-        filters,
-        function (session: RustSession, filters: IFilter[], operationUuid: string): Promise<void> {
-            return session.extractMatchesValues(filters, operationUuid);
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteExtractOptions,
+            operationUuid: string,
+        ): Promise<void> {
+            return session.extractMatchesValues(options.filters, operationUuid, options.scope);
         },
         function (
             data: Uint8Array,
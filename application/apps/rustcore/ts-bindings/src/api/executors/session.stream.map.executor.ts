@@ -1,7 +1,7 @@
 import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
 import { RustSession } from '../../native/native.session';
 import { EventProvider } from '../../api/session.provider';
-import { ISearchMap } from 'platform/types/filter';
+import { ISearchMap, ISearchScope } from 'platform/types/filter';
 
 import * as protocol from 'protocol';
 
@@ -9,6 +9,14 @@ export interface IOptions {
     datasetLength: number;
     from?: number;
     to?: number;
+    /**
+     * Identifier of named search; main search is used if it isn't defined
+     */
+    search?: string;
+    /**
+     * Restricts map to matches of some sources and/or time window
+     */
+    scope?: ISearchScope;
 }
 
 export const executor: TExecutor<ISearchMap, IOptions> = (
@@ -40,7 +48,14 @@ export const executor: TExecutor<ISearchMap, IOptions> = (
                     }
                 }
                 session
-                    .getMap(operationUuid, options.datasetLength, options.from, options.to)
+                    .getMap(
+                        operationUuid,
+                        options.datasetLength,
+                        options.from,
+                        options.to,
+                        options.search,
+                        options.scope,
+                    )
                     .catch(reject);
             });
         },
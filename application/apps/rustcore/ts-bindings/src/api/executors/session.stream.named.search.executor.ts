@@ -0,0 +1,56 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { IFilter, ISearchScope } from 'platform/types/filter';
+
+import * as protocol from 'protocol';
+
+export interface IExecuteNamedSearchOptions {
+    id: string;
+    filters: IFilter[];
+    /**
+     * Restricts search to some sources and/or time window
+     */
+    scope?: ISearchScope;
+}
+
+/**
+ * Named search runs alongside the main search and keeps own results. Applying filters
+ * to an existing named search replaces its results.
+ */
+export const executor: TExecutor<number, IExecuteNamedSearchOptions> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: IExecuteNamedSearchOptions,
+): CancelablePromise<number> => {
+    return AsyncResultsExecutor<number, IExecuteNamedSearchOptions>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteNamedSearchOptions,
+            operationUuid: string,
+        ): Promise<void> {
+            return session.searchNamed(options.id, options.filters, operationUuid, options.scope);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (found: number) => void,
+            reject: (err: Error) => void,
+        ) {
+            const found: number = protocol.decodeResultU64(data);
+            if (typeof found !== 'number' || isNaN(found) || !isFinite(found)) {
+                return reject(
+                    new Error(
+                        `Fail to parse named search results. Invalid format. Expecting valid { number }.`,
+                    ),
+                );
+            }
+            resolve(found);
+        },
+        'named_search',
+    );
+};
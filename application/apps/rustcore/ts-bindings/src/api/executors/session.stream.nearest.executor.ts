@@ -7,6 +7,10 @@ import * as protocol from 'protocol';
 
 export interface IExecuteNearestOptions {
     positionInStream: number;
+    /**
+     * Identifier of named search; main search is used if it isn't defined
+     */
+    search?: string;
 }
 
 export const executor: TExecutor<NearestPosition | undefined, IExecuteNearestOptions> = (
@@ -25,7 +29,7 @@ export const executor: TExecutor<NearestPosition | undefined, IExecuteNearestOpt
             options: IExecuteNearestOptions,
             operationUuid: string,
         ): Promise<any> {
-            return session.getNearestTo(operationUuid, options.positionInStream);
+            return session.getNearestTo(operationUuid, options.positionInStream, options.search);
         },
         function (
             data: Uint8Array,
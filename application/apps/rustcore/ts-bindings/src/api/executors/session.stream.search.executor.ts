@@ -1,23 +1,42 @@
 import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
 import { RustSession } from '../../native/native.session';
 import { EventProvider } from '../../api/session.provider';
-import { IFilter } from 'platform/types/filter';
+import { IFilter, ISearchQuery, ISearchScope } from 'platform/types/filter';
 
 import * as protocol from 'protocol';
 
-export const executor: TExecutor<number, IFilter[]> = (
+export interface IExecuteSearchOptions {
+    filters: IFilter[] | ISearchQuery;
+    /**
+     * Restricts search to some sources and/or time window; all rows are searched if
+     * it isn't defined
+     */
+    scope?: ISearchScope;
+}
+
+/**
+ * Search is done with the list of filters (a row matches if any filter matches) or
+ * with query expression.
+ */
+export const executor: TExecutor<number, IExecuteSearchOptions> = (
     session: RustSession,
     provider: EventProvider,
     logger: Logger,
-    filters: IFilter[],
+    options: IExecuteSearchOptions,
 ): CancelablePromise<number> => {
-    return AsyncResultsExecutor<number, IFilter[]>(
+    return AsyncResultsExecutor<number, IExecuteSearchOptions>(
         session,
         provider,
         logger,
-        filters,
-        function (session: RustSession, filters: IFilter[], operationUuid: string): Promise<void> {
-            return session.search(filters, operationUuid);
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteSearchOptions,
+            operationUuid: string,
+        ): Promise<void> {
+            return Array.isArray(options.filters)
+                ? session.search(options.filters, operationUuid, options.scope)
+                : session.searchQuery(options.filters, operationUuid, options.scope);
         },
         function (
             data: Uint8Array,
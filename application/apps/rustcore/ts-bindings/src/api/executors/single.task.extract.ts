@@ -1,12 +1,13 @@
 import { RustSession } from '../../native/native.session';
 import { ICancelablePromise } from 'platform/env/promise';
 import { EventProvider } from '../../api/session.provider';
-import { IFilter, TExtractedValues } from 'platform/types/filter';
+import { TExtractedValues } from 'platform/types/filter';
 import { Executors } from './session.stream.executors';
+import { IExecuteExtractOptions } from './session.stream.extract.executor';
 import { TaskManager } from './single.task';
 import { scope } from 'platform/env/scope';
 
-export class ExtractTaskManager extends TaskManager<IFilter[], TExtractedValues> {
+export class ExtractTaskManager extends TaskManager<IExecuteExtractOptions, TExtractedValues> {
     protected readonly provider: EventProvider;
     protected readonly session: RustSession;
 
@@ -16,7 +17,7 @@ export class ExtractTaskManager extends TaskManager<IFilter[], TExtractedValues>
         this.session = session;
     }
 
-    executor(filters: IFilter[]): ICancelablePromise<TExtractedValues> {
-        return Executors.extract(this.session, this.provider, this.logger, filters);
+    executor(options: IExecuteExtractOptions): ICancelablePromise<TExtractedValues> {
+        return Executors.extract(this.session, this.provider, this.logger, options);
     }
 }
@@ -2,11 +2,11 @@ import { scope } from 'platform/env/scope';
 import { RustSession } from '../../native/native.session';
 import { ICancelablePromise } from 'platform/env/promise';
 import { EventProvider } from '../../api/session.provider';
-import { IFilter } from 'platform/types/filter';
 import { Executors } from './session.stream.executors';
+import { IExecuteSearchOptions } from './session.stream.search.executor';
 import { TaskManager } from './single.task';
 
-export class SearchTaskManager extends TaskManager<IFilter[], number> {
+export class SearchTaskManager extends TaskManager<IExecuteSearchOptions, number> {
     protected readonly provider: EventProvider;
     protected readonly session: RustSession;
 
@@ -16,7 +16,7 @@ export class SearchTaskManager extends TaskManager<IFilter[], number> {
         this.session = session;
     }
 
-    executor(filters: IFilter[]): ICancelablePromise<number> {
-        return Executors.search(this.session, this.provider, this.logger, filters);
+    executor(options: IExecuteSearchOptions): ICancelablePromise<number> {
+        return Executors.search(this.session, this.provider, this.logger, options);
     }
 }
@@ -6,6 +6,9 @@ import { SomeipStatistic } from 'platform/types/observe/parser/someip';
 import {
     FoldersScanningResult,
     DltStatisticInfo,
+    FormatDetection,
+    ParserRenderOptions,
+    ParserType,
     ShellProfile,
     ProfileList,
 } from 'platform/types/bindings';
@@ -93,6 +96,38 @@ export class Jobs extends Base {
         return job;
     }
 
+    public detectFileFormat(filePath: string): CancelablePromise<FormatDetection> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<FormatDetection> = this.execute(
+            (buf: Uint8Array): FormatDetection | Error => {
+                return decode<FormatDetection>(
+                    buf,
+                    protocol.decodeCommandOutcomeWithFormatDetection,
+                );
+            },
+            this.native.detectFileFormat(sequence, filePath),
+            sequence,
+            'detectFileFormat',
+        );
+        return job;
+    }
+
+    public getParserRenderOptions(parser: ParserType): CancelablePromise<ParserRenderOptions> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<ParserRenderOptions> = this.execute(
+            (buf: Uint8Array): ParserRenderOptions | Error => {
+                return decode<ParserRenderOptions>(
+                    buf,
+                    protocol.decodeCommandOutcomeWithParserRenderOptions,
+                );
+            },
+            this.native.getParserRenderOptions(sequence, protocol.encodeParserType(parser)),
+            sequence,
+            'getParserRenderOptions',
+        );
+        return job;
+    }
+
     public getFileChecksum(path: string): CancelablePromise<string> {
         const sequence = this.sequence();
         const job: CancelablePromise<string> = this.execute(
@@ -198,6 +233,10 @@ export class Jobs extends Base {
                 is_regex: filter.flags.reg,
                 ignore_case: !filter.flags.cases,
                 is_word: filter.flags.word,
+                ...(filter.flags.glob !== undefined ? { is_glob: filter.flags.glob } : {}),
+                ...(filter.flags.distance !== undefined
+                    ? { distance: filter.flags.distance }
+                    : {}),
             }),
             sequence,
             'getRegexError',
@@ -1,5 +1,5 @@
 import { Subject } from 'platform/env/subscription';
-import { ISearchUpdated } from 'platform/types/filter';
+import { ISearchUpdated, INamedSearchUpdated } from 'platform/types/filter';
 import { Computation } from '../provider/provider';
 import { EErrorKind, EErrorSeverity } from '../provider/provider.errors';
 import { IMapEntity, IMatchEntity, FilterMatch } from 'platform/types/filter';
@@ -56,6 +56,8 @@ export interface ISessionEvents {
     SearchUpdated: Subject<ISearchUpdated>;
     SearchValuesUpdated: Subject<Map<number, [number, number]> | null>;
     SearchMapUpdated: Subject<FilterMatch[]>;
+    NamedSearchUpdated: Subject<INamedSearchUpdated>;
+    FilteredViewUpdated: Subject<number | null>;
     MapUpdated: Subject<IEventMapUpdated>;
     IndexedMapUpdated: Subject<IEventIndexedMapUpdated>;
     MatchesUpdated: Subject<IEventMatchesUpdated>;
@@ -77,6 +79,8 @@ interface ISessionEventsSignatures {
     SearchUpdated: 'SearchUpdated';
     SearchValuesUpdated: 'SearchValuesUpdated';
     SearchMapUpdated: 'SearchMapUpdated';
+    NamedSearchUpdated: 'NamedSearchUpdated';
+    FilteredViewUpdated: 'FilteredViewUpdated';
     MapUpdated: 'MapUpdated';
     IndexedMapUpdated: 'IndexedMapUpdated';
     MatchesUpdated: 'MatchesUpdated';
@@ -96,6 +100,8 @@ const SessionEventsSignatures: ISessionEventsSignatures = {
     SearchUpdated: 'SearchUpdated',
     SearchValuesUpdated: 'SearchValuesUpdated',
     SearchMapUpdated: 'SearchMapUpdated',
+    NamedSearchUpdated: 'NamedSearchUpdated',
+    FilteredViewUpdated: 'FilteredViewUpdated',
     MapUpdated: 'MapUpdated',
     IndexedMapUpdated: 'IndexedMapUpdated',
     MatchesUpdated: 'MatchesUpdated',
@@ -115,6 +121,8 @@ interface ISessionEventsInterfaces {
     SearchUpdated: { self: 'object'; found: 'number'; stat: typeof Map };
     SearchValuesUpdated: { self: ['object', null] };
     SearchMapUpdated: { self: [typeof Array, null] };
+    NamedSearchUpdated: { self: 'object'; id: 'string'; found: 'number'; stat: typeof Map };
+    FilteredViewUpdated: { self: ['number', null] };
     MapUpdated: { self: 'object'; map: typeof Array };
     IndexedMapUpdated: { self: 'object'; len: 'number' };
     MatchesUpdated: { self: 'object'; matches: typeof Array };
@@ -145,6 +153,8 @@ const SessionEventsInterfaces: ISessionEventsInterfaces = {
     SearchUpdated: { self: 'object', found: 'number', stat: Map },
     SearchValuesUpdated: { self: ['object', null] },
     SearchMapUpdated: { self: [Array, null] },
+    NamedSearchUpdated: { self: 'object', id: 'string', found: 'number', stat: Map },
+    FilteredViewUpdated: { self: ['number', null] },
     MapUpdated: { self: 'object', map: Array },
     IndexedMapUpdated: { self: 'object', len: 'number' },
     MatchesUpdated: { self: 'object', matches: Array },
@@ -180,6 +190,8 @@ export class EventProvider extends Computation<
         SearchUpdated: new Subject<ISearchUpdated>(),
         SearchValuesUpdated: new Subject<Map<number, [number, number]> | null>(),
         SearchMapUpdated: new Subject<FilterMatch[]>(),
+        NamedSearchUpdated: new Subject<INamedSearchUpdated>(),
+        FilteredViewUpdated: new Subject<number | null>(),
         MapUpdated: new Subject<IEventMapUpdated>(),
         IndexedMapUpdated: new Subject<IEventIndexedMapUpdated>(),
         MatchesUpdated: new Subject<IEventMatchesUpdated>(), // dummy
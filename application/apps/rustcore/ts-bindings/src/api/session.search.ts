@@ -4,7 +4,13 @@ import { RustSession } from '../native/native.session';
 import { ICancelablePromise } from 'platform/env/promise';
 import { EventProvider } from '../api/session.provider';
 import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
-import { IFilter, ISearchMap, TExtractedValues } from 'platform/types/filter';
+import {
+    IFilter,
+    ISearchMap,
+    ISearchQuery,
+    ISearchScope,
+    TExtractedValues,
+} from 'platform/types/filter';
 import { ResultSearchValues, NearestPosition } from 'platform/types/bindings';
 import { Executors } from './executors/session.stream.executors';
 import { SearchTaskManager } from './executors/single.task.search';
@@ -48,9 +54,11 @@ export class SessionSearch {
      * Retruns a chunk of search results, which were gotten with filters by @method setFilters
      * @param start { number } - first row number in search result
      * @param len { number } - count of rows, which should be included into chank from @param start
+     * @param search { string | undefined } - identifier of named search; if it isn't defined,
+     * results of main search are used
      */
-    public grab(start: number, len: number): Promise<GrabbedElement[]> {
-        return this.session.grabSearchChunk(start, len);
+    public grab(start: number, len: number, search?: string): Promise<GrabbedElement[]> {
+        return this.session.grabSearchChunk(start, len, search);
     }
 
     /**
@@ -62,8 +70,52 @@ export class SessionSearch {
         return this.session.grabMatchesChunk(start, len);
     }
 
-    public search(filters: IFilter[]): ICancelablePromise<number> {
-        return this.managers.search.run(filters);
+    /**
+     * Runs search with filters; a row matches if any of filters matches
+     * @param filters { IFilter[] } - filters of search
+     * @param scope { ISearchScope | undefined } - restricts search to some sources and/or
+     * time window; all rows are searched if it isn't defined
+     */
+    public search(filters: IFilter[], scope?: ISearchScope): ICancelablePromise<number> {
+        return this.managers.search.run({ filters, scope });
+    }
+
+    /**
+     * Runs search with query expression. Query combines terms with AND / OR / NOT,
+     * parentheses and proximity condition "A FOLLOWED BY B WITHIN N". Terms can be
+     * restricted to columns ("#1:DIAG"), if query has columns separator (spliter)
+     * @param query { ISearchQuery } - query expression
+     * @param scope { ISearchScope | undefined } - restricts search to some sources and/or
+     * time window
+     */
+    public searchQuery(query: ISearchQuery, scope?: ISearchScope): ICancelablePromise<number> {
+        return this.managers.search.run({ filters: query, scope });
+    }
+
+    /**
+     * Runs named search. Named searches are running alongside the main search and each of
+     * them keeps own results, which are updated on stream changes. Updates are reported
+     * with "NamedSearchUpdated" event. Applying filters to an existing named search
+     * replaces its results
+     * @param id { string } - identifier of named search
+     * @param filters { IFilter[] } - filters of search
+     * @param scope { ISearchScope | undefined } - restricts search to some sources and/or
+     * time window
+     */
+    public named(id: string, filters: IFilter[], scope?: ISearchScope): ICancelablePromise<number> {
+        return Executors.named_search(this.session, this.provider, this.logger, {
+            id,
+            filters,
+            scope,
+        });
+    }
+
+    /**
+     * Drops named search with its results. Resolves "false" if search is still in progress
+     * @param id { string } - identifier of named search
+     */
+    public dropNamed(id: string): Promise<boolean> {
+        return this.session.dropNamedSearch(id);
     }
 
     public searchNestedMatch(
@@ -78,8 +130,8 @@ export class SessionSearch {
         return this.managers.values.run(filters);
     }
 
-    public extract(filters: IFilter[]): ICancelablePromise<TExtractedValues> {
-        return this.managers.extract.run(filters);
+    public extract(filters: IFilter[], scope?: ISearchScope): ICancelablePromise<TExtractedValues> {
+        return this.managers.extract.run({ filters, scope });
     }
 
     public drop(): Promise<boolean> {
@@ -94,11 +146,15 @@ export class SessionSearch {
         datasetLength: number,
         from?: number,
         to?: number,
+        search?: string,
+        scope?: ISearchScope,
     ): ICancelablePromise<ISearchMap> {
         return Executors.map(this.session, this.provider, this.logger, {
             datasetLength,
             from,
             to,
+            search,
+            scope,
         });
     }
 
@@ -114,12 +170,27 @@ export class SessionSearch {
         });
     }
 
-    public getNearest(positionInStream: number): ICancelablePromise<NearestPosition | undefined> {
+    public getNearest(
+        positionInStream: number,
+        search?: string,
+    ): ICancelablePromise<NearestPosition | undefined> {
         return Executors.nearest(this.session, this.provider, this.logger, {
             positionInStream,
+            search,
         });
     }
 
+    /**
+     * Enables or disables n-gram index of session file. Index is built in background
+     * while session is written and is used by searches to skip parts of session file
+     * without matches. For linked files index is saved next to the file (or in chipmunk
+     * home folder, if the folder of the file isn't writable)
+     * @param enabled { boolean }
+     */
+    public setNgramIndex(enabled: boolean): Promise<void> {
+        return this.session.setNgramIndex(enabled);
+    }
+
     public len(): Promise<number> {
         return this.session.getSearchLen();
     }
@@ -6,12 +6,13 @@ import { SdeRequest, SdeResponse } from 'platform/types/sde';
 import { EventProvider } from '../api/session.provider';
 import { Executors } from './executors/session.stream.executors';
 import { EFileOptionsRequirements } from './executors/session.stream.observe.executor';
-import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import { DltContextInfo, GrabbedElement } from 'platform/types/bindings/miscellaneous';
 import { IRange } from 'platform/types/range';
 import { ISourceLink } from 'platform/types/observe/types';
 import { Attachment, IndexingMode } from 'platform/types/content';
 import { IObserve } from 'platform/types/observe';
-import { TextExportOptions } from 'platform/types/exporting';
+import { TextExportOptions, SearchExportOptions } from 'platform/types/exporting';
+import { IFilter } from 'platform/types/filter';
 
 export class SessionStream {
     private readonly _provider: EventProvider;
@@ -46,6 +47,21 @@ export class SessionStream {
         return this._session.getIndexedLen();
     }
 
+    /**
+     * Hides rows matched by the named search from the main view. The view is bound to
+     * the identifier of search, so the search can be started or restarted later. Changes
+     * of the view are reported with "FilteredViewUpdated" event
+     * @param search { string | undefined } - identifier of named search; if it isn't
+     * defined, whole stream becomes visible
+     */
+    public setExclusion(search?: string): Promise<void> {
+        return this._session.setExclusion(search);
+    }
+
+    public getFilteredPosition(row: number): Promise<number | undefined> {
+        return this._session.getFilteredPosition(row);
+    }
+
     public getAroundIndexes(position: number): Promise<{
         before: number | undefined;
         after: number | undefined;
@@ -99,6 +115,10 @@ export class SessionStream {
         return this._session.getAttachments();
     }
 
+    public getDltContexts(): Promise<DltContextInfo[]> {
+        return this._session.getDltContexts();
+    }
+
     public getIndexedRanges(): Promise<IRange[]> {
         return this._session.getIndexedRanges();
     }
@@ -111,6 +131,24 @@ export class SessionStream {
         return Executors.export(this._session, this._provider, this._logger, { dest, ranges, opt });
     }
 
+    /**
+     * Exports matches with rows around them. Matches are taken from the given filters,
+     * otherwise from the active search (or the named one, if search is defined)
+     */
+    public exportSearch(
+        dest: string,
+        options: SearchExportOptions,
+        filters: IFilter[] = [],
+        search?: string,
+    ): ICancelablePromise<boolean> {
+        return Executors.exportSearch(this._session, this._provider, this._logger, {
+            dest,
+            options,
+            filters,
+            search,
+        });
+    }
+
     public exportRaw(dest: string, ranges: IRange[]): ICancelablePromise<boolean> {
         return Executors.exportRaw(this._session, this._provider, this._logger, { dest, ranges });
     }
@@ -54,6 +54,7 @@ export enum Source {
     SetDebug = 'SetDebug',
     SendIntoSde = 'SendIntoSde',
     GetAttachments = 'GetAttachments',
+    GetDltContexts = 'GetDltContexts',
     GetIndexedRanges = 'GetIndexedRanges',
     Concat = 'Concat',
     Merge = 'Merge',
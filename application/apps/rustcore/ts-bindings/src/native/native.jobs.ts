@@ -14,6 +14,13 @@ export abstract class JobsNative {
 
     public abstract isFileBinary(sequence: number, filePath: string): Promise<Uint8Array>;
 
+    public abstract detectFileFormat(sequence: number, filePath: string): Promise<Uint8Array>;
+
+    public abstract getParserRenderOptions(
+        sequence: number,
+        parser: Uint8Array,
+    ): Promise<Uint8Array>;
+
     public abstract jobCancelTest(
         sequence: number,
         num_a: number,
@@ -42,6 +49,8 @@ export abstract class JobsNative {
             is_regex: boolean;
             ignore_case: boolean;
             is_word: boolean;
+            is_glob?: boolean;
+            distance?: number;
         },
     ): Promise<Uint8Array>;
     public abstract installedPluginsList(sequence: number): Promise<Uint8Array>;
@@ -2,8 +2,14 @@
 import { RustSessionRequiered } from '../native/native.session.required';
 import { TEventEmitter } from '../provider/provider.general';
 import { Computation } from '../provider/provider';
-import { IFilter } from 'platform/types/filter';
-import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import {
+    IFilter,
+    IFilterColumns,
+    ISearchQuery,
+    ISearchScope,
+    ITimeWindow,
+} from 'platform/types/filter';
+import { DltContextInfo, GrabbedElement, MatchSpan } from 'platform/types/bindings/miscellaneous';
 import { getNativeModule } from '../native/native';
 import { EFileOptionsRequirements } from '../api/executors/session.stream.observe.executor';
 import { Type, Source, NativeError } from '../interfaces/errors';
@@ -15,11 +21,73 @@ import { IndexingMode, Attachment } from 'platform/types/content';
 import { Logger, utils } from 'platform/log';
 import { scope } from 'platform/env/scope';
 import { IObserve } from 'platform/types/observe';
-import { TextExportOptions } from 'platform/types/exporting';
+import { TextExportOptions, SearchExportOptions } from 'platform/types/exporting';
 
 import * as protocol from 'protocol';
 import * as types from 'platform/types';
 
+/**
+ * Definition of search filter as it's expected by the native module
+ */
+export interface INativeFilter {
+    value: string;
+    is_regex: boolean;
+    ignore_case: boolean;
+    is_word: boolean;
+    columns?: IFilterColumns;
+    is_glob?: boolean;
+    distance?: number;
+}
+
+function asNativeFilter(filter: IFilter): INativeFilter {
+    const native: INativeFilter = {
+        value: filter.filter,
+        is_regex: filter.flags.reg,
+        ignore_case: !filter.flags.cases,
+        is_word: filter.flags.word,
+    };
+    if (filter.columns !== undefined) {
+        native.columns = filter.columns;
+    }
+    if (filter.flags.glob !== undefined) {
+        native.is_glob = filter.flags.glob;
+    }
+    if (filter.flags.distance !== undefined) {
+        native.distance = filter.flags.distance;
+    }
+    return native;
+}
+
+/**
+ * Definition of search scope as it's expected by the native module. Undefined
+ * properties should not be present.
+ */
+export interface INativeSearchScope {
+    sources?: number[];
+    time?: ITimeWindow;
+}
+
+function asNativeScope(scope: ISearchScope | undefined): INativeSearchScope | undefined {
+    if (scope === undefined) {
+        return undefined;
+    }
+    const native: INativeSearchScope = {};
+    if (scope.sources !== undefined) {
+        native.sources = scope.sources;
+    }
+    if (scope.time !== undefined) {
+        const time: ITimeWindow = {};
+        if (scope.time.from !== undefined) {
+            time.from = scope.time.from;
+        }
+        if (scope.time.to !== undefined) {
+            time.to = scope.time.to;
+        }
+        native.time = time;
+    }
+    return native;
+}
+
 export type RustSessionConstructorImpl<T> = new (
     uuid: string,
     provider: Computation<any, any, any>,
@@ -53,6 +121,28 @@ export abstract class RustSession extends RustSessionRequiered {
 
     public abstract getIndexedLen(): Promise<number>;
 
+    /**
+     * Hides rows matched by the named search from the main view. With applied exclusion
+     * ranges of stream chunks and exporting are positions in the reduced view
+     * @param search { string | undefined } identifier of named search; if it isn't defined,
+     * whole stream becomes visible
+     */
+    public abstract setExclusion(search?: string): Promise<void>;
+
+    /**
+     * Enables or disables n-gram index of session file. Index is built in background and
+     * is used by searches to skip parts of session file without matches
+     * @param enabled { boolean }
+     */
+    public abstract setNgramIndex(enabled: boolean): Promise<void>;
+
+    /**
+     * Returns position of row in the main view (reduced view, if exclusion is applied)
+     * @param row { number } position of row in the stream
+     * @returns { number | undefined } undefined if row is hidden
+     */
+    public abstract getFilteredPosition(row: number): Promise<number | undefined>;
+
     public abstract getAroundIndexes(
         position: number,
     ): Promise<{ before: number | undefined; after: number | undefined }>;
@@ -73,10 +163,16 @@ export abstract class RustSession extends RustSessionRequiered {
      * Returns chunk of stream/session file.
      * @param start { number } row number of range's start
      * @param len { number } length of the chunk's range
+     * @param search { string | undefined } identifier of named search; main search is used
+     * if it isn't defined
      * @returns { string }
      * @error In case of incorrect range should return { NativeError }
      */
-    public abstract grabSearchChunk(start: number, len: number): Promise<GrabbedElement[]>;
+    public abstract grabSearchChunk(
+        start: number,
+        len: number,
+        search?: string,
+    ): Promise<GrabbedElement[]>;
 
     /**
      * TODO: @return needs interface. It should not be a string
@@ -144,6 +240,14 @@ export abstract class RustSession extends RustSessionRequiered {
         operationUuid: string,
     ): Promise<void>;
 
+    public abstract exportSearch(
+        dest: string,
+        options: SearchExportOptions,
+        filters: IFilter[],
+        search: string | undefined,
+        operationUuid: string,
+    ): Promise<void>;
+
     public abstract exportRaw(dest: string, ranges: IRange[], operationUuid: string): Promise<void>;
 
     public abstract isRawExportAvailable(): Promise<boolean>;
@@ -154,19 +258,44 @@ export abstract class RustSession extends RustSessionRequiered {
         rev: boolean,
     ): Promise<[number, number] | undefined>;
 
-    public abstract search(filters: IFilter[], operationUuid: string): Promise<void>;
+    public abstract search(
+        filters: IFilter[],
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void>;
+
+    public abstract searchQuery(
+        query: ISearchQuery,
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void>;
+
+    public abstract searchNamed(
+        id: string,
+        filters: IFilter[],
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void>;
+
+    public abstract dropNamedSearch(id: string): Promise<boolean>;
 
     public abstract searchValues(filters: string[], operationUuid: string): Promise<void>;
 
     public abstract dropSearch(): Promise<boolean>;
 
-    public abstract extractMatchesValues(filters: IFilter[], operationUuid: string): Promise<void>;
+    public abstract extractMatchesValues(
+        filters: IFilter[],
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void>;
 
     public abstract getMap(
         operationUuid: string,
         datasetLength: number,
         from?: number,
         to?: number,
+        search?: string,
+        scope?: ISearchScope,
     ): Promise<void>;
 
     public abstract getValues(
@@ -179,6 +308,7 @@ export abstract class RustSession extends RustSessionRequiered {
     public abstract getNearestTo(
         operationUuid: string,
         positionInStream: number,
+        search?: string,
     ): Promise<{ index: number; position: number } | undefined>;
 
     public abstract sendIntoSde(
@@ -187,6 +317,7 @@ export abstract class RustSession extends RustSessionRequiered {
     ): Promise<types.sde.SdeResponse>;
 
     public abstract getAttachments(): Promise<Attachment[]>;
+    public abstract getDltContexts(): Promise<DltContextInfo[]>;
     public abstract getIndexedRanges(): Promise<IRange[]>;
 
     public abstract abort(
@@ -241,6 +372,12 @@ export abstract class RustSessionNative {
 
     public abstract getIndexedLen(): Promise<number>;
 
+    public abstract setExclusion(search?: string): Promise<void>;
+
+    public abstract setNgramIndex(enabled: boolean): Promise<void>;
+
+    public abstract getFilteredPosition(row: number): Promise<number | null>;
+
     public abstract getAroundIndexes(position: number): Promise<[number | null, number | null]>;
 
     public abstract expandBreadcrumbs(
@@ -257,7 +394,7 @@ export abstract class RustSessionNative {
 
     public abstract grabRanges(ranges: number[][]): Promise<Uint8Array>;
 
-    public abstract grabSearch(start: number, len: number): Promise<Uint8Array>;
+    public abstract grabSearch(start: number, len: number, search?: string): Promise<Uint8Array>;
 
     public abstract getSearchLen(): Promise<number>;
 
@@ -270,6 +407,14 @@ export abstract class RustSessionNative {
         operationUuid: string,
     ): Promise<void>;
 
+    public abstract exportSearch(
+        dest: string,
+        search: string | undefined,
+        filters: Array<INativeFilter>,
+        options: SearchExportOptions,
+        operationUuid: string,
+    ): Promise<void>;
+
     public abstract exportRaw(
         dest: string,
         ranges: number[][],
@@ -279,22 +424,29 @@ export abstract class RustSessionNative {
     public abstract isRawExportAvailable(): Promise<boolean>;
 
     public abstract applySearchFilters(
-        filters: Array<{
-            value: string;
-            is_regex: boolean;
-            ignore_case: boolean;
-            is_word: boolean;
-        }>,
+        filters: Array<INativeFilter>,
+        operationUuid: string,
+        scope?: INativeSearchScope,
+    ): Promise<void>;
+
+    public abstract applySearchQuery(
+        query: string,
+        spliter: string | undefined,
+        operationUuid: string,
+        scope?: INativeSearchScope,
+    ): Promise<void>;
+
+    public abstract applyNamedSearch(
+        id: string,
+        filters: Array<INativeFilter>,
         operationUuid: string,
+        scope?: INativeSearchScope,
     ): Promise<void>;
 
+    public abstract dropNamedSearch(id: string): Promise<boolean>;
+
     public abstract searchNestedMatch(
-        filter: {
-            value: string;
-            is_regex: boolean;
-            ignore_case: boolean;
-            is_word: boolean;
-        },
+        filter: INativeFilter,
         from: number,
         rev: boolean,
     ): Promise<[number, number] | undefined>;
@@ -307,13 +459,9 @@ export abstract class RustSessionNative {
     public abstract dropSearch(): Promise<boolean>;
 
     public abstract extractMatches(
-        filters: Array<{
-            value: string;
-            is_regex: boolean;
-            ignore_case: boolean;
-            is_word: boolean;
-        }>,
+        filters: Array<INativeFilter>,
         operationUuid: string,
+        scope?: INativeSearchScope,
     ): Promise<void>;
 
     public abstract getMap(
@@ -321,6 +469,8 @@ export abstract class RustSessionNative {
         datasetLength: number,
         from?: number,
         to?: number,
+        search?: string,
+        scope?: INativeSearchScope,
     ): Promise<void>;
 
     public abstract getValues(
@@ -333,6 +483,7 @@ export abstract class RustSessionNative {
     public abstract getNearestTo(
         operationUuid: string,
         positionInStream: number,
+        search?: string,
     ): Promise<number[] | null>;
 
     public abstract sendIntoSde(
@@ -340,6 +491,7 @@ export abstract class RustSessionNative {
         request: Uint8Array,
     ): Promise<Uint8Array>;
     public abstract getAttachments(): Promise<Uint8Array>;
+    public abstract getDltContexts(): Promise<Uint8Array>;
     public abstract getIndexedRanges(): Promise<Uint8Array>;
 
     public abstract abort(
@@ -571,6 +723,44 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public setExclusion(search?: string): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('setExclusion');
+            this._native
+                .setExclusion(search)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public setNgramIndex(enabled: boolean): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('setNgramIndex');
+            this._native
+                .setNgramIndex(enabled)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getFilteredPosition(row: number): Promise<number | undefined> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getFilteredPosition');
+            this._native
+                .getFilteredPosition(row)
+                .then((position: number | null) => {
+                    resolve(position === null ? undefined : position);
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public getAroundIndexes(
         position: number,
     ): Promise<{ before: number | undefined; after: number | undefined }> {
@@ -672,11 +862,11 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
-    public grabSearchChunk(start: number, len: number): Promise<GrabbedElement[]> {
+    public grabSearchChunk(start: number, len: number, search?: string): Promise<GrabbedElement[]> {
         return new Promise((resolve, reject) => {
             this._provider.debug().emit.operation('grabSearch');
             this._native
-                .grabSearch(start, len)
+                .grabSearch(start, len, search)
                 .then((buf: Uint8Array) => {
                     try {
                         resolve(protocol.decodeGrabbedElementList(buf));
@@ -785,6 +975,28 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public exportSearch(
+        dest: string,
+        options: SearchExportOptions,
+        filters: IFilter[],
+        search: string | undefined,
+        operationUuid: string,
+    ): Promise<void> {
+        return new Promise((resolve, reject) => {
+            try {
+                this._provider.debug().emit.operation('exportSearch', operationUuid);
+                this._native
+                    .exportSearch(dest, search, filters.map(asNativeFilter), options, operationUuid)
+                    .then(resolve)
+                    .catch((err: Error) => {
+                        reject(NativeError.from(err));
+                    });
+            } catch (err) {
+                return reject(new NativeError(NativeError.from(err), Type.Other, Source.Assign));
+            }
+        });
+    }
+
     public exportRaw(dest: string, ranges: IRange[], operationUuid: string): Promise<void> {
         return new Promise((resolve, reject) => {
             try {
@@ -817,21 +1029,66 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
-    public search(filters: IFilter[], operationUuid: string): Promise<void> {
+    public search(filters: IFilter[], operationUuid: string, scope?: ISearchScope): Promise<void> {
         return new Promise((resolve, reject) => {
             try {
                 this._provider.debug().emit.operation('applySearchFilters', operationUuid);
                 this._native
                     .applySearchFilters(
-                        filters.map((filter) => {
-                            return {
-                                value: filter.filter,
-                                is_regex: filter.flags.reg,
-                                ignore_case: !filter.flags.cases,
-                                is_word: filter.flags.word,
-                            };
-                        }),
+                        filters.map(asNativeFilter),
+                        operationUuid,
+                        asNativeScope(scope),
+                    )
+                    .then(resolve)
+                    .catch((err: Error) => {
+                        reject(NativeError.from(err));
+                    });
+            } catch (err) {
+                return reject(new NativeError(NativeError.from(err), Type.Other, Source.Search));
+            }
+        });
+    }
+
+    public searchQuery(
+        query: ISearchQuery,
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void> {
+        return new Promise((resolve, reject) => {
+            try {
+                this._provider.debug().emit.operation('applySearchQuery', operationUuid);
+                this._native
+                    .applySearchQuery(
+                        query.query,
+                        query.spliter,
+                        operationUuid,
+                        asNativeScope(scope),
+                    )
+                    .then(resolve)
+                    .catch((err: Error) => {
+                        reject(NativeError.from(err));
+                    });
+            } catch (err) {
+                return reject(new NativeError(NativeError.from(err), Type.Other, Source.Search));
+            }
+        });
+    }
+
+    public searchNamed(
+        id: string,
+        filters: IFilter[],
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void> {
+        return new Promise((resolve, reject) => {
+            try {
+                this._provider.debug().emit.operation('applyNamedSearch', operationUuid);
+                this._native
+                    .applyNamedSearch(
+                        id,
+                        filters.map(asNativeFilter),
                         operationUuid,
+                        asNativeScope(scope),
                     )
                     .then(resolve)
                     .catch((err: Error) => {
@@ -843,6 +1100,10 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public dropNamedSearch(id: string): Promise<boolean> {
+        return this._native.dropNamedSearch(id);
+    }
+
     public searchNestedMatch(
         filter: IFilter,
         from: number,
@@ -851,16 +1112,7 @@ export class RustSessionWrapper extends RustSession {
         return new Promise((resolve, reject) => {
             try {
                 this._native
-                    .searchNestedMatch(
-                        {
-                            value: filter.filter,
-                            is_regex: filter.flags.reg,
-                            ignore_case: !filter.flags.cases,
-                            is_word: filter.flags.word,
-                        },
-                        from,
-                        rev,
-                    )
+                    .searchNestedMatch(asNativeFilter(filter), from, rev)
                     .then(resolve)
                     .catch((err: Error) => {
                         reject(NativeError.from(err));
@@ -895,21 +1147,19 @@ export class RustSessionWrapper extends RustSession {
         return this._native.dropSearch();
     }
 
-    public extractMatchesValues(filters: IFilter[], operationUuid: string): Promise<void> {
+    public extractMatchesValues(
+        filters: IFilter[],
+        operationUuid: string,
+        scope?: ISearchScope,
+    ): Promise<void> {
         return new Promise((resolve, reject) => {
             try {
                 this._provider.debug().emit.operation('extractMatches', operationUuid);
                 this._native
                     .extractMatches(
-                        filters.map((filter) => {
-                            return {
-                                value: filter.filter,
-                                is_regex: filter.flags.reg,
-                                ignore_case: !filter.flags.cases,
-                                is_word: filter.flags.word,
-                            };
-                        }),
+                        filters.map(asNativeFilter),
                         operationUuid,
+                        asNativeScope(scope),
                     )
                     .then(resolve)
                     .catch((err: Error) => {
@@ -928,14 +1178,30 @@ export class RustSessionWrapper extends RustSession {
         datasetLength: number,
         from?: number,
         to?: number,
+        search?: string,
+        scope?: ISearchScope,
     ): Promise<void> {
         return new Promise((resolve, reject) => {
             this._provider.debug().emit.operation('getMap', operationUuid);
             (() => {
                 if (from === undefined || to === undefined) {
-                    return this._native.getMap(operationUuid, datasetLength);
+                    return this._native.getMap(
+                        operationUuid,
+                        datasetLength,
+                        undefined,
+                        undefined,
+                        search,
+                        asNativeScope(scope),
+                    );
                 } else {
-                    return this._native.getMap(operationUuid, datasetLength, from, to);
+                    return this._native.getMap(
+                        operationUuid,
+                        datasetLength,
+                        from,
+                        to,
+                        search,
+                        asNativeScope(scope),
+                    );
                 }
             })()
                 .then(resolve)
@@ -970,11 +1236,12 @@ export class RustSessionWrapper extends RustSession {
     public getNearestTo(
         operationUuid: string,
         positionInStream: number,
+        search?: string,
     ): Promise<{ index: number; position: number } | undefined> {
         return new Promise((resolve, reject) => {
             this._provider.debug().emit.operation('getNearestTo', operationUuid);
             this._native
-                .getNearestTo(operationUuid, positionInStream)
+                .getNearestTo(operationUuid, positionInStream, search)
                 .then((nearest) => {
                     if (nearest instanceof Array && nearest.length !== 2) {
                         reject(
@@ -1055,6 +1322,33 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public getDltContexts(): Promise<DltContextInfo[]> {
+        return new Promise((resolve, reject) => {
+            this._native
+                .getDltContexts()
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeDltContextInfoList(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GetDltContexts,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public getIndexedRanges(): Promise<IRange[]> {
         return new Promise((resolve, reject) => {
             this._native
@@ -1163,6 +1457,7 @@ export class RustSessionWrapper extends RustSession {
                 source_id: number;
                 pos: number;
                 nature: number;
+                spans: MatchSpan[];
             }> = JSON.parse(this._native.testGrabElsAsJson());
             const elements = lines.map(
                 (
@@ -1171,6 +1466,7 @@ export class RustSessionWrapper extends RustSession {
                         source_id: number;
                         pos: number;
                         nature: number;
+                        spans: MatchSpan[];
                     },
                     i: number,
                 ) => {
@@ -1179,6 +1475,7 @@ export class RustSessionWrapper extends RustSession {
                         source_id: item.source_id,
                         pos: getValidNum(item.pos),
                         nature: item.nature,
+                        spans: item.spans,
                     };
                 },
             );
@@ -11,7 +11,7 @@ describe('test', function () {
         const caseSensitive = true;
         const wholeWord = false;
         const regex = true;
-        const result = get_filter_error(filter, caseSensitive, wholeWord, regex);
+        const result = get_filter_error(filter, caseSensitive, wholeWord, regex, false, undefined);
         expect(result).toBeUndefined();
         done();
     });
@@ -22,7 +22,7 @@ describe('test', function () {
         const caseSensitive = false;
         const wholeWord = true;
         const regex = true;
-        const result = get_filter_error(filter, caseSensitive, wholeWord, regex);
+        const result = get_filter_error(filter, caseSensitive, wholeWord, regex, false, undefined);
         expect(typeof result).toEqual('string');
         done();
     });
@@ -33,7 +33,7 @@ describe('test', function () {
         const caseSensitive = true;
         const wholeWord = false;
         const regex = false;
-        const result = get_filter_error(filter, caseSensitive, wholeWord, regex);
+        const result = get_filter_error(filter, caseSensitive, wholeWord, regex, false, undefined);
         expect(result).toBeUndefined();
         done();
     });
@@ -44,8 +44,21 @@ describe('test', function () {
         const caseSensitive = false;
         const wholeWord = false;
         const regex = true;
-        const result = get_filter_error(filter, caseSensitive, wholeWord, regex);
+        const result = get_filter_error(filter, caseSensitive, wholeWord, regex, false, undefined);
         expect(result).toBeUndefined();
         done();
     });
+
+    it('glob and approximate filters', (done) => {
+        const { get_filter_error } = wasm;
+        expect(
+            get_filter_error('ECU-1??4:*', false, false, false, true, undefined),
+        ).toBeUndefined();
+        expect(typeof get_filter_error('[z-a]', false, false, false, true, undefined)).toEqual(
+            'string',
+        );
+        expect(get_filter_error('ECU-1234', false, false, false, false, 2)).toBeUndefined();
+        expect(typeof get_filter_error('ab', false, false, false, false, 2)).toEqual('string');
+        done();
+    });
 });
@@ -5,29 +5,141 @@ use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
+/// Validates filter in the same way as the search does.
+///
+/// * `glob` - filter is a wildcard pattern
+/// * `distance` - maximum edit distance of approximate matching
 #[wasm_bindgen]
 pub fn get_filter_error(
     filter: String,
     case_sensitive: bool,
     whole_word: bool,
     regex: bool,
+    glob: bool,
+    distance: Option<u8>,
 ) -> Option<String> {
-    let regex_as_str = filter_as_regex(filter, case_sensitive, whole_word, regex);
+    if glob && regex {
+        return Some(String::from(
+            "Wildcards cannot be used with regular expression",
+        ));
+    }
+    if let Some(distance) = distance {
+        if regex || glob {
+            return Some(String::from(
+                "Approximate matching can be used only with plain text",
+            ));
+        }
+        if whole_word {
+            return Some(String::from(
+                "Approximate matching cannot be used with whole word matching",
+            ));
+        }
+        if filter.chars().count() <= distance as usize {
+            return Some(format!(
+                "Filter should be longer than the edit distance ({distance})"
+            ));
+        }
+    }
+    let regex_as_str = filter_as_regex(filter, case_sensitive, whole_word, regex, glob);
     Regex::from_str(&regex_as_str).map_or_else(|err| Some(err.to_string()), |_| None)
 }
 
-fn filter_as_regex(filter: String, case_sensitive: bool, whole_word: bool, regex: bool) -> String {
+fn filter_as_regex(
+    filter: String,
+    case_sensitive: bool,
+    whole_word: bool,
+    regex: bool,
+    glob: bool,
+) -> String {
     let word_marker = if whole_word { "\\b" } else { "" };
     let ignore_case_start = if case_sensitive { "(?i)" } else { "" };
     let ignore_case_end = if case_sensitive { "(?-i)" } else { "" };
     let subject = if regex {
         filter
+    } else if glob {
+        glob_as_regex(&filter)
     } else {
         regex::escape(&filter)
     };
     format!("{ignore_case_start}{word_marker}{subject}{word_marker}{ignore_case_end}",)
 }
 
+/// Converts wildcard pattern into regular expression (the same as `processor` does):
+/// `*` - any sequence of chars, `?` - any single char, `[abc]`, `[a-z]`, `[!abc]` - sets
+/// of chars; wildcards can be escaped with `\`. Results are pinned to the results of
+/// `processor::search::fuzzy::glob_as_regex` by the test `glob_as_processor`.
+fn glob_as_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+    // Sequence of `*` is the same as single `*`
+    let mut any = false;
+    while i < chars.len() {
+        let is_any = chars[i] == '*';
+        match chars[i] {
+            '*' => {
+                if !any {
+                    regex.push_str(".*");
+                }
+            }
+            '?' => regex.push('.'),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                regex.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            '[' => {
+                let mut first = i + 1;
+                if chars.get(first) == Some(&'!') {
+                    first += 1;
+                }
+                if chars.get(first) == Some(&']') {
+                    first += 1;
+                }
+                match chars
+                    .get(first..)
+                    .and_then(|rest| rest.iter().position(|c| *c == ']'))
+                {
+                    Some(pos) => {
+                        let end = first + pos;
+                        regex.push('[');
+                        let mut j = i + 1;
+                        if chars[j] == '!' {
+                            regex.push('^');
+                            j += 1;
+                        }
+                        for c in &chars[j..end] {
+                            if matches!(c, '\\' | '[' | ']' | '&' | '~' | '^') {
+                                regex.push('\\');
+                            }
+                            regex.push(*c);
+                        }
+                        regex.push(']');
+                        i = end;
+                    }
+                    None => regex.push_str("\\["),
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        any = is_any;
+        i += 1;
+    }
+    regex
+}
+
+/// The same cases as in `processor/src/search/tests_fuzzy.rs` (`test_glob`).
+#[allow(dead_code)]
+#[wasm_bindgen_test]
+fn glob_as_processor() {
+    assert_eq!(glob_as_regex("ECU*.err"), r"ECU.*\.err");
+    assert_eq!(glob_as_regex("id-??[0-9][!a]"), r"id\-..[0-9][^a]");
+    assert_eq!(glob_as_regex("[]a]**"), r"[\]a].*");
+    assert_eq!(glob_as_regex("[abc"), r"\[abc");
+    assert_eq!(glob_as_regex(r"a\*b"), r"a\*b");
+    assert_eq!(glob_as_regex(r"a\**"), r"a\*.*");
+    assert_eq!(glob_as_regex("?*?**"), r"..*..*");
+}
+
 #[allow(dead_code)]
 #[wasm_bindgen_test]
 fn plain_string_regex_on() {
@@ -36,7 +148,7 @@ fn plain_string_regex_on() {
     let whole_word = false;
     let regex = true;
     assert_eq!(
-        get_filter_error(filter, case_sesitive, whole_word, regex),
+        get_filter_error(filter, case_sesitive, whole_word, regex, false, None),
         None
     );
 }
@@ -49,7 +161,7 @@ fn plain_string_regex_off() {
     let whole_word = false;
     let regex = false;
     assert_eq!(
-        get_filter_error(filter, case_sesitive, whole_word, regex),
+        get_filter_error(filter, case_sesitive, whole_word, regex, false, None),
         None
     );
 }
@@ -62,7 +174,7 @@ fn valid_regex() {
     let whole_word = false;
     let regex = true;
     assert_eq!(
-        get_filter_error(filter, case_sesitive, whole_word, regex),
+        get_filter_error(filter, case_sesitive, whole_word, regex, false, None),
         None
     );
 }
@@ -74,7 +186,7 @@ fn invalid_regex() {
     let case_sesitive = false;
     let whole_word = false;
     let regex = true;
-    match get_filter_error(filter, case_sesitive, whole_word, regex) {
+    match get_filter_error(filter, case_sesitive, whole_word, regex, false, None) {
         Some(result) => assert_eq!(
             result,
             "regex parse error:\n    \\[Warn(\\]\n          ^\nerror: unclosed group"
@@ -82,3 +194,85 @@ fn invalid_regex() {
         None => panic!("Invalid regular expression should return error message"),
     }
 }
+
+#[allow(dead_code)]
+#[wasm_bindgen_test]
+fn glob_filter() {
+    let case_sesitive = false;
+    let whole_word = false;
+    let regex = false;
+    assert_eq!(
+        get_filter_error(
+            String::from("ECU-1??4:*[!a-c]"),
+            case_sesitive,
+            whole_word,
+            regex,
+            true,
+            None
+        ),
+        None
+    );
+    assert!(
+        get_filter_error(
+            String::from("[z-a]"),
+            case_sesitive,
+            whole_word,
+            regex,
+            true,
+            None
+        )
+        .is_some()
+    );
+    assert!(
+        get_filter_error(
+            String::from("a*"),
+            case_sesitive,
+            whole_word,
+            true,
+            true,
+            None
+        )
+        .is_some()
+    );
+}
+
+#[allow(dead_code)]
+#[wasm_bindgen_test]
+fn approximate_filter() {
+    let case_sesitive = false;
+    let whole_word = false;
+    let regex = false;
+    assert_eq!(
+        get_filter_error(
+            String::from("ECU-1234"),
+            case_sesitive,
+            whole_word,
+            regex,
+            false,
+            Some(2)
+        ),
+        None
+    );
+    assert!(
+        get_filter_error(
+            String::from("ab"),
+            case_sesitive,
+            whole_word,
+            regex,
+            false,
+            Some(2)
+        )
+        .is_some()
+    );
+    assert!(
+        get_filter_error(
+            String::from("abc"),
+            case_sesitive,
+            true,
+            regex,
+            false,
+            Some(1)
+        )
+        .is_some()
+    );
+}
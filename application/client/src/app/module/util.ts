@@ -5,9 +5,13 @@ export function getFilterError(
     caseSensitive: boolean,
     wholeWord: boolean,
     regex: boolean,
+    glob: boolean = false,
+    distance?: number,
 ): string | undefined {
     try {
-        const result = wasm.getBindings().get_filter_error(filter, caseSensitive, wholeWord, regex);
+        const result = wasm
+            .getBindings()
+            .get_filter_error(filter, caseSensitive, wholeWord, regex, glob, distance);
         return typeof result !== 'string' ? undefined : result;
     } catch (_e) {
         return undefined;
@@ -2,7 +2,8 @@ import { Session } from '@service/session/session';
 import { Subject, Subscriber } from '@platform/env/subscription';
 import { Nature } from '@platform/types/content';
 import { EAlias } from '@service/session/dependencies/search/highlights/modifier';
-import { GrabbedElement } from '@platform/types/bindings/miscellaneous';
+import { GrabbedElement, MatchSpan } from '@platform/types/bindings/miscellaneous';
+import { inject } from '@service/session/dependencies/search/highlights/spans';
 
 /**
  * Declares the visual "owner" or container context in which a `Row` is rendered.
@@ -65,6 +66,7 @@ export interface RowSrc {
     source: number;
     session: Session;
     nature: Nature;
+    spans?: MatchSpan[];
 }
 
 const MAX_ROW_LENGTH_LIMIT = 10000;
@@ -139,6 +141,11 @@ export class Row extends Subscriber {
      */
     public cropped: boolean;
 
+    /**
+     * Parts of the content matched by search filters, as found by the backend.
+     */
+    public spans: MatchSpan[];
+
     /**
      * Emits when the row's rendering state is updated (e.g., bookmarked, highlighted).
      * Note: content mutations are not supported and do not trigger this event.
@@ -217,6 +224,7 @@ export class Row extends Subscriber {
         this.position = inputs.position;
         this.owner = inputs.owner;
         this.source = inputs.source;
+        this.spans = inputs.spans ?? [];
         this.delimiter = this.session.render.delimiter();
         this.update();
         this.register(
@@ -238,6 +246,7 @@ export class Row extends Subscriber {
         this.source !== inputs.source && (this.source = inputs.source);
         this.session.uuid() !== inputs.session.uuid() && (this.session = inputs.session);
         this.nature !== inputs.nature && (this.nature = inputs.nature);
+        this.spans = inputs.spans ?? [];
         this.seporator = this.isSeporator();
         this.softUpdate();
     }
@@ -252,6 +261,7 @@ export class Row extends Subscriber {
                     source_id: this.source,
                     content: this.content,
                     nature: 0,
+                    spans: this.spans,
                 };
             },
         };
@@ -313,6 +323,7 @@ export class Row extends Subscriber {
             source: this.source,
             nature: this.nature,
             session: this.session,
+            spans: this.spans,
         };
     }
 
@@ -339,10 +350,12 @@ export class Row extends Subscriber {
             this.matches.filter = injected[EAlias.Filters];
             this.matches.chart = injected[EAlias.Charts];
         };
+        // Matches are marked in the content to be located by highlights after processing
+        const marked = inject(this.content, this.spans);
         if (this.delimiter === undefined) {
             const parsed = this.session.highlights.parse(
                 this.position,
-                Row.removeMarkerSymbols(this.content),
+                Row.removeMarkerSymbols(marked),
                 this.owner,
                 false,
             );
@@ -355,9 +368,11 @@ export class Row extends Subscriber {
             this.background = undefined;
             const columnsMap: [number, number][] = [];
             let cursor = 0;
-            this.columns = this.content.split(this.delimiter).map((str) => {
+            this.content.split(this.delimiter).forEach((str) => {
                 columnsMap.push([cursor, str.length]);
                 cursor += str.length;
+            });
+            this.columns = marked.split(this.delimiter).map((str) => {
                 return Row.removeMarkerSymbols(str);
             });
             const expected = this.session.render.columns();
@@ -48,6 +48,22 @@ export class Implementation extends Render<Columns> {
             caption: 'MSTP',
             desc: 'Message Type (MSTP)',
         },
+        {
+            caption: 'TAGS',
+            desc: 'Tags (TAG), DLT v2 only',
+        },
+        {
+            caption: 'PRLV',
+            desc: 'Privacy Level (PRLV), DLT v2 only',
+        },
+        {
+            caption: 'SGM',
+            desc: 'Segmentation (SGMT), DLT v2 only',
+        },
+        {
+            caption: 'SRC',
+            desc: 'Source File Name and Line Number (SFLN), DLT v2 only',
+        },
         {
             caption: 'PAYLOAD',
             desc: 'Payload',
@@ -60,7 +76,7 @@ export class Implementation extends Render<Columns> {
             new Columns(
                 Implementation.HEADERS,
                 true,
-                [150, 20, 20, 20, 20, 20, 20, 20, 20, 20, -1],
+                [150, 20, 20, 20, 20, 20, 20, 20, 20, 20, 80, 20, 40, 120, -1],
                 MIN_COLUMN_WIDTH,
                 MAX_COLUMN_WIDTH,
             ),
@@ -47,6 +47,14 @@ export class Implementation extends Render<Columns> {
             caption: 'PAYLOAD',
             desc: 'Payload',
         },
+        {
+            caption: 'E2E-CRC',
+            desc: 'The E2E CRC status',
+        },
+        {
+            caption: 'E2E-CNT',
+            desc: 'The E2E counter status',
+        },
     ];
 
     constructor() {
@@ -55,7 +63,7 @@ export class Implementation extends Render<Columns> {
             new Columns(
                 Implementation.HEADERS,
                 true,
-                [50, 50, 50, 30, 30, 30, 30, 30, 30, -1],
+                [50, 50, 50, 30, 30, 30, 30, 30, 30, 500, 50, -1],
                 MIN_COLUMN_WIDTH,
                 MAX_COLUMN_WIDTH,
             ),
@@ -1,5 +1,6 @@
 import { Observe } from '@platform/types/observe';
 import { SdeRequest, SdeResponse } from '@platform/types/sde';
+import { DltControlRequest } from '@platform/types/bindings/miscellaneous';
 
 import * as $ from '@platform/types/observe';
 
@@ -33,6 +34,7 @@ export class ObserveOperation {
     public send(): {
         text(data: string): Promise<SdeResponse>;
         bytes(data: number[]): Promise<SdeResponse>;
+        dlt(request: DltControlRequest): Promise<SdeResponse>;
     } {
         const send = (request: SdeRequest): Promise<SdeResponse> => {
             this._sdeTasksCount += 1;
@@ -61,6 +63,16 @@ export class ObserveOperation {
                     WriteBytes: data,
                 });
             },
+            dlt: (request: DltControlRequest): Promise<SdeResponse> => {
+                if (!this.observe.origin.isSdeSupported()) {
+                    return Promise.reject(
+                        new Error(`Observed origin doesn't support SDE protocol`),
+                    );
+                }
+                return send({
+                    DltControl: request,
+                });
+            },
         };
     }
 
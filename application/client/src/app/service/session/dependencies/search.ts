@@ -24,6 +24,8 @@ export class Search extends Subscriber {
         map: new Subject<void>(),
     });
     private _len: number = 0;
+    // Filters of the recent search; matches of filters in grabbed rows refer to them by index
+    private _searched: IFilter[] = [];
     private _uuid!: string;
     private _store!: {
         filters: FiltersStore;
@@ -91,13 +93,18 @@ export class Search extends Subscriber {
         return this._len;
     }
 
+    public searched(): IFilter[] {
+        return this._searched;
+    }
+
     public search(filters: IFilter[]): Promise<number> {
+        this._searched = filters.filter((f) => FilterRequest.isValid(f));
         return new Promise((resolve, reject) => {
             Requests.IpcRequest.send(
                 Requests.Search.Search.Response,
                 new Requests.Search.Search.Request({
                     session: this._uuid,
-                    filters: filters.filter((f) => FilterRequest.isValid(f)),
+                    filters: this._searched,
                 }),
             )
                 .then((response) => {
@@ -172,6 +179,7 @@ export class Search extends Subscriber {
     }
 
     public drop(): Promise<void> {
+        this._searched = [];
         return new Promise((resolve, reject) => {
             Requests.IpcRequest.send(
                 Requests.Search.Drop.Response,
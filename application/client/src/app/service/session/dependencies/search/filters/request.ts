@@ -98,6 +98,8 @@ export class FilterRequest
                 filter.flags.cases,
                 filter.flags.word,
                 filter.flags.reg,
+                filter.flags.glob,
+                filter.flags.distance,
             ) === undefined
         );
     }
@@ -108,6 +110,8 @@ export class FilterRequest
             filter.flags.cases,
             filter.flags.word,
             filter.flags.reg,
+            filter.flags.glob,
+            filter.flags.distance,
         );
         if (error !== undefined) {
             const match: RegExpMatchArray | null = error.match(/error:.+/i);
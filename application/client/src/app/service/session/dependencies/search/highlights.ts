@@ -6,6 +6,8 @@ import { Owner } from '@schema/content/row';
 import { Session } from '@service/session';
 import { serializeHtml } from '@platform/env/str';
 import { safeEscapeAnsi } from '@module/ansi';
+import { IFilter } from '@platform/types/filter';
+import { extract } from './highlights/spans';
 
 import * as Modifiers from './highlights/modifiers/index';
 
@@ -69,6 +71,11 @@ export class Highlights extends Subscriber {
         this.subjects.destroy();
     }
 
+    /**
+     * Renders the row as HTML.
+     *
+     * @param row - content of the row; matches of filters are marked in it with `inject`
+     */
     public parse(
         position: number,
         row: string,
@@ -83,26 +90,36 @@ export class Highlights extends Subscriber {
     } {
         // Get rid of original HTML in logs
         const serialized = serializeHtml(row);
-        const asci = new Modifiers.AsciModifier(serialized);
-        const escaped = safeEscapeAnsi(serialized);
-        const filtres = new Modifiers.FiltersModifier(
-            this.session.search.store().filters().get(),
-            escaped,
-        );
+        const asci = new Modifiers.AsciModifier(extract(serialized).row);
+        // Matches are found by backend, here they are only located in the escaped row
+        const { row: escaped, ranges } = extract(safeEscapeAnsi(serialized));
         const active = this.session.search.state().getActive();
         const nested = this.session.search.state().nested().get();
+        const searched = this.session.search.searched();
+        const requests = this.session.search.store().filters().get();
+        const filtres = new Modifiers.FiltersModifier(
+            active === undefined ? ranges : [],
+            (filter: number) => {
+                const def: IFilter | undefined = searched[filter];
+                if (def === undefined) {
+                    return undefined;
+                }
+                return requests.find(
+                    (request) =>
+                        request.definition.filter.filter === def.filter &&
+                        request.definition.filter.flags.cases === def.flags.cases &&
+                        request.definition.filter.flags.reg === def.flags.reg &&
+                        request.definition.filter.flags.word === def.flags.word &&
+                        request.definition.filter.flags.glob === def.flags.glob &&
+                        request.definition.filter.flags.distance === def.flags.distance,
+                );
+            },
+        );
         const processor = new ModifierProcessor([
             filtres,
             new Modifiers.ChartsModifier(this.session.search.store().charts().get(), escaped),
             ...this.session.comments.getModifiers(position, escaped, columns),
-            ...(active !== undefined
-                ? [
-                      new Modifiers.ActiveFilterModifier(
-                          [new FilterRequest({ filter: active })],
-                          escaped,
-                      ),
-                  ]
-                : []),
+            ...(active !== undefined ? [new Modifiers.ActiveFilterModifier(ranges)] : []),
             ...(nested !== undefined
                 ? [
                       new Modifiers.NestedSearchModifier(
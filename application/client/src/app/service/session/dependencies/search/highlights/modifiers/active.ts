@@ -6,7 +6,7 @@ import {
     IModifierRange,
     EAlias,
 } from '../modifier';
-import { FilterRequest } from '../../filters/request';
+import { IMatchRange } from '../spans';
 import { settings } from '@service/settings';
 import { getContrastColor } from '@styles/colors';
 
@@ -14,11 +14,10 @@ import * as ModifiersTools from '../tools';
 
 export class ActiveFilterModifier extends Modifier {
     private _ranges: IModifierRange[] = [];
-    private _matched: FilterRequest | undefined;
 
-    constructor(filters: FilterRequest[], row: string) {
+    constructor(ranges: IMatchRange[]) {
         super();
-        this._map(row, filters);
+        this._map(ranges);
     }
 
     public alias(): EAlias {
@@ -78,24 +77,9 @@ export class ActiveFilterModifier extends Modifier {
         return 'ActiveFilterModifier';
     }
 
-    public matched(): FilterRequest | undefined {
-        return this._matched;
-    }
-
-    private _map(row: string, filters: FilterRequest[]) {
-        filters.forEach((request: FilterRequest) => {
-            row.replace(request.as().serializedRegExp(), (match: string, ...args: any[]) => {
-                const offset: number =
-                    typeof args[args.length - 2] === 'number'
-                        ? args[args.length - 2]
-                        : args[args.length - 3];
-                this._ranges.push({
-                    start: offset,
-                    end: offset + match.length,
-                });
-                this._matched = request;
-                return '';
-            });
+    private _map(ranges: IMatchRange[]) {
+        this._ranges = ranges.map((range: IMatchRange) => {
+            return { start: range.start, end: range.end };
         });
         // Remove nested ranges because it doesn't make sense,
         // because color is same
@@ -8,6 +8,7 @@ import {
 } from '../modifier';
 import { FilterRequest } from '../../filters/request';
 import { styles } from '@ui/service/styles';
+import { IMatchRange } from '../spans';
 
 import * as Colors from '@styles/colors';
 import * as ModifiersTools from '../tools';
@@ -20,9 +21,13 @@ export class FiltersModifier extends Modifier {
     private _ranges: IRange[] = [];
     private _matched: FilterRequest | undefined;
 
-    constructor(highlights: FilterRequest[], row: string) {
+    /**
+     * @param ranges - matches of filters found by backend
+     * @param resolve - gives the filter by its identifier in matches
+     */
+    constructor(ranges: IMatchRange[], resolve: (filter: number) => FilterRequest | undefined) {
         super();
-        this._map(row, highlights);
+        this._map(ranges, resolve);
     }
 
     public alias(): EAlias {
@@ -79,26 +84,28 @@ export class FiltersModifier extends Modifier {
         return this._matched;
     }
 
-    private _map(row: string, highlights: FilterRequest[]) {
-        highlights.forEach((request: FilterRequest) => {
-            row.replace(request.as().serializedRegExp(), (match: string, ...args: any[]) => {
-                const offset: number =
-                    typeof args[args.length - 2] === 'number'
-                        ? args[args.length - 2]
-                        : args[args.length - 3];
-                this._ranges.push({
-                    start: offset,
-                    end: offset + match.length,
-                    bgcl:
-                        request.definition.colors.background === Colors.CColors[0]
-                            ? styles.colors().scheme_color_4
-                            : request.definition.colors.background === undefined
-                            ? styles.colors().scheme_color_4
-                            : Colors.shadeColor(request.definition.colors.background, 30),
-                });
-                this._matched = request;
-                return '';
+    private _map(ranges: IMatchRange[], resolve: (filter: number) => FilterRequest | undefined) {
+        // The row is colored by the last matched filter (in order of filters)
+        let last = -1;
+        ranges.forEach((range: IMatchRange) => {
+            const request = resolve(range.filter);
+            if (request === undefined) {
+                return;
+            }
+            this._ranges.push({
+                start: range.start,
+                end: range.end,
+                bgcl:
+                    request.definition.colors.background === Colors.CColors[0]
+                        ? styles.colors().scheme_color_4
+                        : request.definition.colors.background === undefined
+                        ? styles.colors().scheme_color_4
+                        : Colors.shadeColor(request.definition.colors.background, 30),
             });
+            if (range.filter > last) {
+                last = range.filter;
+                this._matched = request;
+            }
         });
         // Remove conflicts
         this._ranges = ModifiersTools.removeCrossing(this._ranges) as IRange[];
@@ -0,0 +1,75 @@
+import { MatchSpan } from '@platform/types/bindings/miscellaneous';
+import { IModifierRange } from './modifier';
+
+// Matches are marked with characters of the private use area: the opening mark is
+// OPEN + filter, the closing mark is CLOSE + filter (filters are identified by u8).
+const OPEN = 0xe000;
+const CLOSE = 0xe100;
+const END = 0xe200;
+
+export interface IMatchRange extends IModifierRange {
+    filter: number;
+}
+
+/**
+ * Marks matches of filters (found by backend) in the row. Marks survive any processing
+ * of the row (escaping of HTML, ANSI escapes, splitting into columns), so the positions
+ * of matches in the processed row can be found with `extract`.
+ *
+ * @param content - original content of the row
+ * @param spans - matches in the content; positions are in characters (code points)
+ * @returns content with marks
+ */
+export function inject(content: string, spans: MatchSpan[]): string {
+    if (spans.length === 0) {
+        return content;
+    }
+    const marks: Map<number, string[]> = new Map();
+    const add = (pos: number, mark: string) => {
+        const list = marks.get(pos);
+        list === undefined ? marks.set(pos, [mark]) : list.push(mark);
+    };
+    spans.forEach((span: MatchSpan) => {
+        add(span.end, String.fromCharCode(CLOSE + span.filter));
+    });
+    spans.forEach((span: MatchSpan) => {
+        add(span.start, String.fromCharCode(OPEN + span.filter));
+    });
+    let marked = '';
+    let pos = 0;
+    for (const char of content) {
+        marked += (marks.get(pos) ?? []).join('') + char;
+        pos += 1;
+    }
+    return marked + (marks.get(pos) ?? []).join('');
+}
+
+/**
+ * Removes marks of matches from the (processed) row.
+ *
+ * @param row - row with marks added by `inject`
+ * @returns row without marks and ranges of matches in it (in UTF-16 code units).
+ * Matches split between columns are closed at the end of the column and opened at
+ * its beginning.
+ */
+export function extract(row: string): { row: string; ranges: IMatchRange[] } {
+    const ranges: IMatchRange[] = [];
+    const opened: Map<number, number> = new Map();
+    let clean = '';
+    for (const char of row) {
+        const code = char.charCodeAt(0);
+        if (code >= OPEN && code < CLOSE) {
+            opened.set(code - OPEN, clean.length);
+        } else if (code >= CLOSE && code < END) {
+            const filter = code - CLOSE;
+            ranges.push({ filter, start: opened.get(filter) ?? 0, end: clean.length });
+            opened.delete(filter);
+        } else {
+            clean += char;
+        }
+    }
+    opened.forEach((start: number, filter: number) => {
+        ranges.push({ filter, start, end: clean.length });
+    });
+    return { row: clean, ranges: ranges.filter((range) => range.end > range.start) };
+}
@@ -23,6 +23,7 @@ async function getRowFrom(
                 ? parseInt(element.source_id, 10)
                 : element.source_id,
         nature: new Nature(element.nature),
+        spans: element.spans,
     };
     if (!row.nature.seporator) {
         return row;
@@ -22,6 +22,7 @@ function getRows(session: Session, range: Range): Promise<IRowsPacket> {
                                 ? parseInt(row.source_id, 10)
                                 : row.source_id,
                         nature: new Nature(row.nature),
+                        spans: row.spans,
                     };
                 });
                 session.cursor.recent(converted);
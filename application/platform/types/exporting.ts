@@ -3,3 +3,15 @@ export interface TextExportOptions {
     spliter: string | undefined;
     delimiter: string | undefined;
 }
+
+export interface SearchExportOptions {
+    // Count of rows before each match to include
+    before: number;
+    // Count of rows after each match to include
+    after: number;
+    // Line written between not adjacent parts of output
+    separator?: string;
+    columns?: number[];
+    spliter?: string;
+    delimiter?: string;
+}
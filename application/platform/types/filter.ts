@@ -2,11 +2,73 @@ export interface IFilterFlags {
     reg: boolean;
     word: boolean;
     cases: boolean;
+    /**
+     * Filter is a wildcard pattern: "*" - any sequence of chars, "?" - any single char,
+     * "[abc]", "[a-z]", "[!abc]" - sets of chars
+     */
+    glob?: boolean;
+    /**
+     * Maximum count of edits (insertions, deletions or substitutions of chars) for
+     * approximate matching of plain text
+     */
+    distance?: number;
+}
+
+/**
+ * Restricts filter to some columns of rows
+ */
+export interface IFilterColumns {
+    /**
+     * Separator of columns in session file (the same as used for exporting)
+     */
+    spliter: string;
+    /**
+     * Indices of columns to search in
+     */
+    columns: number[];
 }
 
 export interface IFilter {
     filter: string;
     flags: IFilterFlags;
+    columns?: IFilterColumns;
+}
+
+export interface ISearchQuery {
+    /**
+     * Query expression, e.g. "ERROR AND ECU1 AND NOT heartbeat"
+     */
+    query: string;
+    /**
+     * Separator of columns; required if terms of query are restricted to columns
+     */
+    spliter?: string;
+}
+
+/**
+ * Time window of search. Rows are checked against the timestamps detected by parsers;
+ * rows without a timestamp get the timestamp of the nearest previous row with one.
+ */
+export interface ITimeWindow {
+    /**
+     * Beginning of the window (inclusive) in milliseconds since the epoch
+     */
+    from?: number;
+    /**
+     * End of the window (inclusive) in milliseconds since the epoch
+     */
+    to?: number;
+}
+
+/**
+ * Restricts search (as well as search map and extracting) to some rows of session
+ */
+export interface ISearchScope {
+    /**
+     * Identifiers of sources to search in; rows of all sources are searched if empty
+     */
+    sources?: number[];
+    time?: ITimeWindow;
 }
 
 export interface FilterDefinition {
@@ -35,6 +97,10 @@ export interface ISearchUpdated {
     stat: Map<string, number>;
 }
 
+export interface INamedSearchUpdated extends ISearchUpdated {
+    id: string;
+}
+
 export enum EFlag {
     cases = 'cases',
     word = 'word',
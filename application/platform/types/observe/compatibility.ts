@@ -65,7 +65,7 @@ export const SDESupport: {
     [Origin.Context.Plugin]: false,
     [Stream.Source.Process]: true,
     [Stream.Source.Serial]: true,
-    [Stream.Source.TCP]: false,
+    [Stream.Source.TCP]: true,
     [Stream.Source.UDP]: false,
 };
 
@@ -42,11 +42,17 @@ export interface IFilters {
     context_id_count: number;
 }
 
+export interface IV2Filters {
+    tags: string[] | undefined;
+    max_privacy_level: number | undefined;
+}
+
 export interface IConfiguration {
     filter_config: IFilters | undefined;
     fibex_file_paths: string[] | undefined;
     with_storage_header: boolean;
     tz: string | undefined;
+    v2_filter_config?: IV2Filters;
 }
 
 @Statics<ConfigurationStaticDesc<IConfiguration, Protocol>>()
@@ -72,6 +78,12 @@ export class Configuration
             obj.getAsNotEmptyStringsArrayOrUndefined(configuration, 'fibex_file_paths');
             obj.getAsObjOrUndefined(configuration, 'filter_config');
             obj.getAsNotEmptyStringOrAsUndefined(configuration, 'tz');
+            obj.getAsObjOrUndefined(configuration, 'v2_filter_config');
+            const v2_filter_config = configuration.v2_filter_config;
+            if (v2_filter_config !== undefined) {
+                obj.getAsNotEmptyStringsArrayOrUndefined(v2_filter_config, 'tags');
+                obj.getAsValidNumberOrUndefined(v2_filter_config, 'max_privacy_level');
+            }
             const filter_config = configuration.filter_config;
             if (filter_config !== undefined) {
                 obj.getAsValidNumber(filter_config, 'min_log_level');
@@ -179,7 +191,9 @@ export class Configuration
                 : this.configuration.fibex_file_paths
             ).join(';')};${this.configuration.with_storage_header};${this.configuration.tz};${
                 filters.min_log_level
-            };${filters.ecu_ids?.length};${filters.app_ids?.length};${filters.context_ids?.length}`,
+            };${filters.ecu_ids?.length};${filters.app_ids?.length};${filters.context_ids?.length};${
+                this.configuration.v2_filter_config?.tags?.join(';') ?? ''
+            };${this.configuration.v2_filter_config?.max_privacy_level ?? ''}`,
         );
     }
 }
@@ -29,6 +29,7 @@ export interface SomeipStatisticDetail {
 
 export interface IConfiguration {
     fibex_file_paths: string[] | undefined;
+    e2e_config_path: string | undefined;
 }
 
 @Statics<ConfigurationStaticDesc<IConfiguration, Protocol>>()
@@ -51,6 +52,7 @@ export class Configuration
     static validate(configuration: IConfiguration): Error | IConfiguration {
         try {
             obj.getAsNotEmptyStringsArrayOrUndefined(configuration, 'fibex_file_paths');
+            obj.getAsNotEmptyStringOrAsUndefined(configuration, 'e2e_config_path');
             return configuration;
         } catch (e) {
             return new Error(error(e));
@@ -65,6 +67,7 @@ export class Configuration
     static initial(): IConfiguration {
         return {
             fibex_file_paths: [],
+            e2e_config_path: undefined,
         };
     }
 
@@ -81,7 +84,7 @@ export class Configuration
             `someip:${(this.configuration.fibex_file_paths === undefined
                 ? []
                 : this.configuration.fibex_file_paths
-            ).join(';')}`,
+            ).join(';')};${this.configuration.e2e_config_path ?? ''}`,
         );
     }
 }
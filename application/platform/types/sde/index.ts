@@ -1,8 +1,12 @@
+import { DltControlRequest } from '../bindings/miscellaneous';
+
 export interface SdeRequest {
     WriteText?: string;
     WriteBytes?: number[];
+    DltControl?: DltControlRequest;
 }
 
 export interface SdeResponse {
     bytes: number;
+    response: string | null;
 }